@@ -0,0 +1,57 @@
+/*
+ * ai_latency.rs - How long each AI difficulty takes to decide a move
+ *
+ * Run with `cargo bench`. Each difficulty is timed on the same mid-game positions:
+ * - An attack with a full hand and half the deck gone
+ * - A defense against three open attacks, also asking whether to take instead
+ */
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use durak::game::{AiDifficulty, AiPlayer, GameState};
+
+const DIFFICULTIES: [AiDifficulty; 4] = [
+    AiDifficulty::Easy,
+    AiDifficulty::Medium,
+    AiDifficulty::Hard,
+    AiDifficulty::Dynamic,
+];
+
+/// The AI opens a round with six cards in hand and twelve left to draw
+fn attack_position() -> GameState {
+    GameState::from_notation(
+        "AI: 6H 8C 9D JS QH AC | Human*: 7H 7D TC KD 6S 9S \
+         | Deck: 8H 8D TH JD QC KC AH 7C JH QS TD 6D | Trump: S | Phase: Attack",
+    )
+    .unwrap()
+}
+
+/// The AI defends against three attacks with a mix of plain cards and trumps
+fn defense_position() -> GameState {
+    GameState::from_notation(
+        "Human*: 6H 9D | AI: 7H JH 9C KC TD QD 8S AS \
+         | Table: 6C-_ 8D-_ TH-_ | Deck: 8H 7D JS QH AC 6D | Trump: S",
+    )
+    .unwrap()
+}
+
+fn bench_ai(c: &mut Criterion) {
+    let attack = attack_position();
+    let defense = defense_position();
+    for difficulty in DIFFICULTIES {
+        let ai = AiPlayer::new(difficulty);
+        c.bench_function(&format!("{} attack", difficulty), |b| {
+            b.iter(|| ai.make_attack_move(black_box(&attack), 0))
+        });
+        c.bench_function(&format!("{} defense", difficulty), |b| {
+            b.iter(|| ai.make_defense_move(black_box(&defense), 1))
+        });
+        c.bench_function(&format!("{} full defense", difficulty), |b| {
+            b.iter(|| ai.make_full_defense(black_box(&defense), 1))
+        });
+        c.bench_function(&format!("{} take decision", difficulty), |b| {
+            b.iter(|| ai.should_take_cards(black_box(&defense), 1))
+        });
+    }
+}
+
+criterion_group!(benches, bench_ai);
+criterion_main!(benches);
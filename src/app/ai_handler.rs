@@ -1,137 +1,273 @@
 use super::app_core::App;
-use crate::game::{GamePhase, PlayerType};
-use crate::ui::debug_overlay::debug;
+use crate::game::{GameError, GamePhase, PlayerType};
+use crate::ui::debug_overlay::{debug, error};
+use std::time::Instant;
 
+/// Lets the AI players move until the human is up, then restarts the human's turn timer.
+/// With `debug_step_ai` set the AI waits for `step_ai_turn` instead. With `ai_think_time`
+/// set the AI only starts thinking here, `resume_ai_turn` moves once the time is up.
 pub fn process_ai_turn(app: &mut App) {
-    let mut turn_counter = 0;
-    const MAX_TURNS: i32 = 10;
-    while turn_counter < MAX_TURNS {
-        turn_counter += 1;
+    if app.debug_step_ai {
+        debug("AI step mode: press '.' to advance the AI");
+    } else if app.ai_think_time.is_some() {
+        debug("AI is thinking");
+        app.ai_thinking_since = Some(Instant::now());
+    } else {
+        run_ai_turns(app);
+    }
+    app.restart_turn_timer();
+}
+/// Called on every tick of the `run` loop. Lets the AI move once it has shown it is
+/// thinking for `ai_think_time`, so at least one frame with the message is drawn first.
+pub fn resume_ai_turn(app: &mut App) {
+    let (Some(since), Some(think_time)) = (app.ai_thinking_since, app.ai_think_time) else {
+        return;
+    };
+    if since.elapsed() < think_time {
+        return;
+    }
+    app.ai_thinking_since = None;
+    // The same seed as before the human's move, so a practice game replays the same answer
+    app.seed_practice_ai();
+    run_ai_turns(app);
+    app.record_practice_state();
+    app.restart_turn_timer();
+}
+/// Advances the AI by a single sub-action, bound to '.' while `debug_step_ai` is set
+pub fn step_ai_turn(app: &mut App) {
+    if ai_step(app) == AiStep::Done {
+        debug("AI step: nothing left for the AI to do");
+    }
+    app.restart_turn_timer();
+}
+/// Runs AI sub-actions until the human is up or the game is over. A round between several
+/// AI players takes any number of them, the loop only stops early if a step changes nothing
+/// and the next one would be the same.
+fn run_ai_turns(app: &mut App) {
+    for turn_counter in 1.. {
         debug(format!("AI turn iteration {}", turn_counter));
-        // Check for game over - this also sets the winner
-        if app.game_state.check_game_over() {
-            app.app_state = super::state::AppState::GameOver;
+        let before = app.game_state.clone();
+        if ai_step(app) == AiStep::Done {
             return;
         }
-        // Get the current player based on game phase
-        let current_player_idx = app.current_player_index();
-        let is_ai_turn =
-            app.game_state.players()[current_player_idx].player_type() == &PlayerType::Computer;
-        if !is_ai_turn {
-            debug("Not AI's turn, ending AI processing");
+        if app.game_state == before {
+            error("AI step left the game unchanged, stopping");
             return;
         }
-        debug(format!(
-            "AI playing in phase: {:?}",
-            app.game_state.game_phase()
-        ));
-        debug(format!(
-            "Current attacker: {}, Current defender: {}",
-            app.game_state.current_attacker(),
-            app.game_state.current_defender()
-        ));
-        match *app.game_state.game_phase() {
-            GamePhase::Attack => {
-                debug("AI attempting to attack");
-                let attack_result = handle_ai_attack(app, current_player_idx);
-                debug(format!("AI attack result: {:?}", attack_result));
-                if *app.game_state.game_phase() == GamePhase::Defense {
-                    let defender_idx = app.game_state.current_defender();
-                    let is_human_defender =
-                        app.game_state.players()[defender_idx].player_type() == &PlayerType::Human;
-                    if is_human_defender {
-                        debug("Human needs to defend, ending AI processing");
-                        return;
-                    } else {
-                        debug("AI needs to defend against itself, continuing");
-                        continue; // Process the defense in the next iteration
-                    }
-                } else if *app.game_state.game_phase() == GamePhase::Attack {
-                    debug("AI passed attack, transitioning to drawing phase");
-                    app.game_state.draw_cards();
+    }
+}
+/// Outcome of a single AI sub-action
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AiStep {
+    Continue, // The AI has more to do
+    Done,     // The human is up, the game is over or the AI has nothing left to do
+}
+/// Executes exactly one AI sub-action (an attack, a defense, a reinforcement or a draw)
+fn ai_step(app: &mut App) -> AiStep {
+    // Check for game over - this also sets the winner
+    if app.game_state.check_game_over() {
+        app.finish_game();
+        return AiStep::Done;
+    }
+    if waiting_for_draw_acknowledgement(app) {
+        debug("Round over, waiting for the human to acknowledge the draw");
+        return AiStep::Done;
+    }
+    // Get the current player based on game phase
+    let current_player_idx = app.current_player_index();
+    let is_ai_turn =
+        app.game_state.players()[current_player_idx].player_type() == &PlayerType::Computer;
+    if !is_ai_turn {
+        debug("Not AI's turn, ending AI processing");
+        return AiStep::Done;
+    }
+    if let Some(violation) = role_violation(app) {
+        error(format!("AI role invariant violated: {}", violation));
+        return AiStep::Done;
+    }
+    debug(format!(
+        "AI playing in phase: {:?}",
+        app.game_state.game_phase()
+    ));
+    debug(format!(
+        "Current attacker: {}, Current defender: {}",
+        app.game_state.current_attacker(),
+        app.game_state.current_defender()
+    ));
+    // The AI is about to move, the human's moves before it are final
+    app.clear_undo();
+    // Out of cards is not a pass, the attack moves on or the game ends
+    if app.game_state.retire_empty_attacker() {
+        debug("AI attacker has no cards left");
+        return AiStep::Continue;
+    }
+    match *app.game_state.game_phase() {
+        GamePhase::Attack => {
+            debug("AI attempting to attack");
+            let attack_result = handle_ai_attack(app, current_player_idx);
+            debug(format!("AI attack result: {:?}", attack_result));
+            if *app.game_state.game_phase() == GamePhase::Defense {
+                let defender_idx = app.game_state.current_defender();
+                let is_human_defender =
+                    app.game_state.players()[defender_idx].player_type() == &PlayerType::Human;
+                if is_human_defender {
+                    debug("Human needs to defend, ending AI processing");
+                    app.defense_briefing = true;
+                    return AiStep::Done;
                 }
-            }
-            GamePhase::Defense => {
-                debug("AI attempting to defend");
-                let defense_result = handle_ai_defense(app, current_player_idx);
-                debug(format!("AI defense result: {:?}", defense_result));
-                if *app.game_state.game_phase() == GamePhase::Defense {
-                    let current_defender = app.game_state.current_defender();
-                    let is_ai_defender = app.game_state.players()[current_defender].player_type()
-                        == &PlayerType::Computer;
-                    if current_defender != current_player_idx {
-                        debug("Pass occurred, roles have changed");
-                        if is_ai_defender {
-                            debug("AI passed to AI, continuing defense");
-                            continue;
-                        } else {
-                            debug("AI passed to human, ending AI processing");
-                            return;
-                        }
-                    } else {
-                        debug("AI defense incomplete, forcing draw phase");
-                        app.game_state.draw_cards();
-                    }
-                } else if *app.game_state.game_phase() == GamePhase::Drawing {
-                    debug("AI defense complete, already in drawing phase");
-                    app.game_state.draw_cards();
+                return ai_defends_next(app); // Process the defense in the next iteration
+            } else if *app.game_state.game_phase() == GamePhase::Attack {
+                match app.game_state.end_attack() {
+                    Ok(()) => debug("AI passed attack, ending the round"),
+                    Err(e) => debug(format!("AI cannot pass: {}", e)),
                 }
             }
-            GamePhase::Drawing => {
-                debug("AI processing drawing phase");
-                app.game_state.draw_cards();
-
-                if *app.game_state.game_phase() == GamePhase::Attack {
-                    let attacker_idx = app.game_state.current_attacker();
-                    let is_human_attacker =
-                        app.game_state.players()[attacker_idx].player_type() == &PlayerType::Human;
-                    if is_human_attacker {
-                        debug("Human's turn after drawing, ending AI processing");
-                        return;
-                    } else {
-                        debug("AI's turn to attack after drawing, continuing to next iteration");
-                        continue;
-                    }
-                }
+        }
+        GamePhase::Defense => {
+            debug("AI attempting to defend");
+            let defense_result = handle_ai_defense(app, current_player_idx);
+            debug(format!("AI defense result: {:?}", defense_result));
+            let step = after_defense(app);
+            // An AI pass that lands on the human is a new attack to look over
+            if step == AiStep::Done
+                && *app.game_state.game_phase() == GamePhase::Defense
+                && app.game_state.players()[app.game_state.current_defender()].player_type()
+                    == &PlayerType::Human
+            {
+                app.defense_briefing = true;
             }
-            _ => {
-                debug("AI turn in unhandled game phase, ending AI processing");
-                return;
+            return step;
+        }
+        GamePhase::Reinforce => {
+            debug("AI deciding whether to reinforce");
+            let reinforce_result = handle_ai_reinforce(app, current_player_idx);
+            debug(format!("AI reinforce result: {:?}", reinforce_result));
+            if *app.game_state.game_phase() == GamePhase::Defense {
+                let defender_idx = app.game_state.current_defender();
+                let is_human_defender =
+                    app.game_state.players()[defender_idx].player_type() == &PlayerType::Human;
+                if is_human_defender {
+                    debug("Human needs to defend the reinforcement, ending AI processing");
+                    app.defense_briefing = true;
+                    return AiStep::Done;
+                }
+                return ai_defends_next(app);
             }
         }
-        if *app.game_state.game_phase() == GamePhase::Drawing {
-            debug("Handling drawing phase transition");
+        GamePhase::Drawing => {
+            debug("AI processing drawing phase");
             app.game_state.draw_cards();
-            if *app.game_state.game_phase() == GamePhase::Drawing {
-                debug("Forcing transition from Drawing to Attack phase");
-                app.game_state = crate::game::GameState::force_attack_phase(app.game_state.clone());
-            }
+
             if *app.game_state.game_phase() == GamePhase::Attack {
                 let attacker_idx = app.game_state.current_attacker();
                 let is_human_attacker =
                     app.game_state.players()[attacker_idx].player_type() == &PlayerType::Human;
-
                 if is_human_attacker {
-                    debug("Human's turn after drawing phase completed, ending AI processing");
-                    return;
+                    debug("Human's turn after drawing, ending AI processing");
+                    return AiStep::Done;
+                } else {
+                    debug("AI's turn to attack after drawing, continuing to next iteration");
+                    return AiStep::Continue;
                 }
             }
         }
-        if app.game_state.check_game_over() {
-            app.app_state = super::state::AppState::GameOver;
-            return;
+        _ => {
+            debug("AI turn in unhandled game phase, ending AI processing");
+            return AiStep::Done;
+        }
+    }
+    if *app.game_state.game_phase() == GamePhase::Drawing {
+        if waiting_for_draw_acknowledgement(app) {
+            debug("Round over, waiting for the human to acknowledge the draw");
+            return AiStep::Done;
         }
-        if turn_counter >= MAX_TURNS - 1 {
-            debug("Reached maximum AI turn iterations, forcing end to prevent issues");
-            if *app.game_state.game_phase() == GamePhase::Drawing {
-                app.game_state = crate::game::GameState::force_attack_phase(app.game_state.clone());
+        debug("Handling drawing phase transition");
+        app.game_state.draw_cards();
+        if *app.game_state.game_phase() == GamePhase::Attack {
+            let attacker_idx = app.game_state.current_attacker();
+            let is_human_attacker =
+                app.game_state.players()[attacker_idx].player_type() == &PlayerType::Human;
+
+            if is_human_attacker {
+                debug("Human's turn after drawing phase completed, ending AI processing");
+                return AiStep::Done;
             }
-            return;
         }
     }
+    if app.game_state.check_game_over() {
+        app.finish_game();
+        return AiStep::Done;
+    }
+    AiStep::Continue
+}
+/// Whether this is the usual game of the human against a single AI
+fn is_heads_up(app: &App) -> bool {
+    app.game_state.players().len() == 2 && app.game_state.has_human_player()
+}
+/// Checks the seating before the AI moves. In a game against a single AI the two players
+/// always face each other, so the AI on both sides of the table is a role-rotation bug.
+fn role_violation(app: &App) -> Option<String> {
+    let attacker = app.game_state.current_attacker();
+    let defender = app.game_state.current_defender();
+    if is_heads_up(app) && attacker == defender {
+        return Some(format!(
+            "player {} is both attacker and defender in {:?}",
+            attacker,
+            app.game_state.game_phase()
+        ));
+    }
+    None
+}
+/// An AI attack or reinforcement left an AI defending. That only happens with several
+/// AI players, against a single AI it means the AI is about to defend against itself.
+fn ai_defends_next(app: &App) -> AiStep {
+    if is_heads_up(app) {
+        error(format!(
+            "AI role invariant violated: AI player {} would defend against its own attack",
+            app.game_state.current_defender()
+        ));
+        return AiStep::Done;
+    }
+    debug("AI needs to defend against another AI, continuing");
+    AiStep::Continue
+}
+/// Who moves after a defense, a pass or a take, shared by the human and AI paths.
+/// `Continue` when an AI player has the next move, `Done` when the human does, when the
+/// round waits for the draw to be acknowledged or when the game is over.
+fn after_defense(app: &mut App) -> AiStep {
+    if app.game_state.check_game_over() {
+        app.finish_game();
+        return AiStep::Done;
+    }
+    if waiting_for_draw_acknowledgement(app) {
+        debug("Round over, waiting for the human to acknowledge the draw");
+        return AiStep::Done;
+    }
+    let next_idx = app.current_player_index();
+    if app.game_state.players()[next_idx].player_type() == &PlayerType::Computer {
+        debug(format!(
+            "Player {} is up after the defense, continuing",
+            next_idx
+        ));
+        AiStep::Continue
+    } else {
+        debug("Human is up after the defense, ending AI processing");
+        AiStep::Done
+    }
+}
+/// Lets the AI answer the human's defense, pass or take when an AI player is up next
+pub fn continue_after_defense(app: &mut App) {
+    if after_defense(app) == AiStep::Continue {
+        process_ai_turn(app);
+    }
+}
+/// The Drawing phase pauses for a keypress whenever a human is playing,
+/// games between AI players keep auto-advancing.
+fn waiting_for_draw_acknowledgement(app: &App) -> bool {
+    *app.game_state.game_phase() == GamePhase::Drawing && app.game_state.has_human_player()
 }
 /// Handle AI attack phase
-fn handle_ai_attack(app: &mut App, player_idx: usize) -> Result<(), String> {
+fn handle_ai_attack(app: &mut App, player_idx: usize) -> Result<(), GameError> {
     debug("AI is attacking");
     if *app.game_state.game_phase() != GamePhase::Attack {
         debug("AI called to attack but not in attack phase");
@@ -164,7 +300,7 @@ fn handle_ai_attack(app: &mut App, player_idx: usize) -> Result<(), String> {
                 }
                 Err(e) => {
                     debug(format!("AI attack failed: {}", e));
-                    return Err(e.to_string());
+                    return Err(e);
                 }
             }
         }
@@ -173,21 +309,43 @@ fn handle_ai_attack(app: &mut App, player_idx: usize) -> Result<(), String> {
                 "AI successfully attacked with {} cards",
                 sorted_indices.len()
             ));
-            // Verify we've transitioned to defense phase
-            if *app.game_state.game_phase() != GamePhase::Defense {
-                debug("Warning: Game did not transition to Defense phase after successful attack");
-                let defender_idx = (player_idx + 1) % app.game_state.players().len();
-                app.game_state
-                    .set_phase_to_defense(player_idx, defender_idx);
-            }
+            // `attack` has moved the game on to the Defense phase and seated the defender
         }
     } else {
         debug("AI decided to pass (no attacks)");
     }
     Ok(())
 }
+/// Handle the AI's turn in the reinforcement window after all attacks were beaten, as the
+/// attacker or as another player throwing in. Reuses the continuation logic of
+/// `make_attack_move`, only cards matching the table are thrown in. If nothing is added
+/// the next player may throw in, or the round ends.
+fn handle_ai_reinforce(app: &mut App, player_idx: usize) -> Result<(), GameError> {
+    if app.game_state.current_thrower() != player_idx {
+        return Ok(());
+    }
+    let mut added = 0;
+    if let Some(cards) = app.ai_player.make_attack_move(&app.game_state, player_idx) {
+        // Highest index first to prevent shifting
+        let mut sorted_indices: Vec<usize> = cards.iter().map(|(idx, _)| *idx).collect();
+        sorted_indices.sort_by(|a, b| b.cmp(a));
+        for idx in sorted_indices {
+            match app.game_state.throw_in(player_idx, idx) {
+                Ok(_) => added += 1,
+                Err(e) => debug(format!("AI skipped reinforcement card {}: {}", idx, e)),
+            }
+        }
+    }
+    if added == 0 {
+        debug(format!("AI player {} adds nothing", player_idx));
+        app.game_state.decline_throw_in();
+    } else {
+        debug(format!("AI reinforced the attack with {} cards", added));
+    }
+    Ok(())
+}
 /// Handle AI defense phase
-fn handle_ai_defense(app: &mut App, player_idx: usize) -> Result<(), String> {
+fn handle_ai_defense(app: &mut App, player_idx: usize) -> Result<(), GameError> {
     debug("AI is defending");
     // Check if the game state is valid for defense
     if *app.game_state.game_phase() != GamePhase::Defense {
@@ -206,9 +364,7 @@ fn handle_ai_defense(app: &mut App, player_idx: usize) -> Result<(), String> {
     // Check if AI should take cards instead of defending
     if app.ai_player.should_take_cards(&app.game_state, player_idx) {
         debug("AI decided to take cards");
-        if let Err(e) = app.game_state.take_cards() {
-            return Err(e.to_string());
-        }
+        app.game_state.take_cards()?;
         return Ok(());
     }
     // Get the table state
@@ -223,79 +379,25 @@ fn handle_ai_defense(app: &mut App, player_idx: usize) -> Result<(), String> {
         debug("All attacks already defended");
         return Ok(());
     }
-    // Try to defend each undefended attack one at a time
-    let mut defense_failed = false;
-    while !defense_failed
-        && app
-            .game_state
-            .table_cards()
+    // Passing is a separate decision, made before looking for a defense
+    if let Some((hand_idx, card)) = app.ai_player.make_pass_move(&app.game_state, player_idx)
+        && let Some(attack_idx) = table_cards
             .iter()
-            .any(|(_, d)| d.is_none())
+            .position(|(_, defense)| defense.is_none())
     {
-        if let Some(defense_cards) = app.ai_player.make_defense_move(&app.game_state, player_idx) {
-            debug(format!("AI defending with cards: {:?}", defense_cards));
-            // Process each defense card
-            for (_table_idx, card) in &defense_cards {
-                // We need to find the hand index of this card
-                if let Some(hand_idx) = app.find_card_index_in_hand(player_idx, *card) {
-                    match app.game_state.defend(hand_idx) {
-                        Ok(_) => {
-                            debug(format!("AI successfully defended with card {}", hand_idx));
-                            // Check if a pass occurred by looking at the defender change
-                            if app.game_state.current_defender() != player_idx {
-                                debug("AI passed the card to a different player");
-                                return Ok(());
-                            }
-                            let all_defended = !app
-                                .game_state
-                                .table_cards()
-                                .iter()
-                                .any(|(_, defense)| defense.is_none());
-                            if all_defended {
-                                debug("AI successfully defended all attacks");
-                                // Get all cards from the table for discarding
-                                let cards_to_discard: Vec<(usize, crate::game::card::Card)> = app
-                                    .game_state
-                                    .table_cards()
-                                    .iter()
-                                    .enumerate()
-                                    .filter_map(|(idx, (_, defense))| {
-                                        defense.map(|card| (idx, card))
-                                    })
-                                    .collect();
-                                // Discard the cards
-                                app.game_state.discard_cards(cards_to_discard);
-                                return Ok(());
-                            }
-                        }
-                        Err(e) => {
-                            debug(format!("AI defense failed: {}", e));
-                            defense_failed = true;
-                            break;
-                        }
-                    }
-                } else {
-                    debug("Card not found in AI's hand");
-                    defense_failed = true;
-                    break;
-                }
-            }
-        } else {
-            debug("AI cannot defend further");
-            defense_failed = true;
-        }
+        debug(format!("AI passing the attack with {}", card));
+        return app.game_state.pass_attack(hand_idx, attack_idx);
     }
-    if defense_failed
-        || app
-            .game_state
-            .table_cards()
-            .iter()
-            .any(|(_, d)| d.is_none())
-    {
-        debug("AI taking cards after failed defense");
-        if let Err(e) = app.game_state.take_cards() {
-            return Err(e.to_string());
-        }
+    // Beat every open attack at once, or take when they can't all be beaten
+    let Some(plan) = app.ai_player.make_full_defense(&app.game_state, player_idx) else {
+        debug("AI cannot beat every attack, taking cards");
+        return app.game_state.take_cards();
+    };
+    debug(format!("AI defending with cards: {:?}", plan));
+    if let Err(e) = app.game_state.defend_with(&plan) {
+        debug(format!("AI defense failed: {}, taking cards", e));
+        return app.game_state.take_cards();
     }
+    debug("AI successfully defended all attacks");
     Ok(())
 }
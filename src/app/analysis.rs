@@ -0,0 +1,252 @@
+/*
+ * analysis.rs - "How the AI would have played your hand"
+ *
+ * This file contains the post-game analysis shown on the game over screen:
+ * - Logging the position before every human move and what the human did there
+ * - Asking the selected difficulty's strategy what it would have done in each position
+ * - Listing the moves where the two differ
+ */
+use super::app_core::App;
+use super::input::AppAction;
+use crate::game::ai::seed_ai_rng;
+use crate::game::{AiDifficulty, AiPlayer, Card, GamePhase, GameState};
+use crate::ui::debug_overlay::debug;
+
+/// A move as the analysis compares it: the kind of decision and the cards it used
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Move {
+    Attack(Vec<Card>), // Opened or threw in with these cards
+    Stop,              // Added nothing more to the round
+    Defend(Vec<Card>), // Beat attacks with these cards
+    Pass(Card),        // Passed the attack on with this card
+    Take,
+}
+
+impl Move {
+    /// Whether `self`, the human's move, is something the AI would also have done.
+    /// A defense or attack with fewer cards still agrees, the rest can follow.
+    /// Duplicates from a multi-deck game count once each, two copies need two in the plan.
+    fn agrees_with(&self, suggested: &Move) -> bool {
+        match (self, suggested) {
+            (Move::Attack(played), Move::Attack(suggested))
+            | (Move::Defend(played), Move::Defend(suggested)) => {
+                cards_gone(played, suggested).is_empty()
+            }
+            (played, suggested) => played == suggested,
+        }
+    }
+}
+
+/// The position before a human move and the move that was made from it
+#[derive(Debug, Clone)]
+pub struct DecisionPoint {
+    pub before: GameState,
+    pub player_idx: usize,
+    pub played: Move,
+}
+
+/// A human move the AI would have made differently
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    pub round: u32,
+    pub played: Move,
+    pub suggested: Move,
+}
+
+/// The result of comparing a finished game's human moves with the AI's choices
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PostGameAnalysis {
+    pub difficulty: AiDifficulty, // The strategy asked, never Dynamic
+    pub decisions: usize,         // Human moves looked at
+    pub divergences: Vec<Divergence>,
+}
+
+/// Cards in `before` that are no longer in `after`, each duplicate counted once
+fn cards_gone(before: &[Card], after: &[Card]) -> Vec<Card> {
+    let mut remaining = after.to_vec();
+    before
+        .iter()
+        .filter(|card| match remaining.iter().position(|c| c == *card) {
+            Some(idx) => {
+                remaining.swap_remove(idx);
+                false
+            }
+            None => true,
+        })
+        .copied()
+        .collect()
+}
+
+/// What the human did with `action`, judged by the hand before and after it.
+/// `None` for actions that aren't a move.
+fn played_move(action: &AppAction, phase: &GamePhase, gone: Vec<Card>) -> Option<Move> {
+    match (phase, action) {
+        (GamePhase::Defense, AppAction::TakeCards) => Some(Move::Take),
+        (GamePhase::Defense, AppAction::PassDefense) => gone.first().copied().map(Move::Pass),
+        (GamePhase::Defense, AppAction::PlaySelectedCard | AppAction::QuickDefend) => {
+            Some(Move::Defend(gone))
+        }
+        (
+            GamePhase::Attack | GamePhase::Reinforce,
+            AppAction::PlaySelectedCard | AppAction::QuickAttack,
+        ) => Some(Move::Attack(gone)),
+        (GamePhase::Attack | GamePhase::Reinforce, AppAction::PassTurn) => Some(Move::Stop),
+        _ => None,
+    }
+}
+
+/// The move `ai` makes in `state` for `player_idx`, decided the way `ai_handler` asks
+fn suggested_move(ai: &AiPlayer, state: &GameState, player_idx: usize) -> Move {
+    let cards = |plan: Vec<(usize, Card)>| plan.into_iter().map(|(_, card)| card).collect();
+    if *state.game_phase() == GamePhase::Defense {
+        if ai.should_take_cards(state, player_idx) {
+            return Move::Take;
+        }
+        if let Some((_, card)) = ai.make_pass_move(state, player_idx) {
+            return Move::Pass(card);
+        }
+        return match ai.make_full_defense(state, player_idx) {
+            Some(plan) => Move::Defend(cards(plan)),
+            None => Move::Take,
+        };
+    }
+    match ai.make_attack_move(state, player_idx) {
+        Some(plan) if !plan.is_empty() => Move::Attack(cards(plan)),
+        _ => Move::Stop,
+    }
+}
+
+/// Compares every logged human move with what `difficulty` would have played.
+/// The AI's chance rolls are seeded per move, so the same game always gets the same analysis.
+pub fn analyse(log: &[DecisionPoint], difficulty: AiDifficulty) -> PostGameAnalysis {
+    let ai = AiPlayer::new(difficulty);
+    let divergences = log
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, decision)| {
+            seed_ai_rng(Some(idx as u64));
+            let suggested = suggested_move(&ai, &decision.before, decision.player_idx);
+            (!decision.played.agrees_with(&suggested)).then(|| Divergence {
+                round: decision.before.current_round(),
+                played: decision.played.clone(),
+                suggested,
+            })
+        })
+        .collect();
+    seed_ai_rng(None);
+    PostGameAnalysis {
+        difficulty,
+        decisions: log.len(),
+        divergences,
+    }
+}
+
+impl App {
+    /// Adds the human move `action` just made from `before` to the decision log.
+    /// Playing on from a position already in the log, after a practice takeback,
+    /// replaces the line that was played from it before.
+    pub fn log_decision(&mut self, before: GameState, player_idx: usize, action: &AppAction) {
+        if before == self.game_state || self.is_hotseat() {
+            return;
+        }
+        let gone = cards_gone(
+            before.players()[player_idx].hand(),
+            self.game_state.players()[player_idx].hand(),
+        );
+        let Some(played) = played_move(action, before.game_phase(), gone) else {
+            return;
+        };
+        if let Some(idx) = self
+            .decision_log
+            .iter()
+            .position(|decision| decision.before == before)
+        {
+            self.decision_log.truncate(idx);
+        }
+        debug(format!("Logged decision: {:?}", played));
+        self.decision_log.push(DecisionPoint {
+            before,
+            player_idx,
+            played,
+        });
+    }
+    /// Runs the analysis of the game that just ended. Dynamic is judged as the level it
+    /// played this game at.
+    pub fn analyse_game(&mut self) {
+        let difficulty = match self.selected_difficulty {
+            AiDifficulty::Dynamic => self.match_record.difficulty(),
+            difficulty => difficulty,
+        };
+        self.analysis_scroll = 0;
+        self.analysis =
+            (!self.decision_log.is_empty()).then(|| analyse(&self.decision_log, difficulty));
+    }
+    /// Scrolls the analysis on the game over screen by one line
+    pub fn scroll_analysis(&mut self, down: bool) {
+        let Some(analysis) = &self.analysis else {
+            return;
+        };
+        self.analysis_scroll = if down {
+            (self.analysis_scroll + 1).min(analysis.divergences.len().saturating_sub(1))
+        } else {
+            self.analysis_scroll.saturating_sub(1)
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::AppState;
+    use crate::game::card::{Rank, Suit};
+    use crossterm::event::KeyCode;
+
+    #[test]
+    /// Test that taking an attack the AI would have beaten is flagged, and that a defense
+    /// with a card from the AI's own plan is not
+    fn test_analysis_flags_a_needless_take() {
+        let mut app = App::new();
+        app.selected_difficulty = AiDifficulty::Medium;
+        app.debug_step_ai = true;
+        let game_state = &mut app.game_state;
+        game_state.trump_suit = Some(Suit::Spades);
+        game_state.players[0].hand = vec![
+            Card::new(Suit::Hearts, Rank::Nine),
+            Card::new(Suit::Clubs, Rank::Six),
+        ];
+        game_state.players[1].hand = vec![Card::new(Suit::Clubs, Rank::Ten); 4];
+        game_state.table_cards = vec![(Card::new(Suit::Hearts, Rank::Seven), None)];
+        game_state.current_attacker = 1;
+        game_state.current_defender = 0;
+        game_state.game_phase = GamePhase::Defense;
+        app.app_state = AppState::Playing;
+        let start = app.game_state.clone();
+
+        app.on_key(KeyCode::Char('t'));
+        assert_eq!(app.decision_log.len(), 1);
+        assert_eq!(app.decision_log[0].played, Move::Take);
+        app.analyse_game();
+        let analysis = app.analysis.clone().unwrap();
+        assert_eq!(analysis.decisions, 1);
+        assert_eq!(
+            analysis.divergences,
+            vec![Divergence {
+                round: 1,
+                played: Move::Take,
+                suggested: Move::Defend(vec![Card::new(Suit::Hearts, Rank::Nine)]),
+            }]
+        );
+
+        // Defending from the same position replaces the logged take
+        app.game_state = start;
+        app.selected_card_idx = Some(0);
+        app.on_key(KeyCode::Enter);
+        assert_eq!(app.decision_log.len(), 1);
+        assert_eq!(
+            app.decision_log[0].played,
+            Move::Defend(vec![Card::new(Suit::Hearts, Rank::Nine)])
+        );
+        app.analyse_game();
+        assert!(app.analysis.unwrap().divergences.is_empty());
+    }
+}
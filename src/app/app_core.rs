@@ -1,24 +1,81 @@
+use super::analysis::{DecisionPoint, PostGameAnalysis};
+use super::demo::DemoGame;
+use super::keybindings::KeyBindings;
+use super::practice::PracticeHistory;
 use super::render::render_ui;
-use super::state::AppState;
+use super::save_game::saved_game_exists;
+use super::state::{AppState, SelectionStyle};
+use crate::game::ai::{record_commentary, trace_decisions};
+use crate::game::game_state::MAX_DECKS;
+use crate::ui::card_view::CardSize;
 use crate::ui::debug_overlay::{debug, error, info};
+use crate::ui::i18n::{current_locale, set_locale};
+use crate::ui::theme::Theme;
 use crossterm::ExecutableCommand;
+use crossterm::event::KeyCode;
 use crossterm::terminal::{LeaveAlternateScreen, disable_raw_mode};
 use ratatui::Terminal;
 use ratatui::backend::Backend;
 use std::io::{self, stdout};
+use std::time::{Duration, Instant};
 
-use crate::game::{AiDifficulty, AiPlayer, GamePhase, GameState, PlayerType};
+use crate::game::{
+    AiDifficulty, AiPlayer, GamePhase, GameState, HandSortMode, MatchRecord, PlayerType,
+};
 
 pub struct App {
     pub game_state: GameState,
     pub app_state: AppState,
     pub selected_card_idx: Option<usize>,
+    pub selected_attack_idx: Option<usize>,
     pub selected_cards: Vec<usize>,
     pub ai_player: AiPlayer,
     pub should_quit: bool,
+    pub pending_quit: bool,
+    pub pending_surrender: bool,
+    pub has_saved_game: bool,
     pub show_debug: bool,
+    pub debug_scroll: usize, // Log messages the debug overlay is scrolled back by
+    pub debug_step_ai: bool, // AI waits for '.' and moves one sub-action at a time
+    pub debug_reveal_hands: bool, // AI hands are drawn face up, only honoured with show_debug
+    pub debug_show_indices: bool, // Hand indices over the human's cards, only honoured with show_debug
     pub multiple_selection_mode: bool,
+    pub hand_sort_mode: HandSortMode, // How the human's hand is ordered, kept across games
+    pub selection_style: SelectionStyle, // Whether the hand cursor wraps around at the ends
+    pub card_size: CardSize,
+    pub theme: Theme,          // Colours of every screen, kept in the settings file
+    pub show_aids: bool,       // Card counts and hand value in the status bar, off for purists
+    pub show_commentary: bool, // The panel explaining the AI's moves in plain words
     pub selected_difficulty: AiDifficulty,
+    pub match_record: MatchRecord, // The human's recent results this session, for Dynamic
+    pub turn_timer: Option<Duration>, // Optional shot clock for the human's turn
+    pub turn_started: Option<Instant>, // When the human's current turn began
+    pub practice_mode: bool,       // New games keep a full move history that PageUp/PageDown walk
+    pub defend_first: bool,        // The AI always opens the first round against the human
+    pub practice: Option<PracticeHistory>, // History of the practice game in progress
+    pub defense_briefing: bool,    // The AI just attacked the human, who reviews the table first
+    pub ai_think_time: Option<Duration>, // How long the AI shows it is thinking, `None` moves at once
+    pub ai_thinking_since: Option<Instant>, // The AI is about to move, see `resume_ai_turn`
+    pub demo_difficulties: [AiDifficulty; 2], // The two AI players of the main menu demo
+    pub demo: Option<DemoGame>,          // The demo game being shown, if any
+    pub viewer: Option<usize>,           // The human whose hand is face up in a hotseat game
+    pub handover_to: Option<usize>,      // Both hands are hidden until this human has the device
+    pub table_scroll: Option<usize>, // Table pair scrolled to with '['/']', see `TableView::scroll`
+    pub deal_interval: Option<Duration>, // Time between dealt cards, `None` deals at once
+    pub dealing_since: Option<Instant>, // The hands are still being revealed, see `dealt_cards`
+    pub decision_log: Vec<DecisionPoint>, // The human's moves this game, for the analysis
+    pub undo_history: Vec<GameState>, // Positions before the human's moves since the AI last moved
+    pub key_bindings: KeyBindings,   // The keys of every action, see `keybindings.rs`
+    pub analysis: Option<PostGameAnalysis>, // Shown on the game over screen, see `analyse_game`
+    pub analysis_scroll: usize,
+    pub replay_step: usize, // Moves into the game the replay is showing, 0 for the deal
+    pub last_toggle: Option<(KeyCode, Instant)>, // Last toggle key seen, see `TOGGLE_DEBOUNCE`
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl App {
@@ -33,12 +90,49 @@ impl App {
             game_state,
             app_state: AppState::MainMenu,
             selected_card_idx: None,
+            selected_attack_idx: None,
             selected_cards: Vec::new(),
             ai_player: AiPlayer::new(AiDifficulty::Medium),
             should_quit: false,
+            pending_quit: false,
+            pending_surrender: false,
+            has_saved_game: saved_game_exists(),
             show_debug: false,
+            debug_scroll: 0,
+            debug_step_ai: false,
+            debug_reveal_hands: false,
+            debug_show_indices: false,
             multiple_selection_mode: false,
+            hand_sort_mode: HandSortMode::default(),
+            selection_style: SelectionStyle::default(),
+            card_size: CardSize::default(),
+            theme: Theme::default(),
+            show_aids: true,
+            show_commentary: false,
             selected_difficulty: AiDifficulty::Medium,
+            match_record: MatchRecord::default(),
+            turn_timer: None,
+            turn_started: None,
+            practice_mode: false,
+            defend_first: false,
+            practice: None,
+            defense_briefing: false,
+            ai_think_time: None,
+            ai_thinking_since: None,
+            demo_difficulties: [AiDifficulty::Medium, AiDifficulty::Hard],
+            demo: None,
+            viewer: None,
+            handover_to: None,
+            table_scroll: None,
+            deal_interval: None,
+            dealing_since: None,
+            decision_log: Vec::new(),
+            undo_history: Vec::new(),
+            key_bindings: KeyBindings::default(),
+            analysis: None,
+            analysis_scroll: 0,
+            replay_step: 0,
+            last_toggle: None,
         }
     }
     /// Safely exits the game, restoring terminal state
@@ -60,6 +154,9 @@ impl App {
     /// Show the debug overlay while in game (Press 'd' to toggle)
     pub fn toggle_debug(&mut self) {
         self.show_debug = !self.show_debug;
+        self.debug_scroll = 0;
+        // The AI only records its reasoning while the overlay can show it
+        trace_decisions(self.show_debug);
     }
     /// Quit the game and call `safe_exit`
     /// Print the error message if terminal restoration fails for debugging purposes.
@@ -69,10 +166,48 @@ impl App {
             error(format!("Failed to restore terminal during quit: {}", e));
         }
     }
+    /// Ask for confirmation before quitting a game in progress.
+    /// The next key press either confirms with 'y' or cancels.
+    pub fn request_quit(&mut self) {
+        self.pending_quit = true;
+    }
+    /// Resolve a pending quit confirmation with the given key.
+    pub fn confirm_quit(&mut self, key: KeyCode) {
+        self.pending_quit = false;
+        if matches!(key, KeyCode::Char('y') | KeyCode::Char('Y')) {
+            // Keep the match so it can be continued from the main menu
+            if self.app_state == AppState::Playing
+                && *self.game_state.game_phase() != GamePhase::GameOver
+                && let Err(e) = self.save_game()
+            {
+                error(format!("Failed to save game: {}", e));
+            }
+            self.quit();
+        } else {
+            debug("Quit cancelled");
+        }
+    }
+    /// Ask for confirmation before conceding the game in progress.
+    pub fn request_surrender(&mut self) {
+        self.pending_surrender = true;
+    }
+    /// Resolve a pending surrender confirmation with the given key.
+    pub fn confirm_surrender(&mut self, key: KeyCode) {
+        self.pending_surrender = false;
+        if matches!(key, KeyCode::Char('y') | KeyCode::Char('Y')) {
+            self.surrender_action();
+        } else {
+            debug("Surrender cancelled");
+        }
+    }
     /// Show rules on the main menu page.
     pub fn show_rules(&mut self) {
         self.app_state = AppState::RulesPage;
     }
+    /// Lists the rules the new game will be dealt with, Enter starts it.
+    pub fn show_rules_summary(&mut self) {
+        self.app_state = AppState::RulesSummary;
+    }
     /// Backlink to the main menu from the menu pages.
     pub fn return_to_menu(&mut self) {
         self.app_state = AppState::MainMenu;
@@ -85,10 +220,165 @@ impl App {
     /// Return to the main menu afterwards.
     pub fn select_difficulty(&mut self, difficulty: AiDifficulty) {
         self.selected_difficulty = difficulty;
-        self.ai_player = AiPlayer::new(difficulty);
+        self.ai_player = self.new_ai_player();
         info(format!("AI difficulty changed to: {}", difficulty));
         self.app_state = AppState::MainMenu;
     }
+    /// An AI for the selected difficulty. Dynamic reads the match record to pick its level.
+    pub fn new_ai_player(&self) -> AiPlayer {
+        match self.selected_difficulty {
+            AiDifficulty::Dynamic => AiPlayer::new_adaptive(&self.match_record),
+            difficulty => AiPlayer::new(difficulty),
+        }
+    }
+    /// Moves to the game over screen and feeds the result into the match record.
+    /// A draw has no loser and is not recorded, and neither is a practice game.
+    /// A demo game stays on the demo screen, see `demo_tick`.
+    pub fn finish_game(&mut self) {
+        if matches!(self.app_state, AppState::GameOver | AppState::Demo) {
+            return;
+        }
+        self.app_state = AppState::GameOver;
+        // Before the result moves the record, so Dynamic is judged at this game's level
+        self.analyse_game();
+        // The record tunes Dynamic against one human, two at one terminal tell it nothing
+        if self.practice.is_some() || self.is_hotseat() {
+            return;
+        }
+        let human_idx = self
+            .game_state
+            .players()
+            .iter()
+            .position(|player| player.player_type() == &PlayerType::Human);
+        if let (Some(human_idx), Some(durak_idx)) = (human_idx, self.game_state.durak()) {
+            self.match_record.record(durak_idx != human_idx);
+            debug(format!("Match record: {:?}", self.match_record));
+        }
+    }
+    /// Cycles the trump rule from the main menu.
+    /// Takes effect the next time `setup_game` deals a new game.
+    pub fn cycle_trump_rule(&mut self) {
+        self.game_state.trump_rule = self.game_state.trump_rule.next();
+        info(format!("Trump rule: {:?}", self.game_state.trump_rule));
+    }
+    /// Toggles the trump reveal-pass house rule from the main menu.
+    pub fn toggle_trump_reveal_pass(&mut self) {
+        self.game_state.allow_trump_reveal_pass = !self.game_state.allow_trump_reveal_pass;
+        info(format!(
+            "Trump reveal pass: {}",
+            self.game_state.allow_trump_reveal_pass
+        ));
+    }
+    /// Toggles the house rule that forbids passing an attack on with a trump.
+    pub fn toggle_trump_pass(&mut self) {
+        self.game_state.forbid_trump_pass = !self.game_state.forbid_trump_pass;
+        info(format!(
+            "No trump pass: {}",
+            self.game_state.forbid_trump_pass
+        ));
+    }
+    /// Toggles the jokers variant from the main menu.
+    /// Takes effect the next time `setup_game` deals a new game.
+    pub fn toggle_jokers(&mut self) {
+        self.game_state.jokers = !self.game_state.jokers;
+        info(format!("Jokers: {}", self.game_state.jokers));
+    }
+    /// Cycles how many packs the deck is made of, from one up to `MAX_DECKS`.
+    /// Takes effect the next time `setup_game` deals a new game.
+    pub fn cycle_deck_count(&mut self) {
+        self.game_state.deck_count = self.game_state.deck_count % MAX_DECKS + 1;
+        info(format!("Decks: {}", self.game_state.deck_count));
+    }
+    /// Cycles the pack between 20, 36 and 52 cards from the difficulty page, skipping
+    /// packs too small for the players seated. Takes effect the next time `setup_game`
+    /// deals a new game.
+    pub fn cycle_deck_size(&mut self) {
+        let mut pack_size = self.game_state.pack_size.next();
+        while !self.game_state.pack_fits_table(pack_size) {
+            pack_size = pack_size.next();
+        }
+        self.game_state.pack_size = pack_size;
+        info(format!(
+            "Deck size: {} cards",
+            self.game_state.pack_size.cards()
+        ));
+    }
+    /// Toggles the mulligan house rule from the main menu.
+    pub fn toggle_mulligan(&mut self) {
+        self.game_state.allow_mulligan = !self.game_state.allow_mulligan;
+        info(format!("Mulligan: {}", self.game_state.allow_mulligan));
+    }
+    /// Switches the hand cursor between wrapping and stopping at the ends, from the main menu.
+    pub fn toggle_selection_style(&mut self) {
+        self.selection_style = self.selection_style.next();
+        info(format!("Selection style: {:?}", self.selection_style));
+    }
+    /// Cycles the size the cards are drawn at from the main menu.
+    pub fn cycle_card_size(&mut self) {
+        self.card_size = self.card_size.next();
+        info(format!("Card size: {:?}", self.card_size));
+    }
+    /// Toggles the unseen-card counts from the main menu.
+    pub fn toggle_aids(&mut self) {
+        self.show_aids = !self.show_aids;
+        info(format!("Show aids: {}", self.show_aids));
+    }
+    /// Shows or hides the AI commentary panel from the main menu. The AI only comments
+    /// on its moves while the panel is on.
+    pub fn toggle_commentary(&mut self) {
+        self.show_commentary = !self.show_commentary;
+        record_commentary(self.show_commentary);
+        info(format!("Show commentary: {}", self.show_commentary));
+    }
+    /// Toggles the flat six-attack limit from the main menu.
+    pub fn toggle_flat_attack_limit(&mut self) {
+        self.game_state.flat_attack_limit = !self.game_state.flat_attack_limit;
+        info(format!(
+            "Flat attack limit: {}",
+            self.game_state.flat_attack_limit
+        ));
+    }
+    /// Switches the human's hand to the next sort order
+    pub fn cycle_sort(&mut self) {
+        self.hand_sort_mode = self.hand_sort_mode.next();
+        info(format!("Hand sort: {:?}", self.hand_sort_mode));
+        self.apply_hand_sort();
+    }
+    /// Re-sorts the humans' hands by `hand_sort_mode`. Selections are card indices into the
+    /// hand on screen, so they are moved along with their cards.
+    pub fn apply_hand_sort(&mut self) {
+        let Some(human_idx) = self.viewing_player() else {
+            return;
+        };
+        let hand = self.game_state.players()[human_idx].hand().to_vec();
+        let selected_card = self
+            .selected_card_idx
+            .and_then(|idx| hand.get(idx).copied());
+        let selected_cards: Vec<_> = self
+            .selected_cards
+            .iter()
+            .filter_map(|&idx| hand.get(idx).copied())
+            .collect();
+        for player in &mut self.game_state.players {
+            if player.player_type() == &PlayerType::Human {
+                player.set_sort_mode(self.hand_sort_mode);
+            }
+        }
+        // Found by index-claiming lookup, identical cards of a multi-deck game stay apart
+        let sorted = &self.game_state.players()[human_idx];
+        self.selected_card_idx = selected_card.and_then(|card| sorted.find_cards(&[card])[0]);
+        self.selected_cards = sorted
+            .find_cards(&selected_cards)
+            .into_iter()
+            .flatten()
+            .collect();
+    }
+    /// Switches the UI to the next available language
+    pub fn cycle_language(&mut self) {
+        let locale = current_locale().next();
+        set_locale(locale);
+        info(format!("Language: {}", locale));
+    }
     /// Toggles the multiple selection mode for the player.
     /// When enabled, the player can select multiple cards of the same rank.
     /// Should update the Controls UI with "ON"
@@ -108,14 +398,46 @@ impl App {
             debug(format!("Selected card at index {}", card_idx));
         }
     }
+    /// Drops the multi-card selection and leaves multiple selection mode.
+    /// Called whenever the hand changes, since `selected_cards` holds indices into it.
+    pub fn clear_multi_selection(&mut self) {
+        self.selected_cards.clear();
+        self.multiple_selection_mode = false;
+    }
+    /// Size of the hand the card cursor moves over, the viewer's
+    fn viewer_hand_size(&self) -> usize {
+        self.viewing_player()
+            .map_or(0, |idx| self.game_state.players()[idx].hand_size())
+    }
+    /// Puts the card cursor back on the first card after a card has been played. The
+    /// played card has left the hand, so the old index would point at its neighbour or
+    /// past the end. `None` once the hand is empty.
+    pub fn reset_card_selection(&mut self) {
+        self.selected_card_idx = (self.viewer_hand_size() > 0).then_some(0);
+    }
+    /// Keeps the card cursor inside the hand, called after every move and draw. A cursor
+    /// past the end of the hand goes back to the first card.
+    pub fn clamp_card_selection(&mut self) {
+        if let Some(idx) = self.selected_card_idx
+            && idx >= self.viewer_hand_size()
+        {
+            self.reset_card_selection();
+        }
+    }
     /// Get the current player index based on the game phase.
     pub fn current_player_index(&self) -> usize {
         match *self.game_state.game_phase() {
             GamePhase::Attack => self.game_state.current_attacker(),
             GamePhase::Defense => self.game_state.current_defender(),
+            GamePhase::Reinforce => self.game_state.current_thrower(),
             _ => self.game_state.current_attacker(),
         }
     }
+    /// Frame of the thinking spinner while the AI is about to move, advancing every tick
+    pub fn ai_thinking_frame(&self) -> Option<usize> {
+        let since = self.ai_thinking_since?;
+        Some((since.elapsed().as_millis() / 100) as usize)
+    }
     /// Calls the render UI method
     /// See `render.rs` for implementation.
     pub fn render<B: Backend>(&self, terminal: &mut Terminal<B>) -> io::Result<()> {
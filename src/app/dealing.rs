@@ -0,0 +1,82 @@
+/*
+ * dealing.rs - The dealing animation at the start of a game
+ *
+ * This file contains the timing of the deal, which is only for show:
+ * - `setup_game` has already dealt every card, the hands are revealed one card at a time
+ * - The reveal advances off the ticks of the `run` loop
+ * - Any key skips the rest of the deal
+ */
+use super::app_core::App;
+use crate::ui::debug_overlay::debug;
+use std::time::Instant;
+
+impl App {
+    /// Starts revealing the freshly dealt hands, unless `deal_interval` is unset or zero
+    pub fn start_dealing(&mut self) {
+        self.dealing_since = self
+            .deal_interval
+            .filter(|interval| !interval.is_zero())
+            .map(|_| Instant::now());
+    }
+    /// How many cards of each hand are shown while the deal is animated, `None` once every
+    /// card is on screen
+    pub fn dealt_cards(&self) -> Option<usize> {
+        let since = self.dealing_since?;
+        let interval = self.deal_interval?.as_millis().max(1);
+        Some((since.elapsed().as_millis() / interval) as usize + 1)
+    }
+    /// Called on every tick of the `run` loop, ends the animation once the largest hand is
+    /// fully shown
+    pub fn advance_dealing(&mut self) {
+        let Some(dealt) = self.dealt_cards() else {
+            return;
+        };
+        let largest_hand = self
+            .game_state
+            .players()
+            .iter()
+            .map(|player| player.hand_size())
+            .max()
+            .unwrap_or(0);
+        if dealt > largest_hand {
+            self.finish_dealing();
+        }
+    }
+    /// Shows every card at once, bound to any key during the deal
+    pub fn finish_dealing(&mut self) {
+        if self.dealing_since.take().is_some() {
+            debug("Deal finished");
+            self.restart_turn_timer();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::AppState;
+    use crossterm::event::KeyCode;
+    use std::time::Duration;
+
+    #[test]
+    /// Test that the deal reveals the hands card by card and a key press shows them all
+    /// without playing a move
+    fn test_dealing_reveals_cards_and_can_be_skipped() {
+        let mut app = App::new();
+        app.start_game_action();
+        assert_eq!(app.dealt_cards(), None);
+        app.deal_interval = Some(Duration::from_secs(60));
+        app.start_game_action();
+        assert_eq!(app.app_state, AppState::Playing);
+        assert_eq!(app.dealt_cards(), Some(1));
+        app.advance_dealing();
+        assert_eq!(app.dealt_cards(), Some(1));
+        let hand = app.game_state.players()[0].hand().to_vec();
+        app.on_key(KeyCode::Enter);
+        assert_eq!(app.dealt_cards(), None);
+        assert_eq!(app.game_state.players()[0].hand(), &hand[..]);
+        app.deal_interval = Some(Duration::ZERO);
+        app.start_dealing();
+        assert_eq!(app.dealt_cards(), None);
+    }
+}
@@ -0,0 +1,159 @@
+/*
+ * demo.rs - AI-vs-AI demo game on the main menu
+ *
+ * This file contains the logic for the demo screen:
+ * - Picking a difficulty for each of the two AI players
+ * - Setting the menu's game aside while two computer players play with the same rules
+ * - Advancing the demo one AI action at a time, with a pause between moves
+ * - Dealing again once a demo game is over, until any key returns to the menu
+ */
+use super::ai_handler::step_ai_turn;
+use super::app_core::App;
+use super::state::AppState;
+use crate::game::ai::record_commentary;
+use crate::game::{AiDifficulty, AiPlayer, GamePhase, GameState, PlayerType};
+use crate::ui::debug_overlay::{debug, info};
+use crate::ui::i18n::tr_difficulty;
+use std::time::{Duration, Instant};
+
+/// Pause between two AI actions, so the demo can be followed
+const DEMO_MOVE_DELAY: Duration = Duration::from_millis(800);
+/// How long a finished demo game stays on screen before the next deal
+const DEMO_RESTART_DELAY: Duration = Duration::from_secs(3);
+
+/// A demo game in progress, along with what it replaced while it runs
+pub struct DemoGame {
+    ais: [AiPlayer; 2], // One AI per seat, swapped into `App::ai_player` for its moves
+    saved_game: GameState, // The menu's game and house rules, put back when the demo ends
+    saved_ai: AiPlayer,
+    last_move: Instant,
+}
+
+/// The next difficulty offered for a demo seat. Dynamic is left out, it adapts to the
+/// human's results and there is no human in a demo.
+fn next_demo_difficulty(difficulty: AiDifficulty) -> AiDifficulty {
+    match difficulty {
+        AiDifficulty::Easy => AiDifficulty::Medium,
+        AiDifficulty::Medium => AiDifficulty::Hard,
+        AiDifficulty::Hard | AiDifficulty::Dynamic => AiDifficulty::Easy,
+    }
+}
+
+/// A freshly dealt game between two computer players, with the house rules of `rules`
+fn deal_demo_game(rules: &GameState, difficulties: [AiDifficulty; 2]) -> GameState {
+    let mut game_state = GameState::new();
+    game_state.trump_rule = rules.trump_rule;
+    game_state.allow_trump_reveal_pass = rules.allow_trump_reveal_pass;
+    game_state.forbid_trump_pass = rules.forbid_trump_pass;
+    game_state.jokers = rules.jokers;
+    game_state.deck_count = rules.deck_count;
+    game_state.pack_size = rules.pack_size;
+    game_state.flat_attack_limit = rules.flat_attack_limit;
+    for (seat, difficulty) in difficulties.into_iter().enumerate() {
+        let name = format!("AI {} ({})", seat + 1, tr_difficulty(difficulty));
+        game_state.add_player(name, PlayerType::Computer);
+    }
+    game_state.setup_game();
+    game_state
+}
+
+impl App {
+    /// Shows the demo page, where the two AI players are picked before the demo starts.
+    pub fn show_demo_select(&mut self) {
+        self.app_state = AppState::DemoSelect;
+    }
+    /// Cycles the difficulty of the AI in `seat` on the demo page.
+    pub fn cycle_demo_difficulty(&mut self, seat: usize) {
+        self.demo_difficulties[seat] = next_demo_difficulty(self.demo_difficulties[seat]);
+        info(format!(
+            "Demo AI {} difficulty: {}",
+            seat + 1,
+            self.demo_difficulties[seat]
+        ));
+    }
+    /// Starts an AI-vs-AI demo game. The menu's game is set aside until `stop_demo`.
+    pub fn start_demo(&mut self) {
+        let demo_state = deal_demo_game(&self.game_state, self.demo_difficulties);
+        let [first, second] = self.demo_difficulties;
+        self.demo = Some(DemoGame {
+            ais: [AiPlayer::new(first), AiPlayer::new(second)],
+            saved_game: std::mem::replace(&mut self.game_state, demo_state),
+            saved_ai: std::mem::replace(&mut self.ai_player, AiPlayer::new(first)),
+            last_move: Instant::now(),
+        });
+        self.app_state = AppState::Demo;
+        record_commentary(self.show_commentary);
+        info(format!("Demo started: {} vs {}", first, second));
+    }
+    /// Ends the demo on any key and puts the menu's game back.
+    pub fn stop_demo(&mut self) {
+        if let Some(demo) = self.demo.take() {
+            self.game_state = demo.saved_game;
+            self.ai_player = demo.saved_ai;
+        }
+        self.app_state = AppState::MainMenu;
+        debug("Demo stopped");
+    }
+    /// Called on every tick of the `run` loop while the demo is shown. Makes one AI action
+    /// once the pause since the last one is over, and deals again after a finished game.
+    pub fn demo_tick(&mut self) {
+        let seat = self.current_player_index();
+        let Some(demo) = &mut self.demo else {
+            return;
+        };
+        let elapsed = demo.last_move.elapsed();
+        if *self.game_state.game_phase() == GamePhase::GameOver {
+            if elapsed >= DEMO_RESTART_DELAY {
+                self.game_state = deal_demo_game(&demo.saved_game, self.demo_difficulties);
+                demo.last_move = Instant::now();
+                debug("Demo game over, dealing again");
+            }
+            return;
+        }
+        if elapsed < DEMO_MOVE_DELAY {
+            return;
+        }
+        demo.last_move = Instant::now();
+        // Nobody has to dismiss the endgame banner, it stays up for one move
+        self.game_state.endgame_notice = false;
+        std::mem::swap(&mut self.ai_player, &mut demo.ais[seat]);
+        step_ai_turn(self);
+        if let Some(demo) = &mut self.demo {
+            std::mem::swap(&mut self.ai_player, &mut demo.ais[seat]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Test that the demo plays between two AI players and leaves the menu's game untouched
+    fn test_demo_restores_menu_game() {
+        let mut app = App::new();
+        app.game_state.jokers = true;
+        let before = app.game_state.clone();
+        app.start_demo();
+        assert_eq!(app.app_state, AppState::Demo);
+        assert!(app.game_state.jokers);
+        assert!(
+            app.game_state
+                .players()
+                .iter()
+                .all(|player| player.player_type() == &PlayerType::Computer)
+        );
+        let dealt = app.game_state.clone();
+        app.demo_tick();
+        assert_eq!(app.game_state, dealt, "the demo waits between moves");
+        if let Some(demo) = &mut app.demo {
+            demo.last_move = Instant::now() - DEMO_MOVE_DELAY;
+        }
+        app.demo_tick();
+        assert_ne!(app.game_state, dealt);
+        app.stop_demo();
+        assert_eq!(app.app_state, AppState::MainMenu);
+        assert_eq!(app.game_state, before);
+        assert!(app.demo.is_none());
+    }
+}
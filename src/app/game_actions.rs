@@ -7,23 +7,36 @@
  * - Card playing logic
  * - Pass/take actions
  */
-use super::ai_handler::process_ai_turn;
+use super::ai_handler::{continue_after_defense, process_ai_turn, step_ai_turn};
 use super::app_core::App;
-use crate::game::card::Card;
-use crate::game::{GamePhase, PlayerType};
-use crate::ui::debug_overlay::{debug, error};
+use super::state::SelectionStyle;
+use crate::game::ai::record_commentary;
+use crate::game::card::{Card, Rank};
+use crate::game::{AiDifficulty, AiPlayer, GameError, GamePhase, PlayerType};
+use crate::ui::debug_overlay::{debug, error, info, log_len};
+
+/// Log messages one PageUp or PageDown scrolls the debug overlay by
+const DEBUG_SCROLL_PAGE: usize = 10;
+
 impl App {
     /// Entry point for starting a new game.
     /// Sets the AppState to Playing and initializes the game state.
     pub fn start_game_action(&mut self) {
         self.app_state = super::state::AppState::Playing;
         self.game_state.setup_game();
+        if self.defend_first {
+            self.seat_human_as_defender();
+        }
+        self.apply_hand_sort();
         // clear cards just in case
         self.selected_card_idx = None;
-        self.selected_cards.clear();
-        self.multiple_selection_mode = false;
+        self.selected_attack_idx = None;
+        self.clear_multi_selection();
+        self.defense_briefing = false;
+        // Nothing from the last game in the commentary
+        record_commentary(self.show_commentary);
         // Create a new AI player with the selected difficulty
-        self.ai_player = crate::game::AiPlayer::new(self.selected_difficulty);
+        self.ai_player = self.new_ai_player();
         debug(format!(
             "Starting game with AI difficulty: {}",
             self.selected_difficulty
@@ -43,129 +56,390 @@ impl App {
                     "Hard AI: Will strategically track cards, exploit weaknesses, and plan ahead",
                 );
             }
+            crate::game::AiDifficulty::Dynamic => {
+                debug(format!(
+                    "Dynamic AI: Plays this game as {} based on your recent results",
+                    self.match_record.difficulty()
+                ));
+            }
         }
         debug("Game started!");
-        // Process AI turn if AI goes first
+        self.decision_log.clear();
+        self.undo_history.clear();
+        self.reset_viewer();
+        self.start_dealing();
+        self.begin_first_turn();
+        self.start_practice();
+    }
+    /// Toggles defending first from the main menu.
+    /// Takes effect the next time a game is started.
+    pub fn toggle_defend_first(&mut self) {
+        self.defend_first = !self.defend_first;
+        info(format!("Defend first: {}", self.defend_first));
+    }
+    /// Overrides the lowest-trump opening so the AI seated before the human attacks the
+    /// human. A hotseat game, with a human in that seat, keeps the usual opening.
+    fn seat_human_as_defender(&mut self) {
+        let players = self.game_state.players();
+        let Some(human_idx) = players
+            .iter()
+            .position(|player| player.player_type() == &PlayerType::Human)
+        else {
+            return;
+        };
+        let attacker = (human_idx + players.len() - 1) % players.len();
+        if players[attacker].player_type() != &PlayerType::Computer {
+            debug("No AI seated before the human, keeping the usual opening");
+            return;
+        }
+        debug(format!(
+            "Defend first: player {} attacks the human",
+            attacker
+        ));
+        self.game_state.current_attacker = attacker;
+        self.game_state.current_defender = human_idx;
+    }
+    /// Hands the opening attack to whoever holds the lowest trump, running the AI
+    /// right away when it goes first
+    fn begin_first_turn(&mut self) {
         let current_player_idx = self.current_player_index();
         let is_ai_turn =
             self.game_state.players()[current_player_idx].player_type() == &PlayerType::Computer;
         if is_ai_turn {
             debug("AI goes first");
+            // The opening move comes before the practice history starts, it can't wait
+            let think_time = self.ai_think_time.take();
             process_ai_turn(self);
+            self.ai_think_time = think_time;
+        } else {
+            self.restart_turn_timer();
+        }
+    }
+    /// Action function that runs when the user presses 'r' to take the one free redeal
+    /// the mulligan house rule allows before the first round.
+    pub fn mulligan_action(&mut self) {
+        if self.game_state.players()[self.current_player_index()].player_type()
+            != &PlayerType::Human
+        {
+            debug("Ignoring mulligan - not the human player's turn");
+            return;
+        }
+        match self.game_state.redeal() {
+            Ok(()) => {
+                info("Cards redealt");
+                self.apply_hand_sort();
+                self.selected_card_idx = None;
+                self.selected_attack_idx = None;
+                self.clear_multi_selection();
+                self.begin_first_turn();
+            }
+            Err(e) => debug(format!("Cannot redeal: {}", e)),
         }
     }
     /// Action function that runs when the user presses '->' or 'l' to select the next card.
     /// Called by `game_loop.rs`
     pub fn select_next_card(&mut self) {
-        if let Some(player) = self.game_state.players().get(self.current_player_index()) {
-            if player.player_type() == &PlayerType::Human {
-                let hand_size = player.hand_size();
-                if hand_size > 0 {
-                    let old_idx = self.selected_card_idx;
-                    self.selected_card_idx = match self.selected_card_idx {
-                        Some(idx) if idx < hand_size - 1 => Some(idx + 1),
-                        None => Some(0),
-                        Some(_) => Some(0), // Wrap around
-                    };
-                    debug(format!(
-                        "Select next: {:?} -> {:?}",
-                        old_idx, self.selected_card_idx
-                    ));
-                }
+        if let Some(player) = self.game_state.players().get(self.current_player_index())
+            && player.player_type() == &PlayerType::Human
+        {
+            let hand_size = player.hand_size();
+            if hand_size > 0 {
+                let old_idx = self.selected_card_idx;
+                self.selected_card_idx = match self.selected_card_idx {
+                    Some(idx) if idx < hand_size - 1 => Some(idx + 1),
+                    None => Some(0),
+                    Some(_) if self.selection_style == SelectionStyle::Clamp => Some(hand_size - 1),
+                    Some(_) => Some(0), // Wrap around
+                };
+                debug(format!(
+                    "Select next: {:?} -> {:?}",
+                    old_idx, self.selected_card_idx
+                ));
             }
         }
     }
     /// Action function that runs when the user presses '<-' or 'h' to select the previous card.
     /// Called by `game_loop.rs`
     pub fn select_prev_card(&mut self) {
-        if let Some(player) = self.game_state.players().get(self.current_player_index()) {
-            if player.player_type() == &PlayerType::Human {
-                let hand_size = player.hand_size();
-                if hand_size > 0 {
-                    let old_idx = self.selected_card_idx;
-                    self.selected_card_idx = match self.selected_card_idx {
-                        Some(idx) if idx > 0 => Some(idx - 1),
-                        None => Some(hand_size - 1), // Wrap around
-                        Some(_) => Some(hand_size - 1),
-                    };
-                    debug(format!(
-                        "Select prev: {:?} -> {:?}",
-                        old_idx, self.selected_card_idx
-                    ));
-                }
+        if let Some(player) = self.game_state.players().get(self.current_player_index())
+            && player.player_type() == &PlayerType::Human
+        {
+            let hand_size = player.hand_size();
+            if hand_size > 0 {
+                let old_idx = self.selected_card_idx;
+                self.selected_card_idx = match self.selected_card_idx {
+                    Some(idx) if idx > 0 => Some(idx - 1),
+                    None => Some(hand_size - 1), // Wrap around
+                    Some(_) if self.selection_style == SelectionStyle::Clamp => Some(0),
+                    Some(_) => Some(hand_size - 1),
+                };
+                debug(format!(
+                    "Select prev: {:?} -> {:?}",
+                    old_idx, self.selected_card_idx
+                ));
             }
         }
     }
+    /// Action function that runs when the user presses a rank key, e.g. '7' or Shift+K.
+    /// Jumps to the first card of that rank, repeated presses cycle through the others.
+    pub fn select_card_by_rank(&mut self, rank: Rank) {
+        let Some(player) = self.game_state.players().get(self.current_player_index()) else {
+            return;
+        };
+        if player.player_type() != &PlayerType::Human {
+            return;
+        }
+        let matches: Vec<usize> = player
+            .hand()
+            .iter()
+            .enumerate()
+            .filter(|(_, card)| card.rank == rank)
+            .map(|(idx, _)| idx)
+            .collect();
+        let next = match self.selected_card_idx {
+            Some(current) => matches
+                .iter()
+                .find(|&&idx| idx > current)
+                .or(matches.first()),
+            None => matches.first(),
+        };
+        if let Some(&idx) = next {
+            debug(format!("Select rank {:?}: card {}", rank, idx));
+            self.selected_card_idx = Some(idx);
+        }
+    }
+    /// Action function that runs when the user presses Tab to pick the next undefended attack.
+    /// Without a selection the first undefended attack is the implicit target.
+    pub fn select_next_attack(&mut self) {
+        self.cycle_attack_selection(true);
+    }
+    /// Action function that runs when the user presses Shift+Tab to pick the previous attack.
+    pub fn select_prev_attack(&mut self) {
+        self.cycle_attack_selection(false);
+    }
+    /// Action function that runs when the user presses '[' or ']' to look at the pairs on
+    /// the table that don't fit on screen. Starts from the first open attack, or the newest
+    /// pair when every attack is beaten.
+    pub fn scroll_table(&mut self, forward: bool) {
+        let table = self.game_state.table_cards();
+        if table.is_empty() {
+            return;
+        }
+        let current = self
+            .table_scroll
+            .filter(|&idx| idx < table.len())
+            .unwrap_or_else(|| {
+                table
+                    .iter()
+                    .position(|(_, defense)| defense.is_none())
+                    .unwrap_or(table.len() - 1)
+            });
+        self.table_scroll = Some(if forward {
+            (current + 1).min(table.len() - 1)
+        } else {
+            current.saturating_sub(1)
+        });
+        // The selected attack would keep the view where it is
+        self.selected_attack_idx = None;
+    }
+    fn cycle_attack_selection(&mut self, forward: bool) {
+        let undefended: Vec<usize> = self
+            .game_state
+            .table_cards()
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, defense))| defense.is_none())
+            .map(|(idx, _)| idx)
+            .collect();
+        if undefended.is_empty() {
+            self.selected_attack_idx = None;
+            return;
+        }
+        let pos = self
+            .selected_attack_idx
+            .and_then(|idx| undefended.iter().position(|&u| u == idx))
+            .unwrap_or(0);
+        let next = if forward {
+            (pos + 1) % undefended.len()
+        } else {
+            (pos + undefended.len() - 1) % undefended.len()
+        };
+        let old_idx = self.selected_attack_idx;
+        self.selected_attack_idx = Some(undefended[next]);
+        debug(format!(
+            "Select attack: {:?} -> {:?}",
+            old_idx, self.selected_attack_idx
+        ));
+    }
     /// Main entry point for Player attack and defense key options.
     /// Calls `handle_attack_phase` or `handle_defense_phase` depending on the current game phase.
     pub fn play_card_action(&mut self) {
         let current_player_idx = self.current_player_index();
         if self.game_state.players()[current_player_idx].player_type() == &PlayerType::Human {
             match *self.game_state.game_phase() {
-                GamePhase::Attack => {
+                GamePhase::Attack | GamePhase::Reinforce => {
                     match self.handle_attack_phase(current_player_idx) {
                         Ok(_) => {
+                            self.clear_multi_selection();
+                            self.reset_card_selection();
                             // If successful attack, game will transition to Defense phase
                             // Process AI's turn if they are the defender
                             process_ai_turn(self);
                         }
-                        Err(e) => {
+                        Err(e) if e.is_illegal_move() => {
                             debug(format!("Attack failed: {}", e));
                             // Not a fatal error, just log it and continue
-                            // Only non-fatal game rule errors should reach here
                         }
+                        Err(e) => error(format!("Attack failed unexpectedly: {}", e)),
                     }
                 }
                 GamePhase::Defense => {
                     match self.handle_defense_phase(current_player_idx) {
                         Ok(_) => {
-                            // After defense, check game state
-                            if *self.game_state.game_phase() == GamePhase::Drawing {
-                                // If drawing phase, proceed with drawing
-                                self.game_state.draw_cards();
-                                // After drawing, process AI's turn if they are next
-                                process_ai_turn(self);
-                            } else if *self.game_state.game_phase() == GamePhase::Defense {
-                                // Check if a different player is now defending (pass occurred)
-                                let current_defender = self.game_state.current_defender();
-                                if current_defender != current_player_idx {
-                                    debug("Detected pass - different player now defending");
-                                    // Check if AI is now the defender
-                                    let is_ai_defender =
-                                        self.game_state.players()[current_defender].player_type()
-                                            == &PlayerType::Computer;
-                                    if is_ai_defender {
-                                        debug("AI is now defending after pass, processing AI turn");
-                                        process_ai_turn(self);
-                                    }
-                                }
-                            }
+                            self.clear_multi_selection();
+                            self.reset_card_selection();
+                            continue_after_defense(self);
                         }
-                        Err(e) => {
+                        Err(e) if e.is_illegal_move() => {
                             debug(format!("Defense failed: {}", e));
                             // Not a fatal error, just log it and continue
-                            // Only non-fatal game rule errors should reach here
                         }
+                        Err(e) => error(format!("Defense failed unexpectedly: {}", e)),
                     }
                 }
                 _ => {}
             }
         }
     }
-    /// Skip to the draw phase if either player gets stuck.  
-    /// TODO: Initally had this for debugging but should be removed
-    pub fn pass_turn_action(&mut self) {
+    /// Selects the card the Easy AI would attack with in the human's seat and plays it,
+    /// the lowest non-trump for an opening attack or the first card matching a table rank.
+    pub fn quick_attack_action(&mut self) {
         let player_idx = self.current_player_index();
-        if *self.game_state.game_phase() == GamePhase::Attack
-            && self.game_state.players()[player_idx].player_type() == &PlayerType::Human
+        if !matches!(
+            self.game_state.game_phase(),
+            GamePhase::Attack | GamePhase::Reinforce
+        ) || self.game_state.players()[player_idx].player_type() != &PlayerType::Human
         {
-            debug("Human player passed attack");
-            self.game_state.draw_cards();
-            process_ai_turn(self);
+            return;
+        }
+        let quick_move = AiPlayer::new(AiDifficulty::Easy)
+            .make_attack_move(&self.game_state, player_idx)
+            .and_then(|cards| cards.first().copied());
+        let Some((card_idx, card)) = quick_move else {
+            debug("Quick attack: no card to play");
+            return;
+        };
+        debug(format!("Quick attack with {}", card));
+        self.selected_cards.clear();
+        self.selected_card_idx = Some(card_idx);
+        self.play_card_action();
+    }
+    /// Beats the chosen attack, or the first open one, with the cheapest card that can:
+    /// the lowest non-trump, then the lowest trump.
+    pub fn quick_defend_action(&mut self) {
+        let player_idx = self.current_player_index();
+        if *self.game_state.game_phase() != GamePhase::Defense
+            || self.game_state.players()[player_idx].player_type() != &PlayerType::Human
+        {
+            return;
+        }
+        let table_cards = self.game_state.table_cards();
+        let attack_idx = self
+            .selected_attack_idx
+            .filter(|&idx| matches!(table_cards.get(idx), Some((_, None))))
+            .or_else(|| {
+                table_cards
+                    .iter()
+                    .position(|(_, defense)| defense.is_none())
+            });
+        let Some(attack_idx) = attack_idx else {
+            return;
+        };
+        let trump_suit = self.game_state.trump_suit();
+        let hand = self.game_state.players()[player_idx].hand();
+        let lowest_defense = self
+            .game_state
+            .legal_defenses(attack_idx)
+            .into_iter()
+            .min_by_key(|&idx| hand[idx].strategic_value(trump_suit));
+        let Some(card_idx) = lowest_defense else {
+            debug(format!(
+                "Quick defend: nothing beats {}",
+                table_cards[attack_idx].0
+            ));
+            return;
+        };
+        self.selected_cards.clear();
+        self.selected_card_idx = Some(card_idx);
+        self.play_card_action();
+    }
+    /// The human stops attacking. During the reinforcement window this ends the round
+    /// without adding cards, in the Attack phase it closes a beaten table with
+    /// `end_attack`. Refused while an attack is still unanswered.
+    pub fn pass_turn_action(&mut self) {
+        let player_idx = self.current_player_index();
+        let is_human = self.game_state.players()[player_idx].player_type() == &PlayerType::Human;
+        if *self.game_state.game_phase() == GamePhase::Reinforce && is_human {
+            debug("Human player adds nothing");
+            self.clear_multi_selection();
+            self.game_state.decline_throw_in();
+            // Another player may still throw in, otherwise the draw waits for the human
+            continue_after_defense(self);
+        } else if *self.game_state.game_phase() == GamePhase::Attack && is_human {
+            match self.game_state.end_attack() {
+                Ok(()) => {
+                    debug("Human player passed attack");
+                    self.clear_multi_selection();
+                    continue_after_defense(self);
+                }
+                Err(e) => debug(format!("Cannot pass: {}", e)),
+            }
         } else {
             debug("Cannot pass - not in attack phase or not human player's turn");
         }
     }
+    /// Passes the attack on to the next player with the selected same-rank card.
+    /// Targets the attack picked with Tab, or the first open one.
+    pub fn pass_defense_action(&mut self) {
+        let player_idx = self.current_player_index();
+        if *self.game_state.game_phase() != GamePhase::Defense
+            || self.game_state.players()[player_idx].player_type() != &PlayerType::Human
+        {
+            debug("Ignoring pass action - not in Defense phase or not human player's turn");
+            return;
+        }
+        let Some(card_idx) = self.selected_card_idx else {
+            debug("Cannot pass: no card selected");
+            return;
+        };
+        let table_cards = self.game_state.table_cards();
+        let attack_idx = self
+            .selected_attack_idx
+            .filter(|&idx| {
+                table_cards
+                    .get(idx)
+                    .is_some_and(|(_, defense)| defense.is_none())
+            })
+            .or_else(|| {
+                table_cards
+                    .iter()
+                    .position(|(_, defense)| defense.is_none())
+            });
+        let Some(attack_idx) = attack_idx else {
+            debug("Cannot pass: no open attack");
+            return;
+        };
+        match self.game_state.pass_attack(card_idx, attack_idx) {
+            Ok(()) => {
+                debug("Human passed the attack");
+                self.selected_attack_idx = None;
+                self.selected_card_idx = None;
+                self.clear_multi_selection();
+                continue_after_defense(self);
+            }
+            Err(e) => debug(format!("Pass failed: {}", e)),
+        }
+    }
     /// Handles the case where the player cannot defend against an attack
     /// Player presses 't' to take cards
     /// Calls `game_state.take_cards`
@@ -175,40 +449,97 @@ impl App {
             && self.game_state.players()[player_idx].player_type() == &PlayerType::Human
         {
             debug("Human player taking cards");
-            if let Err(e) = self.game_state.take_cards() {
-                debug(format!("Error taking cards: {}", e));
-                return;
+            match self.game_state.take_cards() {
+                // Drawing waits for the player to acknowledge, see `acknowledge_draw_action`
+                Ok(()) => {
+                    self.clear_multi_selection();
+                    continue_after_defense(self);
+                }
+                Err(e) => debug(format!("Error taking cards: {}", e)),
             }
-            self.game_state.draw_cards();
-            process_ai_turn(self);
         } else {
             debug("Ignoring take cards action - not in Defense phase or not human player's turn");
         }
     }
+    /// Concedes the game for the human player, who becomes the durak.
+    /// In a hotseat game that is the human whose hand is on screen.
+    /// Confirmed through `request_surrender` first, like quitting.
+    pub fn surrender_action(&mut self) {
+        let Some(human_idx) = self.viewing_player() else {
+            debug("Cannot surrender - no human player");
+            return;
+        };
+        match self.game_state.surrender(human_idx) {
+            Ok(()) => {
+                info("Player surrendered");
+                self.finish_game();
+            }
+            Err(e) => debug(format!("Cannot surrender: {}", e)),
+        }
+    }
+    /// Debug action for 'f': switches the AI between running to completion and single steps.
+    /// Leaving step mode lets the AI finish whatever it still has to do.
+    pub fn toggle_ai_step_mode(&mut self) {
+        self.debug_step_ai = !self.debug_step_ai;
+        info(format!("AI step mode: {}", self.debug_step_ai));
+        if !self.debug_step_ai {
+            process_ai_turn(self);
+        }
+    }
+    /// Debug action for '.': advances the AI by one sub-action while in step mode.
+    pub fn step_ai_action(&mut self) {
+        if self.debug_step_ai {
+            step_ai_turn(self);
+        }
+    }
+    /// Debug action for 'o': shows the AI hands face up.
+    /// Only works with the debug overlay open, so it can't be hit by accident in normal play.
+    pub fn toggle_reveal_hands(&mut self) {
+        if !self.show_debug {
+            return;
+        }
+        self.debug_reveal_hands = !self.debug_reveal_hands;
+        info(format!("Reveal AI hands: {}", self.debug_reveal_hands));
+    }
+    /// Debug action for 'i': numbers the human's cards by their index in the hand, the
+    /// index `attack` and `defend` are called with. Like 'o', only with the debug overlay open.
+    pub fn toggle_show_indices(&mut self) {
+        if !self.show_debug {
+            return;
+        }
+        self.debug_show_indices = !self.debug_show_indices;
+        info(format!("Show hand indices: {}", self.debug_show_indices));
+    }
+    /// Debug action for PageUp/PageDown: pages the overlay's log back towards older
+    /// messages, or forward to the newest. Never scrolls past the oldest message kept.
+    pub fn scroll_debug(&mut self, back: bool) {
+        self.debug_scroll = if back {
+            (self.debug_scroll + DEBUG_SCROLL_PAGE).min(log_len().saturating_sub(1))
+        } else {
+            self.debug_scroll.saturating_sub(DEBUG_SCROLL_PAGE)
+        };
+    }
     /// Handles the drawing phase.
-    /// Attempts to force the attack phase if this is called from GamePhase::Drawing
+    /// The round pauses in GamePhase::Drawing until the player presses a key, then the
+    /// cards are drawn.
     /// Since this is still called when there are no cards left,
     /// GamePhase::GameOver is checked HERE
     pub fn acknowledge_draw_action(&mut self) {
         debug("Acknowledging draw phase");
         if *self.game_state.game_phase() == GamePhase::Drawing {
+            self.clear_multi_selection();
             self.game_state.draw_cards();
-            if *self.game_state.game_phase() == GamePhase::Drawing {
-                debug("Drawing phase stuck, forcing Attack");
-                self.game_state =
-                    crate::game::GameState::force_attack_phase(self.game_state.clone());
-            }
             if self.game_state.check_game_over() {
-                self.app_state = super::state::AppState::GameOver;
+                self.finish_game();
                 return;
             }
             process_ai_turn(self);
         }
     }
     /// Handles the attack phase for single and multi-card attack the human player.
-    pub fn handle_attack_phase(&mut self, player_idx: usize) -> Result<(), String> {
+    pub fn handle_attack_phase(&mut self, player_idx: usize) -> Result<(), GameError> {
         if self.game_state.players()[player_idx].player_type() != &PlayerType::Human {
-            return Err("Not human player's turn".to_string());
+            return Err(GameError::NotYourTurn);
         }
         debug("Human is attacking");
         if self.multiple_selection_mode && !self.selected_cards.is_empty() {
@@ -221,21 +552,22 @@ impl App {
             result
         } else if let Some(idx) = self.selected_card_idx {
             debug(format!("Single attack with card {}", idx));
-            match self.game_state.attack(idx, player_idx) {
-                Ok(()) => Ok(()),
-                Err(e) => Err(e.to_string()),
+            if *self.game_state.game_phase() == GamePhase::Reinforce {
+                self.game_state.reinforce(idx)
+            } else {
+                self.game_state.attack(idx, player_idx)
             }
         } else {
-            Err("No card selected for attack".to_string())
+            Err(GameError::NoCardSelected)
         }
     }
     /// Handles the defense phase for the human player.
-    pub fn handle_defense_phase(&mut self, player_idx: usize) -> Result<(), String> {
+    pub fn handle_defense_phase(&mut self, player_idx: usize) -> Result<(), GameError> {
         if self.game_state.game_phase() != &GamePhase::Defense {
-            return Err("Not in defense phase".to_string());
+            return Err(GameError::WrongPhase);
         }
         if self.game_state.current_defender() != player_idx {
-            return Err("Wrong player defending".to_string());
+            return Err(GameError::NotYourTurn);
         }
         let defender_type = self.game_state.players()[player_idx].player_type();
         match defender_type {
@@ -243,24 +575,23 @@ impl App {
                 if !self.multiple_selection_mode || self.selected_cards.is_empty() {
                     // Single card selection mode or no selections
                     if let Some(idx) = self.selected_card_idx {
-                        if self.game_state.defend(idx).is_ok() {
+                        // Defend the attack the player picked, or the first open one
+                        let result = match self.selected_attack_idx {
+                            Some(attack_idx)
+                                if self
+                                    .game_state
+                                    .table_cards()
+                                    .get(attack_idx)
+                                    .is_some_and(|(_, defense)| defense.is_none()) =>
+                            {
+                                self.game_state.defend_at(attack_idx, idx)
+                            }
+                            _ => self.game_state.defend(idx),
+                        };
+                        if result.is_ok() {
                             debug(format!("Successfully defended with card {}", idx));
+                            self.selected_attack_idx = None;
 
-                            // Check if a pass occurred by looking at the defender change
-                            if self.game_state.current_defender() != player_idx {
-                                debug("Player passed the card to a different player!");
-
-                                // Process AI's turn if they're now the defender after the pass
-                                let new_defender = self.game_state.current_defender();
-                                let is_ai_defender = self.game_state.players()[new_defender]
-                                    .player_type()
-                                    == &PlayerType::Computer;
-                                if is_ai_defender {
-                                    // Don't return yet, let the calling function handle AI processing
-                                    debug("AI needs to defend after player's pass");
-                                }
-                                return Ok(());
-                            }
                             // Check if all attacks are defended
                             let all_defended = !self
                                 .game_state
@@ -287,170 +618,132 @@ impl App {
                                 Ok(())
                             }
                         } else {
-                            Err("Invalid defense".to_string())
+                            result
                         }
                     } else {
-                        Err("No card selected".to_string())
+                        Err(GameError::NoCardSelected)
                     }
                 } else {
                     // Multi-card defense
                     self.handle_multi_defense(player_idx)
                 }
             }
-            _ => Err("Not human player's turn".to_string()),
+            _ => Err(GameError::NotYourTurn),
         }
     }
     /// Seperate function for multi-card defense for the human player.
     /// Called by `handle_defense_phase` above.
-    pub fn handle_multi_defense(&mut self, player_idx: usize) -> Result<(), String> {
-        // Get undefended attacks and player's hand
-        let table_cards = self.game_state.table_cards();
-        let player = &self.game_state.players()[player_idx];
-        let trump_suit = self.game_state.trump_suit();
-        // Count undefended attacks
-        let undefended_attacks: Vec<(usize, &Card)> = table_cards
+    /// Every selected card has to beat one of the open attacks, but they don't have to cover
+    /// all of them: the defender can beat the rest later or take the whole table.
+    pub fn handle_multi_defense(&mut self, player_idx: usize) -> Result<(), GameError> {
+        let undefended_attacks: Vec<usize> = self
+            .game_state
+            .table_cards()
             .iter()
             .enumerate()
             .filter(|(_, (_, defense))| defense.is_none())
-            .map(|(idx, (attack, _))| (idx, attack))
+            .map(|(idx, _)| idx)
             .collect();
-        // If no undefended attacks, nothing to do - defense is complete
         if undefended_attacks.is_empty() {
-            debug("No undefended attacks left, defense complete");
-            return Ok(());
+            return Err(GameError::NoUndefendedAttacks);
+        }
+        if self.selected_cards.is_empty() {
+            return Err(GameError::NoCardSelected);
         }
-        // Check if we have enough selected cards
-        if self.selected_cards.len() != undefended_attacks.len() {
+        if self.selected_cards.len() > undefended_attacks.len() {
             debug(format!(
-                "Selected {} cards but need to defend against {} attacks",
+                "Selected {} cards but only {} attacks are open",
                 self.selected_cards.len(),
                 undefended_attacks.len()
             ));
-            return Ok(());
-        }
-        // Create a mapping of attack cards to selected defense cards
-        let mut defense_mapping = Vec::new();
-        let mut used_card_indices = Vec::new();
-        // Try to find valid defenses for each attack
-        for (table_idx, attack_card) in undefended_attacks {
-            // Try to find a valid defense from selected cards
-            if let Some((hand_idx, defense_card)) = self
-                .selected_cards
-                .iter()
-                .filter(|&&idx| !used_card_indices.contains(&idx))
-                .map(|&idx| (idx, player.hand()[idx]))
-                .find(|(_, card)| {
-                    // Check if card is valid for defense
-                    match trump_suit {
-                        Some(trump) => {
-                            if attack_card.suit == trump {
-                                // If attacking with trump, must defend with higher trump
-                                card.suit == trump && card.rank > attack_card.rank
-                            } else if card.suit == trump {
-                                // Any trump can beat a non-trump
-                                true
-                            } else {
-                                // Otherwise must be same suit and higher rank
-                                card.suit == attack_card.suit && card.rank > attack_card.rank
-                            }
-                        }
-                        None => {
-                            // No trump, just same suit and higher rank
-                            card.suit == attack_card.suit && card.rank > attack_card.rank
-                        }
-                    }
-                })
-            {
-                // Found a valid defense
-                defense_mapping.push((table_idx, hand_idx, defense_card));
-                used_card_indices.push(hand_idx);
-            } else {
-                // Couldn't find a defense for this attack
-                debug("Cannot defend all attacks with selected cards");
-                let _ = self.game_state.take_cards();
-                self.selected_cards.clear();
-                self.selected_card_idx = None;
-                return Ok(());
-            }
+            return Err(GameError::InvalidCombination);
         }
-        // Successfully mapped all attacks to defenses
-        debug(format!("Applying {} valid defenses", defense_mapping.len()));
-        // Format required by discard_cards: Vec<(usize, Card)> where usize is the index in the table
-        let cards_to_discard: Vec<(usize, Card)> = defense_mapping
+        // Which open attacks each selected card could beat
+        let options: Vec<Vec<usize>> = undefended_attacks
             .iter()
-            .map(|&(table_idx, _, card)| (table_idx, card))
+            .map(|&table_idx| self.game_state.legal_defenses(table_idx))
             .collect();
-        // Remove the cards from player's hand using a mutable reference
-        let game_state = &mut self.game_state;
-        for &(_, hand_idx, _) in &defense_mapping {
-            let _ = game_state.players_mut()[player_idx].remove_card(hand_idx);
+        let beats: Vec<Vec<usize>> = self
+            .selected_cards
+            .iter()
+            .map(|hand_idx| {
+                (0..undefended_attacks.len())
+                    .filter(|&attack| options[attack].contains(hand_idx))
+                    .collect()
+            })
+            .collect();
+        let Some(assignment) = match_defenses(&beats) else {
+            debug("Selected cards can't each beat a different attack");
+            return Err(GameError::IllegalDefense);
+        };
+        let mut defense_mapping: Vec<(usize, usize)> = self
+            .selected_cards
+            .iter()
+            .zip(assignment)
+            .map(|(&hand_idx, attack)| (undefended_attacks[attack], hand_idx))
+            .collect();
+        debug(format!("Applying {} valid defenses", defense_mapping.len()));
+        // Take the cards out of the hand from the back so the other indices stay valid
+        defense_mapping.sort_by_key(|&(_, hand_idx)| std::cmp::Reverse(hand_idx));
+        let mut cards_to_discard = Vec::new();
+        for (table_idx, hand_idx) in defense_mapping {
+            let card = self.game_state.players_mut()[player_idx].remove_card(hand_idx);
+            if let Some(card) = card {
+                cards_to_discard.push((table_idx, card));
+            }
         }
-        game_state.discard_cards(cards_to_discard);
-        // Clear selections
+        // Moves on to reinforcing or ends the round once every attack is beaten
+        self.game_state.discard_cards(cards_to_discard);
         self.selected_cards.clear();
         self.selected_card_idx = None;
-        // Check if all cards are defended now
-        let all_defended = !self
-            .game_state
-            .table_cards()
-            .iter()
-            .any(|(_, defense)| defense.is_none());
-        if all_defended {
-            debug("All attacks successfully defended");
-            return Ok(());
-        }
-        Err("Not all cards defended".to_string())
+        Ok(())
     }
-    /// Validates the selected cards for a multi-card attack by the human player
+    /// Validates the selected cards for a multi-card attack by the human player.
+    /// An opening salvo must share a single rank; a continuation may mix ranks as long as
+    /// every selected card matches a rank already on the table.
     pub fn valid_multi_attack(&self, player_idx: usize) -> bool {
         if self.selected_cards.is_empty() {
             return false;
         }
-        let hand = self.game_state.players()[player_idx].hand();
-        // Check for out of bounds indices
-        if self.selected_cards.iter().any(|&idx| idx >= hand.len()) {
-            error(format!(
-                "Out of bounds index in valid_multi_attack: selected cards: {:?}, hand size: {}",
-                self.selected_cards,
-                hand.len()
-            ));
+        // Make sure the attack fits into what the defender can be made to answer,
+        // the round's attack limit, see `GameState::attack_limit`
+        if self.selected_cards.len() > self.game_state.attack_room() {
             return false;
         }
-        let first_idx = self.selected_cards[0];
-        if first_idx >= hand.len() {
-            error(format!(
-                "First card index out of bounds: {}, hand size: {}",
-                first_idx,
-                hand.len()
+        let legal_attacks = self.game_state.legal_attacks(player_idx);
+        if let Some(idx) = self
+            .selected_cards
+            .iter()
+            .find(|idx| !legal_attacks.contains(idx))
+        {
+            debug(format!(
+                "Selected card {} can't be played to the table",
+                idx
             ));
             return false;
         }
-        let first_rank = hand[first_idx].rank;
-        let cards_count = self.selected_cards.len();
-        // Make sure we don't attack with more cards than the defender has
-        let defender = self.game_state.current_defender();
-        if defender >= self.game_state.players().len() {
-            error(format!("Invalid defender index: {}", defender));
-            return false;
-        }
-        let defender_hand_size = self.game_state.players()[defender].hand_size();
-        if cards_count > defender_hand_size {
-            // Make sure we don't attack with more cards than the defender has
-            return false;
+        if !self.game_state.table_cards().is_empty() {
+            // Continuation: `legal_attacks` already checked every card against the table
+            return true;
         }
-        // Return false if selected cards don't all have the same rank, true otherwise
+        // Opening salvo: return false if selected cards don't all have the same rank
+        let hand = self.game_state.players()[player_idx].hand();
+        let first_rank = hand[self.selected_cards[0]].rank;
         self.selected_cards
             .iter()
-            .all(|&idx| idx < hand.len() && hand[idx].rank == first_rank)
+            .all(|&idx| hand[idx].rank == first_rank)
     }
     /// Performs a multi-card attack with the human player.
-    pub fn multi_attack(&mut self, player_idx: usize) -> Result<(), String> {
+    pub fn multi_attack(&mut self, player_idx: usize) -> Result<(), GameError> {
         // General logic for either computer or human attack with multiple cards.
         if self.selected_cards.is_empty() {
-            return Err("No cards selected".to_string());
+            return Err(GameError::NoCardSelected);
         }
-        if self.game_state.game_phase() != &GamePhase::Attack {
-            return Err("Not in attack phase".to_string());
+        if !matches!(
+            self.game_state.game_phase(),
+            GamePhase::Attack | GamePhase::Reinforce
+        ) {
+            return Err(GameError::WrongPhase);
         }
         // Safely clone the selected cards to avoid any potential index issues
         if self
@@ -459,19 +752,21 @@ impl App {
             .any(|&idx| idx >= self.game_state.players()[player_idx].hand_size())
         {
             error("Index out of bounds in multi_attack");
-            let err_msg = "Invalid card index";
-            if let Err(e) = self.safe_exit(Some(err_msg)) {
+            let err = GameError::InvalidCardIndex;
+            if let Err(e) = self.safe_exit(Some(&err.to_string())) {
                 error(format!("Failed to restore terminal: {}", e));
             }
-            return Err(err_msg.to_string());
+            return Err(err);
         }
         // Sort selected cards (highest index first to avoid shifting issues)
         let mut sorted_indexes = self.selected_cards.clone();
         sorted_indexes.sort_by(|a, b| b.cmp(a));
         // Get the hand, validate the indexes.
         if !self.valid_multi_attack(player_idx) {
-            return Err("Selected cards have different ranks".to_string());
+            return Err(GameError::InvalidCombination);
         }
+        // In the reinforcement window the cards are thrown in, whoever is throwing
+        let throwing_in = *self.game_state.game_phase() == GamePhase::Reinforce;
         // Perform the attacks
         for &idx in sorted_indexes.iter() {
             // Double-check index bounds before each attack
@@ -484,23 +779,761 @@ impl App {
                 if let Err(e) = self.safe_exit(Some(&err_msg)) {
                     error(format!("Failed to restore terminal: {}", e));
                 }
-                return Err(err_msg);
+                return Err(GameError::InvalidCardIndex);
             }
-            match self.game_state.attack(idx, player_idx) {
-                Ok(_) => {}
-                Err(e) => return Err(format!("Multi-attack failed: {}", &e)),
+            if throwing_in {
+                self.game_state.throw_in(player_idx, idx)?;
+            } else {
+                self.game_state.attack(idx, player_idx)?;
             }
         }
         Ok(())
     }
-    /// Helper method to find a card's index in a player's hand
-    pub fn find_card_index_in_hand(&self, player_idx: usize, card: Card) -> Option<usize> {
-        let player = &self.game_state.players()[player_idx];
-        player
-            .hand()
+    /// Helper method to find the hand index of each of `cards` in a player's hand, a
+    /// different index for every card even when the hand holds identical copies.
+    /// `None` if any of them isn't in the hand.
+    pub fn find_card_indices_in_hand(
+        &self,
+        player_idx: usize,
+        cards: &[Card],
+    ) -> Option<Vec<usize>> {
+        self.game_state.players()[player_idx]
+            .find_cards(cards)
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Pairs each selected card with a different attack it beats, `beats[card]` listing the
+/// attacks that card can take. Returns the attack for every card, or `None` if some card
+/// is left without one. Tries to re-seat earlier cards before giving up, a greedy pairing
+/// could strand a card that only beats the attack another card took first.
+fn match_defenses(beats: &[Vec<usize>]) -> Option<Vec<usize>> {
+    fn seat(
+        card: usize,
+        beats: &[Vec<usize>],
+        owner: &mut [Option<usize>],
+        tried: &mut [bool],
+    ) -> bool {
+        for &attack in &beats[card] {
+            if tried[attack] {
+                continue;
+            }
+            tried[attack] = true;
+            if owner[attack].is_none_or(|other| seat(other, beats, owner, tried)) {
+                owner[attack] = Some(card);
+                return true;
+            }
+        }
+        false
+    }
+    let attacks = beats.iter().flatten().max().map_or(0, |max| max + 1);
+    let mut owner = vec![None; attacks];
+    for card in 0..beats.len() {
+        if !seat(card, beats, &mut owner, &mut vec![false; attacks]) {
+            return None;
+        }
+    }
+    let mut assignment = vec![0; beats.len()];
+    for (attack, card) in owner.into_iter().enumerate() {
+        if let Some(card) = card {
+            assignment[card] = attack;
+        }
+    }
+    Some(assignment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::ai_handler::resume_ai_turn;
+    use crate::app::state::AppState;
+    use crate::game::card::Suit;
+    use crate::ui::debug_overlay::LOG_CAPACITY;
+    use crossterm::event::KeyCode;
+
+    // Helper to build an app with the human attacking and the given hand and table
+    fn create_attack_app(hand: Vec<Card>, table_cards: Vec<(Card, Option<Card>)>) -> App {
+        let mut app = App::new();
+        let game_state = &mut app.game_state;
+        game_state.trump_suit = Some(Suit::Spades);
+        game_state.players[0].hand = hand;
+        game_state.players[1].hand = vec![Card::new(Suit::Clubs, Rank::Six); 6];
+        game_state.table_cards = table_cards;
+        game_state.current_attacker = 0;
+        game_state.current_defender = 1;
+        game_state.game_phase = GamePhase::Attack;
+        app
+    }
+
+    #[test]
+    /// Test that playing the last card clears the card cursor and that a cursor left past
+    /// the end of a shrunken hand goes back to the first card
+    fn test_selection_cleared_after_last_card() {
+        let mut app = create_attack_app(vec![Card::new(Suit::Hearts, Rank::Seven)], vec![]);
+        app.debug_step_ai = true;
+        app.selected_card_idx = Some(0);
+        app.play_card_action();
+        assert!(app.game_state.players()[0].hand().is_empty());
+        assert_eq!(app.selected_card_idx, None);
+
+        let mut app = create_attack_app(
+            vec![
+                Card::new(Suit::Hearts, Rank::Seven),
+                Card::new(Suit::Clubs, Rank::Nine),
+            ],
+            vec![],
+        );
+        app.selected_card_idx = Some(4);
+        app.clamp_card_selection();
+        assert_eq!(app.selected_card_idx, Some(0));
+        app.selected_card_idx = Some(1);
+        app.clamp_card_selection();
+        assert_eq!(app.selected_card_idx, Some(1));
+    }
+
+    #[test]
+    /// Test that an opening salvo still requires every card to share one rank
+    fn test_valid_multi_attack_opening_requires_same_rank() {
+        let mut app = create_attack_app(
+            vec![
+                Card::new(Suit::Hearts, Rank::Seven),
+                Card::new(Suit::Clubs, Rank::Seven),
+                Card::new(Suit::Hearts, Rank::Nine),
+            ],
+            vec![],
+        );
+        app.selected_cards = vec![0, 1];
+        assert!(app.valid_multi_attack(0));
+        app.selected_cards = vec![0, 2];
+        assert!(!app.valid_multi_attack(0));
+    }
+
+    #[test]
+    /// Test that a continuation may mix ranks as long as each one is on the table
+    fn test_valid_multi_attack_continuation_mixed_ranks() {
+        let mut app = create_attack_app(
+            vec![
+                Card::new(Suit::Hearts, Rank::Seven),
+                Card::new(Suit::Hearts, Rank::Nine),
+                Card::new(Suit::Diamonds, Rank::King),
+            ],
+            vec![(
+                Card::new(Suit::Clubs, Rank::Seven),
+                Some(Card::new(Suit::Clubs, Rank::Nine)),
+            )],
+        );
+        app.selected_cards = vec![0, 1];
+        assert!(app.valid_multi_attack(0));
+        // The King matches nothing on the table
+        app.selected_cards = vec![0, 2];
+        assert!(!app.valid_multi_attack(0));
+    }
+
+    #[test]
+    /// Test that a multi-card attack leaves no selection pointing into the shrunken hand
+    fn test_multi_attack_clears_selection() {
+        let mut app = create_attack_app(
+            vec![
+                Card::new(Suit::Hearts, Rank::Seven),
+                Card::new(Suit::Diamonds, Rank::Seven),
+                Card::new(Suit::Diamonds, Rank::King),
+            ],
+            vec![],
+        );
+        app.debug_step_ai = true;
+        app.toggle_multiple_selection();
+        app.toggle_card_selection(0);
+        app.toggle_card_selection(1);
+        app.play_card_action();
+        assert_eq!(app.game_state.table_cards().len(), 2);
+        assert_eq!(app.game_state.players()[0].hand().len(), 1);
+        assert!(app.selected_cards.is_empty());
+        assert!(!app.multiple_selection_mode);
+    }
+
+    #[test]
+    /// Test that in step mode the AI only moves when stepped
+    fn test_step_ai_runs_one_action() {
+        let mut app = App::new();
+        let game_state = &mut app.game_state;
+        game_state.trump_suit = Some(Suit::Spades);
+        game_state.players[0].hand = vec![Card::new(Suit::Hearts, Rank::Six); 6];
+        game_state.players[1].hand = vec![Card::new(Suit::Clubs, Rank::Seven)];
+        game_state.current_attacker = 1;
+        game_state.current_defender = 0;
+        game_state.game_phase = GamePhase::Attack;
+        app.debug_step_ai = true;
+
+        process_ai_turn(&mut app);
+        assert_eq!(*app.game_state.game_phase(), GamePhase::Attack);
+        assert_eq!(app.game_state.players()[1].hand_size(), 1);
+
+        app.step_ai_action();
+        assert_eq!(*app.game_state.game_phase(), GamePhase::Defense);
+        assert_eq!(app.game_state.table_cards().len(), 1);
+    }
+
+    #[test]
+    /// Test that quick attack opens with the lowest non-trump, whatever is selected
+    fn test_quick_attack_plays_lowest_non_trump() {
+        let mut app = create_attack_app(
+            vec![
+                Card::new(Suit::Hearts, Rank::Nine),
+                Card::new(Suit::Spades, Rank::Six),
+                Card::new(Suit::Clubs, Rank::Seven),
+            ],
+            vec![],
+        );
+        app.debug_step_ai = true;
+        app.selected_card_idx = Some(1);
+        app.quick_attack_action();
+        assert_eq!(
+            app.game_state.table_cards(),
+            [(Card::new(Suit::Clubs, Rank::Seven), None)]
+        );
+        assert_eq!(*app.game_state.game_phase(), GamePhase::Defense);
+    }
+
+    #[test]
+    /// Test that quick defend beats the attack with the cheapest card that can
+    fn test_quick_defend_plays_lowest_beating_card() {
+        let mut app = App::new();
+        let game_state = &mut app.game_state;
+        game_state.trump_suit = Some(Suit::Spades);
+        game_state.players[0].hand = vec![
+            Card::new(Suit::Hearts, Rank::Ace),
+            Card::new(Suit::Spades, Rank::Six),
+            Card::new(Suit::Hearts, Rank::Ten),
+            Card::new(Suit::Hearts, Rank::Six),
+        ];
+        game_state.players[1].hand = vec![Card::new(Suit::Clubs, Rank::Six); 3];
+        // A second open attack keeps the round going so the table can be checked
+        game_state.table_cards = vec![
+            (Card::new(Suit::Hearts, Rank::Eight), None),
+            (Card::new(Suit::Diamonds, Rank::Eight), None),
+        ];
+        game_state.current_attacker = 1;
+        game_state.current_defender = 0;
+        game_state.game_phase = GamePhase::Defense;
+        app.debug_step_ai = true;
+        app.quick_defend_action();
+        assert_eq!(
+            app.game_state.table_cards()[0].1,
+            Some(Card::new(Suit::Hearts, Rank::Ten))
+        );
+    }
+
+    #[test]
+    /// Test that a multi-card defense can beat some attacks and that taking afterwards
+    /// picks up every card on the table
+    fn test_partial_multi_defense_then_take() {
+        let mut app = App::new();
+        let game_state = &mut app.game_state;
+        game_state.trump_suit = Some(Suit::Spades);
+        game_state.players[0].hand = vec![
+            Card::new(Suit::Hearts, Rank::Ace),
+            Card::new(Suit::Clubs, Rank::Seven),
+            Card::new(Suit::Hearts, Rank::Nine),
+        ];
+        game_state.players[1].hand = vec![Card::new(Suit::Clubs, Rank::Six); 3];
+        game_state.table_cards = vec![
+            (Card::new(Suit::Hearts, Rank::Eight), None),
+            (Card::new(Suit::Hearts, Rank::King), None),
+            (Card::new(Suit::Diamonds, Rank::Eight), None),
+        ];
+        game_state.current_attacker = 1;
+        game_state.current_defender = 0;
+        game_state.game_phase = GamePhase::Defense;
+        // A greedy pairing would put the A♥ on the 8♥ and leave the 9♥ with nothing
+        app.selected_cards = vec![0, 2];
+        assert_eq!(app.handle_multi_defense(0), Ok(()));
+        let table = app.game_state.table_cards();
+        assert_eq!(table[0].1, Some(Card::new(Suit::Hearts, Rank::Nine)));
+        assert_eq!(table[1].1, Some(Card::new(Suit::Hearts, Rank::Ace)));
+        assert_eq!(table[2].1, None);
+        assert_eq!(
+            app.game_state.players()[0].hand(),
+            &[Card::new(Suit::Clubs, Rank::Seven)]
+        );
+        assert_eq!(*app.game_state.game_phase(), GamePhase::Defense);
+        // The 7♣ beats none of the open attacks, so nothing changes
+        app.selected_cards = vec![0];
+        assert_eq!(app.handle_multi_defense(0), Err(GameError::IllegalDefense));
+        assert_eq!(app.game_state.players()[0].hand().len(), 1);
+        app.game_state.take_cards().unwrap();
+        assert_eq!(app.game_state.players()[0].hand().len(), 6);
+        assert!(app.game_state.table_cards().is_empty());
+    }
+
+    #[test]
+    /// Test that re-sorting the hand keeps the same cards selected
+    fn test_cycle_sort_remaps_selection() {
+        let mut app = create_attack_app(
+            vec![
+                Card::new(Suit::Clubs, Rank::Nine),
+                Card::new(Suit::Hearts, Rank::Seven),
+                Card::new(Suit::Spades, Rank::Six),
+            ],
+            vec![],
+        );
+        app.game_state.players[0].sort_hand();
+        app.selected_card_idx = Some(0);
+        app.selected_cards = vec![0, 2];
+        app.cycle_sort();
+        let hand = app.game_state.players()[0].hand();
+        assert_eq!(hand[0], Card::new(Suit::Spades, Rank::Six));
+        assert_eq!(
+            hand[app.selected_card_idx.unwrap()],
+            Card::new(Suit::Clubs, Rank::Nine)
+        );
+        let selected: Vec<Card> = app.selected_cards.iter().map(|&idx| hand[idx]).collect();
+        assert_eq!(
+            selected,
+            [
+                Card::new(Suit::Clubs, Rank::Nine),
+                Card::new(Suit::Spades, Rank::Six)
+            ]
+        );
+    }
+
+    #[test]
+    /// Test that the AI refuses to move when it is seated as both attacker and defender
+    fn test_ai_stops_when_defending_against_itself() {
+        let mut app = App::new();
+        let game_state = &mut app.game_state;
+        game_state.trump_suit = Some(Suit::Spades);
+        game_state.players[0].hand = vec![Card::new(Suit::Hearts, Rank::Six); 6];
+        game_state.players[1].hand = vec![Card::new(Suit::Clubs, Rank::Seven); 2];
+        game_state.table_cards = vec![(Card::new(Suit::Clubs, Rank::Six), None)];
+        game_state.current_attacker = 1;
+        game_state.current_defender = 1;
+        game_state.game_phase = GamePhase::Defense;
+        let before = app.game_state.clone();
+        process_ai_turn(&mut app);
+        assert_eq!(app.game_state, before);
+    }
+
+    #[test]
+    /// Test that a seeded game between four AI players runs to its end in one go, however
+    /// many moves a round between them takes
+    fn test_four_ai_players_finish_the_game() {
+        let mut app = App::new();
+        let mut game_state = crate::game::GameState::new_with_seed(967);
+        for seat in 0..4 {
+            game_state.add_player(format!("AI {}", seat), PlayerType::Computer);
+        }
+        game_state.flat_attack_limit = true;
+        game_state.setup_game();
+        app.game_state = game_state;
+        app.app_state = AppState::Playing;
+        process_ai_turn(&mut app);
+        assert_eq!(*app.game_state.game_phase(), GamePhase::GameOver);
+        assert_eq!(app.app_state, AppState::GameOver);
+        // Nobody who went out is holding cards again
+        let game_state = &app.game_state;
+        assert!(
+            game_state
+                .finished
+                .iter()
+                .all(|&idx| game_state.players()[idx].is_empty_hand())
+        );
+    }
+
+    #[test]
+    /// Test that a rank key jumps to the first card of that rank and cycles on repeats
+    fn test_select_card_by_rank_cycles() {
+        let mut app = create_attack_app(
+            vec![
+                Card::new(Suit::Clubs, Rank::Seven),
+                Card::new(Suit::Hearts, Rank::King),
+                Card::new(Suit::Spades, Rank::Seven),
+            ],
+            vec![],
+        );
+        app.select_card_by_rank(Rank::Seven);
+        assert_eq!(app.selected_card_idx, Some(0));
+        app.select_card_by_rank(Rank::Seven);
+        assert_eq!(app.selected_card_idx, Some(2));
+        app.select_card_by_rank(Rank::Seven);
+        assert_eq!(app.selected_card_idx, Some(0));
+        // No ace in hand, the cursor stays put
+        app.select_card_by_rank(Rank::Ace);
+        assert_eq!(app.selected_card_idx, Some(0));
+        app.select_card_by_rank(Rank::King);
+        assert_eq!(app.selected_card_idx, Some(1));
+    }
+
+    #[test]
+    /// Test that the clamping selection style stops the cursor at both ends of the hand
+    fn test_clamped_selection_stops_at_ends() {
+        let mut app = create_attack_app(
+            vec![
+                Card::new(Suit::Clubs, Rank::Nine),
+                Card::new(Suit::Hearts, Rank::Seven),
+            ],
+            vec![],
+        );
+        app.selected_card_idx = Some(1);
+        app.select_next_card();
+        assert_eq!(app.selected_card_idx, Some(0));
+        app.selection_style = SelectionStyle::Clamp;
+        app.select_prev_card();
+        assert_eq!(app.selected_card_idx, Some(0));
+        app.selected_card_idx = Some(1);
+        app.select_next_card();
+        assert_eq!(app.selected_card_idx, Some(1));
+    }
+
+    #[test]
+    /// Test that the AI hands can only be revealed with the debug overlay open
+    fn test_reveal_hands_needs_debug_overlay() {
+        let mut app = App::new();
+        app.toggle_reveal_hands();
+        assert!(!app.debug_reveal_hands);
+        app.show_debug = true;
+        app.toggle_reveal_hands();
+        assert!(app.debug_reveal_hands);
+    }
+
+    #[test]
+    /// Test that PageUp and PageDown page the debug log only while the overlay is open,
+    /// and never past the oldest message kept
+    fn test_scroll_debug_overlay() {
+        for idx in 0..LOG_CAPACITY {
+            debug(format!("Message {}", idx));
+        }
+        let mut app = App::new();
+        app.app_state = AppState::Playing;
+        app.on_key(KeyCode::PageUp);
+        assert_eq!(app.debug_scroll, 0);
+
+        app.toggle_debug();
+        app.on_key(KeyCode::PageUp);
+        app.on_key(KeyCode::PageUp);
+        assert_eq!(app.debug_scroll, 2 * DEBUG_SCROLL_PAGE);
+        app.on_key(KeyCode::PageDown);
+        assert_eq!(app.debug_scroll, DEBUG_SCROLL_PAGE);
+        for _ in 0..LOG_CAPACITY {
+            app.on_key(KeyCode::PageUp);
+        }
+        assert_eq!(app.debug_scroll, LOG_CAPACITY - 1);
+        app.toggle_debug();
+        assert_eq!(app.debug_scroll, 0);
+    }
+
+    #[test]
+    /// Test that after the human passes, the AI defender answers straight away
+    fn test_human_pass_lets_ai_respond() {
+        let mut app = App::new();
+        app.app_state = AppState::Playing;
+        let game_state = &mut app.game_state;
+        game_state.trump_suit = Some(Suit::Spades);
+        game_state.players[0].hand = vec![
+            Card::new(Suit::Diamonds, Rank::Seven),
+            Card::new(Suit::Clubs, Rank::Ace),
+        ];
+        // Nothing here beats a seven or matches its rank, so the AI has to take
+        game_state.players[1].hand = vec![
+            Card::new(Suit::Clubs, Rank::Six),
+            Card::new(Suit::Diamonds, Rank::Six),
+            Card::new(Suit::Hearts, Rank::Six),
+        ];
+        game_state.table_cards = vec![(Card::new(Suit::Hearts, Rank::Seven), None)];
+        game_state.current_attacker = 1;
+        game_state.current_defender = 0;
+        game_state.game_phase = GamePhase::Defense;
+        // Pass with the 7♦, found by card rather than hand position
+        app.selected_card_idx = app
+            .find_card_indices_in_hand(0, &[Card::new(Suit::Diamonds, Rank::Seven)])
+            .map(|indices| indices[0]);
+
+        app.pass_defense_action();
+        assert!(app.game_state.table_cards().is_empty());
+        assert_eq!(app.game_state.players()[1].hand_size(), 5);
+        assert_eq!(*app.game_state.game_phase(), GamePhase::Drawing);
+    }
+
+    #[test]
+    /// Test that with a think time the AI only answers once `resume_ai_turn` runs,
+    /// and keys are ignored until then
+    fn test_thinking_ai_answers_on_resume() {
+        let mut app = App::new();
+        app.app_state = AppState::Playing;
+        app.ai_think_time = Some(std::time::Duration::ZERO);
+        let game_state = &mut app.game_state;
+        game_state.trump_suit = Some(Suit::Spades);
+        game_state.players[0].hand = vec![Card::new(Suit::Diamonds, Rank::Seven); 2];
+        game_state.players[1].hand = vec![Card::new(Suit::Clubs, Rank::Six); 3];
+        game_state.table_cards = vec![(Card::new(Suit::Hearts, Rank::Seven), None)];
+        game_state.current_attacker = 1;
+        game_state.current_defender = 0;
+        game_state.game_phase = GamePhase::Defense;
+        app.selected_card_idx = Some(0);
+        app.pass_defense_action();
+        assert!(app.ai_thinking_frame().is_some());
+        assert_eq!(app.game_state.table_cards().len(), 2);
+        let before = app.game_state.clone();
+        app.on_key(KeyCode::Char('t'));
+        assert_eq!(app.game_state, before);
+        resume_ai_turn(&mut app);
+        assert!(app.ai_thinking_frame().is_none());
+        assert!(app.game_state.table_cards().is_empty());
+        assert_eq!(app.game_state.players()[1].hand_size(), 5);
+    }
+
+    #[test]
+    /// Test that an AI beating two attacks with two identical cards from a multi-deck
+    /// game plays both copies
+    fn test_ai_defends_with_duplicate_cards() {
+        let mut app = App::new();
+        app.app_state = AppState::Playing;
+        let game_state = &mut app.game_state;
+        game_state.deck_count = 2;
+        game_state.trump_suit = Some(Suit::Spades);
+        let nine = Card::new(Suit::Hearts, Rank::Nine);
+        game_state.players[0].hand = vec![Card::new(Suit::Clubs, Rank::Ace); 3];
+        game_state.players[1].hand = vec![Card::new(Suit::Clubs, Rank::Six), nine, nine];
+        game_state.table_cards = vec![
+            (Card::new(Suit::Hearts, Rank::Seven), None),
+            (Card::new(Suit::Hearts, Rank::Seven), None),
+        ];
+        game_state.current_attacker = 0;
+        game_state.current_defender = 1;
+        game_state.game_phase = GamePhase::Defense;
+        process_ai_turn(&mut app);
+        assert_eq!(
+            app.game_state.players()[1].hand(),
+            [Card::new(Suit::Clubs, Rank::Six)]
+        );
+    }
+
+    #[test]
+    /// Test that with defend first the AI opens the game against the human, whatever the
+    /// lowest trump says
+    fn test_defend_first() {
+        for seed in 0..10 {
+            let mut app = App::new();
+            app.defend_first = true;
+            app.game_state.seed = Some(seed);
+            app.start_game_action();
+            assert_eq!(app.game_state.current_attacker(), 1);
+            assert_eq!(app.game_state.current_defender(), 0);
+            assert_eq!(*app.game_state.game_phase(), GamePhase::Defense);
+            assert!(!app.game_state.table_cards().is_empty());
+        }
+    }
+
+    #[test]
+    /// Test that an AI attacker who is out of cards hands the attack to the next AI
+    /// instead of passing, and the game goes on to its end
+    fn test_empty_handed_ai_attacker() {
+        let mut app = App::new();
+        app.app_state = AppState::Playing;
+        app.game_state = crate::game::GameState::from_notation(
+            "Human*: 7H 8D | AI 1: | AI 2: 6C | Attacker: AI 1 | Defender: AI 2 | Trump: S",
+        )
+        .unwrap();
+        process_ai_turn(&mut app);
+        assert_eq!(app.game_state.current_attacker(), 2);
+        assert_eq!(app.game_state.current_defender(), 0);
+        assert_eq!(
+            app.game_state.table_cards(),
+            [(Card::new(Suit::Clubs, Rank::Six), None)]
+        );
+        // Close the briefing, then take: only the human has cards left
+        app.on_key(KeyCode::Char('t'));
+        app.on_key(KeyCode::Char('t'));
+        assert_eq!(*app.game_state.game_phase(), GamePhase::GameOver);
+        assert_eq!(app.game_state.durak(), Some(0));
+    }
+
+    #[test]
+    /// Test that 'p' in the Attack phase discards a beaten table and leaves an unanswered
+    /// attack alone
+    fn test_pass_turn_resolves_table() {
+        let mut app = App::new();
+        app.app_state = AppState::Playing;
+        app.game_state = crate::game::GameState::from_notation(
+            "Human*: 7D | AI: 8C | Table: 7H-9H | Phase: Attack | Trump: S",
+        )
+        .unwrap();
+        app.on_key(KeyCode::Char('p'));
+        assert!(app.game_state.table_cards().is_empty());
+        assert_eq!(app.game_state.discard_pile().len(), 2);
+        assert_eq!(*app.game_state.game_phase(), GamePhase::Drawing);
+
+        let open = crate::game::GameState::from_notation(
+            "Human*: 7D | AI: 8C | Table: 7H-9H 7S-_ | Phase: Attack | Trump: S",
+        )
+        .unwrap();
+        app.game_state = open.clone();
+        app.on_key(KeyCode::Char('p'));
+        assert_eq!(app.game_state, open);
+    }
+
+    #[test]
+    /// Test that an AI attack on the human waits for a key before the human can act
+    fn test_defense_briefing_after_ai_attack() {
+        let mut app = App::new();
+        app.app_state = AppState::Playing;
+        let game_state = &mut app.game_state;
+        game_state.trump_suit = Some(Suit::Spades);
+        game_state.players[0].hand = vec![Card::new(Suit::Hearts, Rank::Ace); 3];
+        game_state.players[1].hand = vec![Card::new(Suit::Clubs, Rank::Six)];
+        game_state.current_attacker = 1;
+        game_state.current_defender = 0;
+        game_state.game_phase = GamePhase::Attack;
+        process_ai_turn(&mut app);
+        assert!(app.defense_briefing);
+        // The first key only closes the briefing, it doesn't take the cards
+        app.on_key(KeyCode::Char('t'));
+        assert!(!app.defense_briefing);
+        assert_eq!(*app.game_state.game_phase(), GamePhase::Defense);
+        assert_eq!(app.game_state.table_cards().len(), 1);
+    }
+
+    /// Plays a whole seeded game between two baseline AIs and returns where it ended
+    fn play_baseline_game(seed: u64) -> crate::game::GameState {
+        let mut app = App::new();
+        app.app_state = AppState::Playing;
+        app.ai_player = AiPlayer::baseline();
+        app.game_state.players[0].player_type = PlayerType::Computer;
+        app.game_state.seed = Some(seed);
+        app.game_state.setup_game();
+        for _ in 0..500 {
+            if *app.game_state.game_phase() == GamePhase::GameOver {
+                break;
+            }
+            process_ai_turn(&mut app);
+        }
+        app.game_state
+    }
+
+    #[test]
+    /// Test that two baseline AIs finish a game the same way every time
+    fn test_baseline_game_is_deterministic() {
+        let first = play_baseline_game(11);
+        assert_eq!(*first.game_phase(), GamePhase::GameOver);
+        assert_eq!(first, play_baseline_game(11));
+    }
+
+    #[test]
+    /// Test that the pass action hands the attack on to the next player
+    fn test_pass_defense_action() {
+        let mut app = App::new();
+        let game_state = &mut app.game_state;
+        game_state.trump_suit = Some(Suit::Spades);
+        game_state.players[0].hand = vec![
+            Card::new(Suit::Spades, Rank::Seven),
+            Card::new(Suit::Hearts, Rank::Six),
+        ];
+        game_state.players[1].hand = vec![Card::new(Suit::Clubs, Rank::Six); 3];
+        game_state.table_cards = vec![(Card::new(Suit::Hearts, Rank::Seven), None)];
+        game_state.current_attacker = 1;
+        game_state.current_defender = 0;
+        game_state.game_phase = GamePhase::Defense;
+        app.debug_step_ai = true;
+        app.selected_card_idx = Some(0);
+
+        app.pass_defense_action();
+        assert_eq!(app.game_state.table_cards().len(), 2);
+        assert_eq!(app.game_state.current_defender(), 1);
+        assert_eq!(app.game_state.players()[0].hand_size(), 1);
+    }
+
+    #[test]
+    /// Test that taking cards pauses in the Drawing phase until the player acknowledges it
+    fn test_drawing_waits_for_acknowledgement() {
+        let mut app = App::new();
+        let game_state = &mut app.game_state;
+        game_state.trump_suit = Some(Suit::Spades);
+        game_state.players[0].hand = vec![Card::new(Suit::Hearts, Rank::Six)];
+        game_state.players[1].hand = vec![Card::new(Suit::Clubs, Rank::Six); 3];
+        game_state.table_cards = vec![(Card::new(Suit::Hearts, Rank::Ace), None)];
+        game_state.set_phase_to_defense(1, 0);
+        let deck_size = game_state.deck().remaining();
+
+        app.take_cards_action();
+        assert_eq!(*app.game_state.game_phase(), GamePhase::Drawing);
+        assert_eq!(app.game_state.deck().remaining(), deck_size);
+        assert_eq!(app.game_state.draw_preview(), vec![4, 3]);
+
+        app.acknowledge_draw_action();
+        assert_ne!(*app.game_state.game_phase(), GamePhase::Drawing);
+        assert_eq!(app.game_state.deck().remaining(), deck_size - 7);
+    }
+
+    #[test]
+    /// Test that surrendering needs a confirmation and then ends the game as a loss
+    fn test_surrender_requires_confirmation() {
+        let mut app = create_attack_app(vec![Card::new(Suit::Hearts, Rank::Six)], vec![]);
+        app.app_state = AppState::Playing;
+        app.request_surrender();
+        app.confirm_surrender(KeyCode::Char('n'));
+        assert_eq!(app.app_state, AppState::Playing);
+
+        app.request_surrender();
+        app.confirm_surrender(KeyCode::Char('y'));
+        assert_eq!(app.app_state, AppState::GameOver);
+        assert_eq!(*app.game_state.game_phase(), GamePhase::GameOver);
+        assert_eq!(app.game_state.durak(), Some(0));
+        assert_eq!(app.game_state.winner(), Some(1));
+        // The loss counts towards the Dynamic difficulty
+        assert_eq!(app.match_record.win_rate(), Some(0.0));
+    }
+
+    #[test]
+    /// Test that game over offers a clean rematch or the menu with the difficulty kept
+    fn test_game_over_rematch_or_menu() {
+        fn surrender(app: &mut App) {
+            // An AI opening attack is briefed first, any key moves on
+            if app.defense_briefing {
+                app.on_key(KeyCode::Esc);
+            }
+            app.on_key(KeyCode::Char('x'));
+            app.on_key(KeyCode::Char('y'));
+            assert_eq!(app.app_state, AppState::GameOver);
+        }
+        let mut app = App::new();
+        app.select_difficulty(AiDifficulty::Hard);
+        app.start_game_action();
+        surrender(&mut app);
+
+        app.on_key(KeyCode::Char('n'));
+        assert_eq!(app.app_state, AppState::Playing);
+        // Nothing from the finished game is carried into the rematch
+        let game_state = &app.game_state;
+        let in_hands: usize = game_state.players().iter().map(|p| p.hand_size()).sum();
+        let on_table: usize = game_state
+            .table_cards()
             .iter()
-            .enumerate()
-            .find(|&(_, &c)| c == card)
-            .map(|(idx, _)| idx)
+            .map(|(_, defense)| 1 + usize::from(defense.is_some()))
+            .sum();
+        assert_eq!(game_state.deck().remaining() + in_hands + on_table, 36);
+        assert!(game_state.discard_pile().is_empty());
+        assert_eq!(game_state.winner(), None);
+
+        surrender(&mut app);
+        app.on_key(KeyCode::Char('m'));
+        assert_eq!(app.app_state, AppState::MainMenu);
+        assert_eq!(app.selected_difficulty, AiDifficulty::Hard);
+    }
+
+    #[test]
+    /// Test that a new game is confirmed from the rules summary, and 'b' backs out to the menu
+    fn test_rules_summary_before_game() {
+        let mut app = App::new();
+        app.on_key(KeyCode::Char('s'));
+        assert_eq!(app.app_state, AppState::RulesSummary);
+        app.on_key(KeyCode::Char('b'));
+        assert_eq!(app.app_state, AppState::MainMenu);
+
+        app.on_key(KeyCode::Char('n'));
+        assert_eq!(app.game_state.deck_size(), 72);
+        app.on_key(KeyCode::Char('s'));
+        app.on_key(KeyCode::Enter);
+        assert_eq!(app.app_state, AppState::Playing);
+        assert_eq!(app.game_state.card_count(), 72);
     }
 }
@@ -5,16 +5,67 @@
  * - Processing user input
  * - Rendering the UI
  * - Main event loop with input polling
+ * - Debouncing held keys
  */
+use super::ai_handler::resume_ai_turn;
 use super::app_core::App;
 use super::input::{AppAction, handle_key_input};
+use super::state::AppState;
 use crate::ui::debug_overlay::{error, trace};
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
 use ratatui::Terminal;
 use ratatui::backend::Backend;
 use std::io;
+use std::time::{Duration, Instant};
+
+/// A toggle key pressed again this soon after the last one is taken as the key being held.
+/// Most terminals send a held key as repeated presses, the first after about half a second.
+const TOGGLE_DEBOUNCE: Duration = Duration::from_millis(500);
 
 impl App {
+    /// Filters a key event from the terminal before `on_key` sees it. Releases are dropped,
+    /// a held key only keeps moving the cursor, and a toggle repeated within
+    /// `TOGGLE_DEBOUNCE` is ignored for as long as the key stays down.
+    pub fn on_key_event(&mut self, key: KeyEvent) {
+        let action = self.key_action(key.code);
+        match key.kind {
+            KeyEventKind::Press => {}
+            KeyEventKind::Repeat if action.is_some_and(AppAction::repeats_when_held) => {}
+            _ => return,
+        }
+        let now = Instant::now();
+        if action.is_some_and(AppAction::is_toggle) {
+            let held = self.last_toggle.is_some_and(|(last_key, at)| {
+                last_key == key.code && now.duration_since(at) < TOGGLE_DEBOUNCE
+            });
+            // Every repeat restarts the window, the key has to be let go to toggle again
+            self.last_toggle = Some((key.code, now));
+            if held {
+                trace(format!("Toggle key {:?} held, ignored", key.code));
+                return;
+            }
+        } else {
+            self.last_toggle = None;
+        }
+        self.on_key(key.code);
+    }
+    /// The action `key` is mapped to. While the debug overlay is open PageUp and PageDown
+    /// scroll its log instead of walking the practice history.
+    fn key_action(&self, key: KeyCode) -> Option<AppAction> {
+        if self.show_debug
+            && let Some(action) = self
+                .key_bindings
+                .action_for(key, &[AppAction::ScrollDebugUp, AppAction::ScrollDebugDown])
+        {
+            return Some(action);
+        }
+        handle_key_input(
+            &self.app_state,
+            self.game_state.game_phase(),
+            key,
+            &self.key_bindings,
+        )
+    }
     /// On key input, check if there is an action mapped to the key and process it
     pub fn on_key(&mut self, key: KeyCode) {
         trace(format!(
@@ -23,8 +74,76 @@ impl App {
             self.app_state,
             self.game_state.game_phase(),
         ));
-        if let Some(action) = handle_key_input(&self.app_state, self.game_state.game_phase(), key) {
+        if self.pending_quit {
+            self.confirm_quit(key);
+            return;
+        }
+        if self.pending_surrender {
+            self.confirm_surrender(key);
+            return;
+        }
+        // Like the draw summary, the endgame banner is dismissed by any key
+        if self.game_state.endgame_notice {
+            self.game_state.endgame_notice = false;
+            return;
+        }
+        // The AI is about to move, nothing can be played until it has
+        if self.ai_thinking_since.is_some() {
+            trace("AI is thinking, key ignored");
+            return;
+        }
+        // Any key tells the game the next human has the device
+        if self.handover_to.is_some() {
+            self.finish_handover();
+            return;
+        }
+        // Any key skips the rest of the deal
+        if self.dealing_since.is_some() {
+            self.finish_dealing();
+            return;
+        }
+        // Any key ends the review of the attacks and hands the turn to the human
+        if self.defense_briefing {
+            self.defense_briefing = false;
+            self.restart_turn_timer();
+            return;
+        }
+        if let Some(action) = self.key_action(key) {
+            let recordable = self.app_state == AppState::Playing
+                && !matches!(action, AppAction::PracticeBack | AppAction::PracticeForward);
+            if recordable {
+                self.seed_practice_ai();
+            }
+            // Looking around the table lasts until the next move, which brings the
+            // attack in play back into view
+            if !matches!(
+                action,
+                AppAction::ScrollTableLeft | AppAction::ScrollTableRight
+            ) {
+                self.table_scroll = None;
+            }
+            // The position before a human move, for the post-game analysis
+            let before = (self.app_state == AppState::Playing && self.is_human_turn())
+                .then(|| (self.game_state.clone(), self.current_player_index()));
+            let undo_before = (self.app_state == AppState::Playing).then(|| {
+                self.snapshot_for_undo(action);
+                self.game_state.clone()
+            });
             self.process_action(action);
+            if let Some(undo_before) = undo_before {
+                self.settle_undo(&undo_before, action);
+            }
+            if let Some((before, player_idx)) = before {
+                self.log_decision(before, player_idx, &action);
+            }
+            if self.app_state == AppState::Playing {
+                self.clamp_card_selection();
+                self.check_handover();
+            }
+            // A thinking AI records the position once it has answered, see `resume_ai_turn`
+            if recordable && self.ai_thinking_since.is_none() {
+                self.record_practice_state();
+            }
         } else {
             trace("No action mapped for key");
         }
@@ -33,8 +152,11 @@ impl App {
     fn process_action(&mut self, action: AppAction) {
         match action {
             AppAction::Quit => self.quit(),
+            AppAction::RequestQuit => self.request_quit(),
+            AppAction::Surrender => self.request_surrender(),
             AppAction::ToggleDebug => self.toggle_debug(),
             AppAction::ShowRules => self.show_rules(),
+            AppAction::ShowRulesSummary => self.show_rules_summary(),
             AppAction::ShowDifficultySelect => self.show_difficulty_select(),
             AppAction::SelectEasyDifficulty => {
                 self.select_difficulty(crate::game::AiDifficulty::Easy)
@@ -45,23 +167,75 @@ impl App {
             AppAction::SelectHardDifficulty => {
                 self.select_difficulty(crate::game::AiDifficulty::Hard)
             }
-            AppAction::ReturnToMenu => self.return_to_menu(),
+            AppAction::SelectDynamicDifficulty => {
+                self.select_difficulty(crate::game::AiDifficulty::Dynamic)
+            }
+            AppAction::CycleTrumpRule => self.cycle_trump_rule(),
+            AppAction::ToggleTrumpRevealPass => self.toggle_trump_reveal_pass(),
+            AppAction::ToggleTrumpPass => self.toggle_trump_pass(),
+            AppAction::ToggleJokers => self.toggle_jokers(),
+            AppAction::CycleDeckCount => self.cycle_deck_count(),
+            AppAction::CycleDeckSize => self.cycle_deck_size(),
+            AppAction::TogglePracticeMode => self.toggle_practice_mode(),
+            AppAction::ToggleDefendFirst => self.toggle_defend_first(),
+            AppAction::ToggleMulligan => self.toggle_mulligan(),
+            AppAction::ToggleFlatAttackLimit => self.toggle_flat_attack_limit(),
+            AppAction::ToggleSelectionStyle => self.toggle_selection_style(),
+            AppAction::CycleCardSize => self.cycle_card_size(),
+            AppAction::ToggleAids => self.toggle_aids(),
+            AppAction::ToggleCommentary => self.toggle_commentary(),
+            AppAction::ToggleHotseat => self.toggle_hotseat(),
+            AppAction::CycleTheme => self.cycle_theme(),
+            AppAction::CycleLanguage => self.cycle_language(),
+            AppAction::ShowDemoSelect => self.show_demo_select(),
+            AppAction::CycleDemoFirstAi => self.cycle_demo_difficulty(0),
+            AppAction::CycleDemoSecondAi => self.cycle_demo_difficulty(1),
+            AppAction::StartDemo => self.start_demo(),
+            AppAction::StopDemo => self.stop_demo(),
+            AppAction::ReturnToMenu | AppAction::LeaveGame => self.return_to_menu(),
             AppAction::SelectNextCard => self.select_next_card(),
             AppAction::SelectPrevCard => self.select_prev_card(),
+            AppAction::SelectNextAttack => self.select_next_attack(),
+            AppAction::SelectPrevAttack => self.select_prev_attack(),
+            AppAction::ScrollTableLeft => self.scroll_table(false),
+            AppAction::ScrollTableRight => self.scroll_table(true),
+            AppAction::SelectRank(rank) => self.select_card_by_rank(rank),
             AppAction::ToggleMultiSelect => self.toggle_multiple_selection(),
+            AppAction::CycleSort => self.cycle_sort(),
+            AppAction::QuickAttack => self.quick_attack_action(),
+            AppAction::QuickDefend => self.quick_defend_action(),
+            AppAction::Mulligan => self.mulligan_action(),
+            AppAction::UndoMove => self.undo_move_action(),
+            AppAction::SaveGame => self.save_game_action(),
             AppAction::ToggleCardSelection => {
-                if self.multiple_selection_mode {
-                    if let Some(idx) = self.selected_card_idx {
-                        self.toggle_card_selection(idx);
-                    }
+                if self.multiple_selection_mode
+                    && let Some(idx) = self.selected_card_idx
+                {
+                    self.toggle_card_selection(idx);
                 }
             }
             AppAction::StartGame => self.start_game_action(),
+            AppAction::ContinueGame => self.continue_game_action(),
             AppAction::PlaySelectedCard => self.play_card_action(),
             AppAction::PassTurn => self.pass_turn_action(),
+            AppAction::PassDefense => self.pass_defense_action(),
             AppAction::TakeCards => self.take_cards_action(),
             AppAction::StartNewGame => self.start_game_action(), // Restart current game
             AppAction::AcknowledgeDraw => self.acknowledge_draw_action(),
+            AppAction::ToggleAiStepMode => self.toggle_ai_step_mode(),
+            AppAction::StepAi => self.step_ai_action(),
+            AppAction::ToggleRevealHands => self.toggle_reveal_hands(),
+            AppAction::ToggleShowIndices => self.toggle_show_indices(),
+            AppAction::PracticeBack => self.practice_back_action(),
+            AppAction::PracticeForward => self.practice_forward_action(),
+            AppAction::ScrollAnalysisUp => self.scroll_analysis(false),
+            AppAction::ScrollDebugUp => self.scroll_debug(true),
+            AppAction::ScrollDebugDown => self.scroll_debug(false),
+            AppAction::ScrollAnalysisDown => self.scroll_analysis(true),
+            AppAction::ShowReplay => self.show_replay(),
+            AppAction::ReplayBack => self.step_replay(false),
+            AppAction::ReplayForward => self.step_replay(true),
+            AppAction::CloseReplay => self.close_replay(),
         }
     }
 
@@ -73,31 +247,108 @@ impl App {
                 error(format!("Render error: {}", e));
                 return self.safe_exit(Some(&format!("Render error: {}", e)));
             }
-            // Read user input every 100ms
-            match event::poll(std::time::Duration::from_millis(100)) {
-                Ok(has_event) => {
-                    if has_event {
-                        match event::read() {
-                            Ok(Event::Key(key)) => {
-                                if key.kind == KeyEventKind::Press {
-                                    // Process key input - if critical errors occur they will trigger safe_exit
-                                    self.on_key(key.code);
-                                }
-                            }
-                            Ok(_) => {} // Other events we ignore
-                            Err(e) => {
-                                error(format!("Event read error: {}", e));
-                                return self.safe_exit(Some(&format!("Event read error: {}", e)));
+            // Wait up to 100ms for input, then take every event already queued so keys
+            // typed faster than a frame are all handled before the next render
+            let mut timeout = Duration::from_millis(100);
+            while !self.should_quit {
+                match event::poll(timeout) {
+                    Ok(false) => break,
+                    Ok(true) => match event::read() {
+                        // Process key input - if critical errors occur they will trigger safe_exit
+                        Ok(Event::Key(key)) => self.on_key_event(key),
+                        Ok(Event::Resize(width, height)) => {
+                            trace(format!("Terminal resized to {}x{}", width, height));
+                            // Redraw right away so the layout never uses stale dimensions
+                            if let Err(e) = terminal.clear().and_then(|_| self.render(terminal)) {
+                                error(format!("Render error after resize: {}", e));
+                                return self.safe_exit(Some(&format!("Render error: {}", e)));
                             }
                         }
+                        Ok(_) => {} // Other events we ignore
+                        Err(e) => {
+                            error(format!("Event read error: {}", e));
+                            return self.safe_exit(Some(&format!("Event read error: {}", e)));
+                        }
+                    },
+                    Err(e) => {
+                        error(format!("Event poll error: {}", e));
+                        return self.safe_exit(Some(&format!("Event poll error: {}", e)));
                     }
                 }
-                Err(e) => {
-                    error(format!("Event poll error: {}", e));
-                    return self.safe_exit(Some(&format!("Event poll error: {}", e)));
+                timeout = Duration::ZERO;
+            }
+            match self.app_state {
+                AppState::Playing => {
+                    self.advance_dealing();
+                    resume_ai_turn(self);
+                    self.clamp_card_selection();
+                    self.check_handover();
+                    self.check_turn_timer();
                 }
+                AppState::Demo => self.demo_tick(),
+                _ => {}
             }
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::GamePhase;
+    use crate::game::card::{Card, Rank, Suit};
+    use crate::game::deck::DeckSize;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode, kind: KeyEventKind) -> KeyEvent {
+        KeyEvent::new_with_kind(code, KeyModifiers::NONE, kind)
+    }
+
+    #[test]
+    /// Test that a held multi-select key toggles once while a held arrow keeps moving
+    fn test_held_keys() {
+        let mut app = App::new();
+        app.app_state = AppState::Playing;
+        app.game_state.game_phase = GamePhase::Attack;
+        app.game_state.players[0].hand = vec![Card::new(Suit::Hearts, Rank::Six); 4];
+        app.selected_card_idx = Some(0);
+
+        // Held as repeated presses, as most terminals send it
+        app.on_key_event(key(KeyCode::Char('m'), KeyEventKind::Press));
+        app.on_key_event(key(KeyCode::Char('m'), KeyEventKind::Press));
+        app.on_key_event(key(KeyCode::Char('m'), KeyEventKind::Repeat));
+        assert!(app.multiple_selection_mode);
+        // Another key in between means 'm' was let go
+        app.on_key_event(key(KeyCode::Right, KeyEventKind::Press));
+        app.on_key_event(key(KeyCode::Char('m'), KeyEventKind::Press));
+        assert!(!app.multiple_selection_mode);
+
+        app.selected_card_idx = Some(0);
+        app.on_key_event(key(KeyCode::Right, KeyEventKind::Press));
+        app.on_key_event(key(KeyCode::Right, KeyEventKind::Repeat));
+        app.on_key_event(key(KeyCode::Right, KeyEventKind::Release));
+        assert_eq!(app.selected_card_idx, Some(2));
+    }
+
+    #[test]
+    /// Test that the difficulty page skips the 20 card pack with four players seated
+    fn test_deck_size_skips_short_pack_for_four_players() {
+        let mut app = App::new();
+        app.app_state = AppState::DifficultySelect;
+        app.on_key(KeyCode::Char('s'));
+        assert_eq!(app.game_state.pack_size, DeckSize::Full);
+        app.on_key(KeyCode::Char('s'));
+        assert_eq!(app.game_state.pack_size, DeckSize::Short);
+        app.on_key(KeyCode::Char('s'));
+        for seat in 2..4 {
+            let name = format!("Computer {}", seat);
+            app.game_state
+                .add_player(name, crate::game::PlayerType::Computer);
+        }
+        app.on_key(KeyCode::Char('s'));
+        assert_eq!(app.game_state.pack_size, DeckSize::Full);
+        app.on_key(KeyCode::Char('s'));
+        assert_eq!(app.game_state.pack_size, DeckSize::Standard);
+    }
+}
@@ -0,0 +1,142 @@
+/*
+ * hotseat.rs - Two people sharing one terminal
+ *
+ * This file contains the logic for local games between two human players:
+ * - Turning the second seat into a human from the main menu
+ * - Keeping track of whose hand is shown face up
+ * - Hiding both hands behind a "pass the device" screen whenever the other human is up
+ */
+use super::app_core::App;
+use crate::game::{GamePhase, PlayerType};
+use crate::ui::debug_overlay::{debug, info};
+
+impl App {
+    /// Switches the second seat between the computer and a second human player.
+    /// Both humans are renamed so the handover screen can tell them apart.
+    pub fn toggle_hotseat(&mut self) {
+        let hotseat = !self.is_hotseat();
+        let players = &mut self.game_state.players;
+        if hotseat {
+            players[0].name = "Player 1".to_string();
+            players[1].name = "Player 2".to_string();
+            players[1].player_type = PlayerType::Human;
+        } else {
+            players[0].name = "Player".to_string();
+            players[1].name = "Computer".to_string();
+            players[1].player_type = PlayerType::Computer;
+        }
+        info(format!("Hotseat: {}", hotseat));
+    }
+    /// Whether more than one human plays at this terminal
+    pub fn is_hotseat(&self) -> bool {
+        self.game_state
+            .players()
+            .iter()
+            .filter(|player| player.player_type() == &PlayerType::Human)
+            .count()
+            > 1
+    }
+    /// The human whose hand is shown face up: the one last handed the device, otherwise the
+    /// only human at the table
+    pub fn viewing_player(&self) -> Option<usize> {
+        self.viewer.or_else(|| {
+            self.game_state
+                .players()
+                .iter()
+                .position(|player| player.player_type() == &PlayerType::Human)
+        })
+    }
+    /// The human who has to move right now, if any
+    fn human_to_act(&self) -> Option<usize> {
+        if !matches!(
+            self.game_state.game_phase(),
+            GamePhase::Attack | GamePhase::Defense | GamePhase::Reinforce
+        ) {
+            return None;
+        }
+        let idx = self.current_player_index();
+        (self.game_state.players()[idx].player_type() == &PlayerType::Human).then_some(idx)
+    }
+    /// Called after every move. In a hotseat game, once the other human is up both hands
+    /// are hidden until the device has been passed on, see `finish_handover`.
+    pub fn check_handover(&mut self) {
+        if !self.is_hotseat() || self.handover_to.is_some() {
+            return;
+        }
+        if let Some(next) = self.human_to_act()
+            && self.viewer != Some(next)
+        {
+            debug(format!("Handing the device to player {}", next));
+            self.handover_to = Some(next);
+            self.selected_card_idx = None;
+            self.selected_attack_idx = None;
+            self.clear_multi_selection();
+            self.restart_turn_timer();
+        }
+    }
+    /// The next player has the device, show their hand. Bound to any key.
+    pub fn finish_handover(&mut self) {
+        if let Some(next) = self.handover_to.take() {
+            self.viewer = Some(next);
+            self.restart_turn_timer();
+        }
+    }
+    /// Forgets who was looking, a new or loaded game starts with a handover when needed
+    pub fn reset_viewer(&mut self) {
+        self.viewer = None;
+        self.handover_to = None;
+        self.check_handover();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::AppState;
+    use crate::game::card::{Card, Rank, Suit};
+    use crossterm::event::KeyCode;
+
+    #[test]
+    /// Test that the hands are hidden between the two humans' turns and the second human
+    /// sees their own hand once they press a key
+    fn test_hotseat_hands_over_between_turns() {
+        let mut app = App::new();
+        app.toggle_hotseat();
+        assert!(app.is_hotseat());
+        let game_state = &mut app.game_state;
+        game_state.trump_suit = Some(Suit::Spades);
+        game_state.players[0].hand = vec![
+            Card::new(Suit::Hearts, Rank::Seven),
+            Card::new(Suit::Clubs, Rank::Nine),
+        ];
+        game_state.players[1].hand = vec![
+            Card::new(Suit::Hearts, Rank::Ten),
+            Card::new(Suit::Diamonds, Rank::Six),
+        ];
+        game_state.current_attacker = 0;
+        game_state.current_defender = 1;
+        game_state.game_phase = GamePhase::Attack;
+        app.app_state = AppState::Playing;
+        app.reset_viewer();
+        // The attacker gets the device first
+        assert_eq!(app.handover_to, Some(0));
+        app.on_key(KeyCode::Enter);
+        assert_eq!(app.handover_to, None);
+        assert_eq!(app.viewing_player(), Some(0));
+        app.selected_card_idx = Some(0);
+        app.on_key(KeyCode::Enter);
+        assert_eq!(*app.game_state.game_phase(), GamePhase::Defense);
+        // The defender is up, nobody's cards are shown until the key press
+        assert_eq!(app.handover_to, Some(1));
+        assert_eq!(app.selected_card_idx, None);
+        app.on_key(KeyCode::Char('a'));
+        assert_eq!(app.viewing_player(), Some(1));
+        assert_eq!(app.game_state.players()[1].hand().len(), 2);
+        app.toggle_hotseat();
+        assert!(!app.is_hotseat());
+        assert_eq!(
+            app.game_state.players()[1].player_type(),
+            &PlayerType::Computer
+        );
+    }
+}
@@ -1,100 +1,321 @@
+use crate::app::keybindings::KeyBindings;
 use crate::app::state::AppState;
 use crate::game::GamePhase;
+use crate::game::card::Rank;
 use crossterm::event::KeyCode;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AppAction {
     // General Actions
     Quit,
+    RequestQuit, // First 'q' while playing asks for confirmation
+    Surrender,   // 'x' while playing concedes the game after confirmation
     ToggleDebug,
+    ToggleAiStepMode,  // 'f' switches between fast-forward and single-step AI
+    StepAi,            // '.' advances the AI by one action in step mode
+    ToggleRevealHands, // 'o' shows the AI hands while the debug overlay is on
+    ToggleShowIndices, // 'i' numbers the human's cards while the debug overlay is on
     // Main Menu Actions
     StartGame,
+    ContinueGame,
     ShowRules,
+    ShowRulesSummary, // 's' lists the rules of the new game before it is dealt
     ShowDifficultySelect,
     SelectEasyDifficulty,
     SelectMediumDifficulty,
     SelectHardDifficulty,
+    SelectDynamicDifficulty,
+    CycleTrumpRule,
+    ToggleTrumpRevealPass,
+    ToggleTrumpPass,
+    ToggleJokers,
+    CycleDeckCount,
+    CycleDeckSize, // 's' on the difficulty page picks a 20, 36 or 52 card deck
+    TogglePracticeMode,
+    ToggleDefendFirst,
+    ToggleMulligan,
+    ToggleFlatAttackLimit,
+    ToggleSelectionStyle,
+    CycleCardSize,
+    ToggleAids,
+    ToggleCommentary,
+    ToggleHotseat,
+    CycleTheme,
+    CycleLanguage,
+    ShowDemoSelect,
+    // Demo Actions
+    CycleDemoFirstAi,  // '1' on the demo page
+    CycleDemoSecondAi, // '2' on the demo page
+    StartDemo,
+    StopDemo, // Any key while the demo runs
     // Rules Page Actions
     ReturnToMenu,
     // Playing Actions
     SelectNextCard,
     SelectPrevCard,
+    SelectNextAttack,
+    SelectPrevAttack,
+    ScrollTableLeft,
+    ScrollTableRight,
+    SelectRank(Rank), // A rank key jumps to the next card of that rank
     ToggleMultiSelect,
+    CycleSort,   // 's' switches the hand between sorting by suit and by rank
+    QuickAttack, // 'a' attacks or throws in with the lowest legal card
+    QuickDefend, // 'a' while defending beats the attack with the lowest card that can
+    Mulligan,    // 'r' redeals once before the first round when the house rule is on
     ToggleCardSelection,
     PlaySelectedCard, // Covers both single and multi-select Enter press
     PassTurn,         // Covers 'p' key
+    PassDefense,      // 'p' while defending passes the attack with a same-rank card
     TakeCards,        // Covers 't' key
+    UndoMove,         // 'u' takes back the human's last move until the AI answers
+    SaveGame,         // F5 saves the game in progress, see `save_game.rs`
     // Game Over Actions
     StartNewGame,
+    LeaveGame, // 'b'/'m' goes back to the menu from the end of a game, Esc doesn't
     // Drawing Phase Actions
     AcknowledgeDraw, // Any key during drawing
+    // Practice Actions
+    PracticeBack,     // PageUp takes back a move in a practice game
+    PracticeForward,  // PageDown replays the last explored move
+    ScrollAnalysisUp, // Up/Down move through the analysis on the game over screen
+    ScrollAnalysisDown,
+    ShowReplay, // 'r' on the game over screen goes back over the game
+    // Replay Actions
+    ReplayBack, // Left/Right step through the moves
+    ReplayForward,
+    CloseReplay,
+    // Debug Actions
+    ScrollDebugUp, // PageUp/PageDown page through the log while the debug overlay is open
+    ScrollDebugDown,
 }
-/// Handle User Input depending on the current AppState and GamePhase
+impl AppAction {
+    /// Actions that flip or cycle a setting, a held key must not keep flipping them
+    pub fn is_toggle(self) -> bool {
+        matches!(
+            self,
+            AppAction::ToggleDebug
+                | AppAction::ToggleAiStepMode
+                | AppAction::ToggleRevealHands
+                | AppAction::ToggleShowIndices
+                | AppAction::CycleTrumpRule
+                | AppAction::ToggleTrumpRevealPass
+                | AppAction::ToggleTrumpPass
+                | AppAction::ToggleJokers
+                | AppAction::CycleDeckCount
+                | AppAction::CycleDeckSize
+                | AppAction::TogglePracticeMode
+                | AppAction::ToggleDefendFirst
+                | AppAction::ToggleMulligan
+                | AppAction::ToggleFlatAttackLimit
+                | AppAction::ToggleSelectionStyle
+                | AppAction::CycleCardSize
+                | AppAction::ToggleAids
+                | AppAction::ToggleCommentary
+                | AppAction::ToggleHotseat
+                | AppAction::CycleTheme
+                | AppAction::CycleLanguage
+                | AppAction::ToggleMultiSelect
+                | AppAction::CycleSort
+                | AppAction::ToggleCardSelection
+        )
+    }
+    /// Moves the human can take back with 'u', see `undo.rs`
+    pub fn is_undoable(self) -> bool {
+        matches!(
+            self,
+            AppAction::PlaySelectedCard
+                | AppAction::QuickAttack
+                | AppAction::QuickDefend
+                | AppAction::PassTurn
+                | AppAction::PassDefense
+                | AppAction::TakeCards
+        )
+    }
+    /// Cursor movement, the only thing a held key keeps doing
+    pub fn repeats_when_held(self) -> bool {
+        matches!(
+            self,
+            AppAction::SelectNextCard
+                | AppAction::SelectPrevCard
+                | AppAction::SelectNextAttack
+                | AppAction::SelectPrevAttack
+                | AppAction::ScrollTableLeft
+                | AppAction::ScrollTableRight
+                | AppAction::ScrollAnalysisUp
+                | AppAction::ScrollAnalysisDown
+                | AppAction::ReplayBack
+                | AppAction::ReplayForward
+                | AppAction::ScrollDebugUp
+                | AppAction::ScrollDebugDown
+        )
+    }
+}
+/// The rank a key stands for: '2' to '9', '0' or '1' for the ten, and Shift+J/Q/K/A for
+/// the face cards. The lowercase letters keep their own bindings.
+fn rank_for_key(key: KeyCode) -> Option<Rank> {
+    match key {
+        KeyCode::Char('2') => Some(Rank::Two),
+        KeyCode::Char('3') => Some(Rank::Three),
+        KeyCode::Char('4') => Some(Rank::Four),
+        KeyCode::Char('5') => Some(Rank::Five),
+        KeyCode::Char('6') => Some(Rank::Six),
+        KeyCode::Char('7') => Some(Rank::Seven),
+        KeyCode::Char('8') => Some(Rank::Eight),
+        KeyCode::Char('9') => Some(Rank::Nine),
+        KeyCode::Char('0') | KeyCode::Char('1') => Some(Rank::Ten),
+        KeyCode::Char('J') => Some(Rank::Jack),
+        KeyCode::Char('Q') => Some(Rank::Queen),
+        KeyCode::Char('K') => Some(Rank::King),
+        KeyCode::Char('A') => Some(Rank::Ace),
+        _ => None,
+    }
+}
+/// Handle User Input depending on the current AppState and GamePhase.
+/// Which key triggers an action comes from `bindings`, which actions a screen offers is
+/// decided here.
 pub fn handle_key_input(
     app_state: &AppState,
     game_phase: &GamePhase,
     key: KeyCode,
+    bindings: &KeyBindings,
 ) -> Option<AppAction> {
+    // Any key ends the demo, 'q' included
+    if *app_state == AppState::Demo {
+        return Some(AppAction::StopDemo);
+    }
+    // Rank keys come before the global keys, Shift+Q is the queen while a card can be played
+    if *app_state == AppState::Playing
+        && matches!(
+            game_phase,
+            GamePhase::Attack | GamePhase::Defense | GamePhase::Reinforce
+        )
+        && let Some(rank) = rank_for_key(key)
+    {
+        return Some(AppAction::SelectRank(rank));
+    }
     // Handle global keys first
-    match key {
-        KeyCode::Char('q') | KeyCode::Char('Q') => return Some(AppAction::Quit),
-        _ => {}
+    // Mid-game quitting needs confirmation so a stray 'q' doesn't lose the match
+    if bindings.is_bound(AppAction::Quit, key) {
+        return Some(if *app_state == AppState::Playing {
+            AppAction::RequestQuit
+        } else {
+            AppAction::Quit
+        });
     }
-    match app_state {
-        AppState::MainMenu => match key {
-            KeyCode::Char('s') | KeyCode::Char('S') => Some(AppAction::StartGame),
-            KeyCode::Char('r') | KeyCode::Char('R') => Some(AppAction::ShowRules),
-            KeyCode::Char('a') | KeyCode::Char('A') => Some(AppAction::ShowDifficultySelect),
-            KeyCode::Char('d') | KeyCode::Char('D') => Some(AppAction::ToggleDebug),
-            _ => None,
-        },
-        AppState::DifficultySelect => match key {
-            KeyCode::Char('1') => Some(AppAction::SelectEasyDifficulty),
-            KeyCode::Char('2') => Some(AppAction::SelectMediumDifficulty),
-            KeyCode::Char('3') => Some(AppAction::SelectHardDifficulty),
-            KeyCode::Char('b') | KeyCode::Char('B') | KeyCode::Esc => Some(AppAction::ReturnToMenu),
-            _ => None,
-        },
-        AppState::RulesPage => match key {
-            KeyCode::Char('b') | KeyCode::Char('B') | KeyCode::Esc => Some(AppAction::ReturnToMenu),
-            _ => None,
-        },
-        AppState::Playing => {
-            match game_phase {
-                GamePhase::Drawing => {
-                    // Any key press acknowledges the draw phase
-                    Some(AppAction::AcknowledgeDraw)
-                }
-                GamePhase::Attack | GamePhase::Defense => match key {
-                    // vim keys
-                    KeyCode::Char('k') | KeyCode::Char('h') => Some(AppAction::SelectPrevCard),
-                    KeyCode::Char('j') | KeyCode::Char('l') => Some(AppAction::SelectNextCard),
-                    KeyCode::Up | KeyCode::Left => Some(AppAction::SelectPrevCard),
-                    KeyCode::Down | KeyCode::Right => Some(AppAction::SelectNextCard),
-                    KeyCode::Char('d') | KeyCode::Char('D') => Some(AppAction::ToggleDebug),
-                    KeyCode::Char('m') | KeyCode::Char('M') => Some(AppAction::ToggleMultiSelect),
-                    KeyCode::Char(' ') => Some(AppAction::ToggleCardSelection),
-                    KeyCode::Enter => Some(AppAction::PlaySelectedCard),
-                    KeyCode::Char('p') | KeyCode::Char('P') if *game_phase == GamePhase::Attack => {
-                        Some(AppAction::PassTurn)
-                    }
-                    KeyCode::Char('t') | KeyCode::Char('T')
-                        if *game_phase == GamePhase::Defense =>
-                    {
-                        Some(AppAction::TakeCards)
-                    }
-                    _ => None,
-                },
-                GamePhase::GameOver => match key {
-                    KeyCode::Char('n') | KeyCode::Char('N') => Some(AppAction::StartNewGame),
-                    _ => None,
-                },
-                _ => None, // Setup phase has no input
-            }
-        }
-        AppState::GameOver => match key {
-            KeyCode::Char('n') | KeyCode::Char('N') => Some(AppAction::StartNewGame),
-            _ => None,
-        },
+    // Practice history works in every phase, including after the game is over
+    if matches!(app_state, AppState::Playing | AppState::GameOver)
+        && let Some(action) =
+            bindings.action_for(key, &[AppAction::PracticeBack, AppAction::PracticeForward])
+    {
+        return Some(action);
     }
+    let actions: &[AppAction] = match app_state {
+        AppState::MainMenu => &[
+            AppAction::ShowRulesSummary,
+            AppAction::ContinueGame,
+            AppAction::ShowRules,
+            AppAction::ShowDifficultySelect,
+            AppAction::ToggleDebug,
+            AppAction::CycleTrumpRule,
+            AppAction::ToggleTrumpRevealPass,
+            AppAction::ToggleTrumpPass,
+            AppAction::ToggleJokers,
+            AppAction::CycleDeckCount,
+            AppAction::TogglePracticeMode,
+            AppAction::ToggleDefendFirst,
+            AppAction::ToggleMulligan,
+            AppAction::ToggleFlatAttackLimit,
+            AppAction::ToggleSelectionStyle,
+            AppAction::CycleCardSize,
+            AppAction::ToggleAids,
+            AppAction::ToggleCommentary,
+            AppAction::ToggleHotseat,
+            AppAction::CycleTheme,
+            AppAction::CycleLanguage,
+            AppAction::ShowDemoSelect,
+        ],
+        AppState::DemoSelect => &[
+            AppAction::CycleDemoFirstAi,
+            AppAction::CycleDemoSecondAi,
+            AppAction::StartDemo,
+            AppAction::ReturnToMenu,
+        ],
+        AppState::Demo => return Some(AppAction::StopDemo),
+        AppState::DifficultySelect => &[
+            AppAction::SelectEasyDifficulty,
+            AppAction::SelectMediumDifficulty,
+            AppAction::SelectHardDifficulty,
+            AppAction::SelectDynamicDifficulty,
+            AppAction::CycleDeckSize,
+            AppAction::ReturnToMenu,
+        ],
+        AppState::RulesSummary => &[AppAction::StartGame, AppAction::ReturnToMenu],
+        AppState::RulesPage => &[AppAction::ReturnToMenu],
+        AppState::Playing => match game_phase {
+            // Any key press acknowledges the draw phase
+            GamePhase::Drawing => return Some(AppAction::AcknowledgeDraw),
+            GamePhase::Attack | GamePhase::Reinforce => &[
+                AppAction::SelectPrevCard,
+                AppAction::SelectNextCard,
+                AppAction::ToggleDebug,
+                AppAction::ToggleAiStepMode,
+                AppAction::StepAi,
+                AppAction::ToggleRevealHands,
+                AppAction::ToggleShowIndices,
+                AppAction::Surrender,
+                AppAction::ToggleMultiSelect,
+                AppAction::CycleSort,
+                AppAction::Mulligan,
+                AppAction::UndoMove,
+                AppAction::SaveGame,
+                AppAction::QuickAttack,
+                AppAction::ToggleCardSelection,
+                AppAction::PlaySelectedCard,
+                AppAction::PassTurn,
+                AppAction::ScrollTableLeft,
+                AppAction::ScrollTableRight,
+            ],
+            GamePhase::Defense => &[
+                AppAction::SelectPrevCard,
+                AppAction::SelectNextCard,
+                AppAction::ToggleDebug,
+                AppAction::ToggleAiStepMode,
+                AppAction::StepAi,
+                AppAction::ToggleRevealHands,
+                AppAction::ToggleShowIndices,
+                AppAction::Surrender,
+                AppAction::ToggleMultiSelect,
+                AppAction::CycleSort,
+                AppAction::Mulligan,
+                AppAction::UndoMove,
+                AppAction::SaveGame,
+                AppAction::QuickDefend,
+                AppAction::ToggleCardSelection,
+                AppAction::PlaySelectedCard,
+                AppAction::PassDefense,
+                AppAction::SelectNextAttack,
+                AppAction::SelectPrevAttack,
+                AppAction::ScrollTableLeft,
+                AppAction::ScrollTableRight,
+                AppAction::TakeCards,
+            ],
+            GamePhase::GameOver => &[AppAction::StartNewGame, AppAction::LeaveGame],
+            GamePhase::Setup => &[], // Setup phase has no input
+        },
+        // 'N' is a rematch with the same settings, 'B'/'M' goes back to change them
+        AppState::GameOver => &[
+            AppAction::StartNewGame,
+            AppAction::LeaveGame,
+            AppAction::ScrollAnalysisUp,
+            AppAction::ScrollAnalysisDown,
+            AppAction::ShowReplay,
+        ],
+        AppState::Replay => &[
+            AppAction::ReplayBack,
+            AppAction::ReplayForward,
+            AppAction::CloseReplay,
+        ],
+    };
+    bindings.action_for(key, actions)
 }
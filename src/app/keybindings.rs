@@ -0,0 +1,375 @@
+/*
+ * keybindings.rs - Remappable keys
+ *
+ * This file contains the key bindings `handle_key_input` dispatches on:
+ * - The default keys of every action, the ones the game has always used
+ * - Reading `<config dir>/durak/keys.toml` at startup to override them
+ * - Naming keys in the file, like "k", "Enter" or "F5"
+ */
+use super::app_core::App;
+use super::input::AppAction;
+use crate::ui::debug_overlay::{info, warn};
+use crossterm::event::KeyCode;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+
+/// Every action a key can be bound to, with its name in the keys file.
+/// Rank keys, and the "any key" of the demo and the draw, are not rebindable.
+const ACTION_NAMES: &[(&str, AppAction)] = &[
+    ("quit", AppAction::Quit),
+    ("surrender", AppAction::Surrender),
+    ("toggle_debug", AppAction::ToggleDebug),
+    ("toggle_ai_step_mode", AppAction::ToggleAiStepMode),
+    ("step_ai", AppAction::StepAi),
+    ("toggle_reveal_hands", AppAction::ToggleRevealHands),
+    ("toggle_show_indices", AppAction::ToggleShowIndices),
+    ("start_game", AppAction::StartGame),
+    ("continue_game", AppAction::ContinueGame),
+    ("show_rules", AppAction::ShowRules),
+    ("show_rules_summary", AppAction::ShowRulesSummary),
+    ("show_difficulty_select", AppAction::ShowDifficultySelect),
+    ("select_easy_difficulty", AppAction::SelectEasyDifficulty),
+    (
+        "select_medium_difficulty",
+        AppAction::SelectMediumDifficulty,
+    ),
+    ("select_hard_difficulty", AppAction::SelectHardDifficulty),
+    (
+        "select_dynamic_difficulty",
+        AppAction::SelectDynamicDifficulty,
+    ),
+    ("cycle_trump_rule", AppAction::CycleTrumpRule),
+    ("toggle_trump_reveal_pass", AppAction::ToggleTrumpRevealPass),
+    ("toggle_trump_pass", AppAction::ToggleTrumpPass),
+    ("toggle_jokers", AppAction::ToggleJokers),
+    ("cycle_deck_count", AppAction::CycleDeckCount),
+    ("cycle_deck_size", AppAction::CycleDeckSize),
+    ("toggle_practice_mode", AppAction::TogglePracticeMode),
+    ("toggle_defend_first", AppAction::ToggleDefendFirst),
+    ("toggle_mulligan", AppAction::ToggleMulligan),
+    ("toggle_flat_attack_limit", AppAction::ToggleFlatAttackLimit),
+    ("toggle_selection_style", AppAction::ToggleSelectionStyle),
+    ("cycle_card_size", AppAction::CycleCardSize),
+    ("toggle_aids", AppAction::ToggleAids),
+    ("toggle_commentary", AppAction::ToggleCommentary),
+    ("toggle_hotseat", AppAction::ToggleHotseat),
+    ("cycle_theme", AppAction::CycleTheme),
+    ("cycle_language", AppAction::CycleLanguage),
+    ("show_demo_select", AppAction::ShowDemoSelect),
+    ("cycle_demo_first_ai", AppAction::CycleDemoFirstAi),
+    ("cycle_demo_second_ai", AppAction::CycleDemoSecondAi),
+    ("start_demo", AppAction::StartDemo),
+    ("return_to_menu", AppAction::ReturnToMenu),
+    ("select_next_card", AppAction::SelectNextCard),
+    ("select_prev_card", AppAction::SelectPrevCard),
+    ("select_next_attack", AppAction::SelectNextAttack),
+    ("select_prev_attack", AppAction::SelectPrevAttack),
+    ("scroll_table_left", AppAction::ScrollTableLeft),
+    ("scroll_table_right", AppAction::ScrollTableRight),
+    ("toggle_multi_select", AppAction::ToggleMultiSelect),
+    ("cycle_sort", AppAction::CycleSort),
+    ("quick_attack", AppAction::QuickAttack),
+    ("quick_defend", AppAction::QuickDefend),
+    ("mulligan", AppAction::Mulligan),
+    ("toggle_card_selection", AppAction::ToggleCardSelection),
+    ("play_selected_card", AppAction::PlaySelectedCard),
+    ("pass_turn", AppAction::PassTurn),
+    ("pass_defense", AppAction::PassDefense),
+    ("take_cards", AppAction::TakeCards),
+    ("undo_move", AppAction::UndoMove),
+    ("save_game", AppAction::SaveGame),
+    ("start_new_game", AppAction::StartNewGame),
+    ("leave_game", AppAction::LeaveGame),
+    ("practice_back", AppAction::PracticeBack),
+    ("practice_forward", AppAction::PracticeForward),
+    ("scroll_analysis_up", AppAction::ScrollAnalysisUp),
+    ("scroll_analysis_down", AppAction::ScrollAnalysisDown),
+    ("show_replay", AppAction::ShowReplay),
+    ("replay_back", AppAction::ReplayBack),
+    ("replay_forward", AppAction::ReplayForward),
+    ("close_replay", AppAction::CloseReplay),
+    ("scroll_debug_up", AppAction::ScrollDebugUp),
+    ("scroll_debug_down", AppAction::ScrollDebugDown),
+];
+
+/// Keys that have a name in the keys file rather than being a single character
+const KEY_NAMES: &[(&str, KeyCode)] = &[
+    ("Space", KeyCode::Char(' ')),
+    ("Enter", KeyCode::Enter),
+    ("Esc", KeyCode::Esc),
+    ("Tab", KeyCode::Tab),
+    ("BackTab", KeyCode::BackTab),
+    ("Backspace", KeyCode::Backspace),
+    ("Up", KeyCode::Up),
+    ("Down", KeyCode::Down),
+    ("Left", KeyCode::Left),
+    ("Right", KeyCode::Right),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("Delete", KeyCode::Delete),
+    ("Insert", KeyCode::Insert),
+];
+
+/// The key named `name` in the keys file: a single character, matched exactly so "x" and
+/// "X" are different keys, one of `KEY_NAMES`, or a function key from "F1" up
+pub fn parse_key(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+    if let Some(&(_, key)) = KEY_NAMES.iter().find(|(key_name, _)| *key_name == name) {
+        return Some(key);
+    }
+    name.strip_prefix('F')
+        .and_then(|n| n.parse().ok())
+        .filter(|&n| n >= 1)
+        .map(KeyCode::F)
+}
+
+/// Location of the keys file, `<config dir>/durak/keys.toml`
+pub fn keys_file_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("durak").join("keys.toml"))
+}
+
+/// The keys that trigger each action. An action missing from the map has no key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyBindings {
+    keys: HashMap<AppAction, Vec<KeyCode>>,
+}
+
+impl Default for KeyBindings {
+    /// The keys the game ships with
+    fn default() -> Self {
+        use AppAction::*;
+        let chars = |keys: &str| keys.chars().map(KeyCode::Char).collect::<Vec<_>>();
+        let keys = [
+            (Quit, chars("qQ")),
+            (Surrender, chars("xX")),
+            (ToggleDebug, chars("dD")),
+            (ToggleAiStepMode, chars("fF")),
+            (StepAi, chars(".")),
+            (ToggleRevealHands, chars("oO")),
+            (ToggleShowIndices, chars("iI")),
+            (StartGame, vec![KeyCode::Enter]),
+            (ContinueGame, chars("cC")),
+            (ShowRules, chars("rR")),
+            (ShowRulesSummary, chars("sS")),
+            (ShowDifficultySelect, chars("aA")),
+            (SelectEasyDifficulty, chars("1")),
+            (SelectMediumDifficulty, chars("2")),
+            (SelectHardDifficulty, chars("3")),
+            (SelectDynamicDifficulty, chars("4")),
+            (CycleTrumpRule, chars("tT")),
+            (ToggleTrumpRevealPass, chars("vV")),
+            (ToggleTrumpPass, chars("uU")),
+            (ToggleJokers, chars("jJ")),
+            (CycleDeckCount, chars("nN")),
+            (CycleDeckSize, chars("sS")),
+            (TogglePracticeMode, chars("pP")),
+            (ToggleDefendFirst, chars("gG")),
+            (ToggleMulligan, chars("mM")),
+            (ToggleFlatAttackLimit, chars("fF")),
+            (ToggleSelectionStyle, chars("wW")),
+            (CycleCardSize, chars("zZ")),
+            (ToggleAids, chars("iI")),
+            (ToggleCommentary, chars("kK")),
+            (ToggleHotseat, chars("hH")),
+            (CycleTheme, chars("oO")),
+            (CycleLanguage, chars("lL")),
+            (ShowDemoSelect, chars("eE")),
+            (CycleDemoFirstAi, chars("1")),
+            (CycleDemoSecondAi, chars("2")),
+            (StartDemo, vec![KeyCode::Enter]),
+            (ReturnToMenu, [chars("bB"), vec![KeyCode::Esc]].concat()),
+            (
+                SelectPrevCard,
+                [chars("kh"), vec![KeyCode::Up, KeyCode::Left]].concat(),
+            ),
+            (
+                SelectNextCard,
+                [chars("jl"), vec![KeyCode::Down, KeyCode::Right]].concat(),
+            ),
+            (SelectNextAttack, vec![KeyCode::Tab]),
+            (SelectPrevAttack, vec![KeyCode::BackTab]),
+            (ScrollTableLeft, chars("[")),
+            (ScrollTableRight, chars("]")),
+            (ToggleMultiSelect, chars("mM")),
+            (CycleSort, chars("sS")),
+            (QuickAttack, chars("a")),
+            (QuickDefend, chars("a")),
+            (Mulligan, chars("rR")),
+            (ToggleCardSelection, chars(" ")),
+            (PlaySelectedCard, vec![KeyCode::Enter]),
+            (PassTurn, chars("pP")),
+            (PassDefense, chars("pP")),
+            (TakeCards, chars("tT")),
+            (UndoMove, chars("uU")),
+            (SaveGame, vec![KeyCode::F(5)]),
+            (StartNewGame, chars("nN")),
+            (LeaveGame, chars("bBmM")),
+            (PracticeBack, vec![KeyCode::PageUp]),
+            (PracticeForward, vec![KeyCode::PageDown]),
+            (ScrollAnalysisUp, vec![KeyCode::Up, KeyCode::Char('k')]),
+            (ScrollAnalysisDown, vec![KeyCode::Down, KeyCode::Char('j')]),
+            (ShowReplay, chars("rR")),
+            (
+                ReplayBack,
+                vec![KeyCode::Left, KeyCode::Up, KeyCode::Char('h')],
+            ),
+            (
+                ReplayForward,
+                vec![KeyCode::Right, KeyCode::Down, KeyCode::Char('l')],
+            ),
+            (CloseReplay, [chars("rRbB"), vec![KeyCode::Esc]].concat()),
+            (ScrollDebugUp, vec![KeyCode::PageUp]),
+            (ScrollDebugDown, vec![KeyCode::PageDown]),
+        ];
+        Self {
+            keys: keys.into_iter().collect(),
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Whether `key` triggers `action`
+    pub fn is_bound(&self, action: AppAction, key: KeyCode) -> bool {
+        self.keys
+            .get(&action)
+            .is_some_and(|keys| keys.contains(&key))
+    }
+    /// The first of `actions` that `key` triggers. The order settles a key bound to two
+    /// actions of the same screen.
+    pub fn action_for(&self, key: KeyCode, actions: &[AppAction]) -> Option<AppAction> {
+        actions
+            .iter()
+            .copied()
+            .find(|&action| self.is_bound(action, key))
+    }
+    /// The defaults with the actions in `toml` rebound, e.g. `take_cards = ["t", "Enter"]`.
+    /// An action left out of the file keeps its default keys, an empty list unbinds it.
+    pub fn from_toml(toml: &str) -> Result<Self, String> {
+        let table: BTreeMap<String, Vec<String>> =
+            toml::from_str(toml).map_err(|e| e.to_string())?;
+        let mut bindings = Self::default();
+        for (name, key_names) in table {
+            let Some(&(_, action)) = ACTION_NAMES.iter().find(|(action, _)| *action == name) else {
+                return Err(format!("Unknown action '{}'", name));
+            };
+            let keys = key_names
+                .iter()
+                .map(|key| parse_key(key).ok_or_else(|| format!("Unknown key '{}'", key)))
+                .collect::<Result<Vec<_>, _>>()?;
+            bindings.keys.insert(action, keys);
+        }
+        Ok(bindings)
+    }
+}
+
+impl App {
+    /// Applies the keys file, if there is one. A file that can't be read or has a mistake
+    /// in it leaves every key at its default.
+    pub fn load_key_bindings(&mut self) {
+        let Some(path) = keys_file_path().filter(|path| path.exists()) else {
+            return;
+        };
+        let bindings = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|toml| KeyBindings::from_toml(&toml));
+        match bindings {
+            Ok(bindings) => {
+                info(format!("Key bindings loaded from {}", path.display()));
+                self.key_bindings = bindings;
+            }
+            Err(e) => warn(format!(
+                "Ignoring keys file {}, using the default keys: {}",
+                path.display(),
+                e
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::input::handle_key_input;
+    use crate::app::state::AppState;
+    use crate::game::GamePhase;
+
+    #[test]
+    /// Test that a keys file rebinds only the actions it names, and that a mistake in it
+    /// is reported rather than half applied
+    fn test_key_bindings_from_toml() {
+        let bindings =
+            KeyBindings::from_toml("select_prev_card = [\"c\", \"Up\"]\nsave_game = [\"F2\"]")
+                .unwrap();
+        let key = |key, phase| handle_key_input(&AppState::Playing, &phase, key, &bindings);
+        assert_eq!(
+            key(KeyCode::Char('c'), GamePhase::Attack),
+            Some(AppAction::SelectPrevCard)
+        );
+        assert_eq!(key(KeyCode::Char('k'), GamePhase::Attack), None);
+        assert_eq!(
+            key(KeyCode::F(2), GamePhase::Defense),
+            Some(AppAction::SaveGame)
+        );
+        // Untouched actions keep their defaults
+        assert_eq!(
+            key(KeyCode::Char('t'), GamePhase::Defense),
+            Some(AppAction::TakeCards)
+        );
+        assert_eq!(
+            handle_key_input(
+                &AppState::MainMenu,
+                &GamePhase::Setup,
+                KeyCode::Char('j'),
+                &bindings
+            ),
+            Some(AppAction::ToggleJokers)
+        );
+
+        assert!(KeyBindings::from_toml("take_all = [\"t\"]").is_err());
+        assert!(KeyBindings::from_toml("take_cards = [\"Hyper\"]").is_err());
+        assert!(KeyBindings::from_toml("take_cards = \"t\"").is_err());
+        assert_eq!(KeyBindings::from_toml("").unwrap(), KeyBindings::default());
+    }
+
+    #[test]
+    /// Test that every screen leaves for the menu on the keys it always did: b and Esc on
+    /// the menu pages, where 'm' means nothing, and b or m at the end of a game, where Esc
+    /// does nothing
+    fn test_back_keys_per_screen() {
+        let bindings = KeyBindings::default();
+        let back = |state, phase, key| {
+            matches!(
+                handle_key_input(&state, &phase, key, &bindings),
+                Some(AppAction::ReturnToMenu | AppAction::LeaveGame)
+            )
+        };
+        for state in [
+            AppState::DemoSelect,
+            AppState::DifficultySelect,
+            AppState::RulesSummary,
+            AppState::RulesPage,
+        ] {
+            for key in [KeyCode::Char('b'), KeyCode::Char('B'), KeyCode::Esc] {
+                assert!(back(state, GamePhase::Setup, key), "{:?} {:?}", state, key);
+            }
+            for key in [KeyCode::Char('m'), KeyCode::Char('M')] {
+                assert!(!back(state, GamePhase::Setup, key), "{:?} {:?}", state, key);
+            }
+        }
+        for (state, phase) in [
+            (AppState::GameOver, GamePhase::GameOver),
+            (AppState::Playing, GamePhase::GameOver),
+        ] {
+            for key in "bBmM".chars().map(KeyCode::Char) {
+                assert!(back(state, phase.clone(), key), "{:?} {:?}", state, key);
+            }
+            assert!(!back(state, phase, KeyCode::Esc));
+        }
+    }
+}
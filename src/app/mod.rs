@@ -1,10 +1,22 @@
 pub mod input;
+pub mod keybindings;
 pub mod render;
+pub mod simulation;
 pub mod state;
 
 mod ai_handler;
+mod analysis;
 mod app_core;
+mod dealing;
+mod demo;
 mod game_actions;
 mod game_loop;
+mod hotseat;
+mod practice;
+mod replay;
+mod save_game;
+mod settings;
+mod turn_timer;
+mod undo;
 
 pub use app_core::App;
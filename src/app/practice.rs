@@ -0,0 +1,238 @@
+/*
+ * practice.rs - Practice mode with a full move history
+ *
+ * This file contains the logic for practice games:
+ * - Recording a snapshot of the game after every human action
+ * - Stepping back and forward through the snapshots with PageUp/PageDown
+ * - Keeping every line that was tried, so a takeback never throws a branch away
+ * - Seeding the AI's chance rolls so replaying a position gets the same answer
+ */
+use super::app_core::App;
+use super::state::AppState;
+use crate::game::ai::seed_ai_rng;
+use crate::game::{GamePhase, GameState};
+use crate::ui::debug_overlay::{debug, info};
+
+/// One explored position in the practice tree
+#[derive(Debug, Clone)]
+struct PracticeNode {
+    state: GameState,
+    parent: Option<usize>,
+    last_child: Option<usize>, // The branch PageDown follows, the one explored most recently
+    depth: u64,
+}
+
+/// Every state reached in a practice game, kept as a tree so alternative lines survive
+#[derive(Debug, Clone)]
+pub struct PracticeHistory {
+    nodes: Vec<PracticeNode>,
+    current: usize,
+    seed: u64,
+}
+
+impl PracticeHistory {
+    /// Starts the tree at the freshly dealt game
+    pub fn new(state: GameState, seed: u64) -> Self {
+        Self {
+            nodes: vec![PracticeNode {
+                state,
+                parent: None,
+                last_child: None,
+                depth: 0,
+            }],
+            current: 0,
+            seed,
+        }
+    }
+    /// The position the game is currently at
+    pub fn current_state(&self) -> &GameState {
+        &self.nodes[self.current].state
+    }
+    /// Number of moves from the deal to the current position
+    pub fn depth(&self) -> u64 {
+        self.nodes[self.current].depth
+    }
+    /// Every position explored so far, across all branches
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+    /// Seed for the AI's rolls from the current position. It only depends on the game
+    /// seed and the move number, so the same move from the same position replays exactly.
+    pub fn ai_seed(&self) -> u64 {
+        self.seed.wrapping_add(self.depth())
+    }
+    /// Records `state` as the result of a move from the current position.
+    /// Returns to an existing child instead of duplicating it when the move was tried before.
+    pub fn record(&mut self, state: &GameState) {
+        if self.current_state() == state {
+            return;
+        }
+        let existing = self
+            .nodes
+            .iter()
+            .position(|node| node.parent == Some(self.current) && node.state == *state);
+        let child = existing.unwrap_or_else(|| {
+            self.nodes.push(PracticeNode {
+                state: state.clone(),
+                parent: Some(self.current),
+                last_child: None,
+                depth: self.depth() + 1,
+            });
+            self.nodes.len() - 1
+        });
+        self.nodes[self.current].last_child = Some(child);
+        self.current = child;
+    }
+    /// Takes back one move. Returns false at the deal.
+    pub fn back(&mut self) -> bool {
+        match self.nodes[self.current].parent {
+            Some(parent) => {
+                self.current = parent;
+                true
+            }
+            None => false,
+        }
+    }
+    /// Replays the most recently explored move from here. Returns false at the end of the line.
+    pub fn forward(&mut self) -> bool {
+        match self.nodes[self.current].last_child {
+            Some(child) => {
+                self.current = child;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl App {
+    /// Toggles practice mode from the main menu.
+    /// Takes effect the next time a game is started.
+    pub fn toggle_practice_mode(&mut self) {
+        self.practice_mode = !self.practice_mode;
+        info(format!("Practice mode: {}", self.practice_mode));
+    }
+    /// Starts a practice history for the game that was just dealt, or drops the old one
+    /// when practice mode is off
+    pub fn start_practice(&mut self) {
+        if !self.practice_mode {
+            self.practice = None;
+            seed_ai_rng(None);
+            return;
+        }
+        let seed = self.game_state.seed.unwrap_or_else(rand::random);
+        self.practice = Some(PracticeHistory::new(self.game_state.clone(), seed));
+        debug(format!("Practice game started with seed {}", seed));
+    }
+    /// Seeds the AI from the current position before a human action, so the AI's
+    /// answer is the same every time this move is replayed
+    pub fn seed_practice_ai(&self) {
+        if let Some(practice) = &self.practice {
+            seed_ai_rng(Some(practice.ai_seed()));
+        }
+    }
+    /// Adds the position reached by the last action to the practice history
+    pub fn record_practice_state(&mut self) {
+        if let Some(practice) = &mut self.practice {
+            practice.record(&self.game_state);
+        }
+    }
+    /// Action function that runs when the user presses PageUp in a practice game
+    pub fn practice_back_action(&mut self) {
+        if let Some(practice) = &mut self.practice
+            && practice.back()
+        {
+            self.restore_practice_state();
+        }
+    }
+    /// Action function that runs when the user presses PageDown in a practice game
+    pub fn practice_forward_action(&mut self) {
+        if let Some(practice) = &mut self.practice
+            && practice.forward()
+        {
+            self.restore_practice_state();
+        }
+    }
+    /// Puts the game back at the practice history's current position
+    fn restore_practice_state(&mut self) {
+        let Some(practice) = &self.practice else {
+            return;
+        };
+        self.game_state = practice.current_state().clone();
+        debug(format!(
+            "Practice: move {} of {} explored positions",
+            practice.depth(),
+            practice.len()
+        ));
+        self.selected_card_idx = None;
+        self.selected_attack_idx = None;
+        self.clear_multi_selection();
+        self.defense_briefing = false;
+        self.app_state = if *self.game_state.game_phase() == GamePhase::GameOver {
+            AppState::GameOver
+        } else {
+            AppState::Playing
+        };
+        self.reset_viewer();
+        self.apply_hand_sort();
+        self.restart_turn_timer();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::PlayerType;
+
+    fn dealt_state(seed: u64) -> GameState {
+        let mut state = GameState::new();
+        state.add_player("Player".to_string(), PlayerType::Human);
+        state.add_player("Computer".to_string(), PlayerType::Computer);
+        state.seed = Some(seed);
+        state.setup_game();
+        state
+    }
+
+    /// Taking a move back and playing another one keeps the first line reachable
+    #[test]
+    fn test_takeback_keeps_old_branch() {
+        let start = dealt_state(1);
+        let mut first = start.clone();
+        first.players[0].name = "first".to_string();
+        let mut second = start.clone();
+        second.players[0].name = "second".to_string();
+        let mut history = PracticeHistory::new(start.clone(), 1);
+        history.record(&first);
+        assert!(history.back());
+        assert_eq!(history.current_state(), &start);
+        assert!(!history.back());
+        history.record(&second);
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.depth(), 1);
+        // Replaying the first move walks back into its branch instead of adding a node
+        assert!(history.back());
+        history.record(&first);
+        assert_eq!(history.len(), 3);
+        assert!(history.back());
+        assert!(history.forward());
+        assert_eq!(history.current_state(), &first);
+        assert!(!history.forward());
+    }
+
+    /// PageUp in a practice game undoes the human's last action
+    #[test]
+    fn test_practice_back_restores_previous_state() {
+        let mut app = App::new();
+        app.debug_step_ai = true;
+        app.practice_mode = true;
+        app.game_state.seed = Some(7);
+        app.start_game_action();
+        let before = app.game_state.clone();
+        app.game_state.players[0].name = "moved".to_string();
+        app.record_practice_state();
+        app.practice_back_action();
+        assert_eq!(app.game_state, before);
+        app.practice_forward_action();
+        assert_eq!(app.game_state.players()[0].name(), "moved");
+    }
+}
@@ -1,39 +1,269 @@
-use crate::app::state::AppState; // Import AppState
 use crate::app::App; // Import App from the app module
+use crate::app::analysis::{Move, PostGameAnalysis};
+use crate::app::state::{AppState, SelectionStyle}; // Import AppState
+use crate::game::ai::{ai_commentary, last_decision_trace};
+use crate::game::history;
+use crate::game::player::Player;
+use crate::game::{Card, TrumpRule};
+use crate::ui::card_view::CardSize;
 use crate::ui::debug_overlay::DebugOverlay;
-use crate::ui::game_ui::GameUI;
+use crate::ui::game_ui::{COMPACT_MIN_HEIGHT, COMPACT_MIN_WIDTH, GameUI, terminal_too_small};
+use crate::ui::i18n::{Text, current_locale, rules_lines, tr, tr_difficulty, tr_fmt, tr_on_off};
+use crate::ui::theme::ThemeKind;
+use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout};
-use ratatui::style::{Color, Style};
+use ratatui::style::Style;
 use ratatui::text::Line;
-use ratatui::widgets::{Block, Borders, Paragraph};
-use ratatui::Frame;
+use ratatui::widgets::block::{Position, Title};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+/// A move of the replay as a sentence, "Computer attacks with 7♥"
+fn replay_move_text(played: &history::Move, players: &[Player]) -> String {
+    let cards = |cards: &[Card]| {
+        let cards: Vec<String> = cards.iter().map(Card::to_string).collect();
+        cards.join(" ")
+    };
+    let name = |player: usize| players[player].name();
+    match played {
+        history::Move::Attack {
+            player,
+            cards: played,
+        } => tr_fmt(Text::CommentAttack, &[&name(*player), &cards(played)]),
+        history::Move::Defend {
+            player,
+            attack,
+            card,
+        } => tr_fmt(Text::ReplayDefend, &[&name(*player), attack, card]),
+        history::Move::Pass { player, card, .. } => {
+            tr_fmt(Text::CommentPass, &[&name(*player), card])
+        }
+        history::Move::Take {
+            player,
+            cards: taken,
+        } => tr_fmt(Text::ReplayTake, &[&name(*player), &cards(taken)]),
+        history::Move::Discard {
+            player,
+            cards: discarded,
+        } => tr_fmt(Text::ReplayDiscard, &[&name(*player), &cards(discarded)]),
+        history::Move::Draw {
+            player,
+            cards: drawn,
+        } => tr_fmt(Text::ReplayDraw, &[&name(*player), &cards(drawn)]),
+    }
+}
+/// A move from the analysis as the end of a sentence, "take the cards"
+fn move_text(played: &Move) -> String {
+    let cards = |cards: &[Card]| {
+        let cards: Vec<String> = cards.iter().map(Card::to_string).collect();
+        cards.join(" ")
+    };
+    match played {
+        Move::Attack(played) => tr_fmt(Text::MoveAttack, &[&cards(played)]),
+        Move::Stop => tr(Text::MoveStop).to_string(),
+        Move::Defend(played) => tr_fmt(Text::MoveDefend, &[&cards(played)]),
+        Move::Pass(card) => tr_fmt(Text::MovePass, &[card]),
+        Move::Take => tr(Text::MoveTake).to_string(),
+    }
+}
+/// The post-game analysis: how many moves the AI agreed with, then each one it wouldn't have made
+fn analysis_lines(analysis: &PostGameAnalysis) -> Vec<Line<'static>> {
+    let agreed = analysis.decisions - analysis.divergences.len();
+    let mut lines = vec![Line::from(tr_fmt(
+        Text::AnalysisSummary,
+        &[&agreed, &analysis.decisions],
+    ))];
+    if analysis.divergences.is_empty() {
+        lines.push(Line::from(tr(Text::AnalysisNoDivergence)));
+    }
+    lines.extend(analysis.divergences.iter().map(|divergence| {
+        Line::from(tr_fmt(
+            Text::AnalysisDivergence,
+            &[
+                &divergence.round,
+                &move_text(&divergence.played),
+                &move_text(&divergence.suggested),
+            ],
+        ))
+    }));
+    lines
+}
+/// The name of a trump rule as the menus show it
+fn trump_rule_name(trump_rule: TrumpRule) -> &'static str {
+    match trump_rule {
+        TrumpRule::BottomCard => tr(Text::TrumpModeStandard),
+        TrumpRule::Random => tr(Text::TrumpModeRandom),
+        TrumpRule::NoTrump => tr(Text::TrumpModeNoTrump),
+        TrumpRule::DealerChooses => tr(Text::TrumpModeDealer),
+    }
+}
+/// The attack limit setting as the menus show it
+fn attack_limit_name(flat_attack_limit: bool) -> &'static str {
+    if flat_attack_limit {
+        tr(Text::AttackLimitSix)
+    } else {
+        tr(Text::AttackLimitHand)
+    }
+}
+/// Every rule the next game is dealt with, one per line. Shown before a game starts and
+/// on the rules page, so a toggle left on from last time is never a surprise.
+fn variant_lines(app: &App) -> Vec<Line<'static>> {
+    let game_state = &app.game_state;
+    [
+        tr_fmt(
+            Text::VariantDeck,
+            &[&game_state.deck_size(), &game_state.players().len()],
+        ),
+        tr_fmt(
+            Text::CurrentDifficulty,
+            &[&tr_difficulty(app.selected_difficulty)],
+        ),
+        tr_fmt(Text::TrumpMode, &[&trump_rule_name(game_state.trump_rule)]),
+        tr_fmt(
+            Text::RevealPassSetting,
+            &[&tr_on_off(game_state.allow_trump_reveal_pass)],
+        ),
+        tr_fmt(
+            Text::TrumpPassSetting,
+            &[&tr_on_off(game_state.forbid_trump_pass)],
+        ),
+        tr_fmt(Text::JokersSetting, &[&tr_on_off(game_state.jokers)]),
+        tr_fmt(Text::DecksSetting, &[&game_state.deck_count]),
+        tr_fmt(
+            Text::AttackLimitSetting,
+            &[&attack_limit_name(game_state.flat_attack_limit)],
+        ),
+        tr_fmt(
+            Text::MulliganSetting,
+            &[&tr_on_off(game_state.allow_mulligan)],
+        ),
+        tr_fmt(Text::DefendFirstSetting, &[&tr_on_off(app.defend_first)]),
+        tr_fmt(Text::PracticeSetting, &[&tr_on_off(app.practice_mode)]),
+        tr_fmt(Text::HotseatSetting, &[&tr_on_off(app.is_hotseat())]),
+    ]
+    .into_iter()
+    .map(Line::from)
+    .collect()
+}
 /// Renders the UI for the game based on the matching AppState.
 pub fn render_ui(app: &App, f: &mut Frame<'_>) {
     let area = f.size();
+    let theme = app.theme;
+    // Below this even the menus can't be laid out, the fixed-size rows would overlap
+    if area.width < COMPACT_MIN_WIDTH || area.height < COMPACT_MIN_HEIGHT {
+        f.render_widget(terminal_too_small(area, &theme), area);
+        return;
+    }
     match app.app_state {
         AppState::MainMenu => {
-            let title = Paragraph::new("Durak Card Game")
-                .style(Style::default().fg(Color::Green))
+            let title = Paragraph::new(tr(Text::GameTitle))
+                .style(Style::default().fg(theme.title))
                 .alignment(ratatui::layout::Alignment::Center)
                 .block(Block::default().borders(Borders::ALL));
-            let difficulty_text = format!("Current AI Difficulty: {}", app.selected_difficulty);
-            let menu = Paragraph::new(vec![
-                Line::from("Press 's' to start a new game"),
-                Line::from("Press 'a' to change AI difficulty"),
-                Line::from("Press 'r' to view game rules"),
-                Line::from("Press 'q' to quit"),
-                Line::from("Press 'd' to toggle debug overlay"),
+            let difficulty_text = tr_fmt(
+                Text::CurrentDifficulty,
+                &[&tr_difficulty(app.selected_difficulty)],
+            );
+            let trump_mode_text = tr_fmt(
+                Text::TrumpMode,
+                &[&trump_rule_name(app.game_state.trump_rule)],
+            );
+            let reveal_pass_text = tr_fmt(
+                Text::RevealPassSetting,
+                &[&tr_on_off(app.game_state.allow_trump_reveal_pass)],
+            );
+            let trump_pass_text = tr_fmt(
+                Text::TrumpPassSetting,
+                &[&tr_on_off(app.game_state.forbid_trump_pass)],
+            );
+            let jokers_text = tr_fmt(Text::JokersSetting, &[&tr_on_off(app.game_state.jokers)]);
+            let decks_text = tr_fmt(Text::DecksSetting, &[&app.game_state.deck_count]);
+            let practice_text = tr_fmt(Text::PracticeSetting, &[&tr_on_off(app.practice_mode)]);
+            let defend_first_text =
+                tr_fmt(Text::DefendFirstSetting, &[&tr_on_off(app.defend_first)]);
+            let attack_limit_text = tr_fmt(
+                Text::AttackLimitSetting,
+                &[&attack_limit_name(app.game_state.flat_attack_limit)],
+            );
+            let mulligan_text = tr_fmt(
+                Text::MulliganSetting,
+                &[&tr_on_off(app.game_state.allow_mulligan)],
+            );
+            let selection_style = match app.selection_style {
+                SelectionStyle::Wrap => tr(Text::SelectionWrap),
+                SelectionStyle::Clamp => tr(Text::SelectionClamp),
+            };
+            let selection_text = tr_fmt(Text::SelectionStyleSetting, &[&selection_style]);
+            let card_size = match app.card_size {
+                CardSize::Small => tr(Text::CardSizeSmall),
+                CardSize::Medium => tr(Text::CardSizeMedium),
+                CardSize::Large => tr(Text::CardSizeLarge),
+            };
+            let card_size_text = tr_fmt(Text::CardSizeSetting, &[&card_size]);
+            let theme_name = match theme.kind {
+                ThemeKind::Default => tr(Text::ThemeDefault),
+                ThemeKind::HighContrast => tr(Text::ThemeHighContrast),
+                ThemeKind::Monochrome => tr(Text::ThemeMonochrome),
+            };
+            let theme_text = tr_fmt(Text::ThemeSetting, &[&theme_name]);
+            let hotseat_text = tr_fmt(Text::HotseatSetting, &[&tr_on_off(app.is_hotseat())]);
+            let aids_text = tr_fmt(Text::AidsSetting, &[&tr_on_off(app.show_aids)]);
+            let commentary_text =
+                tr_fmt(Text::CommentarySetting, &[&tr_on_off(app.show_commentary)]);
+            let language_text = tr_fmt(Text::LanguageSetting, &[&current_locale()]);
+            let mut menu_lines = Vec::new();
+            if app.has_saved_game {
+                menu_lines.push(Line::from(tr(Text::MenuContinue)));
+            }
+            menu_lines.extend([
+                Line::from(tr(Text::MenuStart)),
+                Line::from(tr(Text::MenuDifficulty)),
+                Line::from(tr(Text::MenuTrumpRule)),
+                Line::from(tr(Text::MenuRevealPass)),
+                Line::from(tr(Text::MenuTrumpPass)),
+                Line::from(tr(Text::MenuJokers)),
+                Line::from(tr(Text::MenuDecks)),
+                Line::from(tr(Text::MenuPractice)),
+                Line::from(tr(Text::MenuDefendFirst)),
+                Line::from(tr(Text::MenuMulligan)),
+                Line::from(tr(Text::MenuAttackLimit)),
+                Line::from(tr(Text::MenuSelectionStyle)),
+                Line::from(tr(Text::MenuCardSize)),
+                Line::from(tr(Text::MenuTheme)),
+                Line::from(tr(Text::MenuAids)),
+                Line::from(tr(Text::MenuCommentary)),
+                Line::from(tr(Text::MenuHotseat)),
+                Line::from(tr(Text::MenuDemo)),
+                Line::from(tr(Text::MenuLanguage)),
+                Line::from(tr(Text::MenuRules)),
+                Line::from(tr(Text::MenuQuit)),
+                Line::from(tr(Text::MenuDebug)),
                 Line::from(""),
                 Line::from(difficulty_text),
-            ])
-            .style(Style::default().fg(Color::White))
-            .alignment(ratatui::layout::Alignment::Center);
+                Line::from(trump_mode_text),
+                Line::from(reveal_pass_text),
+                Line::from(trump_pass_text),
+                Line::from(jokers_text),
+                Line::from(decks_text),
+                Line::from(practice_text),
+                Line::from(defend_first_text),
+                Line::from(mulligan_text),
+                Line::from(attack_limit_text),
+                Line::from(selection_text),
+                Line::from(card_size_text),
+                Line::from(theme_text),
+                Line::from(aids_text),
+                Line::from(commentary_text),
+                Line::from(hotseat_text),
+                Line::from(language_text),
+            ]);
+            let menu = Paragraph::new(menu_lines)
+                .style(Style::default().fg(theme.text))
+                .alignment(ratatui::layout::Alignment::Center);
             let layout = Layout::default()
                 .direction(Direction::Vertical)
                 .constraints([
                     Constraint::Percentage(40),
                     Constraint::Length(3),
-                    Constraint::Length(8),
+                    Constraint::Length(42),
                     Constraint::Percentage(40),
                 ])
                 .split(area);
@@ -42,77 +272,119 @@ pub fn render_ui(app: &App, f: &mut Frame<'_>) {
         }
         AppState::DifficultySelect => {
             // Render difficulty selection screen
-            let title = Paragraph::new("Select AI Difficulty")
-                .style(Style::default().fg(Color::Green))
+            let title = Paragraph::new(tr(Text::DifficultyTitle))
+                .style(Style::default().fg(theme.title))
                 .alignment(ratatui::layout::Alignment::Center)
                 .block(Block::default().borders(Borders::ALL));
-            let current_difficulty = format!("Current: {}", app.selected_difficulty);
+            let current_difficulty = tr_fmt(
+                Text::DifficultyCurrent,
+                &[&tr_difficulty(app.selected_difficulty)],
+            );
+            let deck_size = tr_fmt(Text::DeckSizeSetting, &[&app.game_state.pack_size.cards()]);
             let menu = Paragraph::new(vec![
-                Line::from("Press '1' for Easy AI"),
-                Line::from("Press '2' for Medium AI"),
-                Line::from("Press '3' for Hard AI"),
+                Line::from(tr(Text::DifficultyEasyKey)),
+                Line::from(tr(Text::DifficultyMediumKey)),
+                Line::from(tr(Text::DifficultyHardKey)),
+                Line::from(tr(Text::DifficultyDynamicKey)),
+                Line::from(tr(Text::DifficultyDeckSizeKey)),
                 Line::from(""),
                 Line::from(current_difficulty),
+                Line::from(deck_size),
                 Line::from(""),
-                Line::from("Press 'b' to go back to main menu"),
+                Line::from(tr(Text::BackToMenu)),
             ])
-            .style(Style::default().fg(Color::White))
+            .style(Style::default().fg(theme.text))
             .alignment(ratatui::layout::Alignment::Center);
             let layout = Layout::default()
                 .direction(Direction::Vertical)
                 .constraints([
                     Constraint::Percentage(40),
                     Constraint::Length(3),
-                    Constraint::Length(8),
+                    Constraint::Length(11),
                     Constraint::Percentage(40),
                 ])
                 .split(area);
             f.render_widget(title, layout[1]);
             f.render_widget(menu, layout[2]);
         }
+        AppState::DemoSelect => {
+            let title = Paragraph::new(tr(Text::DemoTitle))
+                .style(Style::default().fg(theme.title))
+                .alignment(ratatui::layout::Alignment::Center)
+                .block(Block::default().borders(Borders::ALL));
+            let [first, second] = app.demo_difficulties;
+            let menu = Paragraph::new(vec![
+                Line::from(tr_fmt(Text::DemoFirstAi, &[&tr_difficulty(first)])),
+                Line::from(tr_fmt(Text::DemoSecondAi, &[&tr_difficulty(second)])),
+                Line::from(""),
+                Line::from(tr(Text::DemoStart)),
+                Line::from(tr(Text::BackToMenu)),
+            ])
+            .style(Style::default().fg(theme.text))
+            .alignment(ratatui::layout::Alignment::Center);
+            let layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Percentage(40),
+                    Constraint::Length(3),
+                    Constraint::Length(5),
+                    Constraint::Percentage(40),
+                ])
+                .split(area);
+            f.render_widget(title, layout[1]);
+            f.render_widget(menu, layout[2]);
+        }
+        AppState::Demo => {
+            f.render_widget(
+                GameUI::new(&app.game_state)
+                    .card_size(app.card_size)
+                    .theme(theme)
+                    .commentary(app.show_commentary.then(ai_commentary))
+                    .demo(true),
+                area,
+            );
+        }
+        AppState::RulesSummary => {
+            let title = Paragraph::new(tr(Text::SummaryTitle))
+                .style(Style::default().fg(theme.title))
+                .alignment(ratatui::layout::Alignment::Center)
+                .block(Block::default().borders(Borders::ALL));
+            let mut lines = variant_lines(app);
+            let height = lines.len() as u16 + 2;
+            lines.extend([Line::from(""), Line::from(tr(Text::SummaryStart))]);
+            let summary = Paragraph::new(lines)
+                .style(Style::default().fg(theme.text))
+                .alignment(ratatui::layout::Alignment::Center);
+            let layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Percentage(30),
+                    Constraint::Length(3),
+                    Constraint::Length(height),
+                    Constraint::Percentage(30),
+                ])
+                .split(area);
+            f.render_widget(title, layout[1]);
+            f.render_widget(summary, layout[2]);
+        }
         AppState::RulesPage => {
             // Render rules page
-            let title = Paragraph::new("Durak Game Rules")
-                .style(Style::default().fg(Color::Green))
+            let title = Paragraph::new(tr(Text::RulesTitle))
+                .style(Style::default().fg(theme.title))
                 .alignment(ratatui::layout::Alignment::Center)
                 .block(Block::default().borders(Borders::ALL));
-            let rules_text = vec![
-                Line::from(""),
-                Line::from(" "),
-                Line::from("Objective:"),
-                Line::from("Get rid of all your cards. The last player with cards is the 'durak' (fool)."),
-                Line::from(" "),
-                Line::from("Setup:"),
-                Line::from("- Each player gets 6 cards from a 36-card deck (6 to Ace)"),
-                Line::from("- Bottom card determines trump suit (higher priority)"),
-                Line::from("- Player with lowest trump card goes first"),
-                Line::from(" "),
-                Line::from("Gameplay:"),
-                Line::from("- Attacker plays a card; defender must beat it with higher card of same suit or trump"),
-                Line::from("- Passing: Defender can PASS a card by playing same rank (7♠ → 7♥) to the next player"),
-                Line::from("- When a pass occurs, the original attacker must now defend against both cards"),
-                Line::from("- After successful defense, attacker can add cards of the same rank as those on table"),
-                Line::from("- Defender can defend against multiple cards if they have matching cards"),
-                Line::from("- If defender can't or won't defend, they pick up all cards on the table"),
-                Line::from("- After successful defense, defender becomes next attacker"),
-                Line::from("- Players draw after each round to maintain 6 cards (attacker draws first)"),
-                Line::from(" "),
-                Line::from("Multiple Card Attacks:"),
-                Line::from("- Press 'm' to toggle multiple selection mode"),
-                Line::from("- Use spacebar to select/deselect multiple cards with the same rank"),
-                Line::from("- Press Enter to play all selected cards at once"),
-                Line::from("- You can only attack with cards of ranks already on the table"),
-                Line::from("- You cannot attack with more cards than the defender has in hand"),
-                Line::from(" "),
-                Line::from("End Game:"),
-                Line::from("- Once deck is empty and a player has no cards left, that player is out"),
-                Line::from("- The last player with cards is the 'durak'"),
-                Line::from(" "),
-                Line::from("Press 'b' to go back to the main menu"),
-            ];
+            // The rules in effect first, the general rules below describe the defaults
+            let mut rules_text = vec![Line::from(""), Line::from(tr(Text::RulesActive))];
+            rules_text.extend(variant_lines(app));
+            rules_text.extend(rules_lines().iter().map(|line| Line::from(*line)));
+            rules_text.push(Line::from(tr(Text::BackToMenu)));
             let rules = Paragraph::new(rules_text)
-                .style(Style::default().fg(Color::White))
-                .block(Block::default().borders(Borders::ALL).title("Game Rules"));
+                .style(Style::default().fg(theme.text))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(tr(Text::RulesBlockTitle)),
+                );
             let layout = Layout::default()
                 .direction(Direction::Vertical)
                 .constraints([Constraint::Length(3), Constraint::Min(10)])
@@ -120,53 +392,162 @@ pub fn render_ui(app: &App, f: &mut Frame<'_>) {
             f.render_widget(title, layout[0]);
             f.render_widget(rules, layout[1]);
         }
+        AppState::Playing if app.handover_to.is_some() => {
+            // Hotseat: the table stays hidden until the next player has the device
+            let next = app.handover_to.unwrap_or_default();
+            let name = app.game_state.players()[next].name();
+            let handover = Paragraph::new(vec![
+                Line::from(tr_fmt(Text::HandoverTo, &[&name])),
+                Line::from(""),
+                Line::from(tr(Text::HandoverContinue)),
+            ])
+            .style(Style::default().fg(theme.highlight))
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Block::default().borders(Borders::ALL));
+            let layout = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Percentage(40),
+                    Constraint::Length(5),
+                    Constraint::Percentage(40),
+                ])
+                .split(area);
+            f.render_widget(handover, layout[1]);
+        }
         AppState::Playing => {
-            let mut game_ui = GameUI::new(&app.game_state).select_card(app.selected_card_idx);
+            let mut game_ui = GameUI::new(&app.game_state)
+                .select_card(app.selected_card_idx)
+                .select_attack(app.selected_attack_idx)
+                .card_size(app.card_size)
+                .theme(theme)
+                .show_aids(app.show_aids)
+                .commentary(app.show_commentary.then(ai_commentary))
+                .viewer(app.viewing_player())
+                .dealt_cards(app.dealt_cards())
+                .table_scroll(app.table_scroll)
+                .pending_quit(app.pending_quit)
+                .pending_surrender(app.pending_surrender)
+                .turn_time_left(app.turn_time_left())
+                .ai_thinking(app.ai_thinking_frame())
+                .step_ai(app.debug_step_ai)
+                .reveal_hands(app.show_debug && app.debug_reveal_hands)
+                .show_indices(app.show_debug && app.debug_show_indices)
+                .undo_available(app.can_undo())
+                .defense_briefing(app.defense_briefing)
+                .practice_move(app.practice.as_ref().map(|practice| practice.depth()));
             if app.multiple_selection_mode {
                 game_ui = game_ui.with_multiple_selection(&app.selected_cards);
             }
             f.render_widget(game_ui, area);
         }
+        AppState::Replay => {
+            let replay_state = app.replay_state();
+            let moves = app.game_state.history().len();
+            let step = match app.replay_move() {
+                Some(played) => tr_fmt(
+                    Text::ReplayMove,
+                    &[
+                        &app.replay_step,
+                        &moves,
+                        &replay_move_text(played, replay_state.players()),
+                    ],
+                ),
+                None => tr_fmt(Text::ReplayDeal, &[&moves]),
+            };
+            f.render_widget(
+                GameUI::new(&replay_state)
+                    .card_size(app.card_size)
+                    .theme(theme)
+                    .reveal_hands(true)
+                    .replay(Some(step)),
+                area,
+            );
+        }
         AppState::GameOver => {
             // Create the winner message
-            let winner_message = if let Some(winner_idx) = app.game_state.winner() {
+            let players = app.game_state.players();
+            // With more than two players one winner says little, so name the loser instead
+            let winner_message = if app.game_state.is_draw() {
+                tr(Text::DrawNoDurak).to_string()
+            } else if let Some(durak_idx) = app.game_state.durak()
+                && players.len() > 2
+            {
+                tr_fmt(Text::DurakIs, &[&players[durak_idx].name()])
+            } else if let Some(winner_idx) = app.game_state.winner() {
                 let winner_name = &app.game_state.players()[winner_idx].name();
-                format!("{} is the winner!", winner_name)
+                tr_fmt(Text::WinnerIs, &[winner_name])
             } else {
-                "Game Over!".to_string()
+                tr(Text::GameOverFallback).to_string()
             };
-            // Layout for the game over screen
+            // Layout for the game over screen, moved up to make room for the analysis
+            let margin = if app.analysis.is_some() { 10 } else { 30 };
             let layout = Layout::default()
                 .direction(Direction::Vertical)
                 .constraints([
-                    Constraint::Percentage(30),
+                    Constraint::Percentage(margin),
                     Constraint::Length(3), // Title
                     Constraint::Length(3), // Winner message
                     Constraint::Length(3), // Instructions
-                    Constraint::Percentage(30),
+                    Constraint::Min(0),    // Analysis
+                    Constraint::Percentage(margin),
                 ])
                 .split(area);
             // Game over title
-            let title = Paragraph::new("Game Over")
-                .style(Style::default().fg(Color::Green))
+            let title = Paragraph::new(tr(Text::GameOverTitle))
+                .style(Style::default().fg(theme.title))
                 .alignment(ratatui::layout::Alignment::Center)
                 .block(Block::default().borders(Borders::ALL));
             // Winner message
             let winner_text = Paragraph::new(winner_message)
-                .style(Style::default().fg(Color::Yellow))
+                .style(Style::default().fg(theme.highlight))
                 .alignment(ratatui::layout::Alignment::Center);
             // Instructions
-            let instructions = Paragraph::new("Press 'N' for new game | Press 'Q' to quit")
-                .style(Style::default().fg(Color::White))
+            let instructions = Paragraph::new(tr(Text::GameOverInstructions))
+                .style(Style::default().fg(theme.text))
                 .alignment(ratatui::layout::Alignment::Center);
             // Render all components
             f.render_widget(title, layout[1]);
             f.render_widget(winner_text, layout[2]);
             f.render_widget(instructions, layout[3]);
+            if let Some(analysis) = &app.analysis {
+                let title = tr_fmt(Text::AnalysisTitle, &[&tr_difficulty(analysis.difficulty)]);
+                let list = Paragraph::new(analysis_lines(analysis))
+                    .style(Style::default().fg(theme.text))
+                    .wrap(Wrap { trim: true })
+                    .scroll((app.analysis_scroll as u16, 0))
+                    .block(
+                        Block::default().borders(Borders::ALL).title(title).title(
+                            Title::from(tr(Text::AnalysisScrollHint))
+                                .position(Position::Bottom)
+                                .alignment(ratatui::layout::Alignment::Right),
+                        ),
+                    );
+                f.render_widget(list, layout[4]);
+            }
         }
     }
     if app.show_debug {
-        let debug_overlay = DebugOverlay::new();
+        let mut debug_overlay = DebugOverlay::new().with_scroll(app.debug_scroll);
+        if app.app_state == AppState::Playing
+            && let Some(trump_suit) = app.game_state.trump_suit()
+        {
+            let mut stats = format!("Trumps {} |", trump_suit.symbol());
+            for player in app.game_state.players() {
+                stats.push_str(&format!(
+                    " {}: {} |",
+                    player.name(),
+                    player.trump_count(trump_suit)
+                ));
+            }
+            stats.push_str(&format!(
+                " Unplayed (deck + hands): {}",
+                app.game_state.trumps_remaining_in_deck_and_hands()
+            ));
+            debug_overlay = debug_overlay.with_stats(stats);
+            if let Some(trace) = last_decision_trace() {
+                debug_overlay = debug_overlay.with_ai_trace(trace.to_string());
+            }
+        }
         f.render_widget(debug_overlay, area);
     }
 }
@@ -0,0 +1,97 @@
+/*
+ * replay.rs - Going back over a finished game
+ *
+ * This file contains the logic behind the replay screen:
+ * - Opening it from the game over screen with 'r'
+ * - Stepping through the game's move history with the arrow keys
+ * - Building the game state to draw for the move on screen
+ */
+use super::app_core::App;
+use super::state::AppState;
+use crate::game::GameState;
+use crate::game::history::Move;
+use crate::ui::debug_overlay::debug;
+
+impl App {
+    /// Opens the replay of the game that just ended at the deal
+    pub fn show_replay(&mut self) {
+        if !self.game_state.history().has_deal() {
+            debug("No moves recorded for this game, nothing to replay");
+            return;
+        }
+        self.replay_step = 0;
+        self.app_state = AppState::Replay;
+    }
+    /// Moves the replay one move forward or back, stopping at the deal and the last move
+    pub fn step_replay(&mut self, forward: bool) {
+        self.replay_step = if forward {
+            (self.replay_step + 1).min(self.game_state.history().len())
+        } else {
+            self.replay_step.saturating_sub(1)
+        };
+    }
+    /// Leaves the replay for the game over screen
+    pub fn close_replay(&mut self) {
+        self.app_state = AppState::GameOver;
+    }
+    /// The move that led to the board on screen, `None` at the deal
+    pub fn replay_move(&self) -> Option<&Move> {
+        let step = self.replay_step.checked_sub(1)?;
+        self.game_state.history().moves().get(step)
+    }
+    /// The finished game with the board of the replay's current step laid out on it
+    pub fn replay_state(&self) -> GameState {
+        self.game_state
+            .history()
+            .board_at(&self.game_state, self.replay_step)
+            .unwrap_or_else(|| self.game_state.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::GamePhase;
+    use crossterm::event::KeyCode;
+
+    #[test]
+    /// Test that the replay steps from the deal through every move and back, and that
+    /// each step shows the board that move left
+    fn test_replay_steps_through_moves() {
+        let mut app = App::new();
+        let mut game_state = GameState::new_with_seed(3);
+        game_state.add_player("Player".to_string(), crate::game::PlayerType::Human);
+        game_state.add_player("Computer".to_string(), crate::game::PlayerType::Computer);
+        game_state.setup_game();
+        let dealt = game_state.clone();
+        let attacker = game_state.current_attacker();
+        game_state.attack(0, attacker).unwrap();
+        let attacked = game_state.clone();
+        game_state.take_cards().unwrap();
+        game_state.draw_cards();
+        game_state.game_phase = GamePhase::GameOver;
+        app.game_state = game_state;
+        app.app_state = AppState::GameOver;
+
+        app.on_key(KeyCode::Char('r'));
+        assert_eq!(app.app_state, AppState::Replay);
+        assert_eq!(app.replay_move(), None);
+        assert_eq!(app.replay_state().players(), dealt.players());
+        app.on_key(KeyCode::Right);
+        assert!(matches!(app.replay_move(), Some(Move::Attack { .. })));
+        assert_eq!(app.replay_state().table_cards(), attacked.table_cards());
+        assert_eq!(app.replay_state().players(), attacked.players());
+        app.on_key(KeyCode::Right);
+        assert!(matches!(app.replay_move(), Some(Move::Take { .. })));
+        assert!(app.replay_state().table_cards().is_empty());
+        // Only the attacker was short of cards after the take, and stepping stops at the end
+        assert_eq!(app.game_state.history().len(), 3);
+        app.on_key(KeyCode::Right);
+        app.on_key(KeyCode::Right);
+        assert_eq!(app.replay_move(), app.game_state.history().moves().last());
+        app.on_key(KeyCode::Left);
+        assert!(matches!(app.replay_move(), Some(Move::Take { .. })));
+        app.on_key(KeyCode::Esc);
+        assert_eq!(app.app_state, AppState::GameOver);
+    }
+}
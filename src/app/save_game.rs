@@ -0,0 +1,160 @@
+/*
+ * save_game.rs - Persisting an in-progress game
+ *
+ * This file contains the logic for saving and restoring a game:
+ * - Writing a JSON snapshot to the config directory with F5 or when quitting mid-game
+ * - Restoring the snapshot from the main menu
+ */
+use super::app_core::App;
+use super::state::AppState;
+use crate::game::{AiDifficulty, GamePhase, GameState};
+use crate::ui::debug_overlay::{error, info};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Everything needed to pick a game back up where it was left
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub game_state: GameState,
+    pub difficulty: AiDifficulty,
+}
+
+/// Location of the save file, `<config dir>/durak/savegame.json`
+pub fn save_file_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("durak").join("savegame.json"))
+}
+
+/// Whether a saved game is waiting to be continued
+pub fn saved_game_exists() -> bool {
+    save_file_path().is_some_and(|path| path.exists())
+}
+
+fn no_config_dir() -> io::Error {
+    io::Error::new(io::ErrorKind::NotFound, "No config directory available")
+}
+
+impl App {
+    /// Writes the current game and difficulty to the save file.
+    pub fn save_game(&self) -> io::Result<()> {
+        self.save_game_to(&save_file_path().ok_or_else(no_config_dir)?)
+    }
+    /// Writes the current game and difficulty to `path` as JSON.
+    pub fn save_game_to(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let saved = SavedGame {
+            game_state: self.game_state.clone(),
+            difficulty: self.selected_difficulty,
+        };
+        fs::write(path, serde_json::to_string_pretty(&saved)?)?;
+        info(format!("Game saved to {}", path.display()));
+        Ok(())
+    }
+    /// Action function for F5: saves the game in progress and keeps playing. The menu then
+    /// offers to continue it, also after the game is left without saving.
+    pub fn save_game_action(&mut self) {
+        if *self.game_state.game_phase() == GamePhase::GameOver {
+            return;
+        }
+        match self.save_game() {
+            Ok(()) => self.has_saved_game = true,
+            Err(e) => error(format!("Failed to save game: {}", e)),
+        }
+    }
+    /// Restores the saved game and difficulty and jumps straight back into play.
+    /// The save file is removed once it has been read.
+    pub fn load_game(&mut self) -> io::Result<()> {
+        let path = save_file_path().ok_or_else(no_config_dir)?;
+        self.load_game_from(&path)?;
+        self.has_saved_game = false;
+        // The snapshot has been consumed, a later quit writes a fresh one
+        let _ = fs::remove_file(&path);
+        Ok(())
+    }
+    /// Restores the game and difficulty saved at `path`, with a fresh AI player for the
+    /// difficulty, and jumps straight back into play.
+    pub fn load_game_from(&mut self, path: &Path) -> io::Result<()> {
+        let saved: SavedGame = serde_json::from_str(&fs::read_to_string(path)?)?;
+        self.game_state = saved.game_state;
+        self.selected_difficulty = saved.difficulty;
+        self.ai_player = self.new_ai_player();
+        self.selected_card_idx = None;
+        self.selected_attack_idx = None;
+        self.clear_multi_selection();
+        self.defense_briefing = false;
+        self.app_state = AppState::Playing;
+        // Moves made before the save aren't known, the analysis covers the rest of the game
+        self.decision_log.clear();
+        self.undo_history.clear();
+        self.reset_viewer();
+        self.apply_hand_sort();
+        self.restart_turn_timer();
+        self.start_practice();
+        info("Saved game restored");
+        Ok(())
+    }
+    /// Main menu action for 'c': continue the saved game if there is one.
+    pub fn continue_game_action(&mut self) {
+        if !self.has_saved_game {
+            return;
+        }
+        if let Err(e) = self.load_game() {
+            error(format!("Failed to load saved game: {}", e));
+            self.has_saved_game = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::PlayerType;
+
+    #[test]
+    /// Test that a game in progress survives a JSON round trip
+    fn test_saved_game_round_trip() {
+        let mut game_state = GameState::new();
+        game_state.add_player("Player".to_string(), PlayerType::Human);
+        game_state.add_player("Computer".to_string(), PlayerType::Computer);
+        game_state.setup_game();
+        let saved = SavedGame {
+            game_state,
+            difficulty: AiDifficulty::Hard,
+        };
+        let json = serde_json::to_string(&saved).unwrap();
+        let restored: SavedGame = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.difficulty, AiDifficulty::Hard);
+        assert_eq!(restored.game_state.trump_suit, saved.game_state.trump_suit);
+        assert_eq!(restored.game_state.deck.cards, saved.game_state.deck.cards);
+        assert_eq!(
+            restored.game_state.players[0].hand,
+            saved.game_state.players[0].hand
+        );
+        assert_eq!(restored.game_state.game_phase, saved.game_state.game_phase);
+    }
+
+    #[test]
+    /// Test that a game saved to a file is picked up again with its difficulty and AI
+    fn test_save_and_load_file() {
+        let path = std::env::temp_dir().join(format!("durak-save-{}.json", std::process::id()));
+        let mut app = App::new();
+        app.selected_difficulty = AiDifficulty::Hard;
+        app.game_state.seed = Some(4);
+        app.start_game_action();
+        app.save_game_to(&path).unwrap();
+
+        let mut restored = App::new();
+        restored.load_game_from(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        assert_eq!(restored.app_state, AppState::Playing);
+        assert_eq!(restored.selected_difficulty, AiDifficulty::Hard);
+        assert_eq!(restored.game_state, app.game_state);
+        assert_eq!(
+            restored.ai_player.params(),
+            &crate::game::ai::AiParams::for_difficulty(AiDifficulty::Hard)
+        );
+    }
+}
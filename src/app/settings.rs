@@ -0,0 +1,89 @@
+/*
+ * settings.rs - Preferences kept between sessions
+ *
+ * This file contains the logic for the settings file:
+ * - Reading `<config dir>/durak/settings.json` at startup
+ * - Writing it back whenever a persisted setting is changed from the menu
+ */
+use super::app_core::App;
+use crate::ui::debug_overlay::{info, warn};
+use crate::ui::theme::{Theme, ThemeKind};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// The persisted preferences. Fields missing from an older file keep their defaults.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Settings {
+    #[serde(default)]
+    pub theme: ThemeKind,
+}
+
+/// Location of the settings file, `<config dir>/durak/settings.json`
+pub fn settings_file_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("durak").join("settings.json"))
+}
+
+impl App {
+    /// Applies the settings file, if there is one. A file that can't be read leaves the
+    /// defaults in place.
+    pub fn load_settings(&mut self) {
+        let Some(path) = settings_file_path().filter(|path| path.exists()) else {
+            return;
+        };
+        let settings = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|json| serde_json::from_str::<Settings>(&json).map_err(|e| e.to_string()));
+        match settings {
+            Ok(settings) => self.apply_settings(&settings),
+            Err(e) => warn(format!("Ignoring settings file {}: {}", path.display(), e)),
+        }
+    }
+    /// Writes the persisted preferences to the settings file.
+    pub fn save_settings(&self) -> io::Result<()> {
+        let path = settings_file_path().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, "No config directory available")
+        })?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(&self.settings())?)?;
+        Ok(())
+    }
+    /// The current values of the persisted preferences
+    pub fn settings(&self) -> Settings {
+        Settings {
+            theme: self.theme.kind,
+        }
+    }
+    fn apply_settings(&mut self, settings: &Settings) {
+        self.theme = Theme::new(settings.theme);
+    }
+    /// Switches to the next colour theme from the main menu and remembers it.
+    pub fn cycle_theme(&mut self) {
+        self.theme = self.theme.next();
+        info(format!("Theme: {:?}", self.theme.kind));
+        if let Err(e) = self.save_settings() {
+            warn(format!("Failed to save settings: {}", e));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Test that the settings survive a JSON round trip and an empty file means defaults
+    fn test_settings_round_trip() {
+        let mut app = App::new();
+        app.theme = Theme::new(ThemeKind::Monochrome);
+        let json = serde_json::to_string(&app.settings()).unwrap();
+        let mut restored = App::new();
+        restored.apply_settings(&serde_json::from_str(&json).unwrap());
+        assert_eq!(restored.theme, app.theme);
+        let defaults: Settings = serde_json::from_str("{}").unwrap();
+        assert_eq!(defaults.theme, ThemeKind::Default);
+    }
+}
@@ -0,0 +1,114 @@
+/*
+ * simulation.rs - Headless AI-vs-AI matches for batch runs
+ *
+ * This file contains the logic behind `durak --sim N`:
+ * - Playing whole seeded games between two AI players, see `game::simulation`
+ * - Recording the seed, the durak, the number of rounds and who attacked first
+ * - Printing the results as JSON or CSV, so win rates can be worked out elsewhere
+ */
+use crate::game::AiDifficulty;
+use crate::game::simulation::simulate_game;
+use crate::ui::debug_overlay::info;
+use serde::Serialize;
+
+/// How the results of a simulation are printed
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SimFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+/// The outcome of one simulated game
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MatchResult {
+    pub seed: u64,
+    pub durak: Option<usize>, // Seat of the loser, `None` for a draw or a game that got stuck
+    pub rounds: u32,
+    pub first_attacker: usize,
+}
+
+/// Plays one game between two AI players, seat 0 at `difficulties[0]`. The deal and the AI's
+/// chance rolls both come from `seed`, so the same seed always plays the same game.
+pub fn play_match(difficulties: [AiDifficulty; 2], seed: u64) -> MatchResult {
+    let result = simulate_game(difficulties[0], difficulties[1], seed);
+    MatchResult {
+        seed,
+        durak: result.durak,
+        rounds: result.rounds,
+        first_attacker: result.first_attacker,
+    }
+}
+
+/// Plays `games` games, the n-th one seeded with `seed + n`
+pub fn run_simulation(
+    games: usize,
+    difficulties: [AiDifficulty; 2],
+    seed: u64,
+) -> Vec<MatchResult> {
+    info(format!(
+        "Simulating {} games: {} vs {}, first seed {}",
+        games, difficulties[0], difficulties[1], seed
+    ));
+    (0..games as u64)
+        .map(|game| play_match(difficulties, seed.wrapping_add(game)))
+        .collect()
+}
+
+/// The results as a JSON array or as CSV with a header line
+pub fn format_results(results: &[MatchResult], format: SimFormat) -> String {
+    match format {
+        SimFormat::Json => {
+            let mut json = serde_json::to_string_pretty(results).unwrap_or_default();
+            json.push('\n');
+            json
+        }
+        SimFormat::Csv => {
+            let mut csv = String::from("seed,durak,rounds,first_attacker\n");
+            for result in results {
+                let durak = result
+                    .durak
+                    .map(|seat| seat.to_string())
+                    .unwrap_or_default();
+                csv.push_str(&format!(
+                    "{},{},{},{}\n",
+                    result.seed, durak, result.rounds, result.first_attacker
+                ));
+            }
+            csv
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Test that a simulated game finishes and replays exactly from its seed
+    fn test_play_match_is_reproducible() {
+        let difficulties = [AiDifficulty::Hard, AiDifficulty::Medium];
+        let first = play_match(difficulties, 42);
+        assert_eq!(first, play_match(difficulties, 42));
+        assert!(first.rounds > 0);
+        assert!(first.first_attacker < 2);
+        assert!(first.durak.is_none_or(|seat| seat < 2));
+    }
+
+    #[test]
+    /// Test that both output formats hold one entry per game
+    fn test_format_results() {
+        let results = run_simulation(3, [AiDifficulty::Easy, AiDifficulty::Easy], 7);
+        assert_eq!(
+            results.iter().map(|result| result.seed).collect::<Vec<_>>(),
+            vec![7, 8, 9]
+        );
+        let csv = format_results(&results, SimFormat::Csv);
+        assert_eq!(csv.lines().count(), 4);
+        assert!(csv.starts_with("seed,durak,rounds,first_attacker\n7,"));
+        let json: Vec<serde_json::Value> =
+            serde_json::from_str(&format_results(&results, SimFormat::Json)).unwrap();
+        assert_eq!(json.len(), 3);
+        assert_eq!(json[0]["seed"], 7);
+    }
+}
@@ -4,6 +4,27 @@ pub enum AppState {
     MainMenu,
     DifficultySelect,
     RulesPage,
+    RulesSummary, // The rules a new game will be dealt with, confirmed with Enter
     Playing,
     GameOver,
+    DemoSelect, // Picking the two AI players for a demo game
+    Demo,       // An AI-vs-AI game plays itself until any key is pressed
+    Replay,     // Stepping through the moves of the game that just ended
+}
+
+/// What the hand cursor does when it is moved past either end of the hand
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SelectionStyle {
+    #[default]
+    Wrap, // Jump to the other end of the hand
+    Clamp, // Stay on the first or last card
+}
+
+impl SelectionStyle {
+    pub fn next(self) -> Self {
+        match self {
+            SelectionStyle::Wrap => SelectionStyle::Clamp,
+            SelectionStyle::Clamp => SelectionStyle::Wrap,
+        }
+    }
 }
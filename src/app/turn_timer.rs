@@ -0,0 +1,114 @@
+/*
+ * turn_timer.rs - Optional shot clock for the human player
+ *
+ * This file contains the countdown logic for the human's turn:
+ * - Restarting the clock whenever control passes to the human
+ * - Reporting the time left for the status bar
+ * - Making a move for the human when the clock runs out
+ */
+use super::app_core::App;
+use crate::game::{GamePhase, PlayerType};
+use crate::ui::debug_overlay::{debug, info};
+use std::time::{Duration, Instant};
+
+impl App {
+    /// Whether the human has to make a move right now. The clock waits until the
+    /// deal is shown, the defense briefing has been acknowledged and a hotseat handover
+    /// is done.
+    pub fn is_human_turn(&self) -> bool {
+        !self.defense_briefing
+            && self.dealing_since.is_none()
+            && self.handover_to.is_none()
+            && matches!(
+                self.game_state.game_phase(),
+                GamePhase::Attack | GamePhase::Defense | GamePhase::Reinforce
+            )
+            && self.game_state.players()[self.current_player_index()].player_type()
+                == &PlayerType::Human
+    }
+    /// Starts a fresh countdown if it is the human's turn, stops the clock otherwise.
+    /// Called whenever control may have passed back to the human.
+    pub fn restart_turn_timer(&mut self) {
+        self.turn_started = match self.turn_timer {
+            Some(_) if self.is_human_turn() => Some(Instant::now()),
+            _ => None,
+        };
+    }
+    /// Time left on the human's clock, `None` when no countdown is running
+    pub fn turn_time_left(&self) -> Option<Duration> {
+        let limit = self.turn_timer?;
+        let started = self.turn_started?;
+        Some(limit.saturating_sub(started.elapsed()))
+    }
+    /// Called on every tick of the `run` loop. When the clock runs out the human takes the
+    /// cards on defense and ends the round during reinforcement. An opening attack cannot be
+    /// passed, so the lowest card is played instead (non-trumps first).
+    pub fn check_turn_timer(&mut self) {
+        if self.turn_time_left() != Some(Duration::ZERO) || !self.is_human_turn() {
+            return;
+        }
+        info("Turn timer expired");
+        self.turn_started = None;
+        self.clear_multi_selection();
+        match *self.game_state.game_phase() {
+            GamePhase::Defense => self.take_cards_action(),
+            GamePhase::Reinforce => self.pass_turn_action(),
+            GamePhase::Attack => {
+                let trump_suit = self.game_state.trump_suit();
+                let player = &self.game_state.players()[self.current_player_index()];
+                self.selected_card_idx = player
+                    .hand()
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, card)| (trump_suit == Some(card.suit), card.rank))
+                    .map(|(idx, _)| idx);
+                debug(format!(
+                    "Auto-attacking with card {:?}",
+                    self.selected_card_idx
+                ));
+                self.play_card_action();
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::card::{Card, Rank, Suit};
+
+    #[test]
+    /// Test that an expired clock on defense makes the human take the cards
+    fn test_expired_timer_takes_cards_on_defense() {
+        let mut app = App::new();
+        let game_state = &mut app.game_state;
+        game_state.trump_suit = Some(Suit::Spades);
+        game_state.players[0].hand = vec![Card::new(Suit::Hearts, Rank::Six)];
+        game_state.players[1].hand = vec![Card::new(Suit::Clubs, Rank::Six); 3];
+        game_state.table_cards = vec![(Card::new(Suit::Hearts, Rank::Ace), None)];
+        game_state.set_phase_to_defense(1, 0);
+        app.turn_timer = Some(Duration::ZERO);
+        app.restart_turn_timer();
+        assert_eq!(app.turn_time_left(), Some(Duration::ZERO));
+
+        app.check_turn_timer();
+        assert_eq!(*app.game_state.game_phase(), GamePhase::Drawing);
+        assert_eq!(app.game_state.players()[0].hand_size(), 2);
+        assert_eq!(app.turn_time_left(), None);
+    }
+
+    #[test]
+    /// Test that no countdown runs without a configured timer or on the AI's turn
+    fn test_timer_only_runs_on_human_turn() {
+        let mut app = App::new();
+        app.game_state.players[1].hand = vec![Card::new(Suit::Clubs, Rank::Six)];
+        app.game_state.table_cards = vec![(Card::new(Suit::Hearts, Rank::Ace), None)];
+        app.game_state.set_phase_to_defense(0, 1);
+        app.restart_turn_timer();
+        assert_eq!(app.turn_time_left(), None);
+        app.turn_timer = Some(Duration::from_secs(30));
+        app.restart_turn_timer();
+        assert_eq!(app.turn_time_left(), None);
+    }
+}
@@ -0,0 +1,109 @@
+/*
+ * undo.rs - Taking back the human's last move
+ *
+ * This file contains the logic behind the 'u' key:
+ * - Keeping a snapshot of the game before each of the human's moves
+ * - Dropping the snapshots once the AI has answered or new cards were drawn
+ * - Putting the game back at the last snapshot
+ */
+use super::app_core::App;
+use super::input::AppAction;
+use crate::game::GameState;
+use crate::ui::debug_overlay::debug;
+
+/// Most moves that can be taken back in a row
+pub const UNDO_LIMIT: usize = 10;
+
+impl App {
+    /// Whether undo is offered at all: a practice game has its own takebacks, and in a
+    /// hotseat game the move to take back may be the other human's
+    fn undo_enabled(&self) -> bool {
+        self.practice.is_none() && !self.is_hotseat()
+    }
+    /// Whether 'u' would take a move back right now
+    pub fn can_undo(&self) -> bool {
+        self.undo_enabled() && self.is_human_turn() && !self.undo_history.is_empty()
+    }
+    /// Keeps the position before the human's `action`, called before it is made
+    pub fn snapshot_for_undo(&mut self, action: AppAction) {
+        if !action.is_undoable() || !self.undo_enabled() || !self.is_human_turn() {
+            return;
+        }
+        if self.undo_history.len() == UNDO_LIMIT {
+            self.undo_history.remove(0);
+        }
+        self.undo_history.push(self.game_state.clone());
+    }
+    /// Tidies the snapshots once `action` has been made from `before`. A move that was
+    /// rejected leaves no snapshot; any other change to the game, like a draw, can't be
+    /// taken back and ends the undo history.
+    pub fn settle_undo(&mut self, before: &GameState, action: AppAction) {
+        if *before != self.game_state {
+            if !action.is_undoable() && action != AppAction::UndoMove {
+                self.undo_history.clear();
+            }
+        } else if action.is_undoable() && self.undo_history.last() == Some(before) {
+            self.undo_history.pop();
+        }
+    }
+    /// The AI moved: taking the human's move back now would replay the AI's answer with
+    /// the human knowing it, so the history is dropped
+    pub fn clear_undo(&mut self) {
+        if !self.undo_history.is_empty() {
+            debug("AI answered, the human's moves can no longer be taken back");
+            self.undo_history.clear();
+        }
+    }
+    /// Action function that runs when the user presses 'u'
+    pub fn undo_move_action(&mut self) {
+        if !self.can_undo() {
+            debug("Nothing to undo");
+            return;
+        }
+        let Some(state) = self.undo_history.pop() else {
+            return;
+        };
+        self.game_state = state;
+        debug("Took back the last move");
+        self.selected_attack_idx = None;
+        self.clear_multi_selection();
+        self.reset_card_selection();
+        self.restart_turn_timer();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::AppState;
+    use crossterm::event::KeyCode;
+
+    #[test]
+    /// Test that a defense of one of two attacks can be taken back, and that nothing can
+    /// once the AI has answered
+    fn test_undo_until_ai_answers() {
+        let mut app = App::new();
+        app.game_state = GameState::from_notation(
+            "Player*: 8H 9D 6C | Computer: 7C 7S | Table: 7H-_ 7D-_ | Trump: S \
+             | Phase: Defense | Attacker: Computer | Defender: Player",
+        )
+        .unwrap();
+        app.app_state = AppState::Playing;
+        let start = app.game_state.clone();
+        assert!(!app.can_undo());
+
+        app.selected_card_idx = Some(0);
+        app.on_key(KeyCode::Enter);
+        assert!(app.game_state.table_cards()[0].1.is_some());
+        assert!(app.can_undo());
+        app.on_key(KeyCode::Char('u'));
+        assert_eq!(app.game_state, start);
+        assert!(!app.can_undo());
+
+        // Taking the cards hands the turn to the AI, which attacks again straight away
+        app.on_key(KeyCode::Char('t'));
+        app.on_key(KeyCode::Char(' '));
+        assert!(!app.can_undo());
+        assert!(app.undo_history.is_empty());
+    }
+}
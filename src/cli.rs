@@ -0,0 +1,279 @@
+/*
+ * cli.rs - Command line options
+ *
+ * Lets a game be launched straight from the shell, e.g.
+ * `durak --difficulty hard --players 3 --seed 42 --deck 36`.
+ * With no options the interactive main menu is shown as usual.
+ * `--sim` plays AI-vs-AI games without a terminal and prints the results instead.
+ */
+use crate::app::App;
+use crate::app::simulation::SimFormat;
+use crate::game::deck::DeckSize;
+use crate::game::{AiDifficulty, PlayerType};
+use std::time::Duration;
+
+pub const USAGE: &str = "Usage: durak [OPTIONS]
+
+Options:
+  --difficulty <easy|medium|hard|dynamic>  AI difficulty
+  --players <2-6>                          Number of players (one human, the rest AI)
+  --seed <number>                          Shuffle seed for a reproducible deal
+  --deck <20|36|52>                        Deck size
+  --turn-timer <seconds>                   Time limit for each of your turns
+  --ai-delay <milliseconds>                How long the AI shows it is thinking (default 400)
+  --sim <games>                            Play AI-vs-AI games headless and print the results
+  --p0 <easy|medium|hard>                  AI in seat 0 of a simulation (default medium)
+  --p1 <easy|medium|hard>                  AI in seat 1 of a simulation (default hard)
+  --format <json|csv>                      Output of a simulation (default json)
+  -h, --help                               Print this help
+
+Without options the interactive menu is shown.";
+
+/// How long the AI shows it is thinking before it moves, unless `--ai-delay` says otherwise
+const DEFAULT_AI_DELAY_MS: u64 = 400;
+/// Time between two cards of the dealing animation
+const DEAL_INTERVAL_MS: u64 = 120;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CliOptions {
+    pub difficulty: Option<AiDifficulty>,
+    pub players: Option<usize>,
+    pub seed: Option<u64>,
+    pub deck: Option<DeckSize>,
+    pub turn_timer: Option<u64>,
+    pub ai_delay: Option<u64>,
+    pub sim: Option<usize>,
+    pub p0: Option<AiDifficulty>,
+    pub p1: Option<AiDifficulty>,
+    pub format: Option<SimFormat>,
+    pub help: bool,
+}
+
+impl CliOptions {
+    /// Parse the program arguments (without the binary name).
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut options = CliOptions::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => options.help = true,
+                "--difficulty" => {
+                    options.difficulty = Some(parse_difficulty(&next_value(&mut args, &arg)?)?);
+                }
+                "--players" => {
+                    let players = parse_number(&next_value(&mut args, &arg)?, &arg)?;
+                    if !(2..=6).contains(&players) {
+                        return Err(format!(
+                            "--players must be between 2 and 6, got {}",
+                            players
+                        ));
+                    }
+                    options.players = Some(players);
+                }
+                "--seed" => {
+                    options.seed = Some(parse_number(&next_value(&mut args, &arg)?, &arg)?);
+                }
+                "--deck" => {
+                    let deck = parse_number(&next_value(&mut args, &arg)?, &arg)?;
+                    options.deck = Some(
+                        DeckSize::from_cards(deck)
+                            .ok_or_else(|| format!("Unsupported deck size: {}", deck))?,
+                    );
+                }
+                "--turn-timer" => {
+                    let seconds = parse_number(&next_value(&mut args, &arg)?, &arg)?;
+                    if seconds == 0 {
+                        return Err("--turn-timer must be at least 1 second".to_string());
+                    }
+                    options.turn_timer = Some(seconds);
+                }
+                "--ai-delay" => {
+                    options.ai_delay = Some(parse_number(&next_value(&mut args, &arg)?, &arg)?);
+                }
+                "--sim" => {
+                    let games = parse_number(&next_value(&mut args, &arg)?, &arg)?;
+                    if games == 0 {
+                        return Err("--sim must play at least 1 game".to_string());
+                    }
+                    options.sim = Some(games);
+                }
+                "--p0" | "--p1" => {
+                    let difficulty = parse_difficulty(&next_value(&mut args, &arg)?)?;
+                    // Dynamic follows a human's results, a simulation has no human
+                    if difficulty == AiDifficulty::Dynamic {
+                        return Err(format!("{} cannot be dynamic", arg));
+                    }
+                    if arg == "--p0" {
+                        options.p0 = Some(difficulty);
+                    } else {
+                        options.p1 = Some(difficulty);
+                    }
+                }
+                "--format" => {
+                    let value = next_value(&mut args, &arg)?;
+                    options.format = Some(match value.to_lowercase().as_str() {
+                        "json" => SimFormat::Json,
+                        "csv" => SimFormat::Csv,
+                        _ => return Err(format!("Unknown format: {}", value)),
+                    });
+                }
+                _ => return Err(format!("Unknown argument: {}", arg)),
+            }
+        }
+        let sim_only = options.p0.is_some() || options.p1.is_some() || options.format.is_some();
+        if sim_only && options.sim.is_none() {
+            return Err("--p0, --p1 and --format need --sim".to_string());
+        }
+        Ok(options)
+    }
+
+    /// The two AI players of a simulation, Medium against Hard unless `--p0`/`--p1` say otherwise
+    pub fn sim_difficulties(&self) -> [AiDifficulty; 2] {
+        [
+            self.p0.unwrap_or(AiDifficulty::Medium),
+            self.p1.unwrap_or(AiDifficulty::Hard),
+        ]
+    }
+
+    /// Whether any option that configures a game was given.
+    /// The turn timer and the AI delay are preferences and still show the menu.
+    pub fn has_game_options(&self) -> bool {
+        self.difficulty.is_some()
+            || self.players.is_some()
+            || self.seed.is_some()
+            || self.deck.is_some()
+    }
+
+    /// Configure the app from the parsed options before the game starts
+    pub fn apply(&self, app: &mut App) {
+        if let Some(difficulty) = self.difficulty {
+            app.select_difficulty(difficulty);
+        }
+        if let Some(players) = self.players {
+            while app.game_state.players().len() < players {
+                let name = format!("Computer {}", app.game_state.players().len());
+                app.game_state.add_player(name, PlayerType::Computer);
+            }
+        }
+        if let Some(deck) = self.deck {
+            app.game_state.pack_size = deck;
+        }
+        app.game_state.seed = self.seed;
+        app.turn_timer = self.turn_timer.map(Duration::from_secs);
+        app.ai_think_time = Some(Duration::from_millis(
+            self.ai_delay.unwrap_or(DEFAULT_AI_DELAY_MS),
+        ));
+        app.deal_interval = Some(Duration::from_millis(DEAL_INTERVAL_MS));
+    }
+}
+
+fn parse_difficulty(value: &str) -> Result<AiDifficulty, String> {
+    match value.to_lowercase().as_str() {
+        "easy" => Ok(AiDifficulty::Easy),
+        "medium" => Ok(AiDifficulty::Medium),
+        "hard" => Ok(AiDifficulty::Hard),
+        "dynamic" => Ok(AiDifficulty::Dynamic),
+        _ => Err(format!("Unknown difficulty: {}", value)),
+    }
+}
+
+fn next_value<I: Iterator<Item = String>>(args: &mut I, flag: &str) -> Result<String, String> {
+    args.next()
+        .ok_or_else(|| format!("Missing value for {}", flag))
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str, flag: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid value for {}: {}", flag, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_game_options() {
+        let options = CliOptions::parse(args(&[
+            "--difficulty",
+            "hard",
+            "--players",
+            "3",
+            "--seed",
+            "42",
+            "--deck",
+            "36",
+        ]))
+        .unwrap();
+        assert_eq!(options.difficulty, Some(AiDifficulty::Hard));
+        assert_eq!(options.players, Some(3));
+        assert_eq!(options.seed, Some(42));
+        assert_eq!(options.deck, Some(DeckSize::Standard));
+        assert!(options.has_game_options());
+    }
+
+    #[test]
+    /// Test that each deck size parses and is what the game gets dealt from
+    fn test_parse_deck_sizes() {
+        for (cards, size) in [
+            ("20", DeckSize::Short),
+            ("36", DeckSize::Standard),
+            ("52", DeckSize::Full),
+        ] {
+            let options = CliOptions::parse(args(&["--deck", cards])).unwrap();
+            assert_eq!(options.deck, Some(size));
+            let mut app = App::new();
+            options.apply(&mut app);
+            assert_eq!(app.game_state.pack_size, size);
+        }
+        assert!(CliOptions::parse(args(&["--deck", "32"])).is_err());
+    }
+
+    #[test]
+    fn test_ai_delay_is_a_preference() {
+        let options = CliOptions::parse(args(&["--ai-delay", "0"])).unwrap();
+        assert_eq!(options.ai_delay, Some(0));
+        assert!(!options.has_game_options());
+        let mut app = App::new();
+        options.apply(&mut app);
+        assert_eq!(app.ai_think_time, Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_parse_no_options_keeps_menu() {
+        let options = CliOptions::parse(args(&[])).unwrap();
+        assert!(!options.has_game_options());
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_and_invalid() {
+        assert!(CliOptions::parse(args(&["--fast"])).is_err());
+        assert!(CliOptions::parse(args(&["--players", "9"])).is_err());
+        assert!(CliOptions::parse(args(&["--seed"])).is_err());
+        assert!(CliOptions::parse(args(&["--difficulty", "expert"])).is_err());
+        assert!(CliOptions::parse(args(&["--turn-timer", "0"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_simulation_options() {
+        let options = CliOptions::parse(args(&[
+            "--sim", "100", "--p0", "hard", "--p1", "medium", "--format", "csv",
+        ]))
+        .unwrap();
+        assert_eq!(options.sim, Some(100));
+        assert_eq!(
+            options.sim_difficulties(),
+            [AiDifficulty::Hard, AiDifficulty::Medium]
+        );
+        assert_eq!(options.format, Some(SimFormat::Csv));
+        assert!(!options.has_game_options());
+        assert!(CliOptions::parse(args(&["--p0", "hard"])).is_err());
+        assert!(CliOptions::parse(args(&["--sim", "0"])).is_err());
+        assert!(CliOptions::parse(args(&["--sim", "5", "--p1", "dynamic"])).is_err());
+        assert!(CliOptions::parse(args(&["--sim", "5", "--format", "xml"])).is_err());
+    }
+}
@@ -1,14 +1,233 @@
 use crate::game::card::{Card, Rank, Suit};
+use crate::game::deck::DeckSize;
 use crate::game::game_state::GameState;
+use crate::game::player::PlayerType;
 use crate::ui::debug_overlay::debug;
-use std::collections::{HashMap, HashSet};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
 use std::fmt::Display;
 
+thread_local! {
+    /// Shared source of the chance rolls of every AI without its own generator. Seeded in
+    /// practice mode so a replayed position gets the same answer; `None` falls back to the
+    /// thread RNG.
+    static AI_RNG: RefCell<Option<StdRng>> = const { RefCell::new(None) };
+    /// Reasoning behind the AI's latest decision, `None` while tracing is off
+    static DECISION_TRACE: RefCell<Option<DecisionTrace>> = const { RefCell::new(None) };
+    /// The player-facing feed of the AI's latest moves, `None` while commentary is off
+    static COMMENTARY: RefCell<Option<Commentary>> = const { RefCell::new(None) };
+}
+
+/// How many comments the commentary feed keeps, older ones are dropped
+pub const COMMENTARY_LEN: usize = 6;
+
+/// Seeds the AI's chance rolls, or returns them to the thread RNG with `None`
+pub fn seed_ai_rng(seed: Option<u64>) {
+    AI_RNG.with(|rng| *rng.borrow_mut() = seed.map(StdRng::seed_from_u64));
+}
+
+/// What a strategy worked out on the way to its latest decision, for the debug overlay
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DecisionTrace {
+    pub player_idx: usize,
+    pub decision: &'static str,                   // "attack" or "defend"
+    pub probable_weak_ranks: Vec<Rank>, // Ranks the defender is likely out of, Hard AI only
+    pub defense_plan: Option<Vec<(usize, Card)>>, // (table index, card) pairs weighed or played
+    pub notes: Vec<String>,
+}
+
+impl Display for DecisionTrace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AI {} {}", self.player_idx + 1, self.decision)?;
+        if !self.probable_weak_ranks.is_empty() {
+            let ranks: Vec<&str> = self.probable_weak_ranks.iter().map(Rank::symbol).collect();
+            write!(f, " | weak ranks: {}", ranks.join(" "))?;
+        }
+        if let Some(plan) = &self.defense_plan {
+            let cards: Vec<String> = plan
+                .iter()
+                .map(|(table_idx, card)| format!("{}->{}", card, table_idx + 1))
+                .collect();
+            write!(f, " | plan: {}", cards.join(" "))?;
+        }
+        for note in &self.notes {
+            write!(f, " | {}", note)?;
+        }
+        Ok(())
+    }
+}
+
+/// Turns decision tracing on or off. Off by default, so normal play never builds a trace.
+pub fn trace_decisions(enabled: bool) {
+    DECISION_TRACE.with(|trace| *trace.borrow_mut() = enabled.then(DecisionTrace::default));
+}
+
+/// The reasoning behind the AI's latest decision, if tracing is on
+pub fn last_decision_trace() -> Option<DecisionTrace> {
+    DECISION_TRACE.with(|trace| trace.borrow().clone())
+}
+
+/// Runs `f` on the current trace. Does nothing while tracing is off, so callers can
+/// clone and format in `f` without costing the normal path anything.
+fn with_trace(f: impl FnOnce(&mut DecisionTrace)) {
+    DECISION_TRACE.with(|trace| {
+        if let Some(trace) = trace.borrow_mut().as_mut() {
+            f(trace);
+        }
+    });
+}
+
+/// Starts a fresh trace for a new decision by `player_idx`
+fn begin_trace(player_idx: usize, decision: &'static str) {
+    with_trace(|trace| {
+        *trace = DecisionTrace {
+            player_idx,
+            decision,
+            ..DecisionTrace::default()
+        }
+    });
+}
+
+/// Why a strategy chose its attack, when there is more to it than playing a cheap card.
+/// Set at the strategy's decision point and picked up by `AiPlayer::make_attack_move`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttackReason {
+    WeakRank,    // The defender has probably run out of answers to the rank
+    DrainTrumps, // A trump thrown in after the defender already had to use one
+    Forcing,     // A high card to stop the defender emptying their hand in the endgame
+}
+
+/// One AI move told the way a player would put it, for the commentary panel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AiComment {
+    Attack {
+        player_idx: usize,
+        cards: Vec<Card>,
+        adding: bool, // Thrown in on a table that already has cards
+        reason: Option<AttackReason>,
+    },
+    StopAdding {
+        player_idx: usize,
+    },
+    Defend {
+        player_idx: usize,
+        pairs: Vec<(Card, Card)>, // (attack, defense)
+        used_trump: bool,
+        saved_trumps: bool, // Beat everything without trumps while holding some
+    },
+    Take {
+        player_idx: usize,
+        could_not_beat: bool, // Looked for a defense and found none, rather than chose to take
+    },
+    Pass {
+        player_idx: usize,
+        card: Card,
+    },
+}
+
+impl AiComment {
+    pub fn player_idx(&self) -> usize {
+        match self {
+            AiComment::Attack { player_idx, .. }
+            | AiComment::StopAdding { player_idx }
+            | AiComment::Defend { player_idx, .. }
+            | AiComment::Take { player_idx, .. }
+            | AiComment::Pass { player_idx, .. } => *player_idx,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Commentary {
+    comments: VecDeque<AiComment>,
+    reason: Option<AttackReason>, // Noted by the strategy for the attack being decided
+}
+
+/// Turns the player-facing commentary on or off, either way starting from an empty feed
+pub fn record_commentary(enabled: bool) {
+    COMMENTARY.with(|commentary| *commentary.borrow_mut() = enabled.then(Commentary::default));
+}
+
+/// The AI's latest moves, oldest first. Empty while commentary is off.
+pub fn ai_commentary() -> Vec<AiComment> {
+    COMMENTARY.with(|commentary| {
+        commentary
+            .borrow()
+            .as_ref()
+            .map(|commentary| commentary.comments.iter().cloned().collect())
+            .unwrap_or_default()
+    })
+}
+
+/// Remembers why the attack being decided was chosen, see `AttackReason`
+fn note_reason(reason: Option<AttackReason>) {
+    COMMENTARY.with(|commentary| {
+        if let Some(commentary) = commentary.borrow_mut().as_mut() {
+            commentary.reason = reason;
+        }
+    });
+}
+
+fn take_reason() -> Option<AttackReason> {
+    COMMENTARY.with(|commentary| {
+        commentary
+            .borrow_mut()
+            .as_mut()
+            .and_then(|commentary| commentary.reason.take())
+    })
+}
+
+/// Adds a comment to the feed. Only moves by computer players are commented on, the
+/// quick-play keys run the AI in the human's seat.
+fn comment(game_state: &GameState, comment: AiComment) {
+    if game_state.players()[comment.player_idx()].player_type() != &PlayerType::Computer {
+        return;
+    }
+    COMMENTARY.with(|commentary| {
+        if let Some(commentary) = commentary.borrow_mut().as_mut() {
+            if commentary.comments.len() == COMMENTARY_LEN {
+                commentary.comments.pop_front();
+            }
+            commentary.comments.push_back(comment);
+        }
+    });
+}
+
+/// A chance roll in `[0, 1)` from the shared source, see `seed_ai_rng`
+fn roll() -> f32 {
+    AI_RNG.with(|rng| match rng.borrow_mut().as_mut() {
+        Some(rng) => rng.r#gen::<f32>(),
+        None => rand::random::<f32>(),
+    })
+}
+
+/// The chance rolls of one AI. An AI made with `AiPlayer::new_seeded` rolls from its own
+/// generator, any other AI from the shared source.
+#[derive(Default)]
+pub struct AiRng(RefCell<Option<StdRng>>);
+
+impl AiRng {
+    fn seeded(seed: u64) -> Self {
+        Self(RefCell::new(Some(StdRng::seed_from_u64(seed))))
+    }
+    /// A chance roll in `[0, 1)` compared against the `AiParams` probabilities
+    fn roll(&self) -> f32 {
+        match self.0.borrow_mut().as_mut() {
+            Some(rng) => rng.r#gen::<f32>(),
+            None => roll(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AiDifficulty {
     Easy,
     Medium,
     Hard,
+    Dynamic, // Plays each game as Easy, Medium or Hard depending on the human's recent results
 }
 impl Display for AiDifficulty {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -16,39 +235,453 @@ impl Display for AiDifficulty {
             AiDifficulty::Easy => write!(f, "Easy"),
             AiDifficulty::Medium => write!(f, "Medium"),
             AiDifficulty::Hard => write!(f, "Hard"),
+            AiDifficulty::Dynamic => write!(f, "Dynamic"),
+        }
+    }
+}
+
+/// Probabilities the strategies roll against when they make a non-deterministic choice,
+/// and the weights of the opening attack (see `opening_attack`).
+/// Each difficulty has its own defaults, see `AiParams::for_difficulty`.
+/// A chance a strategy never rolls against, or a weight it never uses, is left at 0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AiParams {
+    /// Chance to pick up the table even though a defense is possible
+    pub take_chance: f32,
+    /// Chance to stop adding cards to an ongoing attack
+    pub stop_adding_chance: f32,
+    /// Chance to stop adding cards when every attack so far was beaten
+    pub easy_defense_stop_chance: f32,
+    /// Chance to add a matching trump after the defender spent a trump
+    pub add_trump_chance: f32,
+    /// Chance to pass an attack with a card of the same rank
+    pub pass_chance: f32,
+    /// Chance to spend a trump on a high-value attack card
+    pub trump_on_high_chance: f32,
+    /// Value the AI gives up to open with a rank it holds two or more of
+    pub pair_lead_bonus: u32,
+    /// Value the AI gives up per card of the lead's rank the defender can't be holding
+    pub exhausted_rank_bonus: u32,
+}
+
+impl AiParams {
+    pub fn for_difficulty(difficulty: AiDifficulty) -> Self {
+        match difficulty {
+            AiDifficulty::Easy => Self {
+                take_chance: 0.5,
+                stop_adding_chance: 0.0,
+                easy_defense_stop_chance: 0.0,
+                add_trump_chance: 0.0,
+                pass_chance: 0.0,
+                trump_on_high_chance: 0.0,
+                pair_lead_bonus: 0,
+                exhausted_rank_bonus: 0,
+            },
+            // Dynamic starts out as Medium until the human's record says otherwise
+            AiDifficulty::Medium | AiDifficulty::Dynamic => Self {
+                take_chance: 0.4,
+                stop_adding_chance: 0.3,
+                easy_defense_stop_chance: 0.0,
+                add_trump_chance: 0.3,
+                pass_chance: 0.3,
+                trump_on_high_chance: 0.7,
+                pair_lead_bonus: 0,
+                exhausted_rank_bonus: 0,
+            },
+            AiDifficulty::Hard => Self {
+                take_chance: 0.0,
+                stop_adding_chance: 0.5,
+                easy_defense_stop_chance: 0.8,
+                add_trump_chance: 0.7,
+                pass_chance: 0.6,
+                trump_on_high_chance: 0.0,
+                pair_lead_bonus: 3,
+                exhausted_rank_bonus: 1,
+            },
+        }
+    }
+}
+
+/// Every card the players have seen leave the table: beaten and discarded, or picked up by
+/// a defender. A picked-up card stays known to be in its taker's hand until it is played
+/// again. Kept up to date by `GameState` as rounds end, so the AI never has to rebuild it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CardTracker {
+    copies: usize, // Packs in the deck, every card comes this many times
+    discarded: Vec<Card>,
+    picked_up: Vec<(usize, Card)>, // (taker, card) for cards not played since
+}
+
+impl Default for CardTracker {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl CardTracker {
+    /// An empty tracker for a deck of `copies` packs
+    pub fn new(copies: usize) -> Self {
+        Self {
+            copies,
+            discarded: Vec::new(),
+            picked_up: Vec::new(),
+        }
+    }
+    /// Forgets the picked-up copy of `card` a player just played, if one is known
+    fn forget_pick_up(&mut self, card: &Card) {
+        if let Some(idx) = self.picked_up.iter().position(|(_, known)| known == card) {
+            self.picked_up.swap_remove(idx);
+        }
+    }
+    /// Records the beaten table of a defended round going to the discard pile
+    pub fn record_discard(&mut self, cards: &[Card]) {
+        for card in cards {
+            self.forget_pick_up(card);
+            self.discarded.push(*card);
+        }
+    }
+    /// Records `player_idx` taking `cards` from the table into their hand
+    pub fn record_pick_up(&mut self, player_idx: usize, cards: &[Card]) {
+        for card in cards {
+            self.forget_pick_up(card);
+            self.picked_up.push((player_idx, *card));
+        }
+    }
+    /// Whether a copy of `card` has been discarded or picked up
+    pub fn seen(&self, card: &Card) -> bool {
+        self.discarded.contains(card) || self.picked_up.iter().any(|(_, known)| known == card)
+    }
+    /// Cards of `rank` whose whereabouts are unknown: neither discarded nor known to be
+    /// in a hand. They are in the deck, on the table or in a hand since the deal.
+    pub fn remaining_of_rank(&self, rank: Rank) -> usize {
+        let seen = self
+            .discarded
+            .iter()
+            .filter(|card| card.rank == rank)
+            .count()
+            + self
+                .picked_up
+                .iter()
+                .filter(|(_, card)| card.rank == rank)
+                .count();
+        (4 * self.copies).saturating_sub(seen)
+    }
+    /// The cards `player_idx` is known to hold, picked up and not played since
+    pub fn known_hand(&self, player_idx: usize) -> impl Iterator<Item = &Card> {
+        self.picked_up
+            .iter()
+            .filter(move |(taker, _)| *taker == player_idx)
+            .map(|(_, card)| card)
+    }
+}
+
+/// Rolling record of the human's most recent results, used by the Dynamic difficulty.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MatchRecord {
+    results: VecDeque<bool>, // true when the human won
+}
+
+impl MatchRecord {
+    /// Only the last few games count, so the AI follows the player's current form
+    const WINDOW: usize = 5;
+    /// Games needed before the AI moves away from Medium
+    const MIN_GAMES: usize = 3;
+
+    pub fn record(&mut self, human_won: bool) {
+        self.results.push_back(human_won);
+        if self.results.len() > Self::WINDOW {
+            self.results.pop_front();
+        }
+    }
+    /// Share of the recorded games the human won, `None` before the first game
+    pub fn win_rate(&self) -> Option<f32> {
+        if self.results.is_empty() {
+            return None;
+        }
+        let wins = self.results.iter().filter(|&&won| won).count();
+        Some(wins as f32 / self.results.len() as f32)
+    }
+    /// The strategy the next Dynamic game delegates to: Hard for a player who keeps
+    /// winning, Easy for one who keeps losing, Medium otherwise.
+    pub fn difficulty(&self) -> AiDifficulty {
+        match self.win_rate() {
+            Some(_) if self.results.len() < Self::MIN_GAMES => AiDifficulty::Medium,
+            Some(rate) if rate > 0.6 => AiDifficulty::Hard,
+            Some(rate) if rate < 0.4 => AiDifficulty::Easy,
+            _ => AiDifficulty::Medium,
         }
     }
 }
 
 trait AiStrategy {
-    fn should_take_cards(&self, game_state: &GameState, player_idx: usize) -> bool;
+    fn should_take_cards(
+        &self,
+        game_state: &GameState,
+        player_idx: usize,
+        params: &AiParams,
+        rng: &AiRng,
+    ) -> bool;
 
     fn make_attack_move(
         &self,
         game_state: &GameState,
         player_idx: usize,
+        params: &AiParams,
+        rng: &AiRng,
     ) -> Option<Vec<(usize, Card)>>; //Always will return cards to attack with or an error.
 
     fn make_defense_move(
         &self,
         game_state: &GameState,
         player_idx: usize,
+        params: &AiParams,
+        rng: &AiRng,
     ) -> Option<Vec<(usize, Card)>>; //Always will return cards to attack with or an error.
+
+    /// Picks a same-rank card to pass the first open attack on to the next player.
+    /// `None` means the AI defends or takes instead.
+    fn make_pass_move(
+        &self,
+        _game_state: &GameState,
+        _player_idx: usize,
+        _params: &AiParams,
+        _rng: &AiRng,
+    ) -> Option<(usize, Card)> {
+        None
+    }
+
+    /// Picks a card for every open attack at once, as (table index, card) pairs.
+    /// Follows `make_defense_move` one attack at a time and falls back to searching every
+    /// pairing when that leaves an attack it can no longer beat.
+    /// `None` means the attacks can't all be beaten together.
+    fn make_full_defense(
+        &self,
+        game_state: &GameState,
+        player_idx: usize,
+        params: &AiParams,
+        rng: &AiRng,
+    ) -> Option<Vec<(usize, Card)>> {
+        let mut state = game_state.clone();
+        let mut plan = Vec::new();
+        while let Some(attack_idx) = state
+            .table_cards()
+            .iter()
+            .position(|(_, defense)| defense.is_none())
+        {
+            let next_defense = self
+                .make_defense_move(&state, player_idx, params, rng)
+                .and_then(|cards| cards.first().copied());
+            match next_defense {
+                Some((hand_idx, card)) if state.defend_at(attack_idx, hand_idx).is_ok() => {
+                    plan.push((attack_idx, card));
+                }
+                _ => return plan_full_defense(game_state, player_idx),
+            }
+        }
+        Some(plan)
+    }
+}
+
+/// Finds cards that beat every open attack together, as (table index, card) pairs.
+/// Each attack tries the cheapest cards first, so the first complete pairing found is a
+/// cheap one. `None` if no pairing of the hand beats them all.
+fn plan_full_defense(game_state: &GameState, player_idx: usize) -> Option<Vec<(usize, Card)>> {
+    let trump_suit = game_state.trump_suit();
+    let mut hand = game_state.players()[player_idx].hand().to_vec();
+    hand.sort_by_key(|card| card.strategic_value(trump_suit));
+    let open_attacks: Vec<(usize, Card)> = game_state
+        .table_cards()
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, defense))| defense.is_none())
+        .map(|(idx, (attack, _))| (idx, *attack))
+        .collect();
+    let mut used = vec![false; hand.len()];
+    let mut plan = Vec::with_capacity(open_attacks.len());
+    assign_defenses(&open_attacks, &hand, trump_suit, &mut used, &mut plan).then_some(plan)
+}
+
+/// Backtracking step of `plan_full_defense`: beats the next attack in `attacks` with an
+/// unused card and recurses, undoing the choice when the remaining attacks can't be covered.
+fn assign_defenses(
+    attacks: &[(usize, Card)],
+    hand: &[Card],
+    trump_suit: Option<Suit>,
+    used: &mut [bool],
+    plan: &mut Vec<(usize, Card)>,
+) -> bool {
+    let Some(&(table_idx, attack)) = attacks.get(plan.len()) else {
+        return true;
+    };
+    for (i, card) in hand.iter().enumerate() {
+        if used[i] || !card.can_beat(&attack, trump_suit) {
+            continue;
+        }
+        used[i] = true;
+        plan.push((table_idx, *card));
+        if assign_defenses(attacks, hand, trump_suit, used, plan) {
+            return true;
+        }
+        used[i] = false;
+        plan.pop();
+    }
+    false
+}
+
+/// The rank that holds the place in this game's pack that `standard` holds among the nine
+/// ranks of the 36 card pack, counted from the ace. The high-card cutoffs of the strategies
+/// are given for the standard pack: a Jack and up is high there, a Queen and up in a 20 card
+/// pack and a Ten and up in a 52 card one.
+fn high_rank(game_state: &GameState, standard: Rank) -> Rank {
+    let standard_ranks = DeckSize::Standard.ranks();
+    let ranks = game_state.pack_size.ranks();
+    let from_top = standard_ranks.len()
+        - 1
+        - standard_ranks
+            .iter()
+            .position(|&rank| rank == standard)
+            .unwrap_or(0);
+    let scaled = (from_top * ranks.len() + standard_ranks.len() / 2) / standard_ranks.len();
+    ranks[ranks.len() - 1 - scaled.min(ranks.len() - 1)]
+}
+
+/// Picks the card to open a round with. Each card costs its strategic value, so the lowest
+/// non-trump leads unless a rank is worth more than the difference:
+/// - a rank held two or more times costs `pair_lead_bonus` less, the other card is a ready
+///   throw-in once the lead is beaten
+/// - every card of the lead's rank the defender can't be holding, in the AI's hand or the
+///   discard pile, costs `exhausted_rank_bonus` less, the defender can't pass it on
+///
+/// A pair made up only of high trumps (Jack up in a 36 card pack, see `high_rank`) gets no
+/// bonus, those are kept for defending.
+fn opening_attack(
+    game_state: &GameState,
+    player_idx: usize,
+    params: &AiParams,
+) -> Option<(usize, Card)> {
+    let trump_suit = game_state.trump_suit();
+    let hand = game_state.players()[player_idx].hand();
+    let in_hand = |rank: Rank| hand.iter().filter(|card| card.rank == rank).count() as u32;
+    let seen = |rank: Rank| {
+        in_hand(rank)
+            + game_state
+                .discard_pile()
+                .iter()
+                .filter(|card| card.rank == rank)
+                .count() as u32
+    };
+    let high = high_rank(game_state, Rank::Jack);
+    let high_trump = |card: &Card| card.is_trump(trump_suit) && card.rank >= high;
+    let cost = |card: &Card| {
+        let mut cost = card.strategic_value(trump_suit);
+        let pair = in_hand(card.rank) >= 2
+            && hand
+                .iter()
+                .any(|other| other.rank == card.rank && !high_trump(other));
+        if pair {
+            cost = cost.saturating_sub(params.pair_lead_bonus);
+        }
+        cost.saturating_sub(params.exhausted_rank_bonus * seen(card.rank).saturating_sub(1))
+    };
+    hand.iter()
+        .enumerate()
+        .min_by_key(|(_, card)| (cost(card), card.strategic_value(trump_suit)))
+        .map(|(idx, &card)| (idx, card))
 }
 
 struct EasyStrategy;
 struct MediumStrategy;
 struct HardStrategy;
+/// Deterministic opponent for tests: no chance rolls, always the least valuable legal card,
+/// never passes and only takes when the attacks can't be beaten. Not offered in the menus.
+struct BaselineStrategy;
+/// Dynamic difficulty: hands a whole game to one of the fixed strategies,
+/// picked from the human's record when the game starts.
+struct AdaptiveStrategy {
+    delegate: Box<dyn AiStrategy>,
+}
+
+impl AdaptiveStrategy {
+    fn new(difficulty: AiDifficulty) -> Self {
+        let delegate: Box<dyn AiStrategy> = match difficulty {
+            AiDifficulty::Easy => Box::new(EasyStrategy),
+            AiDifficulty::Medium | AiDifficulty::Dynamic => Box::new(MediumStrategy),
+            AiDifficulty::Hard => Box::new(HardStrategy),
+        };
+        Self { delegate }
+    }
+}
+
+impl AiStrategy for AdaptiveStrategy {
+    fn should_take_cards(
+        &self,
+        game_state: &GameState,
+        player_idx: usize,
+        params: &AiParams,
+        rng: &AiRng,
+    ) -> bool {
+        self.delegate
+            .should_take_cards(game_state, player_idx, params, rng)
+    }
+
+    fn make_attack_move(
+        &self,
+        game_state: &GameState,
+        player_idx: usize,
+        params: &AiParams,
+        rng: &AiRng,
+    ) -> Option<Vec<(usize, Card)>> {
+        self.delegate
+            .make_attack_move(game_state, player_idx, params, rng)
+    }
+
+    fn make_defense_move(
+        &self,
+        game_state: &GameState,
+        player_idx: usize,
+        params: &AiParams,
+        rng: &AiRng,
+    ) -> Option<Vec<(usize, Card)>> {
+        self.delegate
+            .make_defense_move(game_state, player_idx, params, rng)
+    }
+
+    fn make_pass_move(
+        &self,
+        game_state: &GameState,
+        player_idx: usize,
+        params: &AiParams,
+        rng: &AiRng,
+    ) -> Option<(usize, Card)> {
+        self.delegate
+            .make_pass_move(game_state, player_idx, params, rng)
+    }
+
+    fn make_full_defense(
+        &self,
+        game_state: &GameState,
+        player_idx: usize,
+        params: &AiParams,
+        rng: &AiRng,
+    ) -> Option<Vec<(usize, Card)>> {
+        self.delegate
+            .make_full_defense(game_state, player_idx, params, rng)
+    }
+}
 
 impl AiStrategy for EasyStrategy {
     /// Easy AI follows the specific logic: if *any* single attacking card cannot be beaten,
     /// immediately decide to pick up all cards.
-    fn should_take_cards(&self, game_state: &GameState, player_idx: usize) -> bool {
+    fn should_take_cards(
+        &self,
+        game_state: &GameState,
+        player_idx: usize,
+        params: &AiParams,
+        rng: &AiRng,
+    ) -> bool {
         let player = &game_state.players()[player_idx];
         let hand = player.hand();
-        let trump_suit = game_state
-            .trump_suit()
-            .unwrap_or_else(|| panic!("Trump suit should be set"));
+        let trump_suit = game_state.trump_suit();
         // Find the first undefended attack
         for (attack_card, _) in game_state
             .table_cards()
@@ -68,8 +701,8 @@ impl AiStrategy for EasyStrategy {
                 return true;
             }
         }
-        // If all cards can be beaten, still 50% chance to take cards
-        let random_take = rand::random::<f32>() < 0.5;
+        // If all cards can be beaten, still a chance to take cards
+        let random_take = rng.roll() < params.take_chance;
         if random_take {
             debug(format!(
                 "Easy AI ({}) randomly deciding to take cards",
@@ -84,6 +717,8 @@ impl AiStrategy for EasyStrategy {
         &self,
         game_state: &GameState,
         player_idx: usize,
+        _params: &AiParams,
+        _rng: &AiRng,
     ) -> Option<Vec<(usize, Card)>> {
         let player = &game_state.players()[player_idx];
         let hand = player.hand();
@@ -94,40 +729,25 @@ impl AiStrategy for EasyStrategy {
         // If there are cards on the table, check for cards of matching rank to add
         let table_cards = game_state.table_cards();
         if !table_cards.is_empty() {
-            // Get ranks of cards on the table
-            let table_ranks: HashSet<Rank> = table_cards
-                .iter()
-                .flat_map(|(attack, defense)| {
-                    let mut ranks = vec![attack.rank];
-                    if let Some(def) = defense {
-                        ranks.push(def.rank);
-                    }
-                    ranks
-                })
-                .collect();
-            // Find any card in hand that matches a rank on the table
-            for (idx, card) in hand.iter().enumerate() {
-                if table_ranks.contains(&card.rank) {
-                    debug(format!(
-                        "Easy AI adding matching card {} to the attack",
-                        card
-                    ));
-                    return Some(vec![(idx, *card)]);
-                }
+            // Add the first card that may join the table, or nothing if none can
+            let added = game_state
+                .legal_attacks(player_idx)
+                .first()
+                .map(|&idx| (idx, hand[idx]));
+            if let Some((_, card)) = added {
+                debug(format!(
+                    "Easy AI adding matching card {} to the attack",
+                    card
+                ));
             }
+            return Some(added.into_iter().collect());
         }
         // For initial attack, find the lowest non-trump card
         let mut lowest_card = None;
         let mut lowest_idx = 0;
-        let mut lowest_value = u8::MAX;
+        let mut lowest_value = u32::MAX;
         for (idx, card) in hand.iter().enumerate() {
-            // Calculate card value - non-trumps are lower value than trumps
-            let is_trump = trump_suit == Some(card.suit);
-            let card_value = if is_trump {
-                100 + card.rank as u8
-            } else {
-                card.rank as u8
-            };
+            let card_value = card.strategic_value(trump_suit);
 
             if card_value < lowest_value {
                 lowest_value = card_value;
@@ -147,55 +767,38 @@ impl AiStrategy for EasyStrategy {
         &self,
         game_state: &GameState,
         player_idx: usize,
+        _params: &AiParams,
+        _rng: &AiRng,
     ) -> Option<Vec<(usize, Card)>> {
         let player = &game_state.players()[player_idx];
         let hand = player.hand();
-        let trump_suit = game_state
-            .trump_suit()
-            .expect("Trump suit required for defense");
+        let trump_suit = game_state.trump_suit();
 
         // Find the first undefended attack
-        if let Some((attack_card, _)) = game_state
+        if let Some(attack_idx) = game_state
             .table_cards()
             .iter()
-            .find(|(_, defense)| defense.is_none())
+            .position(|(_, defense)| defense.is_none())
         {
+            let (non_trump_defenses, trump_defenses): (Vec<_>, Vec<_>) = game_state
+                .legal_defenses(attack_idx)
+                .into_iter()
+                .map(|idx| (idx, hand[idx]))
+                .partition(|(_, card)| !card.is_trump(trump_suit));
             // First check if we can beat with a non-trump of the same suit
-            let non_trump_defenses: Vec<(usize, Card)> = hand
-                .iter()
-                .enumerate()
-                .filter(|(_, card)| {
-                    card.suit == attack_card.suit
-                        && card.suit != trump_suit
-                        && card.rank > attack_card.rank
-                })
-                .map(|(idx, &card)| (idx, card))
-                .collect();
             // Use the lowest non-trump if available
-            if !non_trump_defenses.is_empty() {
-                if let Some(&(idx, card)) = non_trump_defenses.iter().min_by_key(|(_, c)| c.rank) {
-                    debug(format!("Easy AI defending with non-trump: {}", card));
-                    return Some(vec![(idx, card)]);
-                }
+            if !non_trump_defenses.is_empty()
+                && let Some(&(idx, card)) = non_trump_defenses.iter().min_by_key(|(_, c)| c.rank)
+            {
+                debug(format!("Easy AI defending with non-trump: {}", card));
+                return Some(vec![(idx, card)]);
             }
-            // If no non-trump defense, check for any trump that can beat it
-            let trump_defenses: Vec<(usize, Card)> = hand
-                .iter()
-                .enumerate()
-                .filter(|(_, card)| {
-                    (card.suit == trump_suit && attack_card.suit != trump_suit)
-                        || (card.suit == trump_suit
-                            && attack_card.suit == trump_suit
-                            && card.rank > attack_card.rank)
-                })
-                .map(|(idx, &card)| (idx, card))
-                .collect();
-            // Use the lowest trump if available
-            if !trump_defenses.is_empty() {
-                if let Some(&(idx, card)) = trump_defenses.iter().min_by_key(|(_, c)| c.rank) {
-                    debug(format!("Easy AI defending with trump: {}", card));
-                    return Some(vec![(idx, card)]);
-                }
+            // If no non-trump defense, use the lowest trump that can beat it
+            if !trump_defenses.is_empty()
+                && let Some(&(idx, card)) = trump_defenses.iter().min_by_key(|(_, c)| c.rank)
+            {
+                debug(format!("Easy AI defending with trump: {}", card));
+                return Some(vec![(idx, card)]);
             }
         }
         // Cannot defend - will need to take cards
@@ -207,15 +810,19 @@ impl AiStrategy for MediumStrategy {
     /// Medium AI evaluates all attacking cards before playing any defense.
     /// Medium AI will take cards if:
     /// 1. Multiple valuable trumps are required (2 or more)
-    /// 2. Any high trumps (Jack+) are required
+    /// 2. Any high trumps (Jack+ in a 36 card pack, see `high_rank`) are required
     /// 3. There are 4 or more attacks to defend against
     /// 4. Random 40% chance to take cards if 2+ trumps are needed
-    fn should_take_cards(&self, game_state: &GameState, player_idx: usize) -> bool {
+    fn should_take_cards(
+        &self,
+        game_state: &GameState,
+        player_idx: usize,
+        params: &AiParams,
+        rng: &AiRng,
+    ) -> bool {
         let player = &game_state.players()[player_idx];
         let hand = player.hand();
-        let trump_suit = game_state
-            .trump_suit()
-            .expect("Trump suit required for defense decision");
+        let trump_suit = game_state.trump_suit();
         // Get all undefended attacks
         let undefended_attacks: Vec<&Card> = game_state
             .table_cards()
@@ -228,7 +835,7 @@ impl AiStrategy for MediumStrategy {
         }
         // Count how many trump cards would be needed to defend
         let mut trump_cards_needed = 0;
-        let mut high_trumps_needed = 0; // Trumps higher than 10
+        let mut high_trumps_needed = 0; // Trumps from `high_rank` up
         //
         // Check if any card cannot be beaten
         for attack_card in &undefended_attacks {
@@ -247,14 +854,17 @@ impl AiStrategy for MediumStrategy {
                 return true; // Cannot defend this card, must take all
             }
             // Check if defense requires a trump
-            let requires_trump = possible_defenses.iter().all(|card| card.suit == trump_suit);
+            let requires_trump = possible_defenses
+                .iter()
+                .all(|card| card.is_trump(trump_suit));
             if requires_trump {
                 trump_cards_needed += 1;
-                // Check if it requires a high trump (Jack or higher)
+                // Check if it requires a high trump (Jack or higher in a 36 card pack)
+                let high = high_rank(game_state, Rank::Jack);
                 let requires_high_trump = possible_defenses
                     .iter()
-                    .filter(|card| card.suit == trump_suit)
-                    .all(|card| card.rank >= Rank::Jack);
+                    .filter(|card| card.is_trump(trump_suit))
+                    .all(|card| card.rank >= high);
                 if requires_high_trump {
                     high_trumps_needed += 1;
                 }
@@ -268,7 +878,7 @@ impl AiStrategy for MediumStrategy {
             return true;
         }
         if trump_cards_needed >= 2 {
-            let random_take = rand::random::<f32>() < 0.4;
+            let random_take = rng.roll() < params.take_chance;
             if random_take {
                 debug(format!(
                     "Medium AI ({}) taking cards to save multiple trumps",
@@ -293,6 +903,8 @@ impl AiStrategy for MediumStrategy {
         &self,
         game_state: &GameState,
         player_idx: usize,
+        params: &AiParams,
+        rng: &AiRng,
     ) -> Option<Vec<(usize, Card)>> {
         let player = &game_state.players()[player_idx];
         let hand = player.hand();
@@ -304,19 +916,10 @@ impl AiStrategy for MediumStrategy {
         let table_cards = game_state.table_cards();
         // If this is an additional attack (not the first card played)
         if !table_cards.is_empty() {
-            // Get all ranks already on the table
-            let valid_ranks: HashSet<Rank> = table_cards
-                .iter()
-                .flat_map(|(attack, defense)| {
-                    let mut ranks = vec![attack.rank];
-                    if let Some(def) = defense {
-                        ranks.push(def.rank);
-                    }
-                    ranks
-                })
-                .collect();
-            // Medium AI has a 30% chance to stop adding cards
-            let stop_adding = rand::random::<f32>() < 0.3;
+            // Cards that may join the table
+            let legal_attacks = game_state.legal_attacks(player_idx);
+            // Medium AI has a chance to stop adding cards
+            let stop_adding = rng.roll() < params.stop_adding_chance;
             if stop_adding {
                 debug(format!(
                     "Medium AI ({}) decided to stop adding cards",
@@ -328,9 +931,7 @@ impl AiStrategy for MediumStrategy {
             let matching_non_trumps: Vec<(usize, Card)> = hand
                 .iter()
                 .enumerate()
-                .filter(|(_, card)| {
-                    valid_ranks.contains(&card.rank) && trump_suit != Some(card.suit)
-                })
+                .filter(|(idx, card)| legal_attacks.contains(idx) && !card.is_trump(trump_suit))
                 .map(|(idx, &card)| (idx, card))
                 .collect();
             if !matching_non_trumps.is_empty() {
@@ -347,19 +948,19 @@ impl AiStrategy for MediumStrategy {
                     return Some(vec![(idx, card)]);
                 }
             }
-            // If defender used a trump, medium AI might add a matching trump (30% chance)
-            let defender_used_trump = table_cards.iter().any(
-                |(_, defense)| matches!((defense, trump_suit), (Some(d), Some(t)) if d.suit == t),
-            );
+            // If defender used a trump, medium AI might add a matching trump
+            let defender_used_trump = table_cards
+                .iter()
+                .any(|(_, defense)| matches!(defense, Some(d) if d.is_trump(trump_suit)));
             if defender_used_trump {
-                let add_trump = rand::random::<f32>() < 0.3;
+                let add_trump = rng.roll() < params.add_trump_chance;
                 if add_trump {
                     // Look for matching trump cards
                     let matching_trumps: Vec<(usize, Card)> = hand
                         .iter()
                         .enumerate()
-                        .filter(|(_, card)| {
-                            valid_ranks.contains(&card.rank) && trump_suit == Some(card.suit)
+                        .filter(|(idx, card)| {
+                            legal_attacks.contains(idx) && card.is_trump(trump_suit)
                         })
                         .map(|(idx, &card)| (idx, card))
                         .collect();
@@ -391,31 +992,28 @@ impl AiStrategy for MediumStrategy {
         let non_trump_pairs: Vec<(&Rank, &Vec<(usize, Card)>)> = rank_counts
             .iter()
             .filter(|(_, cards)| {
-                cards.len() >= 2
-                    && cards.iter().any(|(_, c)| {
-                        matches!(trump_suit, Some(t) if c.suit != t) || trump_suit.is_none()
-                    })
+                cards.len() >= 2 && cards.iter().any(|(_, c)| !c.is_trump(trump_suit))
             })
             .collect();
         // Try to play a card from the lowest pair
-        if !non_trump_pairs.is_empty() {
-            if let Some((_, cards)) = non_trump_pairs.iter().min_by_key(|(rank, _)| *rank) {
-                // Find the lowest non-trump in this group
-                if let Some((idx, card)) = cards
-                    .iter()
-                    .filter(|(_, c)| trump_suit != Some(c.suit))
-                    .min_by_key(|(_, c)| c.rank)
-                {
-                    debug(format!("Medium AI playing from pair: {}", card));
-                    return Some(vec![(*idx, *card)]);
-                }
+        if !non_trump_pairs.is_empty()
+            && let Some((_, cards)) = non_trump_pairs.iter().min_by_key(|(rank, _)| *rank)
+        {
+            // Find the lowest non-trump in this group
+            if let Some((idx, card)) = cards
+                .iter()
+                .filter(|(_, c)| !c.is_trump(trump_suit))
+                .min_by_key(|(_, c)| c.rank)
+            {
+                debug(format!("Medium AI playing from pair: {}", card));
+                return Some(vec![(*idx, *card)]);
             }
         }
         // If no pairs, play the lowest non-trump card
         let lowest_non_trump = hand
             .iter()
             .enumerate()
-            .filter(|(_, card)| trump_suit != Some(card.suit))
+            .filter(|(_, card)| !card.is_trump(trump_suit))
             .min_by_key(|(_, card)| card.rank);
         if let Some((idx, &card)) = lowest_non_trump {
             debug(format!("Medium AI playing lowest non-trump: {}", card));
@@ -425,30 +1023,72 @@ impl AiStrategy for MediumStrategy {
         let trump_cards: Vec<(usize, Card)> = hand
             .iter()
             .enumerate()
-            .filter(|(_, card)| trump_suit != Some(card.suit))
+            .filter(|(_, card)| !card.is_trump(trump_suit))
             .map(|(idx, &card)| (idx, card))
             .collect();
-        if trump_cards.len() > 1 {
-            if let Some((idx, card)) = trump_cards
+        if trump_cards.len() > 1
+            && let Some((idx, card)) = trump_cards
                 .iter()
                 .min_by_key(|(_, c)| c.rank)
                 .map(|(i, c)| (*i, *c))
-            {
-                debug(format!(
-                    "Medium AI playing lowest trump (has multiple): {}",
-                    card
-                ));
-                return Some(vec![(idx, card)]);
-            }
+        {
+            debug(format!(
+                "Medium AI playing lowest trump (has multiple): {}",
+                card
+            ));
+            return Some(vec![(idx, card)]);
         }
-        // Last resort - play any card (lowest by rank)
-        if let Some((idx, &card)) = hand.iter().enumerate().min_by_key(|(_, c)| c.rank) {
+        // Last resort - play the least valuable card
+        if let Some((idx, &card)) = hand
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, c)| c.strategic_value(trump_suit))
+        {
             debug(format!("Medium AI playing lowest card: {}", card));
             return Some(vec![(idx, card)]);
         }
         // Should never reach here
         None
     }
+    /// Medium AI passes now and then with its lowest same-rank card, preferring non-trumps
+    fn make_pass_move(
+        &self,
+        game_state: &GameState,
+        player_idx: usize,
+        params: &AiParams,
+        rng: &AiRng,
+    ) -> Option<(usize, Card)> {
+        let hand = game_state.players()[player_idx].hand();
+        let trump_suit = game_state.trump_suit();
+        let (attacking_card, _) = game_state
+            .table_cards()
+            .iter()
+            .find(|(_, defense)| defense.is_none())?;
+        // Medium AI: chance to pass if possible
+        let possible_passes: Vec<(usize, Card)> = hand
+            .iter()
+            .enumerate()
+            .filter(|(_, card)| game_state.can_pass_with(card, attacking_card))
+            .map(|(idx, &card)| (idx, card))
+            .collect();
+        if !possible_passes.is_empty() {
+            let pass_chance = rng.roll();
+            if pass_chance < params.pass_chance {
+                // Choose the lowest pass card
+                let lowest_pass = possible_passes
+                    .iter()
+                    .min_by_key(|(_, card)| card.strategic_value(trump_suit));
+                if let Some(&(hand_idx, pass_card)) = lowest_pass {
+                    debug(format!(
+                        "Medium AI choosing to PASS with {} (same rank as {})",
+                        pass_card, attacking_card,
+                    ));
+                    return Some((hand_idx, pass_card));
+                }
+            }
+        }
+        None
+    }
     /// Medium AI strategy:
     /// 1. If the attack card is high value, might use a trump strategically
     /// 2. Otherwise, prefer non-trump defenses
@@ -456,66 +1096,39 @@ impl AiStrategy for MediumStrategy {
         &self,
         game_state: &GameState,
         player_idx: usize,
+        params: &AiParams,
+        rng: &AiRng,
     ) -> Option<Vec<(usize, Card)>> {
         let player = &game_state.players()[player_idx];
         let hand = player.hand();
-        let trump_suit = game_state
-            .trump_suit()
-            .expect("Trump suit required for defense");
+        let trump_suit = game_state.trump_suit();
         // Find the first undefended attack
-        if let Some((attacking_card, _)) = game_state
+        if let Some((attack_idx, (attacking_card, _))) = game_state
             .table_cards()
             .iter()
-            .find(|(_, defense)| defense.is_none())
+            .enumerate()
+            .find(|(_, (_, defense))| defense.is_none())
         {
-            // Medium AI: 30% chance to pass if possible
-            let possible_passes: Vec<(usize, Card)> = hand
-                .iter()
-                .enumerate()
-                .filter(|(_, card)| card.can_pass(attacking_card))
-                .map(|(idx, &card)| (idx, card))
-                .collect();
-            if !possible_passes.is_empty() {
-                let pass_chance = rand::random::<f32>();
-                if pass_chance < 0.3 {
-                    // Choose the lowest pass card
-                    let lowest_pass = possible_passes.iter().min_by_key(|(_, card)| {
-                        // Prefer non-trumps for passing
-                        if card.suit == trump_suit {
-                            100 + card.rank as u8
-                        } else {
-                            card.rank as u8
-                        }
-                    });
-                    if let Some(&(hand_idx, pass_card)) = lowest_pass {
-                        debug(format!(
-                            "Medium AI choosing to PASS with {} (same rank as {})",
-                            pass_card, attacking_card,
-                        ));
-                        return Some(vec![(hand_idx, pass_card)]);
-                    }
-                }
-            }
             // Find all valid defenses
-            let valid_defenses: Vec<(usize, Card)> = hand
-                .iter()
-                .enumerate()
-                .filter(|(_, card)| card.can_beat(attacking_card, trump_suit))
-                .map(|(idx, &card)| (idx, card))
+            let valid_defenses: Vec<(usize, Card)> = game_state
+                .legal_defenses(attack_idx)
+                .into_iter()
+                .map(|idx| (idx, hand[idx]))
                 .collect();
             if valid_defenses.is_empty() {
                 return None; // Cannot defend
             }
             // Determine if this is a high-value card that's worth using a trump on
-            let is_high_value = attacking_card.rank >= Rank::Jack
-                || (attacking_card.suit == trump_suit && attacking_card.rank >= Rank::Ten);
+            let is_high_value = attacking_card.rank >= high_rank(game_state, Rank::Jack)
+                || (attacking_card.is_trump(trump_suit)
+                    && attacking_card.rank >= high_rank(game_state, Rank::Ten));
             if is_high_value {
-                // For high-value attacks, might use a trump (70% chance)
-                let use_trump_strategically = rand::random::<f32>() < 0.7;
+                // For high-value attacks, might use a trump
+                let use_trump_strategically = rng.roll() < params.trump_on_high_chance;
                 if use_trump_strategically {
                     let trump_defenses: Vec<&(usize, Card)> = valid_defenses
                         .iter()
-                        .filter(|(_, card)| card.suit == trump_suit)
+                        .filter(|(_, card)| card.is_trump(trump_suit))
                         .collect();
                     if !trump_defenses.is_empty() {
                         // Use the lowest trump that can beat it
@@ -533,7 +1146,7 @@ impl AiStrategy for MediumStrategy {
             // Try to find a non-trump defense first
             let non_trump_defenses: Vec<&(usize, Card)> = valid_defenses
                 .iter()
-                .filter(|(_, card)| card.suit != trump_suit)
+                .filter(|(_, card)| !card.is_trump(trump_suit))
                 .collect();
             if !non_trump_defenses.is_empty() {
                 // Use the lowest non-trump defense
@@ -547,7 +1160,7 @@ impl AiStrategy for MediumStrategy {
             // If forced to use a trump, use the lowest one
             if let Some(&(idx, card)) = valid_defenses
                 .iter()
-                .filter(|(_, c)| c.suit == trump_suit)
+                .filter(|(_, c)| c.is_trump(trump_suit))
                 .min_by_key(|(_, c)| c.rank)
             {
                 debug(format!(
@@ -565,19 +1178,22 @@ impl AiStrategy for MediumStrategy {
 }
 
 impl AiStrategy for HardStrategy {
-    /// To calculate the cost-benefit of picking up the AI will evaluate the number of valuable cards 
+    /// To calculate the cost-benefit of picking up the AI will evaluate the number of valuable cards
     /// where it considers trump cards bigger than Jack to be valuable. In the future, I want to
     /// implement a more dynamic valueable calculation.  
-    fn should_take_cards(&self, game_state: &GameState, player_idx: usize) -> bool {
+    fn should_take_cards(
+        &self,
+        game_state: &GameState,
+        player_idx: usize,
+        _params: &AiParams,
+        _rng: &AiRng,
+    ) -> bool {
         // Hard AI makes a strategic decision weighing multiple factors
         let player = &game_state.players()[player_idx];
         let hand = player.hand();
-        let trump_suit = game_state
-            .trump_suit()
-            .expect("Trump suit required for defense decision");
+        let trump_suit = game_state.trump_suit();
         // Track played cards to better understand the game state
         let table_cards = game_state.table_cards();
-        let discard_pile = game_state.discard_pile();
         let deck_empty = game_state.deck().is_empty();
         // Find all undefended attacks
         let undefended_attacks: Vec<&Card> = table_cards
@@ -606,69 +1222,61 @@ impl AiStrategy for HardStrategy {
         }
         // Calculate the cost of defending vs. the benefit of picking up
         // 1. Evaluate defense cost: How many valuable cards would be spent?
-        let mut defense_plan: HashMap<usize, Card> = HashMap::new(); // Maps attack index -> defense card
+        let Some(defense_plan) = plan_full_defense(game_state, player_idx) else {
+            // No pairing of our cards beats every attack, must take
+            return true;
+        };
+        with_trace(|trace| trace.defense_plan = Some(defense_plan.clone()));
         let mut valuable_cards_used = 0;
         let mut high_trumps_used = 0;
-        // For each attack, find the optimal defense card
-        for (attack_idx, attack_card) in undefended_attacks.iter().enumerate() {
-            // Get all possible defenses for this attack
-            let possible_defenses: Vec<(usize, Card)> = hand
-                .iter()
-                .enumerate()
-                .filter(|(_idx, card)|
-                    // Skip cards already assigned to defend other attacks
-                    !defense_plan.values().any(|c| *c == **card) &&
-                    card.can_beat(attack_card, trump_suit))
-                .map(|(idx, &card)| (idx, card))
-                .collect();
-            if possible_defenses.is_empty() {
-                // If we can't defend with remaining cards, must take
-                return true;
-            }
-            // First try to find a non-trump defense
-            let non_trump_defense = possible_defenses
-                .iter()
-                .filter(|(_, card)|card.suit != trump_suit)
-                .min_by_key(|(_, card)| card.rank);
-            if let Some(&(_idx, card)) = non_trump_defense {
-                // Use this non-trump card
-                defense_plan.insert(attack_idx, card);
-                // Count valuable non-trump cards (Jack or higher)
-                if card.rank >= Rank::Jack {
-                    valuable_cards_used += 1;
-                }
-            } else {
-                // Must use a trump
-                let trump_defense = possible_defenses
-                    .iter()
-                    .filter(|(_, card)|card.suit == trump_suit)
-                    .min_by_key(|(_, card)| card.rank);
-                if let Some(&(_idx, card)) = trump_defense {
-                    defense_plan.insert(attack_idx, card);
-                    // Any trump is valuable, higher trumps are more so
-                    valuable_cards_used += 1;
-                    if card.rank >= Rank::Jack {
-                        high_trumps_used += 1;
-                    }
+        let high = high_rank(game_state, Rank::Jack);
+        for (_, card) in &defense_plan {
+            if card.is_trump(trump_suit) {
+                // Any trump is valuable, higher trumps are more so
+                valuable_cards_used += 1;
+                if card.rank >= high {
+                    high_trumps_used += 1;
                 }
+            } else if card.rank >= high {
+                // Count valuable non-trump cards (Jack or higher in a 36 card pack)
+                valuable_cards_used += 1;
             }
         }
+        with_trace(|trace| {
+            trace.notes.push(format!(
+                "valuable cards used: {}, high trumps used: {}",
+                valuable_cards_used, high_trumps_used
+            ))
+        });
         // 2. Endgame considerations
         let is_endgame = deck_empty || game_state.deck().size() <= 2;
         // In endgame, conserving high trumps is critical for winning
         if is_endgame && high_trumps_used > 0 {
-            // Count how many high trumps might still be unplayed
-            let high_trumps_played = discard_pile
-                .iter()
-                .filter(|card| card.suit == trump_suit && card.rank >= Rank::Jack)
-                .count();
+            // High trumps an opponent may still hold: not in our hand and either unseen
+            // or known to be picked up by someone else
+            let tracker = game_state.card_tracker();
+            let picked_up_by_other = |card: &Card| {
+                (0..game_state.players().len())
+                    .filter(|&idx| idx != player_idx)
+                    .any(|idx| tracker.known_hand(idx).any(|known| known == card))
+            };
+            let high_trumps_out = trump_suit.map_or(0, |trump| {
+                Rank::all()
+                    .into_iter()
+                    .filter(|&rank| rank >= high)
+                    .map(|rank| Card::new(trump, rank))
+                    .filter(|card| {
+                        !hand.contains(card) && (!tracker.seen(card) || picked_up_by_other(card))
+                    })
+                    .count()
+            });
             // If we'd use our last high trump, consider picking up instead
             let holding_last_high_trumps = high_trumps_used
                 >= hand
                     .iter()
-                    .filter(|card| card.suit == trump_suit && card.rank >= Rank::Jack)
+                    .filter(|card| card.is_trump(trump_suit) && card.rank >= high)
                     .count();
-            if holding_last_high_trumps && high_trumps_played < 4 {
+            if holding_last_high_trumps && high_trumps_out > 0 {
                 debug(format!(
                     "Hard AI ({}) preserving last high trumps in endgame",
                     player_idx
@@ -723,23 +1331,24 @@ impl AiStrategy for HardStrategy {
         ));
         false
     }
-    /// Hard AI has various strategies for making attacking moves. 
+    /// Hard AI has various strategies for making attacking moves.
     /// Plan A. If it is late into the game the AI will analyze the player's hand, discard pile, and
-    ///    table cards to determine the best attack move. 
+    ///    table cards to determine the best attack move.
     /// Plan B. If it is an inital attack during the endgame, the AI will try to prevent the opponent
     ///    from discarding cards. Otherwise, the AI will try to play the lowest-ranking card that can
-    ///    beat the attacker. 
+    ///    beat the attacker.
     /// A fallback strategy is also implemented in case the AI cannot find a good attack move.
     fn make_attack_move(
         &self,
         game_state: &GameState,
         player_idx: usize,
+        params: &AiParams,
+        rng: &AiRng,
     ) -> Option<Vec<(usize, Card)>> {
         let player = &game_state.players()[player_idx];
         let hand = player.hand();
         let trump_suit = game_state.trump_suit();
         let deck_empty = game_state.deck().is_empty();
-        let discard_pile = game_state.discard_pile();
         let table_cards = game_state.table_cards();
         // Get information about the defender
         let defender_idx = game_state.current_defender();
@@ -750,66 +1359,59 @@ impl AiStrategy for HardStrategy {
         }
         // ### Plan A: Not the first card attack ###
         if !table_cards.is_empty() {
-            // Get ranks of cards already on the table
-            let valid_ranks: HashSet<Rank> = table_cards
+            // Ranks already on the table and the cards that may join them
+            let valid_ranks = game_state.table_ranks();
+            let legal_attacks = game_state.legal_attacks(player_idx);
+            // Cards of each rank the defender could be holding: the tracker's unknown ones,
+            // less those on the table or in our hand that it hasn't seen yet
+            let tracker = game_state.card_tracker();
+            let unaccounted = |rank: Rank| {
+                let in_sight = table_cards
+                    .iter()
+                    .flat_map(|(attack, defense)| std::iter::once(attack).chain(defense))
+                    .chain(hand)
+                    .filter(|card| card.rank == rank && !tracker.seen(card))
+                    .count();
+                tracker.remaining_of_rank(rank).saturating_sub(in_sight)
+            };
+            let defender_used_trump = table_cards
                 .iter()
-                .flat_map(|(attack, defense)| {
-                    let mut ranks = vec![attack.rank];
-                    if let Some(def) = defense {
-                        ranks.push(def.rank);
-                    }
-                    ranks
+                .any(|(_, defense)| matches!(defense, Some(d) if d.is_trump(trump_suit)));
+            // Find weaknesses in defender's hand: table ranks with few cards left they could
+            // hold, and none they are known to have picked up
+            let mut probable_weak_ranks: Vec<Rank> = valid_ranks
+                .into_iter()
+                .filter(|&rank| {
+                    unaccounted(rank) <= 2
+                        && !tracker
+                            .known_hand(defender_idx)
+                            .any(|card| card.rank == rank)
                 })
                 .collect();
-            // Track played cards of each rank to guide our attack strategy
-            let mut rank_card_count: HashMap<Rank, usize> = HashMap::new();
-            // Count cards in discard pile by rank
-            for card in discard_pile {
-                *rank_card_count.entry(card.rank).or_insert(0) += 1;
-            }
-            // Count cards on table by rank
-            for (attack, defense) in table_cards {
-                *rank_card_count.entry(attack.rank).or_insert(0) += 1;
-                if let Some(def) = defense {
-                    *rank_card_count.entry(def.rank).or_insert(0) += 1;
-                }
-            }
-            let defender_used_trump = table_cards.iter().any(|(_, defense)| {
-                matches!((defense, trump_suit), (Some(d), Some(t)) if d.suit == t)
-            });
-            // Find weaknesses in defender's hand
-            let mut probable_weak_ranks: Vec<Rank> = Vec::new();
-            // Ranks where many cards are already out are likely weak points
-            for (rank, count) in &rank_card_count {
-                if valid_ranks.contains(rank) && *count >= 2 {
-                    // If defender has defended against this rank, but many cards of this rank
-                    // are already played, they likely have few or no more of this rank
-                    probable_weak_ranks.push(*rank);
-                }
-            }
+            probable_weak_ranks.sort();
+            with_trace(|trace| trace.probable_weak_ranks = probable_weak_ranks.clone());
             // Try adding cards of ranks that are likely weak points for defender
             if !probable_weak_ranks.is_empty() {
+                let high = high_rank(game_state, Rank::Jack);
                 let matching_cards: Vec<(usize, Card)> = hand
                     .iter()
                     .enumerate()
-                    .filter(|(_, card)| 
+                    .filter(|(idx, card)| {
+                        legal_attacks.contains(idx) &&
                         probable_weak_ranks.contains(&card.rank) &&
                         // Don't waste high trumps on additional attacks
-                        !(card.suit == trump_suit.unwrap_or(Suit::Spades) && card.rank >= Rank::Jack)
-                    )
+                        !(card.is_trump(trump_suit) && card.rank >= high)
+                    })
                     .map(|(idx, &card)| (idx, card))
                     .collect();
                 if !matching_cards.is_empty() {
                     // Choose lowest card from weak ranks
-                    if let Some(&(idx, card)) = matching_cards.iter().min_by_key(|(_, c)| {
-                        // Non-trumps first, then by rank
-                        if c.suit == trump_suit.unwrap_or(Suit::Spades) {
-                            100 + c.rank as u8
-                        } else {
-                            c.rank as u8
-                        }
-                    }) {
+                    if let Some(&(idx, card)) = matching_cards
+                        .iter()
+                        .min_by_key(|(_, c)| c.strategic_value(trump_suit))
+                    {
                         debug(format!("Hard AI exploiting weak rank with {}", card));
+                        note_reason(Some(AttackReason::WeakRank));
                         return Some(vec![(idx, card)]);
                     }
                 }
@@ -818,7 +1420,7 @@ impl AiStrategy for HardStrategy {
             let matching_cards: Vec<(usize, Card)> = hand
                 .iter()
                 .enumerate()
-                .filter(|(_, card)| valid_ranks.contains(&card.rank))
+                .filter(|(idx, _)| legal_attacks.contains(idx))
                 .map(|(idx, &card)| (idx, card))
                 .collect();
             if !matching_cards.is_empty() {
@@ -826,10 +1428,11 @@ impl AiStrategy for HardStrategy {
                 if defender_used_trump {
                     let matching_trumps: Vec<&(usize, Card)> = matching_cards
                         .iter()
-                        .filter(|(_, card)| trump_suit == Some(card.suit))
+                        .filter(|(_, card)| card.is_trump(trump_suit))
                         .collect();
-                    // Hard AI will strategically add trumps 70% of the time if defender used trumps
-                    let add_trump = !matching_trumps.is_empty() && rand::random::<f32>() < 0.7;
+                    // Hard AI will strategically add trumps if defender used trumps
+                    let add_trump =
+                        !matching_trumps.is_empty() && rng.roll() < params.add_trump_chance;
                     if add_trump {
                         // Use lowest matching trump
                         if let Some(&&(idx, card)) =
@@ -839,6 +1442,7 @@ impl AiStrategy for HardStrategy {
                                 "Hard AI strategically adding trump {} to pressure defender",
                                 card
                             ));
+                            note_reason(Some(AttackReason::DrainTrumps));
                             return Some(vec![(idx, card)]);
                         }
                     }
@@ -846,7 +1450,7 @@ impl AiStrategy for HardStrategy {
                 // Otherwise prefer non-trumps
                 let non_trump_matches: Vec<&(usize, Card)> = matching_cards
                     .iter()
-                    .filter(|(_, card)| trump_suit != Some(card.suit))
+                    .filter(|(_, card)| !card.is_trump(trump_suit))
                     .collect();
                 if !non_trump_matches.is_empty() {
                     // Choose the lowest non-trump match
@@ -870,8 +1474,12 @@ impl AiStrategy for HardStrategy {
             // or if there are already many cards on the table
             let easy_defense = table_cards.iter().all(|(_, defense)| defense.is_some());
             if easy_defense || table_cards.len() >= 3 {
-                let stop_chance = if easy_defense { 0.8 } else { 0.5 };
-                if rand::random::<f32>() < stop_chance {
+                let stop_chance = if easy_defense {
+                    params.easy_defense_stop_chance
+                } else {
+                    params.stop_adding_chance
+                };
+                if rng.roll() < stop_chance {
                     debug(format!(
                         "Hard AI strategically stops adding cards (easy defense: {})",
                         easy_defense
@@ -889,11 +1497,12 @@ impl AiStrategy for HardStrategy {
             // If defender has few cards, try to prevent them from discarding
             if defender_hand_size <= 2 {
                 // Check if we have high cards or trumps that might force pickup
+                let high_trump = high_rank(game_state, Rank::Ten);
                 let forcing_cards: Vec<(usize, Card)> = hand
                     .iter()
                     .enumerate()
                     .filter(|(_, card)| {
-                        (card.suit == trump_suit.unwrap_or(Suit::Spades) && card.rank >= Rank::Ten)
+                        (card.is_trump(trump_suit) && card.rank >= high_trump)
                             || card.rank >= Rank::Ace
                     })
                     .map(|(idx, &card)| (idx, card))
@@ -902,184 +1511,140 @@ impl AiStrategy for HardStrategy {
                     // Use a threatening card to prevent easy discard
                     if let Some(&(idx, card)) = forcing_cards.iter().min_by_key(|(_, c)| c.rank) {
                         debug(format!("Hard AI playing forcing card {} in endgame", card));
+                        note_reason(Some(AttackReason::Forcing));
                         return Some(vec![(idx, card)]);
                     }
                 }
             }
         }
-        // Check for duplicate ranks (pairs) for strategic play
-        let mut rank_counts: HashMap<Rank, Vec<(usize, Card)>> = HashMap::new();
-        for (idx, card) in hand.iter().enumerate() {
-            rank_counts.entry(card.rank).or_default().push((idx, *card));
-        }
-        // Hard AI prefers to lead with cards where it has multiple of the same rank
-        let pairs: Vec<(Rank, &Vec<(usize, Card)>)> = rank_counts
+        // Lead with the cheapest card, or a pair or an exhausted rank worth more
+        let (idx, card) = opening_attack(game_state, player_idx, params)?;
+        debug(format!("Hard AI opening with {}", card));
+        Some(vec![(idx, card)])
+    }
+    /// Hard AI passes often, but not with high trumps or aces unless it has nothing else to pass
+    /// with. It always passes when no card in hand can beat the attack.
+    fn make_pass_move(
+        &self,
+        game_state: &GameState,
+        player_idx: usize,
+        params: &AiParams,
+        rng: &AiRng,
+    ) -> Option<(usize, Card)> {
+        let hand = game_state.players()[player_idx].hand();
+        let trump_suit = game_state.trump_suit();
+        let (attack_card, _) = game_state
+            .table_cards()
+            .iter()
+            .find(|(_, defense)| defense.is_none())?;
+        let possible_passes: Vec<(usize, Card)> = hand
             .iter()
-            .filter(|(_, cards)| cards.len() >= 2)
-            .map(|(rank, cards)| (*rank, cards))
+            .enumerate()
+            .filter(|(_, card)| game_state.can_pass_with(card, attack_card))
+            .map(|(idx, &card)| (idx, card))
             .collect();
-        if !pairs.is_empty() {
-            // Use the lowest pair that's not high trumps
-            let non_high_trump_pairs: Vec<(Rank, &Vec<(usize, Card)>)> = pairs
+        // Hard AI is aggressive with passing if available
+        // but won't pass high trumps or valuable cards
+        if !possible_passes.is_empty() {
+            // Filter out valuable cards to avoid passing them
+            let high = high_rank(game_state, Rank::Jack);
+            let safe_passes: Vec<(usize, Card)> = possible_passes
                 .iter()
-                .filter(|(_, cards)| {
-                    !cards.iter().all(|(_, c)| {
-                        c.suit == trump_suit.unwrap_or(Suit::Spades) && c.rank >= Rank::Jack
-                    })
-                })
-                .map(|(r, c)| (*r, *c))
+                .filter(|(_, card)|
+                    // Don't pass high trumps or aces
+                    !(card.is_trump(trump_suit) && card.rank >= high) &&
+                    card.rank != Rank::Ace)
+                .map(|&(idx, card)| (idx, card))
                 .collect();
-            if !non_high_trump_pairs.is_empty() {
-                if let Some((_, cards)) = non_high_trump_pairs.iter().min_by_key(|(rank, _)| *rank)
-                {
-                    // Find a non-trump from this pair if possible
-                    let non_trump = cards
-                        .iter()
-                        .filter(|(_, c)| trump_suit != Some(c.suit))
-                        .min_by_key(|(_, c)| c.rank);
-
-                    if let Some(&(idx, card)) = non_trump {
-                        debug(format!("Hard AI playing from pair: {}", card));
-                        return Some(vec![(idx, card)]);
-                    } else {
-                        // Use lowest card from the pair
-                        let lowest = cards.iter().min_by_key(|(_, c)| c.rank);
-                        if let Some(&(idx, card)) = lowest {
-                            debug(format!("Hard AI playing from pair: {}", card));
-                            return Some(vec![(idx, card)]);
-                        }
-                    }
+            if !safe_passes.is_empty() && rng.roll() < params.pass_chance {
+                // Choose the best pass card - prefer non-trumps
+                let best_pass = safe_passes
+                    .iter()
+                    .min_by_key(|(_, card)| card.strategic_value(trump_suit));
+                if let Some(&(hand_idx, pass_card)) = best_pass {
+                    debug(format!(
+                        "Hard AI strategically passing with {} (same rank as {})",
+                        pass_card, attack_card
+                    ));
+                    return Some((hand_idx, pass_card));
                 }
             }
-        }
-        // Regular strategy - play lowest non-trump probing card
-        let non_trumps: Vec<(usize, Card)> = hand
-            .iter()
-            .enumerate()
-            .filter(|(_, card)| trump_suit != Some(card.suit))
-            .map(|(idx, &card)| (idx, card))
-            .collect();
-        if !non_trumps.is_empty() {
-            // Use lowest non-trump
-            if let Some(&(idx, card)) = non_trumps.iter().min_by_key(|(_, c)| c.rank) {
-                debug(format!("Hard AI playing lowest non-trump: {}", card));
-                return Some(vec![(idx, card)]);
+            // In emergency (no other defense), pass with anything
+            if !possible_passes.is_empty() && safe_passes.is_empty() {
+                // Find the lowest value pass card
+                if let Some(&(hand_idx, pass_card)) =
+                    possible_passes.iter().min_by_key(|(_, card)| card.rank)
+                {
+                    debug(format!(
+                        "Hard AI forced to pass with {} as last resort",
+                        pass_card
+                    ));
+                    return Some((hand_idx, pass_card));
+                }
             }
         }
-        // If only trumps are available, use lowest one
-        if let Some((idx, &card)) = hand
+        // If we have no defense cards but do have pass cards, force a pass
+        let can_defend = hand
             .iter()
-            .enumerate()
-            .filter(|(_, c)| trump_suit == Some(c.suit))
-            .min_by_key(|(_, c)| c.rank)
-        {
-            debug(format!("Hard AI playing lowest trump: {}", card));
-            return Some(vec![(idx, card)]);
+            .any(|card| card.can_beat(attack_card, trump_suit));
+        if !can_defend && let Some(&(hand_idx, pass_card)) = possible_passes.first() {
+            debug(format!(
+                "Hard AI forced to pass with {} (no other defense)",
+                pass_card
+            ));
+            return Some((hand_idx, pass_card));
         }
-        // Fallback - play any card
-        let (idx, &card) = hand.iter().enumerate().min_by_key(|(_, c)| c.rank).unwrap(); // Safe because we checked for empty hand
-        debug(format!("Hard AI playing lowest card: {}", card));
-        Some(vec![(idx, card)])
+        None
+    }
+    /// Plays the same pairing `should_take_cards` weighed up, so the decision to defend
+    /// and the cards it defends with always agree.
+    fn make_full_defense(
+        &self,
+        game_state: &GameState,
+        player_idx: usize,
+        _params: &AiParams,
+        _rng: &AiRng,
+    ) -> Option<Vec<(usize, Card)>> {
+        plan_full_defense(game_state, player_idx)
     }
-    /// First the AI considers passing with 60% probability because don't want to pass trumps or
-    /// value cards, then AI tries to find the lowest card it can beat the attacker with. If it is
-    /// a trump suit, it will try to find the lowest trump that can beat the attacker.
+    /// The AI tries to find the lowest card it can beat the attacker with. If it is a trump
+    /// suit, it will try to find the lowest trump that can beat the attacker.
     fn make_defense_move(
         &self,
         game_state: &GameState,
         player_idx: usize,
+        _params: &AiParams,
+        _rng: &AiRng,
     ) -> Option<Vec<(usize, Card)>> {
         let player = &game_state.players()[player_idx];
         let hand = player.hand();
-        let trump_suit = game_state
-            .trump_suit()
-            .expect("Trump suit required for defense");
+        let trump_suit = game_state.trump_suit();
         // These variables are used by hard AI for tracking
         let _table_cards = game_state.table_cards();
         let _discard_pile = game_state.discard_pile();
         let is_endgame = game_state.deck().is_empty();
         // Find the first undefended attack
-        if let Some((_attack_idx, attack_card)) = game_state
+        if let Some((attack_idx, attack_card)) = game_state
             .table_cards()
             .iter()
             .enumerate()
             .find(|(_, (_, defense))| defense.is_none())
             .map(|(idx, (attack, _))| (idx, attack))
         {
-            // Hard AI first considers passing as a strategic option
-            let possible_passes: Vec<(usize, Card)> = hand
-                .iter()
-                .enumerate()
-                .filter(|(_, card)| card.can_pass(attack_card))
-                .map(|(idx, &card)| (idx, card))
-                .collect();
-            // Hard AI is aggressive with passing (60% chance if available)
-            // but won't pass high trumps or valuable cards
-            if !possible_passes.is_empty() {
-                // Filter out valuable cards to avoid passing them
-                let safe_passes: Vec<(usize, Card)> = possible_passes
-                    .iter()
-                    .filter(|(_, card)|
-                        // Don't pass high trumps or aces
-                        !(card.suit == trump_suit && card.rank >= Rank::Jack) &&
-                        card.rank != Rank::Ace)
-                    .map(|&(idx, card)| (idx, card))
-                    .collect();
-                if !safe_passes.is_empty() && rand::random::<f32>() < 0.6 {
-                    // Choose the best pass card - prefer non-trumps
-                    let best_pass = safe_passes.iter().min_by_key(|(_, card)| {
-                        if card.suit == trump_suit {
-                            100 + card.rank as u8
-                        } else {
-                            card.rank as u8
-                        }
-                    });
-                    if let Some(&(hand_idx, pass_card)) = best_pass {
-                        debug(format!(
-                            "Hard AI strategically passing with {} (same rank as {})",
-                            pass_card, attack_card
-                        ));
-                        return Some(vec![(hand_idx, pass_card)]);
-                    }
-                }
-                // In emergency (no other defense), pass with anything
-                if !possible_passes.is_empty() && safe_passes.is_empty() {
-                    // Find the lowest value pass card
-                    if let Some(&(hand_idx, pass_card)) =
-                        possible_passes.iter().min_by_key(|(_, card)| card.rank)
-                    {
-                        debug(format!(
-                            "Hard AI forced to pass with {} as last resort",
-                            pass_card
-                        ));
-                        return Some(vec![(hand_idx, pass_card)]);
-                    }
-                }
-            }
             // Find all cards that can beat this attack
-            let valid_defenses: Vec<(usize, Card)> = hand
-                .iter()
-                .enumerate()
-                .filter(|(_, card)|card.can_beat(attack_card, trump_suit))
-                .map(|(idx, &card)| (idx, card))
+            let valid_defenses: Vec<(usize, Card)> = game_state
+                .legal_defenses(attack_idx)
+                .into_iter()
+                .map(|idx| (idx, hand[idx]))
                 .collect();
             if valid_defenses.is_empty() {
-                // If we have no defense cards but do have pass cards, force a pass
-                if !possible_passes.is_empty() {
-                    let (hand_idx, pass_card) = possible_passes[0];
-                    debug(format!(
-                        "Hard AI forced to pass with {} (no other defense)",
-                        pass_card
-                    ));
-                    return Some(vec![(hand_idx, pass_card)]);
-                }
                 return None; // Can't defend at all
             }
             // Hard AI strategy: Use the absolute lowest card that can beat the attack
             // First, try to use a non-trump defense if possible
             let non_trump_defenses: Vec<&(usize, Card)> = valid_defenses
                 .iter()
-                .filter(|(_, card)|card.suit != trump_suit)
+                .filter(|(_, card)| !card.is_trump(trump_suit))
                 .collect();
             if !non_trump_defenses.is_empty() {
                 // Use the lowest non-trump that beats it
@@ -1093,19 +1658,23 @@ impl AiStrategy for HardStrategy {
             // If forced to use a trump, use the lowest possible one
             let trump_defenses: Vec<&(usize, Card)> = valid_defenses
                 .iter()
-                .filter(|(_, card)| card.suit == trump_suit)
+                .filter(|(_, card)| card.is_trump(trump_suit))
                 .collect();
             if !trump_defenses.is_empty() {
                 // In endgame, think hard about using high trumps
                 if is_endgame {
-                    let is_high_value_attack = attack_card.rank >= Rank::Queen
-                        || (attack_card.suit == trump_suit && attack_card.rank >= Rank::Ten);
+                    let is_high_value_attack = attack_card.rank
+                        >= high_rank(game_state, Rank::Queen)
+                        || (attack_card.is_trump(trump_suit)
+                            && attack_card.rank >= high_rank(game_state, Rank::Ten));
                     // Only use high trumps against high-value cards in endgame
                     if !is_high_value_attack {
-                        // Find the lowest trump that's not too valuable (less than Jack)
+                        // Find the lowest trump that's not too valuable (less than Jack
+                        // in a 36 card pack)
+                        let high = high_rank(game_state, Rank::Jack);
                         let low_trump_defense = trump_defenses
                             .iter()
-                            .filter(|(_, card)| card.rank < Rank::Jack)
+                            .filter(|(_, card)| card.rank < high)
                             .min_by_key(|(_, card)| card.rank);
 
                         if let Some(&&(hand_idx, card)) = low_trump_defense {
@@ -1131,38 +1700,242 @@ impl AiStrategy for HardStrategy {
     }
 }
 
+impl AiStrategy for BaselineStrategy {
+    /// Takes only when no combination of cards beats every open attack
+    fn should_take_cards(
+        &self,
+        game_state: &GameState,
+        player_idx: usize,
+        _params: &AiParams,
+        _rng: &AiRng,
+    ) -> bool {
+        plan_full_defense(game_state, player_idx).is_none()
+    }
+    /// Opens with the least valuable card, then throws in the least valuable card matching
+    /// the table while it can
+    fn make_attack_move(
+        &self,
+        game_state: &GameState,
+        player_idx: usize,
+        _params: &AiParams,
+        _rng: &AiRng,
+    ) -> Option<Vec<(usize, Card)>> {
+        let trump_suit = game_state.trump_suit();
+        let hand = game_state.players()[player_idx].hand();
+        game_state
+            .legal_attacks(player_idx)
+            .into_iter()
+            .min_by_key(|&idx| hand[idx].strategic_value(trump_suit))
+            .map(|idx| vec![(idx, hand[idx])])
+    }
+    /// Beats the first open attack with the least valuable card that can
+    fn make_defense_move(
+        &self,
+        game_state: &GameState,
+        player_idx: usize,
+        _params: &AiParams,
+        _rng: &AiRng,
+    ) -> Option<Vec<(usize, Card)>> {
+        let trump_suit = game_state.trump_suit();
+        let attack_idx = game_state
+            .table_cards()
+            .iter()
+            .position(|(_, defense)| defense.is_none())?;
+        let hand = game_state.players()[player_idx].hand();
+        game_state
+            .legal_defenses(attack_idx)
+            .into_iter()
+            .min_by_key(|&idx| hand[idx].strategic_value(trump_suit))
+            .map(|idx| vec![(idx, hand[idx])])
+    }
+}
+
 // Update AiPlayer to use strategy pattern
 pub struct AiPlayer {
     strategy: Box<dyn AiStrategy>,
+    params: AiParams,
+    rng: AiRng,
 }
 
 impl AiPlayer {
     pub fn new(difficulty: AiDifficulty) -> Self {
+        Self::with_params(difficulty, AiParams::for_difficulty(difficulty))
+    }
+
+    /// Create an AI with custom randomness instead of the difficulty defaults
+    pub fn with_params(difficulty: AiDifficulty, params: AiParams) -> Self {
         let strategy: Box<dyn AiStrategy> = match difficulty {
             AiDifficulty::Easy => Box::new(EasyStrategy),
             AiDifficulty::Medium => Box::new(MediumStrategy),
             AiDifficulty::Hard => Box::new(HardStrategy),
+            AiDifficulty::Dynamic => Box::new(AdaptiveStrategy::new(AiDifficulty::Medium)),
         };
-        Self { strategy }
+        Self {
+            strategy,
+            params,
+            rng: AiRng::default(),
+        }
+    }
+
+    /// Create an AI whose chance rolls come from its own generator seeded with `seed`,
+    /// so it makes the same moves from the same positions every run
+    pub fn new_seeded(difficulty: AiDifficulty, seed: u64) -> Self {
+        Self {
+            rng: AiRng::seeded(seed),
+            ..Self::new(difficulty)
+        }
+    }
+
+    /// The deterministic baseline opponent, for reproducible games in tests
+    pub fn baseline() -> Self {
+        Self {
+            strategy: Box::new(BaselineStrategy),
+            params: AiParams::for_difficulty(AiDifficulty::Easy),
+            rng: AiRng::default(),
+        }
+    }
+
+    /// Create a Dynamic AI for the next game, playing at the level the human's record calls for
+    pub fn new_adaptive(record: &MatchRecord) -> Self {
+        let difficulty = record.difficulty();
+        Self {
+            strategy: Box::new(AdaptiveStrategy::new(difficulty)),
+            params: AiParams::for_difficulty(difficulty),
+            rng: AiRng::default(),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn params(&self) -> &AiParams {
+        &self.params
     }
 
+    /// Starts a new trace, the pass and defense that follow add to it
     pub fn should_take_cards(&self, game_state: &GameState, player_idx: usize) -> bool {
-        self.strategy.should_take_cards(game_state, player_idx)
+        begin_trace(player_idx, "defend");
+        let take = self
+            .strategy
+            .should_take_cards(game_state, player_idx, &self.params, &self.rng);
+        with_trace(|trace| trace.notes.push(format!("take: {}", take)));
+        if take {
+            comment(
+                game_state,
+                AiComment::Take {
+                    player_idx,
+                    could_not_beat: false,
+                },
+            );
+        }
+        take
     }
 
+    /// Cards to attack or throw in with, never more than the round has room for
     pub fn make_attack_move(
         &self,
         game_state: &GameState,
         player_idx: usize,
     ) -> Option<Vec<(usize, Card)>> {
-        self.strategy.make_attack_move(game_state, player_idx)
+        begin_trace(player_idx, "attack");
+        note_reason(None);
+        let mut cards =
+            self.strategy
+                .make_attack_move(game_state, player_idx, &self.params, &self.rng)?;
+        cards.truncate(game_state.attack_room());
+        with_trace(|trace| {
+            let played: Vec<String> = cards.iter().map(|(_, card)| card.to_string()).collect();
+            trace.notes.push(format!("plays: [{}]", played.join(" ")))
+        });
+        let adding = !game_state.table_cards().is_empty();
+        let reason = take_reason();
+        if !cards.is_empty() {
+            let cards = cards.iter().map(|&(_, card)| card).collect();
+            comment(
+                game_state,
+                AiComment::Attack {
+                    player_idx,
+                    cards,
+                    adding,
+                    reason,
+                },
+            );
+        } else if adding {
+            comment(game_state, AiComment::StopAdding { player_idx });
+        }
+        Some(cards)
     }
 
+    /// The defense for the first open attack alone, play goes through `make_full_defense`
+    #[allow(dead_code)]
     pub fn make_defense_move(
         &self,
         game_state: &GameState,
         player_idx: usize,
     ) -> Option<Vec<(usize, Card)>> {
-        self.strategy.make_defense_move(game_state, player_idx)
+        self.strategy
+            .make_defense_move(game_state, player_idx, &self.params, &self.rng)
+    }
+
+    /// Cards that beat every open attack together, as (table index, card) pairs
+    pub fn make_full_defense(
+        &self,
+        game_state: &GameState,
+        player_idx: usize,
+    ) -> Option<Vec<(usize, Card)>> {
+        let plan = self
+            .strategy
+            .make_full_defense(game_state, player_idx, &self.params, &self.rng);
+        with_trace(|trace| trace.defense_plan = plan.clone());
+        let Some(plan) = plan else {
+            comment(
+                game_state,
+                AiComment::Take {
+                    player_idx,
+                    could_not_beat: true,
+                },
+            );
+            return None;
+        };
+        let trump_suit = game_state.trump_suit();
+        let pairs: Vec<(Card, Card)> = plan
+            .iter()
+            .filter_map(|&(attack_idx, card)| {
+                let (attack, _) = game_state.table_cards().get(attack_idx)?;
+                Some((*attack, card))
+            })
+            .collect();
+        let used_trump = pairs.iter().any(|(_, card)| card.is_trump(trump_suit));
+        let holds_trump = game_state.players()[player_idx]
+            .hand()
+            .iter()
+            .any(|card| card.is_trump(trump_suit));
+        comment(
+            game_state,
+            AiComment::Defend {
+                player_idx,
+                pairs,
+                used_trump,
+                saved_trumps: !used_trump && holds_trump,
+            },
+        );
+        Some(plan)
+    }
+
+    /// A card to pass the first open attack with, if the AI wants to pass rather than defend
+    pub fn make_pass_move(
+        &self,
+        game_state: &GameState,
+        player_idx: usize,
+    ) -> Option<(usize, Card)> {
+        if !game_state.pass_open() || !game_state.pass_fits(1) {
+            return None;
+        }
+        let pass = self
+            .strategy
+            .make_pass_move(game_state, player_idx, &self.params, &self.rng);
+        if let Some((_, card)) = pass {
+            with_trace(|trace| trace.notes.push(format!("passes with {}", card)));
+            comment(game_state, AiComment::Pass { player_idx, card });
+        }
+        pass
     }
 }
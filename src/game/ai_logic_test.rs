@@ -1,10 +1,15 @@
 #[cfg(test)]
 mod tests {
-    use crate::game::ai::{AiDifficulty, AiPlayer};
+    use crate::game::ai::{
+        AiComment, AiDifficulty, AiParams, AiPlayer, AttackReason, CardTracker, MatchRecord,
+        ai_commentary, last_decision_trace, record_commentary, trace_decisions,
+    };
     use crate::game::card::{Card, Rank, Suit};
-    use crate::game::deck::Deck;
-    use crate::game::game_state::{GamePhase, GameState};
-    use crate::game::player::{Player, PlayerType};
+    use crate::game::deck::{Deck, DeckSize};
+    use crate::game::error::GameError;
+    use crate::game::game_state::{GamePhase, GameState, TrumpRule};
+    use crate::game::history::MoveHistory;
+    use crate::game::player::{HandSortMode, Player, PlayerType};
 
     // Helper function to create a game state for testing
     fn create_test_game_state(
@@ -12,17 +17,19 @@ mod tests {
         table_cards: Vec<(Card, Option<Card>)>,
         trump_suit: Suit,
     ) -> GameState {
-        let game_state = GameState {
+        GameState {
             players: vec![
                 Player {
                     name: "AI".to_string(),
                     player_type: PlayerType::Computer,
                     hand: ai_hand,
+                    sort_mode: HandSortMode::Suit,
                 },
                 Player {
                     name: "Human".to_string(),
                     player_type: PlayerType::Human,
                     hand: vec![],
+                    sort_mode: HandSortMode::Suit,
                 },
             ],
             deck: Deck {
@@ -36,9 +43,41 @@ mod tests {
             trump_suit: Some(trump_suit),
             game_phase: GamePhase::Defense,
             winner: None,
-            stuck_counter: 0,
-        };
-        game_state
+            finished: Vec::new(),
+            durak: None,
+            trump_rule: TrumpRule::BottomCard,
+            seed: None,
+            allow_trump_reveal_pass: false,
+            forbid_trump_pass: false,
+            jokers: false,
+            deck_count: 1,
+            pack_size: DeckSize::Standard,
+            allow_mulligan: false,
+            mulligan_used: false,
+            endgame_notice: false,
+            round_defender_hand: None,
+            flat_attack_limit: false,
+            round: 0,
+            last_round_summary: None,
+            thrower: None,
+            last_actions: vec![],
+            card_tracker: CardTracker::default(),
+            history: MoveHistory::default(),
+        }
+    }
+
+    /// Seats the AI as the attacker against a Human defender holding `defender_hand`,
+    /// for tests of throwing in
+    fn seat_ai_as_attacker(game_state: &mut GameState, defender_hand: Vec<Card>) {
+        game_state.current_attacker = 0;
+        game_state.current_defender = 1;
+        game_state.players[1].hand = defender_hand;
+    }
+
+    /// Puts `cards` on the discard pile the way a defended round does
+    fn discard(game_state: &mut GameState, cards: Vec<Card>) {
+        game_state.card_tracker.record_discard(&cards);
+        game_state.discard_pile.extend(cards);
     }
 
     #[test]
@@ -55,6 +94,40 @@ mod tests {
         assert!(ai.should_take_cards(&game_state, 0));
     }
 
+    #[test]
+    /// Same scenario as above, written in notation
+    fn test_easy_should_take_cards_from_notation() {
+        let ai = AiPlayer::new(AiDifficulty::Easy);
+        let game_state = GameState::from_notation(
+            "AI: 7H | Human*: | Table: 6H-_ 9H-_ | Trump: S | Attacker: Human | Defender: AI",
+        )
+        .unwrap();
+
+        assert!(ai.should_take_cards(&game_state, 0));
+    }
+
+    #[test]
+    /// Test that the take chance comes from the AI's params rather than a fixed value
+    fn test_easy_take_chance_from_params() {
+        let table_cards = vec![(Card::new(Suit::Hearts, Rank::Six), None)];
+        let ai_hand = vec![Card::new(Suit::Hearts, Rank::Ace)];
+        let game_state = create_test_game_state(ai_hand, table_cards, Suit::Spades);
+
+        let always_take = AiParams {
+            take_chance: 1.0,
+            ..AiParams::for_difficulty(AiDifficulty::Easy)
+        };
+        let ai = AiPlayer::with_params(AiDifficulty::Easy, always_take);
+        assert!(ai.should_take_cards(&game_state, 0));
+
+        let never_take = AiParams {
+            take_chance: 0.0,
+            ..AiParams::for_difficulty(AiDifficulty::Easy)
+        };
+        let ai = AiPlayer::with_params(AiDifficulty::Easy, never_take);
+        assert!(!ai.should_take_cards(&game_state, 0));
+    }
+
     #[test]
     /// Test that the Easy AI can make an attack move with the lowest-ranking card and save the trump
     fn test_easy_make_attack_move_initial() {
@@ -70,6 +143,29 @@ mod tests {
         assert_eq!(attack_move[0].1, Card::new(Suit::Hearts, Rank::Seven));
     }
 
+    #[test]
+    /// Test that with a 52 card deck every difficulty leads with a two, before a six or a trump
+    fn test_attack_move_with_full_deck() {
+        let ai_hand = vec![
+            Card::new(Suit::Hearts, Rank::Six),
+            Card::new(Suit::Clubs, Rank::Two),
+            Card::new(Suit::Spades, Rank::Ace), // Trump
+        ];
+        let mut game_state = create_test_game_state(ai_hand, vec![], Suit::Spades);
+        game_state.pack_size = DeckSize::Full;
+        for difficulty in [AiDifficulty::Easy, AiDifficulty::Medium, AiDifficulty::Hard] {
+            let attack_move = AiPlayer::new(difficulty)
+                .make_attack_move(&game_state, 0)
+                .unwrap();
+            assert_eq!(
+                attack_move[0].1,
+                Card::new(Suit::Clubs, Rank::Two),
+                "{}",
+                difficulty
+            );
+        }
+    }
+
     #[test]
     /// Test that the Easy AI recognizes that it can "pass" with the same card
     fn test_easy_make_attack_move_add_to_attack() {
@@ -79,7 +175,8 @@ mod tests {
             Card::new(Suit::Diamonds, Rank::Ten),
         ];
         let table_cards = vec![(Card::new(Suit::Hearts, Rank::Ten), None)];
-        let game_state = create_test_game_state(ai_hand, table_cards, Suit::Spades);
+        let mut game_state = create_test_game_state(ai_hand, table_cards, Suit::Spades);
+        seat_ai_as_attacker(&mut game_state, vec![Card::new(Suit::Clubs, Rank::Six); 3]);
 
         let attack_move = ai.make_attack_move(&game_state, 0).unwrap();
         assert_eq!(attack_move.len(), 1);
@@ -128,6 +225,24 @@ mod tests {
         assert!(ai.should_take_cards(&game_state, 0));
     }
 
+    #[test]
+    /// Test that in a 20 card pack a Jack is one of the lowest trumps and not worth saving,
+    /// while a Queen is
+    fn test_medium_high_trumps_in_short_deck() {
+        let ai = AiPlayer::new(AiDifficulty::Medium);
+        let ai_hand = vec![
+            Card::new(Suit::Spades, Rank::Jack), // Trump
+            Card::new(Suit::Hearts, Rank::Queen),
+        ];
+        let table_cards = vec![(Card::new(Suit::Diamonds, Rank::King), None)];
+        let mut game_state = create_test_game_state(ai_hand, table_cards, Suit::Spades);
+        assert!(ai.should_take_cards(&game_state, 0));
+        game_state.pack_size = DeckSize::Short;
+        assert!(!ai.should_take_cards(&game_state, 0));
+        game_state.players[0].hand[0] = Card::new(Suit::Spades, Rank::Queen);
+        assert!(ai.should_take_cards(&game_state, 0));
+    }
+
     #[test]
     /// Test that the Medium AI can make an attack move with the lowest-ranking card and save the trump
     fn test_medium_make_attack_move_initial() {
@@ -171,12 +286,411 @@ mod tests {
         ];
         let table_cards = vec![(Card::new(Suit::Hearts, Rank::Ten), None)];
         let mut game_state = create_test_game_state(ai_hand, table_cards, Suit::Spades);
-        game_state
-            .discard_pile
-            .push(Card::new(Suit::Clubs, Rank::Ten));
+        seat_ai_as_attacker(&mut game_state, vec![Card::new(Suit::Clubs, Rank::Six); 3]);
+        discard(&mut game_state, vec![Card::new(Suit::Clubs, Rank::Ten)]);
 
         let attack_move = ai.make_attack_move(&game_state, 0).unwrap();
         assert_eq!(attack_move.len(), 1);
         assert_eq!(attack_move[0].1, Card::new(Suit::Diamonds, Rank::Ten));
     }
+
+    #[test]
+    /// Test that a rank the defender picked up earlier is no longer taken for a weak one,
+    /// however few of it are left unseen
+    fn test_hard_weak_rank_counts_picked_up_cards() {
+        let ai = AiPlayer::new(AiDifficulty::Hard);
+        let mut game_state = create_test_game_state(
+            vec![
+                Card::new(Suit::Hearts, Rank::Seven),
+                Card::new(Suit::Diamonds, Rank::Ten),
+            ],
+            vec![(
+                Card::new(Suit::Hearts, Rank::Ten),
+                Some(Card::new(Suit::Hearts, Rank::Queen)),
+            )],
+            Suit::Spades,
+        );
+        seat_ai_as_attacker(&mut game_state, vec![Card::new(Suit::Clubs, Rank::Six); 3]);
+        game_state.game_phase = GamePhase::Attack;
+        discard(&mut game_state, vec![Card::new(Suit::Clubs, Rank::Ten)]);
+        trace_decisions(true);
+        ai.make_attack_move(&game_state, 0);
+        assert_eq!(
+            last_decision_trace().unwrap().probable_weak_ranks,
+            vec![Rank::Ten]
+        );
+
+        game_state
+            .card_tracker
+            .record_pick_up(1, &[Card::new(Suit::Spades, Rank::Ten)]);
+        assert!(
+            game_state
+                .card_tracker()
+                .seen(&Card::new(Suit::Spades, Rank::Ten))
+        );
+        assert_eq!(game_state.card_tracker().remaining_of_rank(Rank::Ten), 2);
+        ai.make_attack_move(&game_state, 0);
+        assert!(
+            last_decision_trace()
+                .unwrap()
+                .probable_weak_ranks
+                .is_empty()
+        );
+        trace_decisions(false);
+    }
+
+    #[test]
+    /// Test that the Hard AI opens with a pair over a slightly lower single card, but not
+    /// over a much lower one, and never spends a trump while it holds plain cards
+    fn test_hard_opening_prefers_pair() {
+        let ai = AiPlayer::new(AiDifficulty::Hard);
+        let ai_hand = vec![
+            Card::new(Suit::Hearts, Rank::Seven),
+            Card::new(Suit::Clubs, Rank::Nine),
+            Card::new(Suit::Spades, Rank::Six), // Low trump
+            Card::new(Suit::Diamonds, Rank::Nine),
+        ];
+        let game_state = create_test_game_state(ai_hand, vec![], Suit::Spades);
+        let attack_move = ai.make_attack_move(&game_state, 0).unwrap();
+        assert_eq!(attack_move, vec![(1, Card::new(Suit::Clubs, Rank::Nine))]);
+        // Without the pair bonus the lowest plain card leads as before
+        let no_book = AiPlayer::with_params(
+            AiDifficulty::Hard,
+            AiParams {
+                pair_lead_bonus: 0,
+                ..AiParams::for_difficulty(AiDifficulty::Hard)
+            },
+        );
+        let attack_move = no_book.make_attack_move(&game_state, 0).unwrap();
+        assert_eq!(attack_move[0].1, Card::new(Suit::Hearts, Rank::Seven));
+    }
+
+    #[test]
+    /// Test that a pair of a rank the defender can't hold any more is worth leading even
+    /// when the pair alone isn't
+    fn test_hard_opening_weighs_exhausted_rank() {
+        let ai = AiPlayer::new(AiDifficulty::Hard);
+        let ai_hand = vec![
+            Card::new(Suit::Hearts, Rank::Seven),
+            Card::new(Suit::Clubs, Rank::Queen),
+            Card::new(Suit::Diamonds, Rank::Queen),
+        ];
+        let mut game_state = create_test_game_state(ai_hand, vec![], Suit::Spades);
+        let attack_move = ai.make_attack_move(&game_state, 0).unwrap();
+        assert_eq!(attack_move[0].1, Card::new(Suit::Hearts, Rank::Seven));
+        game_state.discard_pile = vec![
+            Card::new(Suit::Hearts, Rank::Queen),
+            Card::new(Suit::Spades, Rank::Queen),
+        ];
+        let attack_move = ai.make_attack_move(&game_state, 0).unwrap();
+        assert_eq!(attack_move[0].1.rank, Rank::Queen);
+    }
+
+    #[test]
+    /// Test that every difficulty keeps its jokers for when nothing else beats the attack
+    fn test_ai_defends_with_joker_last() {
+        let table_cards = vec![(Card::new(Suit::Hearts, Rank::Seven), None)];
+        let ai_hand = vec![
+            Card::joker(Suit::Hearts),
+            Card::new(Suit::Spades, Rank::Ace),
+            Card::new(Suit::Hearts, Rank::Ten),
+        ];
+        let game_state = create_test_game_state(ai_hand, table_cards, Suit::Spades);
+        for difficulty in [AiDifficulty::Easy, AiDifficulty::Medium, AiDifficulty::Hard] {
+            let ai = AiPlayer::with_params(
+                difficulty,
+                AiParams {
+                    trump_on_high_chance: 0.0,
+                    ..AiParams::for_difficulty(difficulty)
+                },
+            );
+            let defense_move = ai.make_defense_move(&game_state, 0).unwrap();
+            assert_eq!(defense_move[0].1, Card::new(Suit::Hearts, Rank::Ten));
+        }
+        // Against a trump Ace only the joker will do
+        let table_cards = vec![(Card::new(Suit::Spades, Rank::Ace), None)];
+        let ai_hand = vec![
+            Card::joker(Suit::Hearts),
+            Card::new(Suit::Spades, Rank::King),
+        ];
+        let game_state = create_test_game_state(ai_hand, table_cards, Suit::Spades);
+        let ai = AiPlayer::new(AiDifficulty::Easy);
+        let defense_move = ai.make_defense_move(&game_state, 0).unwrap();
+        assert_eq!(defense_move[0].1, Card::joker(Suit::Hearts));
+    }
+
+    #[test]
+    /// Test that a full defense finds the one pairing that beats both attacks, even when
+    /// the first card the strategy reaches for would leave the second attack unbeatable
+    fn test_full_defense_finds_pairing() {
+        let table_cards = vec![
+            (Card::new(Suit::Hearts, Rank::King), None),
+            (Card::new(Suit::Diamonds, Rank::Six), None),
+        ];
+        let ai_hand = vec![
+            Card::new(Suit::Spades, Rank::Seven),
+            Card::new(Suit::Hearts, Rank::Ace),
+        ];
+        let game_state = create_test_game_state(ai_hand, table_cards, Suit::Spades);
+        // Medium always spends a trump on a high attack with this param
+        let ai = AiPlayer::with_params(
+            AiDifficulty::Medium,
+            AiParams {
+                trump_on_high_chance: 1.0,
+                ..AiParams::for_difficulty(AiDifficulty::Medium)
+            },
+        );
+        let first_move = ai.make_defense_move(&game_state, 0).unwrap();
+        assert_eq!(first_move[0].1, Card::new(Suit::Spades, Rank::Seven));
+        let expected = vec![
+            (0, Card::new(Suit::Hearts, Rank::Ace)),
+            (1, Card::new(Suit::Spades, Rank::Seven)),
+        ];
+        assert_eq!(ai.make_full_defense(&game_state, 0), Some(expected.clone()));
+        let hard = AiPlayer::new(AiDifficulty::Hard);
+        assert_eq!(hard.make_full_defense(&game_state, 0), Some(expected));
+    }
+
+    #[test]
+    /// Test that no full defense is offered when one attack can't be beaten
+    fn test_full_defense_none_when_unbeatable() {
+        let table_cards = vec![
+            (Card::new(Suit::Hearts, Rank::Six), None),
+            (Card::new(Suit::Spades, Rank::Ace), None),
+        ];
+        let ai_hand = vec![
+            Card::new(Suit::Hearts, Rank::Seven),
+            Card::new(Suit::Spades, Rank::King),
+        ];
+        let game_state = create_test_game_state(ai_hand, table_cards, Suit::Spades);
+        for difficulty in [AiDifficulty::Easy, AiDifficulty::Medium, AiDifficulty::Hard] {
+            let ai = AiPlayer::new(difficulty);
+            assert!(ai.make_full_defense(&game_state, 0).is_none());
+        }
+    }
+
+    #[test]
+    /// Test that the Dynamic record stays at Medium until enough games are played, then follows
+    /// the win rate over the most recent games only
+    fn test_match_record_difficulty() {
+        let mut record = MatchRecord::default();
+        assert_eq!(record.difficulty(), AiDifficulty::Medium);
+        record.record(true);
+        record.record(true);
+        assert_eq!(record.difficulty(), AiDifficulty::Medium);
+        record.record(true);
+        assert_eq!(record.difficulty(), AiDifficulty::Hard);
+        for _ in 0..4 {
+            record.record(false);
+        }
+        // Only the last five games count: one win, four losses
+        assert_eq!(record.win_rate(), Some(0.2));
+        assert_eq!(record.difficulty(), AiDifficulty::Easy);
+    }
+
+    #[test]
+    /// Test that a Dynamic AI for a losing player plays the whole game with Easy's params
+    fn test_adaptive_player_uses_record_difficulty() {
+        let mut record = MatchRecord::default();
+        for _ in 0..3 {
+            record.record(false);
+        }
+        let ai = AiPlayer::new_adaptive(&record);
+        assert_eq!(*ai.params(), AiParams::for_difficulty(AiDifficulty::Easy));
+        let fresh = AiPlayer::new(AiDifficulty::Dynamic);
+        assert_eq!(
+            *fresh.params(),
+            AiParams::for_difficulty(AiDifficulty::Medium)
+        );
+    }
+
+    #[test]
+    /// Test that the baseline beats with its cheapest card and only takes when it must
+    fn test_baseline_defends_cheapest_and_takes_when_forced() {
+        let ai = AiPlayer::baseline();
+        let game_state = GameState::from_notation(
+            "AI: 6S KH 8H | Human*: | Table: 7H-_ | Trump: S | Attacker: Human | Defender: AI",
+        )
+        .unwrap();
+        assert!(!ai.should_take_cards(&game_state, 0));
+        assert_eq!(
+            ai.make_defense_move(&game_state, 0),
+            Some(vec![(2, Card::new(Suit::Hearts, Rank::Eight))])
+        );
+        let game_state = GameState::from_notation(
+            "AI: 6H 6C | Human*: | Table: 7H-_ | Trump: S | Attacker: Human | Defender: AI",
+        )
+        .unwrap();
+        assert!(ai.should_take_cards(&game_state, 0));
+    }
+
+    #[test]
+    /// Test that a traced Hard AI reports the weak ranks and defense plan it worked out
+    fn test_hard_decision_trace() {
+        let ai = AiPlayer::new(AiDifficulty::Hard);
+        let mut game_state = create_test_game_state(
+            vec![
+                Card::new(Suit::Clubs, Rank::Seven),
+                Card::new(Suit::Diamonds, Rank::King),
+            ],
+            vec![(
+                Card::new(Suit::Hearts, Rank::Seven),
+                Some(Card::new(Suit::Hearts, Rank::Eight)),
+            )],
+            Suit::Spades,
+        );
+        discard(
+            &mut game_state,
+            vec![Card::new(Suit::Diamonds, Rank::Seven)],
+        );
+        game_state.current_attacker = 0;
+        game_state.current_defender = 1;
+        game_state.game_phase = GamePhase::Attack;
+        trace_decisions(false);
+        ai.make_attack_move(&game_state, 0);
+        assert_eq!(last_decision_trace(), None);
+
+        trace_decisions(true);
+        ai.make_attack_move(&game_state, 0);
+        let trace = last_decision_trace().unwrap();
+        assert_eq!(trace.decision, "attack");
+        assert_eq!(trace.probable_weak_ranks, vec![Rank::Seven]);
+
+        let game_state = create_test_game_state(
+            vec![Card::new(Suit::Hearts, Rank::Nine)],
+            vec![(Card::new(Suit::Hearts, Rank::Seven), None)],
+            Suit::Spades,
+        );
+        ai.should_take_cards(&game_state, 0);
+        let trace = last_decision_trace().unwrap();
+        assert_eq!(trace.decision, "defend");
+        assert!(trace.probable_weak_ranks.is_empty());
+        assert_eq!(
+            trace.defense_plan,
+            Some(vec![(0, Card::new(Suit::Hearts, Rank::Nine))])
+        );
+        trace_decisions(false);
+    }
+
+    #[test]
+    /// Test that the commentary explains a Hard AI throw-in and a defense that kept the
+    /// trumps back, and stays empty while switched off
+    fn test_ai_commentary() {
+        let ai = AiPlayer::new(AiDifficulty::Hard);
+        let mut game_state = create_test_game_state(
+            vec![
+                Card::new(Suit::Clubs, Rank::Seven),
+                Card::new(Suit::Diamonds, Rank::King),
+            ],
+            vec![(
+                Card::new(Suit::Hearts, Rank::Seven),
+                Some(Card::new(Suit::Hearts, Rank::Eight)),
+            )],
+            Suit::Spades,
+        );
+        discard(
+            &mut game_state,
+            vec![Card::new(Suit::Diamonds, Rank::Seven)],
+        );
+        seat_ai_as_attacker(
+            &mut game_state,
+            vec![
+                Card::new(Suit::Clubs, Rank::Six),
+                Card::new(Suit::Clubs, Rank::Eight),
+            ],
+        );
+        game_state.game_phase = GamePhase::Attack;
+        record_commentary(false);
+        ai.make_attack_move(&game_state, 0);
+        assert!(ai_commentary().is_empty());
+
+        record_commentary(true);
+        ai.make_attack_move(&game_state, 0);
+        assert_eq!(
+            ai_commentary(),
+            vec![AiComment::Attack {
+                player_idx: 0,
+                cards: vec![Card::new(Suit::Clubs, Rank::Seven)],
+                adding: true,
+                reason: Some(AttackReason::WeakRank),
+            }]
+        );
+
+        let game_state = create_test_game_state(
+            vec![
+                Card::new(Suit::Hearts, Rank::Nine),
+                Card::new(Suit::Spades, Rank::Six),
+            ],
+            vec![(Card::new(Suit::Hearts, Rank::Seven), None)],
+            Suit::Spades,
+        );
+        ai.make_full_defense(&game_state, 0);
+        assert_eq!(
+            ai_commentary().last(),
+            Some(&AiComment::Defend {
+                player_idx: 0,
+                pairs: vec![(
+                    Card::new(Suit::Hearts, Rank::Seven),
+                    Card::new(Suit::Hearts, Rank::Nine)
+                )],
+                used_trump: false,
+                saved_trumps: true,
+            })
+        );
+        record_commentary(false);
+    }
+
+    #[test]
+    /// Test that with `forbid_trump_pass` neither the rules nor the AI pass with a trump,
+    /// while a plain card of the same rank still passes
+    fn test_no_trump_pass() {
+        let always_pass = AiParams {
+            pass_chance: 1.0,
+            ..AiParams::for_difficulty(AiDifficulty::Medium)
+        };
+        let ai = AiPlayer::with_params(AiDifficulty::Medium, always_pass);
+        let mut game_state = create_test_game_state(
+            vec![Card::new(Suit::Spades, Rank::Seven)],
+            vec![(Card::new(Suit::Hearts, Rank::Seven), None)],
+            Suit::Spades,
+        );
+        game_state.players[1].hand = vec![Card::new(Suit::Clubs, Rank::Six); 3];
+        assert!(ai.make_pass_move(&game_state, 0).is_some());
+
+        game_state.forbid_trump_pass = true;
+        assert!(ai.make_pass_move(&game_state, 0).is_none());
+        assert_eq!(
+            game_state.clone().pass_attack(0, 0),
+            Err(GameError::TrumpPass)
+        );
+        game_state.players[0].hand = vec![Card::new(Suit::Clubs, Rank::Seven)];
+        assert!(game_state.clone().pass_attack(0, 0).is_ok());
+    }
+
+    #[test]
+    /// Test that once a card on the table is beaten neither the rules nor the AI pass
+    fn test_no_pass_after_defense() {
+        let ai = AiPlayer::new(AiDifficulty::Hard);
+        let mut game_state = create_test_game_state(
+            vec![Card::new(Suit::Clubs, Rank::Seven)],
+            vec![
+                (
+                    Card::new(Suit::Hearts, Rank::Seven),
+                    Some(Card::new(Suit::Hearts, Rank::Nine)),
+                ),
+                (Card::new(Suit::Diamonds, Rank::Seven), None),
+            ],
+            Suit::Spades,
+        );
+        game_state.players[1].hand = vec![Card::new(Suit::Clubs, Rank::Six); 3];
+        assert!(ai.make_pass_move(&game_state, 0).is_none());
+        assert_eq!(
+            game_state.pass_attack(0, 1),
+            Err(GameError::PassAfterDefense)
+        );
+        assert_eq!(
+            GameError::PassAfterDefense.to_string(),
+            "Cannot pass once a card on the table is beaten"
+        );
+    }
 }
@@ -1,7 +1,8 @@
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::hash::Hash;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Suit {
     Clubs,
     Diamonds,
@@ -28,8 +29,12 @@ impl Suit {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum Rank {
+    Two, // Two to Five only come in the 52 card deck, see `DeckSize`
+    Three,
+    Four,
+    Five,
     Six,
     Seven,
     Eight,
@@ -39,11 +44,17 @@ pub enum Rank {
     Queen,
     King,
     Ace,
+    Joker, // Only in the jokers variant, never part of `Rank::all`
 }
 
 impl Rank {
+    /// Every rank of a full 52 card pack, lowest first
     pub fn all() -> Vec<Rank> {
         vec![
+            Rank::Two,
+            Rank::Three,
+            Rank::Four,
+            Rank::Five,
             Rank::Six,
             Rank::Seven,
             Rank::Eight,
@@ -58,6 +69,10 @@ impl Rank {
 
     pub fn symbol(&self) -> &str {
         match self {
+            Rank::Two => "2",
+            Rank::Three => "3",
+            Rank::Four => "4",
+            Rank::Five => "5",
             Rank::Six => "6",
             Rank::Seven => "7",
             Rank::Eight => "8",
@@ -67,11 +82,12 @@ impl Rank {
             Rank::Queen => "Q",
             Rank::King => "K",
             Rank::Ace => "A",
+            Rank::Joker => "JK",
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Card {
     pub suit: Suit,
     pub rank: Rank,
@@ -82,26 +98,67 @@ impl Card {
         Self { suit, rank }
     }
 
+    /// A joker, red for a red suit and black otherwise
+    pub fn joker(suit: Suit) -> Self {
+        Self::new(suit, Rank::Joker)
+    }
+
+    pub fn is_joker(&self) -> bool {
+        self.rank == Rank::Joker
+    }
+
+    /// Whether this card plays as a trump. Jokers rank above every trump and count as one.
+    pub fn is_trump(&self, trump_suit: Option<Suit>) -> bool {
+        self.is_joker() || trump_suit == Some(self.suit)
+    }
+
+    /// How much the card is worth keeping, for choosing which card to spend.
+    /// Every non-trump is worth less than every trump, and jokers, the highest rank,
+    /// are worth the most.
+    pub fn strategic_value(&self, trump_suit: Option<Suit>) -> u32 {
+        const TRUMP_BONUS: u32 = 100;
+        let rank = self.rank as u32;
+        if self.is_trump(trump_suit) {
+            TRUMP_BONUS + rank
+        } else {
+            rank
+        }
+    }
+
     /// Determines if this card can beat another card in Durak rules
     ///
     /// A card can beat another card if:
     /// 1. It is the same suit but higher rank, OR
     /// 2. It is a trump card and the other card is not
     ///
+    /// In a no-trump game (`trump_suit` is `None`) only the first rule applies.
+    /// A joker beats any card and can't be beaten itself.
+    ///
     /// # Arguments
     /// * `other` - The attacking card to beat
-    /// * `trump_suit` - The current trump suit for the game
+    /// * `trump_suit` - The current trump suit for the game, if any
     ///
     /// # Returns
     /// `true` if this card can beat the other card, `false` otherwise
-    pub fn can_beat(&self, other: &Card, trump_suit: Suit) -> bool {
+    pub fn can_beat(&self, other: &Card, trump_suit: Option<Suit>) -> bool {
+        // Jokers beat everything, nothing beats a joker
+        if other.is_joker() {
+            return false;
+        }
+        if self.is_joker() {
+            return true;
+        }
+
         // Case 1: Same suit - higher rank wins
         if self.suit == other.suit {
             return self.rank > other.rank;
         }
 
         // Case 2: Different suits - trump beats non-trump
-        if self.suit == trump_suit && other.suit != trump_suit {
+        if let Some(trump_suit) = trump_suit
+            && self.suit == trump_suit
+            && other.suit != trump_suit
+        {
             return true;
         }
 
@@ -126,6 +183,10 @@ impl Card {
 
 impl fmt::Display for Card {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_joker() {
+            // The suit of a joker only sets its colour
+            return write!(f, "{}", self.rank.symbol());
+        }
         write!(f, "{}{}", self.rank.symbol(), self.suit.symbol())
     }
 }
@@ -139,10 +200,10 @@ mod tests {
     /// Basic Rank and Suit tests
     fn test_can_beat_same_suit() {
         // Create two cards of the same suit.
-        let trump_suit = Suit::Spades;
+        let trump_suit = Some(Suit::Spades);
         let card1 = Card::new(Suit::Hearts, Rank::Seven); // 7 of Hearts
         let card2 = Card::new(Suit::Hearts, Rank::Ten); // 10 of Hearts
-                                                        // We expect card2 to beat card1 because it has a higher rank.
+        // We expect card2 to beat card1 because it has a higher rank.
         let card3 = Card::new(Suit::Diamonds, Rank::Seven); // 7 of Diamonds
         assert!(card2.can_beat(&card1, trump_suit));
         assert!(!card1.can_beat(&card2, trump_suit));
@@ -152,10 +213,10 @@ mod tests {
     #[test]
     /// Test that a trump card can beat a lower trump card
     fn test_can_beat_trump_to_trump() {
-        let trump_suit = Suit::Spades;
+        let trump_suit = Some(Suit::Spades);
         let card1 = Card::new(Suit::Spades, Rank::Six); // 6 of Spades
         let card2 = Card::new(Suit::Spades, Rank::Seven); // 7 of Spades
-                                                          // We expect card2 to beat card1 because it has a higher rank.
+        // We expect card2 to beat card1 because it has a higher rank.
         assert!(card2.can_beat(&card1, trump_suit));
         assert!(!card1.can_beat(&card2, trump_suit));
     }
@@ -163,11 +224,51 @@ mod tests {
     /// Test that a non-trump card cannot beat a trump card
     fn test_can_beat_trump() {
         // Create a trump card and a non-trump card.
-        let trump_suit = Suit::Spades;
+        let trump_suit = Some(Suit::Spades);
         let trump_card = Card::new(Suit::Spades, Rank::Six); // 6 of Spades
         let other_card = Card::new(Suit::Hearts, Rank::Ace); // Ace of Hearts
         assert!(trump_card.can_beat(&other_card, trump_suit));
         // The non-trump card cannot beat the trump card.
         assert!(!other_card.can_beat(&trump_card, trump_suit));
     }
+    #[test]
+    /// Test that suits carry no special weight in a no-trump game
+    fn test_can_beat_no_trump() {
+        let spade = Card::new(Suit::Spades, Rank::Six); // 6 of Spades
+        let heart = Card::new(Suit::Hearts, Rank::Seven); // 7 of Hearts
+        let higher_heart = Card::new(Suit::Hearts, Rank::Ace); // Ace of Hearts
+        // Spades would be trump otherwise, but nothing is trump here
+        assert!(!spade.can_beat(&heart, None));
+        assert!(!heart.can_beat(&spade, None));
+        // Same suit still goes by rank
+        assert!(higher_heart.can_beat(&heart, None));
+    }
+    #[test]
+    /// Test that a joker beats even the trump Ace
+    fn test_joker_beats_trump_ace() {
+        let trump_suit = Some(Suit::Spades);
+        let joker = Card::joker(Suit::Hearts);
+        assert!(joker.can_beat(&Card::new(Suit::Spades, Rank::Ace), trump_suit));
+        assert!(joker.can_beat(&Card::new(Suit::Clubs, Rank::Six), None));
+    }
+    #[test]
+    /// Test that nothing beats a joker, not even the other joker
+    fn test_nothing_beats_joker() {
+        let trump_suit = Some(Suit::Spades);
+        let joker = Card::joker(Suit::Spades);
+        assert!(!Card::new(Suit::Spades, Rank::Ace).can_beat(&joker, trump_suit));
+        assert!(!Card::joker(Suit::Hearts).can_beat(&joker, trump_suit));
+    }
+    #[test]
+    /// Test that any non-trump is worth less than the lowest trump, and a joker the most
+    fn test_strategic_value_ordering() {
+        let trump_suit = Some(Suit::Spades);
+        let non_trump_ace = Card::new(Suit::Hearts, Rank::Ace).strategic_value(trump_suit);
+        let low_trump = Card::new(Suit::Spades, Rank::Six).strategic_value(trump_suit);
+        let high_trump = Card::new(Suit::Spades, Rank::Ace).strategic_value(trump_suit);
+        let joker = Card::joker(Suit::Hearts).strategic_value(trump_suit);
+        assert!(non_trump_ace < low_trump);
+        assert!(low_trump < high_trump);
+        assert!(high_trump < joker);
+    }
 }
@@ -1,33 +1,116 @@
 use rand::seq::SliceRandom;
-use rand::thread_rng;
+use rand::{Rng, thread_rng};
+use serde::{Deserialize, Serialize};
 
 use super::card::{Card, Rank, Suit};
 
-#[derive(Debug, Clone)]
+/// How many cards one pack holds, all four suits of a range of ranks up to the ace
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeckSize {
+    Short, // 20 cards, Ten to Ace, for a quick game
+    #[default]
+    Standard, // 36 cards, Six to Ace
+    Full,  // 52 cards, Two to Ace
+}
+
+impl DeckSize {
+    pub fn next(self) -> Self {
+        match self {
+            DeckSize::Short => DeckSize::Standard,
+            DeckSize::Standard => DeckSize::Full,
+            DeckSize::Full => DeckSize::Short,
+        }
+    }
+    /// The ranks of a pack, lowest first
+    pub fn ranks(self) -> Vec<Rank> {
+        let lowest = match self {
+            DeckSize::Short => Rank::Ten,
+            DeckSize::Standard => Rank::Six,
+            DeckSize::Full => Rank::Two,
+        };
+        Rank::all()
+            .into_iter()
+            .filter(|&rank| rank >= lowest)
+            .collect()
+    }
+    /// Cards in one pack
+    pub fn cards(self) -> usize {
+        self.ranks().len() * Suit::all().len()
+    }
+    /// The pack holding `cards` cards, if there is one
+    pub fn from_cards(cards: usize) -> Option<Self> {
+        [DeckSize::Short, DeckSize::Standard, DeckSize::Full]
+            .into_iter()
+            .find(|size| size.cards() == cards)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Deck {
     pub cards: Vec<Card>,
     pub trump_suit: Option<Suit>,
 }
 
+impl Default for Deck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Deck {
     pub fn new() -> Self {
-        let mut cards = Vec::with_capacity(36);
+        Self::with_decks(1)
+    }
+
+    /// A deck made of `deck_count` 36 card packs shuffled together, see `with_size`
+    pub fn with_decks(deck_count: usize) -> Self {
+        Self::with_size(DeckSize::Standard, deck_count)
+    }
 
-        for suit in Suit::all() {
-            for rank in Rank::all() {
-                cards.push(Card::new(suit, rank));
+    /// A deck made of `deck_count` packs of `size` shuffled together, so with more than
+    /// one every card comes in `deck_count` identical copies
+    pub fn with_size(size: DeckSize, deck_count: usize) -> Self {
+        let mut cards = Vec::with_capacity(size.cards() * deck_count);
+
+        for _ in 0..deck_count {
+            for suit in Suit::all() {
+                for rank in size.ranks() {
+                    cards.push(Card::new(suit, rank));
+                }
             }
         }
 
+        debug_assert_eq!(
+            cards.iter().collect::<std::collections::HashSet<_>>().len(),
+            size.cards().min(cards.len()),
+            "every pack must hold the same distinct cards"
+        );
         Self {
             cards,
             trump_suit: None,
         }
     }
 
+    /// Adds a red and a black joker for the jokers variant
+    pub fn add_jokers(&mut self) {
+        self.cards.push(Card::joker(Suit::Hearts));
+        self.cards.push(Card::joker(Suit::Spades));
+    }
+
     pub fn shuffle(&mut self) {
-        let mut rng = thread_rng();
-        self.cards.shuffle(&mut rng);
+        self.shuffle_with_rng(&mut thread_rng());
+    }
+
+    /// Shuffle with a caller-provided RNG, e.g. a seeded one for reproducible deals
+    pub fn shuffle_with_rng<R: Rng>(&mut self, rng: &mut R) {
+        self.cards.shuffle(rng);
+        // A joker can't name the trump suit, swap it with the last ordinary card
+        if self.cards.last().is_some_and(Card::is_joker)
+            && let Some(idx) = self.cards.iter().rposition(|card| !card.is_joker())
+        {
+            let last = self.cards.len() - 1;
+            self.cards.swap(idx, last);
+        }
 
         // The bottom card determines the trump suit
         if let Some(bottom_card) = self.cards.last() {
@@ -35,16 +118,12 @@ impl Deck {
         }
     }
 
+    /// Deals up to `count` cards from the top of the deck, so the face-up trump card at the
+    /// bottom is always dealt last. Returns fewer cards than asked for when the deck runs low,
+    /// callers should go by the length of the returned hand.
     pub fn deal(&mut self, count: usize) -> Vec<Card> {
-        let mut hand = Vec::with_capacity(count);
-        for _ in 0..count {
-            if let Some(card) = self.cards.pop() {
-                hand.push(card);
-            } else {
-                break;
-            }
-        }
-        hand
+        let dealt = count.min(self.cards.len());
+        self.cards.drain(..dealt).collect()
     }
 
     pub fn trump_suit(&self) -> Option<Suit> {
@@ -64,7 +143,7 @@ impl Deck {
         self.cards.len()
     }
 
-    #[allow(dead_code)]
+    /// The face-up card under the deck that shows the trump suit
     pub fn bottom_card(&self) -> Option<&Card> {
         self.cards.last()
     }
@@ -78,4 +157,71 @@ mod tests {
         let deck = Deck::new();
         assert_eq!(deck.remaining(), 36);
     }
+    #[test]
+    fn test_double_deck() {
+        let deck = Deck::with_decks(2);
+        assert_eq!(deck.remaining(), 72);
+        let ace = Card::new(Suit::Spades, Rank::Ace);
+        assert_eq!(deck.cards.iter().filter(|&&card| card == ace).count(), 2);
+    }
+    #[test]
+    fn test_deck_sizes() {
+        let short = Deck::with_size(DeckSize::Short, 1);
+        assert_eq!(short.remaining(), 20);
+        assert!(short.cards.iter().all(|card| card.rank >= Rank::Ten));
+        let full = Deck::with_size(DeckSize::Full, 2);
+        assert_eq!(full.remaining(), 104);
+        assert_eq!(DeckSize::Full.ranks(), Rank::all());
+        assert_eq!(DeckSize::Standard.cards(), 36);
+        assert_eq!(Deck::new(), Deck::with_size(DeckSize::default(), 1));
+    }
+    #[test]
+    fn test_seeded_shuffle_is_reproducible() {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+        let mut first = Deck::new();
+        let mut second = Deck::new();
+        first.shuffle_with_rng(&mut StdRng::seed_from_u64(42));
+        second.shuffle_with_rng(&mut StdRng::seed_from_u64(42));
+        assert_eq!(first.cards, second.cards);
+        assert_eq!(first.trump_suit(), second.trump_suit());
+    }
+    #[test]
+    fn test_joker_never_sets_trump() {
+        use rand::SeedableRng;
+        use rand::rngs::StdRng;
+        for seed in 0..50 {
+            let mut deck = Deck::new();
+            deck.add_jokers();
+            deck.shuffle_with_rng(&mut StdRng::seed_from_u64(seed));
+            assert_eq!(deck.remaining(), 38);
+            assert!(!deck.bottom_card().unwrap().is_joker());
+        }
+    }
+    #[test]
+    fn test_deal_from_nearly_empty_deck() {
+        let mut deck = Deck::new();
+        deck.shuffle();
+        let bottom_card = *deck.bottom_card().unwrap();
+        assert_eq!(deck.deal(34).len(), 34);
+        // The trump card stays at the bottom until the very last deal
+        let last_cards = deck.deal(6);
+        assert_eq!(last_cards.len(), 2);
+        assert_eq!(last_cards[1], bottom_card);
+        assert!(deck.is_empty());
+        assert!(deck.deal(6).is_empty());
+    }
+    #[test]
+    fn test_trump_card_is_dealt_last() {
+        let mut deck = Deck::new();
+        deck.shuffle();
+        let bottom_card = *deck.bottom_card().unwrap();
+        let mut dealt = Vec::new();
+        while !deck.is_empty() {
+            dealt.extend(deck.deal(6));
+        }
+        assert_eq!(dealt.len(), 36);
+        assert_eq!(dealt.last(), Some(&bottom_card));
+        assert_eq!(Some(bottom_card.suit), deck.trump_suit());
+    }
 }
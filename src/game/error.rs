@@ -0,0 +1,65 @@
+use std::fmt::Display;
+
+/// Reasons a move can be rejected by the game rules or the current game state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameError {
+    InvalidCardIndex,
+    InvalidAttackIndex,
+    AlreadyDefended,
+    NoUndefendedAttacks,
+    NoCardsOnTable,
+    IllegalAttack,
+    IllegalDefense,
+    IllegalPass,
+    PassAfterDefense,
+    TrumpPass,
+    NoPassTarget,
+    IllegalReinforcement,
+    TableFull,
+    InvalidCombination,
+    IncompleteDefense,
+    NoCardSelected,
+    WrongPhase,
+    NotYourTurn,
+    MulliganUnavailable,
+}
+
+impl GameError {
+    /// Whether the error is an ordinary illegal move the player can retry,
+    /// as opposed to a state that should never be reached.
+    pub fn is_illegal_move(&self) -> bool {
+        !matches!(
+            self,
+            GameError::InvalidCardIndex | GameError::InvalidAttackIndex
+        )
+    }
+}
+
+impl Display for GameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            GameError::InvalidCardIndex => "Invalid card index",
+            GameError::InvalidAttackIndex => "Invalid attack index",
+            GameError::AlreadyDefended => "Attack is already defended",
+            GameError::NoUndefendedAttacks => "No undefended attacks to defend against",
+            GameError::NoCardsOnTable => "No cards on the table",
+            GameError::IllegalAttack => "Invalid attack - card does not match a rank on the table",
+            GameError::IllegalDefense => "Invalid defense - card cannot beat the attack",
+            GameError::IllegalPass => "Invalid pass - card does not match the attack",
+            GameError::PassAfterDefense => "Cannot pass once a card on the table is beaten",
+            GameError::TrumpPass => "Passing with a trump is not allowed",
+            GameError::NoPassTarget => "Nobody left in the game to pass to",
+            GameError::IllegalReinforcement => "Reinforcement must match a rank on the table",
+            GameError::TableFull => "Attack limit reached",
+            GameError::InvalidCombination => "Selected cards cannot be played together",
+            GameError::IncompleteDefense => "Not all cards defended",
+            GameError::NoCardSelected => "No card selected",
+            GameError::WrongPhase => "Not allowed in the current game phase",
+            GameError::NotYourTurn => "Not this player's turn",
+            GameError::MulliganUnavailable => "No redeal available",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for GameError {}
@@ -1,14 +1,33 @@
-use super::card::{Card, Suit};
-use super::deck::Deck;
+use super::ai::CardTracker;
+use super::card::{Card, Rank, Suit};
+use super::deck::{Deck, DeckSize};
+use super::error::GameError;
+use super::history::{Board, Move, MoveHistory};
 use super::player::{Player, PlayerType};
-use std::collections::VecDeque;
+use crate::ui::debug_overlay::{debug, info, warn};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fmt::Display;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Most attacks a single round can hold
+pub const MAX_ATTACKS: usize = 6;
+/// Most packs the multi-deck mode shuffles together
+pub const MAX_DECKS: usize = 2;
+
+/// Games saved before the multi-deck mode were played with a single pack
+fn default_deck_count() -> usize {
+    1
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GamePhase {
     Setup,
     Attack,
     Defense,
+    Reinforce, // Every attack is beaten, the attacker may add matching cards or end the round
     Drawing,
     GameOver,
 }
@@ -18,7 +37,57 @@ impl Display for GamePhase {
     }
 }
 
-#[derive(Debug, Clone)]
+/// How the trump suit is picked when a game is dealt
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrumpRule {
+    #[default]
+    BottomCard, // The bottom card of the deck, turned face up
+    Random,        // A random suit, whatever the bottom card is
+    NoTrump,       // Every suit is equal
+    DealerChooses, // The dealer names the suit they hold most of after the deal
+}
+impl TrumpRule {
+    /// The next rule in the main menu's cycle
+    pub fn next(self) -> Self {
+        match self {
+            TrumpRule::BottomCard => TrumpRule::Random,
+            TrumpRule::Random => TrumpRule::NoTrump,
+            TrumpRule::NoTrump => TrumpRule::DealerChooses,
+            TrumpRule::DealerChooses => TrumpRule::BottomCard,
+        }
+    }
+}
+
+/// The most recent thing a player did, as a physical opponent would see it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PlayerAction {
+    Attacking,
+    Defended,
+    PassedAttack,
+    Took(usize),
+    DrewTo(usize),
+}
+impl Display for PlayerAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlayerAction::Attacking => write!(f, "attacking"),
+            PlayerAction::Defended => write!(f, "defended"),
+            PlayerAction::PassedAttack => write!(f, "passed the attack"),
+            PlayerAction::Took(1) => write!(f, "took 1 card"),
+            PlayerAction::Took(count) => write!(f, "took {} cards", count),
+            PlayerAction::DrewTo(count) => write!(f, "drew to {}", count),
+        }
+    }
+}
+
+/// How the last round ended, shown while the players draw back up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoundSummary {
+    Discarded { defender: usize, cards: usize }, // Every attack was beaten
+    Taken { defender: usize, cards: usize },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GameState {
     pub players: Vec<Player>,
     pub deck: Deck,
@@ -28,8 +97,52 @@ pub struct GameState {
     pub current_defender: usize,
     pub trump_suit: Option<Suit>,
     pub game_phase: GamePhase,
-    pub winner: Option<usize>,
-    pub stuck_counter: usize, // Add this field to track stuck states
+    pub winner: Option<usize>, // The first player out, `None` if everyone went out together
+    #[serde(default)]
+    pub finished: Vec<usize>, // Players out of the game in the order they went out
+    #[serde(default)]
+    pub durak: Option<usize>, // The loser, the last player left holding cards or whoever surrendered
+    #[serde(default)]
+    pub trump_rule: TrumpRule,
+    pub seed: Option<u64>, // Fixed shuffle seed for reproducible deals
+    #[serde(default)]
+    pub allow_trump_reveal_pass: bool, // House rule: showing a same-rank trump passes the attack
+    #[serde(default)]
+    pub forbid_trump_pass: bool, // House rule: an attack can't be passed on with a trump
+    #[serde(default)]
+    pub jokers: bool, // Jokers variant: two unbeatable jokers join the deck
+    #[serde(default = "default_deck_count")]
+    pub deck_count: usize, // Packs shuffled into the deck, every card comes this many times
+    #[serde(default)]
+    pub pack_size: DeckSize, // Cards in each of those packs: 20, 36 or 52
+    #[serde(default)]
+    pub allow_mulligan: bool, // House rule: one free redeal before the first round is played
+    #[serde(default)]
+    pub mulligan_used: bool,
+    #[serde(default)]
+    pub endgame_notice: bool, // The last card was just drawn, cleared once the human has seen it
+    #[serde(default)]
+    pub round_defender_hand: Option<usize>, // Defender's hand size when the round's first attack landed
+    #[serde(default)]
+    pub flat_attack_limit: bool, // House rule: six attacks a round, whatever the defender held
+    #[serde(default)]
+    pub round: u32, // Rounds resolved so far, the one in play is `current_round`
+    #[serde(default)]
+    pub last_round_summary: Option<RoundSummary>, // Cleared when the next round's first attack lands
+    #[serde(default)]
+    pub thrower: Option<usize>, // Who may throw in while the reinforcement window is open
+    #[serde(default)]
+    pub last_actions: Vec<Option<PlayerAction>>, // Most recent action per player index
+    #[serde(default)]
+    pub card_tracker: CardTracker, // Cards seen discarded or picked up this game
+    #[serde(default)]
+    pub history: MoveHistory, // Every move of this game, for the replay
+}
+
+impl Default for GameState {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl GameState {
@@ -47,28 +160,160 @@ impl GameState {
             trump_suit: None,
             game_phase: GamePhase::Setup,
             winner: None,
-            stuck_counter: 0, // Initialize counter
+            finished: Vec::new(),
+            durak: None,
+            trump_rule: TrumpRule::BottomCard,
+            seed: None,
+            allow_trump_reveal_pass: false,
+            forbid_trump_pass: false,
+            jokers: false,
+            deck_count: 1,
+            pack_size: DeckSize::Standard,
+            allow_mulligan: false,
+            mulligan_used: false,
+            endgame_notice: false,
+            round_defender_hand: None,
+            flat_attack_limit: false,
+            round: 0,
+            last_round_summary: None,
+            thrower: None,
+            last_actions: Vec::new(),
+            card_tracker: CardTracker::default(),
+            history: MoveHistory::default(),
+        }
+    }
+
+    /// A game whose deals all come from `seed`, see `seed`
+    pub fn new_with_seed(seed: u64) -> Self {
+        Self {
+            seed: Some(seed),
+            ..Self::new()
         }
     }
 
     /// Adds a new player to the `players` vector of the GameState
     pub fn add_player(&mut self, name: String, player_type: PlayerType) {
         self.players.push(Player::new(name, player_type));
+        self.last_actions.push(None);
     }
     /// Sets up the game by creating a new deck, shuffling it, and dealing 6 cards to each player.
     /// The player with the lowest trump card delt is determined as the starting attacker.
+    /// The trump suit comes from `trump_rule`, see `choose_trump_suit`.
+    /// With `jokers` set the deck gets two jokers before shuffling.
+    /// `deck_count` packs of `pack_size` go into the deck, see `Deck::with_size`. A pack too
+    /// small to deal everyone a full hand is swapped for a standard one.
     pub fn setup_game(&mut self) {
-        self.deck = Deck::new();
-        self.deck.shuffle();
-        self.trump_suit = self.deck.trump_suit();
+        // A rematch deals into the finished game, clear what it left behind.
+        // `durak` stays until this game ends, the last game's durak is the dealer.
+        for player in &mut self.players {
+            player.hand.clear();
+        }
+        self.table_cards.clear();
+        self.discard_pile.clear();
+        self.winner = None;
+        self.finished.clear();
+        self.endgame_notice = false;
+        self.round_defender_hand = None;
+        if !self.pack_fits_table(self.pack_size) {
+            warn(format!(
+                "A {} card pack can't deal {} players, using a standard pack",
+                self.pack_size.cards(),
+                self.players.len()
+            ));
+            self.pack_size = DeckSize::Standard;
+        }
+        self.deck = Deck::with_size(self.pack_size, self.deck_count);
+        self.card_tracker = CardTracker::new(self.deck_count);
+        if self.jokers {
+            self.deck.add_jokers();
+        }
+        let mut rng = self.seed.map(StdRng::seed_from_u64);
+        match &mut rng {
+            Some(rng) => self.deck.shuffle_with_rng(rng),
+            None => self.deck.shuffle(),
+        }
         for player in &mut self.players {
             let cards = self.deck.deal(6);
+            // `pack_fits_table` made sure there are enough cards
+            debug_assert_eq!(cards.len(), 6, "deck ran out while dealing");
             player.add_cards(cards);
         }
+        self.trump_suit = self.choose_trump_suit(rng.as_mut());
         self.determine_first_player();
         self.current_defender = (self.current_attacker + 1) % self.players.len();
         self.game_phase = GamePhase::Attack;
-        self.stuck_counter = 0; // Reset stuck counter when starting a new game
+        self.last_actions = vec![None; self.players.len()];
+        self.round = 0;
+        self.last_round_summary = None;
+        self.thrower = None;
+        self.mulligan_used = false;
+        self.history = MoveHistory::new(Board::of(self));
+    }
+    /// Whether the mulligan house rule still allows a redeal. It is open until anyone
+    /// answers the opening attack, so the defender of the first attack can use it too.
+    pub fn can_redeal(&self) -> bool {
+        self.allow_mulligan
+            && !self.mulligan_used
+            && matches!(
+                self.game_phase,
+                GamePhase::Setup | GamePhase::Attack | GamePhase::Defense
+            )
+            && self.discard_pile.is_empty()
+            && self
+                .last_actions
+                .iter()
+                .all(|action| matches!(action, None | Some(PlayerAction::Attacking)))
+    }
+    /// Mulligan: returns every hand and the opening attack to the deck, reshuffles and
+    /// deals again. Only allowed once per game, see `can_redeal`.
+    /// A seeded game reshuffles with the next seed, so the redeal is reproducible too.
+    pub fn redeal(&mut self) -> Result<(), GameError> {
+        if !self.can_redeal() {
+            return Err(GameError::MulliganUnavailable);
+        }
+        for player in &mut self.players {
+            player.hand.clear();
+        }
+        self.table_cards.clear();
+        let seed = self.seed;
+        self.seed = seed.map(|seed| seed.wrapping_add(1));
+        self.setup_game();
+        self.seed = seed;
+        self.mulligan_used = true;
+        Ok(())
+    }
+    /// Picks the trump suit for a fresh deal according to `trump_rule`.
+    /// A seeded game draws the random suit from the shuffle's RNG so it stays reproducible.
+    fn choose_trump_suit(&self, rng: Option<&mut StdRng>) -> Option<Suit> {
+        match self.trump_rule {
+            TrumpRule::BottomCard => self.deck.trump_suit(),
+            TrumpRule::NoTrump => None,
+            TrumpRule::Random => {
+                let suits = Suit::all();
+                match rng {
+                    Some(rng) => suits.choose(rng).copied(),
+                    None => suits.choose(&mut rand::thread_rng()).copied(),
+                }
+            }
+            TrumpRule::DealerChooses => self.dealer_choice(),
+        }
+    }
+    /// The dealer is the loser of the previous game, or the seat before the first player.
+    /// They name the suit they hold most of, the higher top card breaking ties, so the
+    /// choice is made for them automatically, human or computer.
+    fn dealer_choice(&self) -> Option<Suit> {
+        let dealer = self
+            .durak
+            .filter(|&idx| idx < self.players.len())
+            .unwrap_or(self.players.len().checked_sub(1)?);
+        let hand = &self.players[dealer].hand;
+        Suit::all().into_iter().max_by_key(|&suit| {
+            let cards = hand
+                .iter()
+                .filter(|card| !card.is_joker() && card.suit == suit);
+            let top_rank = cards.clone().map(|card| card.rank).max();
+            (cards.count(), top_rank)
+        })
     }
     /// The player with the lowest trump card is determined as the starting attacker.
     /// If no trump suit is present, the player is chosen.
@@ -78,11 +323,11 @@ impl GameState {
             let mut lowest_player = 0;
             let mut lowest_rank = None;
             for (i, player) in self.players.iter().enumerate() {
-                if let Some((_, card)) = player.get_lowest_trump(trump_suit) {
-                    if lowest_rank.is_none() || card.rank < lowest_rank.unwrap() {
-                        lowest_rank = Some(card.rank);
-                        lowest_player = i;
-                    }
+                if let Some((_, card)) = player.get_lowest_trump(trump_suit)
+                    && (lowest_rank.is_none() || card.rank < lowest_rank.unwrap())
+                {
+                    lowest_rank = Some(card.rank);
+                    lowest_player = i;
                 }
             }
             // If someone has a trump card, they go first
@@ -94,207 +339,545 @@ impl GameState {
         // If no one has a trump card or there's no trump suit, just start with player 0
         self.current_attacker = 0;
     }
-    /// General attack logic
-    pub fn attack(&mut self, card_idx: usize, player_idx: usize) -> Result<(), &'static str> {
+    /// The cap on attacks for a defender who starts the round holding `start_hand` cards
+    fn attack_cap(&self, start_hand: usize) -> usize {
+        if self.flat_attack_limit {
+            MAX_ATTACKS
+        } else {
+            start_hand.min(MAX_ATTACKS)
+        }
+    }
+    /// Most attacks the current round allows: six, or as many cards as the defender held
+    /// when the round began if that is fewer. With `flat_attack_limit` it is always six.
+    pub fn attack_limit(&self) -> usize {
+        let defender = &self.players[self.current_defender];
+        let start_hand = match self.round_defender_hand {
+            Some(start_hand) if !self.table_cards.is_empty() => start_hand,
+            // Not recorded (an older save): every card the defender has put down this
+            // round is still on the table
+            _ => {
+                defender.hand_size()
+                    + self
+                        .table_cards
+                        .iter()
+                        .filter(|(_, defense)| defense.is_some())
+                        .count()
+            }
+        };
+        self.attack_cap(start_hand)
+    }
+    /// How many more attacks fit into the current round. A defender without cards
+    /// can't be attacked at all.
+    pub fn attack_room(&self) -> usize {
+        if self.players[self.current_defender].is_empty_hand() {
+            return 0;
+        }
+        self.attack_limit().saturating_sub(self.table_cards.len())
+    }
+    /// General attack logic. The first attack of a round fixes the round's cap from the
+    /// defender's hand and the roles, later ones join the wave against the same defender.
+    /// Every attack, the first one too, fails with `TableFull` once `attack_room` is used up,
+    /// the same limit multi-card and AI attacks are held to. Any other card `legal_attacks`
+    /// doesn't offer is an `IllegalAttack`.
+    pub fn attack(&mut self, card_idx: usize, player_idx: usize) -> Result<(), GameError> {
+        if !matches!(self.game_phase, GamePhase::Attack | GamePhase::Defense) {
+            return Err(GameError::WrongPhase);
+        }
+        if card_idx >= self.players[player_idx].hand_size() {
+            return Err(GameError::InvalidCardIndex);
+        }
+        let opening = self.table_cards.is_empty();
+        let defender_idx = if opening {
+            self.next_in_play(player_idx)
+        } else {
+            self.current_defender
+        };
+        if opening {
+            let defender_hand = self.players[defender_idx].hand_size();
+            if defender_hand == 0 {
+                return Err(GameError::TableFull);
+            }
+            self.round_defender_hand = Some(defender_hand);
+            self.last_round_summary = None;
+        } else if self.attack_room() == 0 {
+            return Err(GameError::TableFull);
+        } else if !self.legal_attacks(player_idx).contains(&card_idx) {
+            return Err(GameError::IllegalAttack);
+        }
         let attacker = &mut self.players[player_idx];
         if let Some(card) = attacker.remove_card(card_idx) {
             self.table_cards.push((card, None));
-            // Transition to Defense phase after successful attack
-            self.game_phase = GamePhase::Defense;
-            // Set the attacker and defender roles properly
-            self.current_attacker = player_idx;
-            self.current_defender = (player_idx + 1) % self.players.len();
+            self.record_action(player_idx, PlayerAction::Attacking);
+            // Only the opening attack seats the attacker and defender
+            if opening {
+                self.current_attacker = player_idx;
+                self.current_defender = defender_idx;
+            }
+            if self.game_phase != GamePhase::Defense {
+                self.game_phase = GamePhase::Defense;
+            }
+            self.record_move(Move::Attack {
+                player: player_idx,
+                cards: vec![card],
+            });
             return Ok(());
         }
-        Err("Invalid card index")
+        Err(GameError::InvalidCardIndex)
     }
 
-    /// Handle passing an attack to the next player if cards are the same rank
-    pub fn pass_attack(&mut self, card_idx: usize, _attack_idx: usize) -> Result<(), &'static str> {
-        let defender = &mut self.players[self.current_defender];
+    /// Whether the next player still in the game could take on the table plus `added` more
+    /// attacks, as the defender of a passed round. Nobody can once everyone else is out.
+    pub fn pass_fits(&self, added: usize) -> bool {
+        let Some(next_defender) = self.next_defender(self.current_defender) else {
+            return false;
+        };
+        let next_hand = self.players[next_defender].hand_size();
+        self.table_cards.len() + added <= self.attack_cap(next_hand)
+    }
+    /// Whether the defender may still pass the round on. Passing is only allowed before
+    /// they beat any card on the table.
+    pub fn pass_open(&self) -> bool {
+        self.table_cards
+            .iter()
+            .all(|(_, defense)| defense.is_none())
+    }
+    /// Whether `card` may pass `attack` on: the same rank, and not a trump when
+    /// `forbid_trump_pass` is set
+    pub fn can_pass_with(&self, card: &Card, attack: &Card) -> bool {
+        card.can_pass(attack) && !(self.forbid_trump_pass && self.trump_suit == Some(card.suit))
+    }
+    /// Handle passing an attack to the next player if cards are the same rank.
+    /// With `allow_trump_reveal_pass` a same-rank trump is only shown and stays in hand.
+    /// The passed round starts over for the next defender, so its attacks must fit their hand.
+    pub fn pass_attack(&mut self, card_idx: usize, attack_idx: usize) -> Result<(), GameError> {
+        let Some((attack_card, _)) = self.table_cards.get(attack_idx) else {
+            return Err(GameError::InvalidAttackIndex);
+        };
+        if !self.pass_open() {
+            return Err(GameError::PassAfterDefense);
+        }
+        let defender = &self.players[self.current_defender];
+        let reveal = match defender.hand().get(card_idx) {
+            None => return Err(GameError::InvalidCardIndex),
+            Some(card) if !card.can_pass(attack_card) => return Err(GameError::IllegalPass),
+            Some(card) if !self.can_pass_with(card, attack_card) => {
+                return Err(GameError::TrumpPass);
+            }
+            Some(card) => self.allow_trump_reveal_pass && self.trump_suit == Some(card.suit),
+        };
+        // Only a player still in the game can be passed to
+        let Some(next_defender) = self.next_defender(self.current_defender) else {
+            return Err(GameError::NoPassTarget);
+        };
+        if !self.pass_fits(if reveal { 0 } else { 1 }) {
+            return Err(GameError::TableFull);
+        }
+        self.round_defender_hand = Some(self.players[next_defender].hand_size());
+        if reveal {
+            // Reveal pass - the table stays as it is and only the roles move on
+            let old_defender = self.current_defender;
+            let card = self.players[old_defender].hand()[card_idx];
+            self.record_action(old_defender, PlayerAction::PassedAttack);
+            self.current_attacker = old_defender;
+            self.current_defender = next_defender;
+            self.game_phase = GamePhase::Defense;
+            self.record_move(Move::Pass {
+                player: old_defender,
+                card,
+                reveal: true,
+            });
+            return Ok(());
+        }
         // Remove the card from defender's hand
-        if let Some(card) = defender.remove_card(card_idx) {
+        if let Some(card) = self.players[self.current_defender].remove_card(card_idx) {
             // Add a new attack card to the table
             self.table_cards.push((card, None));
             // Swap the roles - the current defender becomes the attacker
             let old_defender = self.current_defender;
+            self.record_action(old_defender, PlayerAction::PassedAttack);
             self.current_attacker = old_defender;
-            // The original attacker becomes the defender
-            self.current_defender = (old_defender + 1) % self.players.len();
+            // The next player still in the game becomes the defender
+            self.current_defender = next_defender;
             // Stay in Defense phase
             self.game_phase = GamePhase::Defense;
+            self.record_move(Move::Pass {
+                player: old_defender,
+                card,
+                reveal: false,
+            });
             return Ok(());
         }
-        Err("Failed to remove card from hand during pass")
+        Err(GameError::InvalidCardIndex)
     }
     /// General defense logic
-    pub fn defend(&mut self, card_idx: usize) -> Result<(), &'static str> {
+    /// Defends the first undefended attack on the table, see `defend_at`.
+    pub fn defend(&mut self, card_idx: usize) -> Result<(), GameError> {
         // Find the first undefended attack card
         let undefended_idx = self
             .table_cards
             .iter()
             .position(|(_, defense)| defense.is_none());
         if let Some(attack_idx) = undefended_idx {
-            let defender = &mut self.players[self.current_defender];
-            if card_idx >= defender.hand().len() {
-                return Err("Invalid card index");
-            }
-            let defense_card = defender.hand()[card_idx];
-            let attack_card = self.table_cards[attack_idx].0;
-            // First check if this is a pass (podkidnoy variant)
-            // Check for same rank (passing condition)
-            if defense_card.can_pass(&attack_card) {
-                // This is a pass - handle differently from a regular defense
-                return self.pass_attack(card_idx, attack_idx);
-            }
-            // Check if defense is valid
-            let is_valid = if let Some(trump) = self.trump_suit {
-                if attack_card.suit == trump {
-                    // If attacking with trump, must defend with higher trump
-                    defense_card.suit == trump && defense_card.rank > attack_card.rank
-                } else if defense_card.suit == trump {
-                    // Trump can beat any non-trump
-                    true
-                } else {
-                    // Same suit, higher rank
-                    defense_card.suit == attack_card.suit && defense_card.rank > attack_card.rank
-                }
-            } else {
-                // No trump suit - just check for same suit and higher rank
-                defense_card.suit == attack_card.suit && defense_card.rank > attack_card.rank
-            };
-            if is_valid {
-                // Remove the card from defender's hand
-                if let Some(card) = defender.remove_card(card_idx) {
-                    // Add as defense card
-                    self.table_cards[attack_idx].1 = Some(card);
-                    return Ok(());
-                }
-                Err("Failed to remove card from hand")
-            } else {
-                Err("Invalid defense - card cannot beat the attack")
-            }
+            self.defend_at(attack_idx, card_idx)
         } else {
-            Err("No undefended attacks to defend against")
+            Err(GameError::NoUndefendedAttacks)
         }
     }
+    /// Defends the attack at `attack_idx` on the table with the card at `card_idx` in the
+    /// defender's hand. Passing with a same-rank card is a separate move, see `pass_attack`.
+    pub fn defend_at(&mut self, attack_idx: usize, card_idx: usize) -> Result<(), GameError> {
+        match self.table_cards.get(attack_idx) {
+            None => return Err(GameError::InvalidAttackIndex),
+            Some((_, Some(_))) => return Err(GameError::AlreadyDefended),
+            Some((_, None)) => {}
+        }
+        if card_idx >= self.players[self.current_defender].hand().len() {
+            return Err(GameError::InvalidCardIndex);
+        }
+        // Check if defense is valid
+        if !self.legal_defenses(attack_idx).contains(&card_idx) {
+            return Err(GameError::IllegalDefense);
+        }
+        // Remove the card from defender's hand
+        if let Some(card) = self.players[self.current_defender].remove_card(card_idx) {
+            // Add as defense card
+            self.table_cards[attack_idx].1 = Some(card);
+            self.record_action(self.current_defender, PlayerAction::Defended);
+            self.record_move(Move::Defend {
+                player: self.current_defender,
+                attack: self.table_cards[attack_idx].0,
+                card,
+            });
+            return Ok(());
+        }
+        Err(GameError::InvalidCardIndex)
+    }
+    /// Beats open attacks with a plan of (table index, card) pairs, the way
+    /// `AiPlayer::make_full_defense` makes them, then closes the defense like `discard_cards`.
+    /// Either the whole plan is played or, on an error, none of it.
+    pub fn defend_with(&mut self, plan: &[(usize, Card)]) -> Result<(), GameError> {
+        // The plan names cards, pin each to its own hand index before anything is played so
+        // identical copies from a multi-deck game aren't both matched to the first one
+        let cards: Vec<Card> = plan.iter().map(|&(_, card)| card).collect();
+        let hand_indices: Vec<usize> = self.players[self.current_defender]
+            .find_cards(&cards)
+            .into_iter()
+            .collect::<Option<_>>()
+            .ok_or(GameError::InvalidCardIndex)?;
+        let mut moves: Vec<(usize, usize)> = plan
+            .iter()
+            .map(|&(attack_idx, _)| attack_idx)
+            .zip(hand_indices)
+            .collect();
+        // Highest hand index first, so playing a card doesn't shift the ones still to come
+        moves.sort_by_key(|&(_, hand_idx)| std::cmp::Reverse(hand_idx));
+        let before_defense = self.clone();
+        for (attack_idx, hand_idx) in moves {
+            if let Err(e) = self.defend_at(attack_idx, hand_idx) {
+                *self = before_defense;
+                return Err(e);
+            }
+        }
+        self.discard_cards(Vec::new());
+        Ok(())
+    }
     /// Checks defense then puts cards into the table.
+    /// Once every attack is beaten the attacker gets a reinforcement window if they can
+    /// add to the table, otherwise the round ends straight away.
     pub fn discard_cards(&mut self, cards: Vec<(usize, Card)>) {
-        cards.iter().for_each(|(idx, card)| {
-            self.table_cards[*idx].1 = Some(*card); // add card to defended table
-        });
+        for &(idx, card) in &cards {
+            // Pairs beaten before, by `defend_at`, are already in the history
+            let (attack, defense) = &mut self.table_cards[idx];
+            if defense.replace(card).is_none() {
+                let attack = *attack;
+                self.record_move(Move::Defend {
+                    player: self.current_defender,
+                    attack,
+                    card,
+                });
+            }
+        }
+        if !cards.is_empty() {
+            self.record_action(self.current_defender, PlayerAction::Defended);
+        }
         // Check if all attacks are defended
         let all_defended = !self
             .table_cards
             .iter()
             .any(|(_, defense)| defense.is_none());
         if all_defended {
-            // All attacks successfully defended
-            // Move cards from table to discard pile
-            let mut cards_to_discard = Vec::new();
-            for (attack, defense) in std::mem::take(&mut self.table_cards) {
-                cards_to_discard.push(attack);
-                if let Some(def_card) = defense {
-                    cards_to_discard.push(def_card);
+            match self.next_thrower(None) {
+                Some(thrower) => {
+                    self.thrower = Some(thrower);
+                    self.game_phase = GamePhase::Reinforce;
                 }
+                None => self.end_round(),
             }
-            self.discard_pile.extend(cards_to_discard);
-            // Successful defense - swap attacker and defender roles
-            // After successful defense, defender becomes new attacker
-            let old_defender = self.current_defender;
-            self.current_attacker = old_defender;
-            self.current_defender = (old_defender + 1) % self.players.len();
-            // Move to drawing phase
-            self.game_phase = GamePhase::Drawing;
         }
     }
-    /// Take cards from the table and put them into the player's hand.
-    pub fn take_cards(&mut self) -> Result<(), &'static str> {
-        assert!(self.game_phase == GamePhase::Defense);
+    /// Ranks of every attacking and defending card on the table.
+    pub fn table_ranks(&self) -> HashSet<Rank> {
+        self.table_cards
+            .iter()
+            .flat_map(|(attack, defense)| {
+                std::iter::once(attack.rank).chain(defense.map(|d| d.rank))
+            })
+            .collect()
+    }
+    /// Hand indices of the cards `player_idx` may put on the table. Any card opens a round,
+    /// after that only cards matching a rank on the table while the round has room for
+    /// another attack (see `attack_room`). The defender never attacks.
+    /// Whose turn it is and the phase are left to the caller.
+    pub fn legal_attacks(&self, player_idx: usize) -> Vec<usize> {
+        let Some(player) = self.players.get(player_idx) else {
+            return Vec::new();
+        };
+        if player_idx == self.current_defender {
+            return Vec::new();
+        }
         if self.table_cards.is_empty() {
-            return Err("No cards on table to take");
-        }
-        let defender = &mut self.players[self.current_defender];
-        let table_cards = std::mem::take(&mut self.table_cards);
-        let mut cards_to_take = Vec::new();
-        for (attack, defense) in table_cards {
-            cards_to_take.push(attack);
-            if let Some(card) = defense {
-                cards_to_take.push(card);
+            return (0..player.hand_size()).collect();
+        }
+        if self.attack_room() == 0 {
+            return Vec::new();
+        }
+        let table_ranks = self.table_ranks();
+        player
+            .hand()
+            .iter()
+            .enumerate()
+            .filter(|(_, card)| table_ranks.contains(&card.rank))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+    /// Hand indices of the defender's cards that beat the attack at `attack_idx`.
+    /// Empty when there is no attack there or it is already beaten.
+    pub fn legal_defenses(&self, attack_idx: usize) -> Vec<usize> {
+        let Some((attack_card, None)) = self.table_cards.get(attack_idx) else {
+            return Vec::new();
+        };
+        self.players[self.current_defender]
+            .hand()
+            .iter()
+            .enumerate()
+            .filter(|(_, card)| card.can_beat(attack_card, self.trump_suit))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+    /// Whether `player_idx` may throw in another card: there is a round on the table and
+    /// they hold a card `legal_attacks` allows.
+    pub fn can_throw_in(&self, player_idx: usize) -> bool {
+        !self.table_cards.is_empty() && !self.legal_attacks(player_idx).is_empty()
+    }
+    /// The next player who can throw in after `after`, or the first one with `None`.
+    /// The attacker goes first, then everyone else but the defender around the table.
+    fn next_thrower(&self, after: Option<usize>) -> Option<usize> {
+        let player_count = self.players.len();
+        let order = (0..player_count)
+            .map(|offset| (self.current_attacker + offset) % player_count)
+            .filter(|&idx| idx != self.current_defender);
+        let skip = match after {
+            Some(after) => order.clone().position(|idx| idx == after)? + 1,
+            None => 0,
+        };
+        order.skip(skip).find(|&idx| self.can_throw_in(idx))
+    }
+    /// The player whose turn it is in the reinforcement window. Outside it, the attacker.
+    pub fn current_thrower(&self) -> usize {
+        self.thrower.unwrap_or(self.current_attacker)
+    }
+    /// Adds the current thrower's card at `card_idx` to the table during the reinforcement
+    /// window, see `throw_in`.
+    pub fn reinforce(&mut self, card_idx: usize) -> Result<(), GameError> {
+        self.throw_in(self.current_thrower(), card_idx)
+    }
+    /// Adds the card at `card_idx` of any player but the defender to the table. The card
+    /// must match a rank on the table and the defender is back on defense. The attacker
+    /// and defender stay the same, whoever threw the card in.
+    pub fn throw_in(&mut self, player_idx: usize, card_idx: usize) -> Result<(), GameError> {
+        if player_idx == self.current_defender {
+            return Err(GameError::NotYourTurn);
+        }
+        let Some(card) = self.players[player_idx].hand().get(card_idx) else {
+            return Err(GameError::InvalidCardIndex);
+        };
+        if !self.table_ranks().contains(&card.rank) {
+            return Err(GameError::IllegalReinforcement);
+        }
+        if self.attack_room() == 0 {
+            return Err(GameError::TableFull);
+        }
+        let Some(card) = self.players[player_idx].remove_card(card_idx) else {
+            return Err(GameError::InvalidCardIndex);
+        };
+        self.table_cards.push((card, None));
+        self.record_action(player_idx, PlayerAction::Attacking);
+        self.thrower = None;
+        self.game_phase = GamePhase::Defense;
+        self.record_move(Move::Attack {
+            player: player_idx,
+            cards: vec![card],
+        });
+        Ok(())
+    }
+    /// The current thrower adds nothing. The next player who can throw in gets the chance,
+    /// and once nobody can the round ends.
+    pub fn decline_throw_in(&mut self) {
+        match self.next_thrower(Some(self.current_thrower())) {
+            Some(thrower) => {
+                debug(format!("Player {} may throw in next", thrower));
+                self.thrower = Some(thrower);
             }
+            None => self.end_round(),
+        }
+    }
+    /// Closes a successfully defended round: the table goes to the discard pile and the
+    /// defender becomes the next attacker.
+    ///
+    /// The table is cleared in exactly two places. A defended round ends here and its cards
+    /// are discarded, a taken one ends in `take_cards` and its cards go to the defender's
+    /// hand. A passed attack doesn't end the round, the table stays for the next defender.
+    pub fn end_round(&mut self) {
+        debug_assert!(
+            self.table_cards
+                .iter()
+                .all(|(_, defense)| defense.is_some()),
+            "discarding a table with unbeaten attacks, they should have been taken"
+        );
+        let card_count = self.card_count();
+        let cards_to_discard = self.clear_table();
+        self.resolve_round(RoundSummary::Discarded {
+            defender: self.current_defender,
+            cards: cards_to_discard.len(),
+        });
+        self.card_tracker.record_discard(&cards_to_discard);
+        self.record_move(Move::Discard {
+            player: self.current_defender,
+            cards: cards_to_discard.clone(),
+        });
+        self.discard_pile.extend(cards_to_discard);
+        debug_assert_eq!(self.card_count(), card_count, "cards lost ending the round");
+        self.thrower = None;
+        // Successful defense - swap attacker and defender roles
+        // After successful defense, defender becomes new attacker
+        let old_defender = self.current_defender;
+        self.current_attacker = old_defender;
+        self.current_defender = self.next_in_play(old_defender);
+        // Move to drawing phase
+        self.game_phase = GamePhase::Drawing;
+    }
+    /// The attacker stops attacking in the Attack phase. Beaten cards still on the table
+    /// close the round the way `end_round` does: discarded, and the defender attacks next.
+    /// An attack the defender hasn't answered can't be walked away from, and a round that
+    /// hasn't been opened can't be passed.
+    pub fn end_attack(&mut self) -> Result<(), GameError> {
+        if self.game_phase != GamePhase::Attack {
+            return Err(GameError::WrongPhase);
+        }
+        if self.table_cards.is_empty() {
+            return Err(GameError::NoCardsOnTable);
         }
+        if self
+            .table_cards
+            .iter()
+            .any(|(_, defense)| defense.is_none())
+        {
+            return Err(GameError::IncompleteDefense);
+        }
+        self.end_round();
+        Ok(())
+    }
+    /// Counts a finished round and keeps its outcome for the draw summary
+    fn resolve_round(&mut self, summary: RoundSummary) {
+        self.round += 1;
+        self.last_round_summary = Some(summary);
+        info(format!("Round {} resolved: {:?}", self.round, summary));
+    }
+    /// The number of the round in play, starting from 1
+    pub fn current_round(&self) -> u32 {
+        self.round + 1
+    }
+    /// Take cards from the table and put them into the player's hand.
+    pub fn take_cards(&mut self) -> Result<(), GameError> {
+        if self.game_phase != GamePhase::Defense {
+            return Err(GameError::WrongPhase);
+        }
+        if self.table_cards.is_empty() {
+            return Err(GameError::NoCardsOnTable);
+        }
+        let card_count = self.card_count();
+        let cards_to_take = self.clear_table();
         // adding cards to defender hand.
-        defender.add_cards(cards_to_take);
+        let taken = cards_to_take.len();
+        self.card_tracker
+            .record_pick_up(self.current_defender, &cards_to_take);
+        let taken_cards = cards_to_take.clone();
+        self.players[self.current_defender].add_cards(cards_to_take);
+        debug_assert_eq!(self.card_count(), card_count, "cards lost taking the table");
+        self.record_action(self.current_defender, PlayerAction::Took(taken));
+        self.resolve_round(RoundSummary::Taken {
+            defender: self.current_defender,
+            cards: taken,
+        });
         // Move to drawing phase
         self.game_phase = GamePhase::Drawing;
+        self.record_move(Move::Take {
+            player: self.current_defender,
+            cards: taken_cards,
+        });
         Ok(())
     }
-    /// General Draw cards logic.
+    /// General Draw cards logic. Every hand is filled back up to 6 in `drawing_order`
+    /// while the deck lasts.
     pub fn draw_cards(&mut self) {
         if self.game_phase != GamePhase::Drawing {
             return;
         }
-        // Increment stuck counter to detect infinite loops
-        self.stuck_counter += 1;
-        if self.stuck_counter > 5 {
-            // Reset game phase and counter
-            self.game_phase = GamePhase::Attack;
-            self.stuck_counter = 0;
-            // Clear the table if needed
-            if !self.table_cards.is_empty() {
-                self.discard_pile
-                    .extend(self.table_cards.drain(..).flat_map(|(a, d)| {
-                        let mut cards = vec![a];
-                        if let Some(def) = d {
-                            cards.push(def);
-                        }
-                        cards
-                    }));
-            }
-            return;
-        }
+        // Only `end_round` and `take_cards` lead here and both have cleared the table
+        debug_assert!(
+            self.table_cards.is_empty(),
+            "drawing with cards on the table"
+        );
         // Early return if there are no players who need cards
         let players_need_cards = self
             .players
             .iter()
             .any(|p| p.hand_size() < 6 && !self.deck.is_empty());
         if !players_need_cards {
-            self.game_phase = GamePhase::Attack;
-            self.stuck_counter = 0;
+            // With the deck gone the round may have put players out, or ended the game
+            if !self.check_game_over() {
+                self.seat_next_round();
+                self.game_phase = GamePhase::Attack;
+            }
             return;
         }
-        // Drawing logic - first attacker draws, then defender, then others
+        // Drawing logic - see `drawing_order`
         if !self.deck.is_empty() {
-            let player_count = self.players.len();
-            let mut drawing_order = VecDeque::new();
-            // Start with attacker
-            let mut idx = self.current_attacker;
-            for _ in 0..player_count {
-                drawing_order.push_back(idx);
-                idx = (idx + 1) % player_count;
-            }
             // Draw cards to bring each hand back to 6
-            while let Some(player_idx) = drawing_order.pop_front() {
+            for player_idx in self.drawing_order() {
                 let player = &mut self.players[player_idx];
                 let cards_needed = 6usize.saturating_sub(player.hand_size());
                 if cards_needed > 0 && !self.deck.is_empty() {
+                    // Near the end the deck may hold fewer cards than needed (the trump card
+                    // comes last), later players in the drawing order then get nothing
                     let new_cards = self.deck.deal(cards_needed);
-                    // No need to track if cards are drawn
-                    player.add_cards(new_cards);
+                    player.add_cards(new_cards.clone());
+                    let hand_size = player.hand_size();
+                    self.record_action(player_idx, PlayerAction::DrewTo(hand_size));
+                    self.record_move(Move::Draw {
+                        player: player_idx,
+                        cards: new_cards,
+                    });
                 }
             }
+            // This draw took the last card, from here on nobody refills their hand
+            if self.deck.is_empty() {
+                self.endgame_notice = true;
+            }
             // Check if any player has run out of cards and the game is over
             self.check_game_over();
             if self.game_phase != GamePhase::GameOver {
-                // Only change attacker/defender if the table is NOT empty
-                // If table is empty, the defender already became the attacker in the defend method
-                if !self.table_cards.is_empty() {
-                    let _prev_attacker = self.current_attacker;
-                    let _prev_defender = self.current_defender;
-                    // If the defender took cards, they're skipped
-                    self.current_attacker = (self.current_defender + 1) % self.players.len();
-                    self.current_defender = (self.current_attacker + 1) % self.players.len();
-                }
+                self.seat_next_round();
                 // Set the game phase back to Attack
                 self.game_phase = GamePhase::Attack;
             }
@@ -302,47 +885,214 @@ impl GameState {
             // Check for game over condition
             self.check_game_over();
             if self.game_phase != GamePhase::GameOver {
+                self.seat_next_round();
                 self.game_phase = GamePhase::Attack;
             }
         }
-        // At the end of draw_cards, reset the stuck counter if we successfully transitioned
-        if self.game_phase == GamePhase::Attack || self.game_phase == GamePhase::GameOver {
-            self.stuck_counter = 0;
+    }
+    /// Whether `player_idx` is out of the game: the deck is gone and so is their hand.
+    /// It doesn't matter whether they played their last card attacking or defending.
+    pub fn is_out(&self, player_idx: usize) -> bool {
+        self.deck.is_empty() && self.players[player_idx].is_empty_hand()
+    }
+    /// The first player after `player_idx` around the table who is still in the game.
+    /// Falls back to the next seat when everyone else is out, the game is over then anyway.
+    /// A pass doesn't end the round, so it seats its defender with `next_defender`.
+    pub fn next_in_play(&self, player_idx: usize) -> usize {
+        self.next_defender(player_idx)
+            .unwrap_or((player_idx + 1) % self.players.len())
+    }
+    /// The first player after `player_idx` around the table who is still in the game,
+    /// `None` when everyone else is out
+    pub fn next_defender(&self, player_idx: usize) -> Option<usize> {
+        let player_count = self.players.len();
+        (1..player_count)
+            .map(|offset| (player_idx + offset) % player_count)
+            .find(|&idx| !self.is_out(idx))
+    }
+    /// Picks the attacker and defender of the round after the draw. After a take the player
+    /// behind the defender attacks, after a defense the defender does, and players who are
+    /// out are skipped for both roles.
+    fn seat_next_round(&mut self) {
+        let attacker = match self.last_round_summary {
+            Some(RoundSummary::Taken { defender, .. }) => self.next_in_play(defender),
+            _ if self.is_out(self.current_attacker) => self.next_in_play(self.current_attacker),
+            _ => self.current_attacker,
+        };
+        self.current_attacker = attacker;
+        self.current_defender = self.next_in_play(attacker);
+    }
+    /// An attacker left without cards can't open the round, and that is not the same as
+    /// declining to attack. Once the deck is gone they are out: the game ends if only one
+    /// player still holds cards, otherwise the next player in play attacks instead. While
+    /// the deck lasts they draw back up like after any round.
+    /// Returns whether the attacker's empty hand was dealt with.
+    pub fn retire_empty_attacker(&mut self) -> bool {
+        if self.game_phase != GamePhase::Attack
+            || !self.table_cards.is_empty()
+            || !self.players[self.current_attacker].is_empty_hand()
+        {
+            return false;
+        }
+        if self.check_game_over() {
+            return true;
         }
+        if !self.deck.is_empty() {
+            debug(format!(
+                "Player {} has no cards to attack with, drawing",
+                self.current_attacker
+            ));
+            self.game_phase = GamePhase::Drawing;
+            self.draw_cards();
+            return true;
+        }
+        let attacker = self.next_in_play(self.current_attacker);
+        debug(format!(
+            "Player {} is out, player {} attacks instead",
+            self.current_attacker, attacker
+        ));
+        self.current_attacker = attacker;
+        self.current_defender = self.next_in_play(attacker);
+        true
+    }
+    /// The order hands are refilled in after a round: the attacker first, then the other
+    /// players going round the table from the attacker, the defender last. Each player fills
+    /// up to 6 before the next draws, so when the deck runs short the players early in the
+    /// order are topped up and the defender is the one left short.
+    pub fn drawing_order(&self) -> Vec<usize> {
+        let player_count = self.players.len();
+        let mut order: Vec<usize> = (0..player_count)
+            .map(|offset| (self.current_attacker + offset) % player_count)
+            .filter(|&idx| idx != self.current_defender)
+            .collect();
+        if self.current_defender < player_count {
+            order.push(self.current_defender);
+        }
+        order
     }
-    /// Check game over logic.
+    /// How many cards each player will draw on the next `draw_cards`, indexed by player.
+    /// Follows `drawing_order` until the deck runs out.
+    pub fn draw_preview(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.players.len()];
+        let mut remaining = self.deck.remaining();
+        for idx in self.drawing_order() {
+            let needed = 6usize
+                .saturating_sub(self.players[idx].hand_size())
+                .min(remaining);
+            counts[idx] = needed;
+            remaining -= needed;
+        }
+        counts
+    }
+    /// Whether a human is seated, i.e. someone has to acknowledge the draw.
+    pub fn has_human_player(&self) -> bool {
+        self.players
+            .iter()
+            .any(|player| player.player_type() == &PlayerType::Human)
+    }
+    /// Concede the game: `player_idx` becomes the durak and everyone else wins over them.
+    /// The first player out is the winner, or the next player in play if nobody is out yet.
+    pub fn surrender(&mut self, player_idx: usize) -> Result<(), GameError> {
+        if self.game_phase == GamePhase::GameOver {
+            return Err(GameError::WrongPhase);
+        }
+        self.durak = Some(player_idx);
+        self.winner = Some(
+            self.finished
+                .first()
+                .copied()
+                .unwrap_or_else(|| self.next_in_play(player_idx)),
+        );
+        self.game_phase = GamePhase::GameOver;
+        Ok(())
+    }
+    /// Adds the players who just went out to `finished`. Players going out in the same round
+    /// are ordered the way they draw, the attacker first.
+    /// Returns how many went out.
+    fn record_finished(&mut self) -> usize {
+        let out: Vec<usize> = self
+            .drawing_order()
+            .into_iter()
+            .filter(|&idx| self.is_out(idx) && !self.finished.contains(&idx))
+            .collect();
+        for &idx in &out {
+            debug(format!("Player {} is out of the game", idx));
+        }
+        self.finished.extend(&out);
+        out.len()
+    }
+    /// Check game over logic. Only decided between rounds: a player who empties their hand
+    /// on the table, attacking or defending, waits for the round to be beaten or taken.
+    /// Players who are out leave the table, and the game goes on until one player is left
+    /// holding cards, the durak. The first player out is the winner.
     pub fn check_game_over(&mut self) -> bool {
-        if self.deck.is_empty() {
-            let mut players_with_cards = 0;
-            let mut last_player_with_cards = None;
-            // Count players with cards and remember the last one with cards
-            for (idx, player) in self.players.iter().enumerate() {
-                if !player.is_empty_hand() {
-                    players_with_cards += 1;
-                    last_player_with_cards = Some(idx);
-                }
+        if self.game_phase == GamePhase::GameOver {
+            return true;
+        }
+        if !self.table_cards.is_empty() || !self.deck.is_empty() {
+            return false;
+        }
+        let just_out = self.record_finished();
+        let holding: Vec<usize> = (0..self.players.len())
+            .filter(|idx| !self.finished.contains(idx))
+            .collect();
+        match holding[..] {
+            [loser_idx] => {
+                self.winner = self.finished.first().copied();
+                self.durak = Some(loser_idx);
             }
-            // Game ends when only one player (or zero) has cards left
-            if players_with_cards <= 1 {
-                // If there's one player with cards, they're the "durak" (loser)
-                // In Durak, the winner is the player who gets rid of cards first
-                if players_with_cards == 1 {
-                    if let Some(loser_idx) = last_player_with_cards {
-                        // In a 2-player game, if player 1 is the loser, then player 0 is the winner
-                        let winner_idx = if loser_idx == 1 { 0 } else { 1 };
-                        self.winner = Some(winner_idx);
-                        self.game_phase = GamePhase::GameOver;
-                    }
-                } else if players_with_cards == 0 {
-                    // Draw or edge case - no real winner in Durak, but let's handle it
-                    self.game_phase = GamePhase::GameOver;
-                }
-                return true;
+            [] => {
+                // The last players emptied their hands in the same round: nobody is the durak,
+                // and if that was everyone there is no winner either
+                self.winner = (just_out < self.players.len())
+                    .then(|| self.finished.first().copied())
+                    .flatten();
+                self.durak = None;
             }
-            false // More than one player has cards
-        } else {
-            false // Deck is not empty
+            _ => return false,
+        }
+        self.game_phase = GamePhase::GameOver;
+        true
+    }
+    /// Every player from first out to the durak. Players still holding cards when the game
+    /// ended by surrender come after those who are out, in seating order.
+    pub fn rankings(&self) -> Vec<usize> {
+        let mut rankings = self.finished.clone();
+        rankings.extend(
+            (0..self.players.len())
+                .filter(|idx| !self.finished.contains(idx) && Some(*idx) != self.durak),
+        );
+        rankings.extend(self.durak);
+        rankings
+    }
+    /// Counts the trumps not yet out of play: those still in the deck plus those in every hand.
+    /// Returns 0 in a no-trump game.
+    pub fn trumps_remaining_in_deck_and_hands(&self) -> usize {
+        let Some(trump_suit) = self.trump_suit else {
+            return 0;
+        };
+        let in_deck = self
+            .deck
+            .cards
+            .iter()
+            .filter(|card| card.suit == trump_suit)
+            .count();
+        let in_hands: usize = self
+            .players
+            .iter()
+            .map(|player| player.trump_count(trump_suit))
+            .sum();
+        in_deck + in_hands
+    }
+    fn record_action(&mut self, player_idx: usize, action: PlayerAction) {
+        if self.last_actions.len() < self.players.len() {
+            self.last_actions.resize(self.players.len(), None);
         }
+        self.last_actions[player_idx] = Some(action);
+    }
+    /// Logs `played` in the move history
+    fn record_move(&mut self, played: Move) {
+        self.history.record(played);
     }
     // Getters
     pub fn players(&self) -> &[Player] {
@@ -357,6 +1107,14 @@ impl GameState {
     pub fn trump_suit(&self) -> Option<Suit> {
         self.trump_suit
     }
+    /// The face-up card at the bottom of the deck. `None` once it has been drawn with the
+    /// rest of the deck, and unless `TrumpRule::BottomCard` picked the trump: under the
+    /// other rules the bottom card is just a card, dealt face down like the rest.
+    pub fn trump_card(&self) -> Option<&Card> {
+        self.deck
+            .bottom_card()
+            .filter(|_| self.trump_rule == TrumpRule::BottomCard && self.trump_suit.is_some())
+    }
     pub fn table_cards(&self) -> &[(Card, Option<Card>)] {
         &self.table_cards
     }
@@ -369,33 +1127,82 @@ impl GameState {
     pub fn game_phase(&self) -> &GamePhase {
         &self.game_phase
     }
+    pub fn last_action(&self, player_idx: usize) -> Option<PlayerAction> {
+        self.last_actions.get(player_idx).copied().flatten()
+    }
     pub fn winner(&self) -> Option<usize> {
         self.winner
     }
+    pub fn durak(&self) -> Option<usize> {
+        self.durak
+    }
+    /// Whether the game ended with the last hands emptied at once, leaving no durak
+    pub fn is_draw(&self) -> bool {
+        self.game_phase == GamePhase::GameOver && self.durak.is_none()
+    }
+    /// How many cards of the trump suit `from_perspective` hasn't seen, see `unseen_in_suit`.
+    /// `None` in a no-trump game.
+    pub fn unseen_trumps(&self, from_perspective: usize) -> Option<usize> {
+        Some(self.unseen_in_suit(from_perspective, self.trump_suit?))
+    }
+    /// How many cards of `suit` `from_perspective` hasn't seen: not in their hand, the
+    /// discard pile, on the table or face up under the deck, see `trump_card`. The rest
+    /// are in other hands or still in the deck. Every pack in a multi-deck game adds a
+    /// full suit.
+    pub fn unseen_in_suit(&self, from_perspective: usize, suit: Suit) -> usize {
+        let table = self
+            .table_cards
+            .iter()
+            .flat_map(|(attack, defense)| std::iter::once(attack).chain(defense));
+        let seen = self.players[from_perspective]
+            .hand()
+            .iter()
+            .chain(&self.discard_pile)
+            .chain(table)
+            .chain(self.trump_card())
+            .filter(|card| card.suit == suit && !card.is_joker())
+            .count();
+        (self.pack_size.ranks().len() * self.deck_count).saturating_sub(seen)
+    }
     #[allow(dead_code)]
     pub fn discard_pile(&self) -> &[Card] {
         &self.discard_pile
     }
-    /// Helper method to force the game state into Attack phase
-    /// Only used as an emergency measure to prevent freezes
-    pub fn force_attack_phase(mut state: GameState) -> GameState {
-        //warn!("EMERGENCY: Forcing game to Attack phase");
-        state.game_phase = GamePhase::Attack;
-        state.stuck_counter = 0; // Reset stuck counter when forcing attack phase
-                                 // Clear the table if needed
-        if !state.table_cards.is_empty() {
-            // No need to track the number of discarded cards
-            // Move cards to discard pile
-            let mut cards_to_discard = Vec::new();
-            for (attack, defense) in state.table_cards.drain(..) {
-                cards_to_discard.push(attack);
-                if let Some(def_card) = defense {
-                    cards_to_discard.push(def_card);
-                }
-            }
-            state.discard_pile.extend(cards_to_discard);
-        }
-        state
+    /// Every move of this game, see `MoveHistory`
+    pub fn history(&self) -> &MoveHistory {
+        &self.history
+    }
+    /// The cards seen leave the table this game, see `CardTracker`
+    pub fn card_tracker(&self) -> &CardTracker {
+        &self.card_tracker
+    }
+    /// Whether `deck_count` packs of `size` deal a full hand to everyone at the table.
+    /// A 20 card pack covers three players, a 36 card one six.
+    pub fn pack_fits_table(&self, size: DeckSize) -> bool {
+        size.cards() * self.deck_count >= self.players.len() * 6
+    }
+    /// How many cards a new deal starts with: every pack plus the jokers
+    pub fn deck_size(&self) -> usize {
+        self.pack_size.cards() * self.deck_count + if self.jokers { 2 } else { 0 }
+    }
+    /// Every card in the game, wherever it is: the deck, the hands, the table and the
+    /// discard pile. No move changes it, cards only ever move between those places.
+    pub fn card_count(&self) -> usize {
+        let in_hands: usize = self.players.iter().map(Player::hand_size).sum();
+        let on_table: usize = self
+            .table_cards
+            .iter()
+            .map(|(_, defense)| 1 + usize::from(defense.is_some()))
+            .sum();
+        self.deck.remaining() + in_hands + on_table + self.discard_pile.len()
+    }
+    /// Takes every card off the table, attacks and defenses alike. The caller decides where
+    /// they go, see `end_round` and `take_cards`.
+    fn clear_table(&mut self) -> Vec<Card> {
+        std::mem::take(&mut self.table_cards)
+            .into_iter()
+            .flat_map(|(attack, defense)| std::iter::once(attack).chain(defense))
+            .collect()
     }
     /// Helper method to set the game to defense phase
     pub fn set_phase_to_defense(&mut self, attacker_idx: usize, defender_idx: usize) {
@@ -404,3 +1211,1028 @@ impl GameState {
         self.current_defender = defender_idx;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Test that no-trump mode leaves the trump suit unset and ignores suits when defending
+    fn test_no_trump_mode_ignores_trumps() {
+        let mut game_state = GameState::new();
+        game_state.add_player("Player".to_string(), PlayerType::Human);
+        game_state.add_player("Computer".to_string(), PlayerType::Computer);
+        game_state.trump_rule = TrumpRule::NoTrump;
+        game_state.setup_game();
+        assert_eq!(game_state.trump_suit(), None);
+        // A spade would be trump in a normal game, but it cannot beat a heart here
+        game_state.table_cards = vec![(Card::new(Suit::Hearts, Rank::Seven), None)];
+        game_state.players[1].hand = vec![Card::new(Suit::Spades, Rank::Ace)];
+        game_state.set_phase_to_defense(0, 1);
+        assert!(game_state.defend(0).is_err());
+        assert!(game_state.table_cards[0].1.is_none());
+    }
+
+    #[test]
+    /// Test that the random trump rule is reproducible for a seed and covers other suits
+    /// than the bottom card's
+    fn test_random_trump_rule_is_seeded() {
+        let deal = |seed| {
+            let mut game_state = GameState::new();
+            game_state.add_player("Player".to_string(), PlayerType::Human);
+            game_state.add_player("Computer".to_string(), PlayerType::Computer);
+            game_state.trump_rule = TrumpRule::Random;
+            game_state.seed = Some(seed);
+            game_state.setup_game();
+            game_state
+        };
+        assert_eq!(deal(3).trump_suit(), deal(3).trump_suit());
+        assert!((0..50).map(deal).any(|game_state| {
+            game_state.trump_suit() != game_state.deck().bottom_card().map(|card| card.suit)
+        }));
+    }
+
+    #[test]
+    /// Test that the dealer, the last game's durak, names the suit they hold most of
+    fn test_dealer_chooses_trump() {
+        let mut game_state = GameState::new();
+        game_state.add_player("Player".to_string(), PlayerType::Human);
+        game_state.add_player("Computer".to_string(), PlayerType::Computer);
+        game_state.trump_rule = TrumpRule::DealerChooses;
+        game_state.durak = Some(0);
+        game_state.players[0].hand = vec![
+            Card::new(Suit::Clubs, Rank::Six),
+            Card::new(Suit::Clubs, Rank::Seven),
+            Card::new(Suit::Hearts, Rank::Ace),
+        ];
+        assert_eq!(game_state.dealer_choice(), Some(Suit::Clubs));
+        game_state.players[0].hand.pop();
+        game_state.players[0]
+            .hand
+            .push(Card::new(Suit::Diamonds, Rank::Ace));
+        game_state.players[0]
+            .hand
+            .push(Card::new(Suit::Diamonds, Rank::King));
+        // Two clubs against two diamonds, the ace of diamonds breaks the tie
+        assert_eq!(game_state.dealer_choice(), Some(Suit::Diamonds));
+    }
+
+    #[test]
+    /// Test that the draw preview fills the attacker first and stops when the deck runs out
+    fn test_draw_preview_attacker_first() {
+        let mut game_state = GameState::new();
+        game_state.add_player("Player".to_string(), PlayerType::Human);
+        game_state.add_player("Computer".to_string(), PlayerType::Computer);
+        game_state.deck.cards = vec![Card::new(Suit::Hearts, Rank::Six); 4];
+        game_state.players[0].hand = vec![Card::new(Suit::Clubs, Rank::Ten); 3];
+        game_state.players[1].hand = vec![Card::new(Suit::Clubs, Rank::Nine); 4];
+        game_state.current_attacker = 1;
+        game_state.current_defender = 0;
+        assert_eq!(game_state.draw_preview(), vec![2, 2]);
+        game_state.current_attacker = 0;
+        game_state.current_defender = 1;
+        assert_eq!(game_state.draw_preview(), vec![3, 1]);
+    }
+
+    #[test]
+    /// Test that trumps on the table or in the discard pile are not counted as remaining
+    fn test_trumps_remaining_in_deck_and_hands() {
+        let mut game_state = GameState::new();
+        game_state.add_player("Player".to_string(), PlayerType::Human);
+        game_state.add_player("Computer".to_string(), PlayerType::Computer);
+        game_state.trump_suit = Some(Suit::Spades);
+        game_state.deck.cards = vec![
+            Card::new(Suit::Spades, Rank::Ace),
+            Card::new(Suit::Hearts, Rank::Six),
+        ];
+        game_state.players[0].hand = vec![
+            Card::new(Suit::Spades, Rank::Six),
+            Card::new(Suit::Clubs, Rank::Ten),
+        ];
+        game_state.players[1].hand = vec![Card::new(Suit::Spades, Rank::Nine)];
+        game_state.table_cards = vec![(Card::new(Suit::Spades, Rank::Seven), None)];
+        game_state.discard_pile = vec![Card::new(Suit::Spades, Rank::Eight)];
+        assert_eq!(game_state.trumps_remaining_in_deck_and_hands(), 3);
+        game_state.trump_suit = None;
+        assert_eq!(game_state.trumps_remaining_in_deck_and_hands(), 0);
+    }
+
+    // Helper for the end of a round with `deck` cards left and the given hand sizes.
+    // Seat 0 attacked seat 1, everyone else threw in.
+    fn create_drawing_state(deck: usize, hand_sizes: &[usize]) -> GameState {
+        let mut game_state = GameState::new();
+        for (seat, &hand_size) in hand_sizes.iter().enumerate() {
+            game_state.add_player(format!("Player {}", seat), PlayerType::Computer);
+            game_state.players[seat].hand = vec![Card::new(Suit::Clubs, Rank::Ten); hand_size];
+        }
+        game_state.deck.cards = vec![Card::new(Suit::Hearts, Rank::Six); deck];
+        game_state.current_attacker = 0;
+        game_state.current_defender = 1;
+        game_state.game_phase = GamePhase::Drawing;
+        game_state
+    }
+
+    #[test]
+    /// Test that a deck too small for everyone fills the attacker up first and leaves the
+    /// defender short
+    fn test_short_deck_fills_attacker_first() {
+        let mut game_state = create_drawing_state(3, &[3, 2]);
+        assert_eq!(game_state.drawing_order(), vec![0, 1]);
+        assert_eq!(game_state.draw_preview(), vec![3, 0]);
+        game_state.draw_cards();
+        assert_eq!(game_state.players[0].hand_size(), 6);
+        assert_eq!(game_state.players[1].hand_size(), 2);
+        assert!(game_state.deck().is_empty());
+        // With one card to spare the defender gets it
+        let mut game_state = create_drawing_state(4, &[3, 2]);
+        game_state.draw_cards();
+        assert_eq!(game_state.players[1].hand_size(), 3);
+    }
+
+    #[test]
+    /// Test that the other players draw before the defender, going round from the attacker
+    fn test_defender_draws_last() {
+        let mut game_state = create_drawing_state(5, &[4, 1, 3, 6]);
+        assert_eq!(game_state.drawing_order(), vec![0, 2, 3, 1]);
+        assert_eq!(game_state.draw_preview(), vec![2, 0, 3, 0]);
+        game_state.draw_cards();
+        let hand_sizes: Vec<usize> = game_state.players.iter().map(Player::hand_size).collect();
+        assert_eq!(hand_sizes, vec![6, 1, 6, 6]);
+    }
+
+    // Helper to put player 1 on defense against the given attacks
+    fn create_defense_state(hand: Vec<Card>, attacks: Vec<Card>) -> GameState {
+        let mut game_state = GameState::new();
+        game_state.add_player("Player".to_string(), PlayerType::Human);
+        game_state.add_player("Computer".to_string(), PlayerType::Computer);
+        game_state.trump_suit = Some(Suit::Spades);
+        game_state.players[1].hand = hand;
+        game_state.table_cards = attacks.into_iter().map(|card| (card, None)).collect();
+        game_state.set_phase_to_defense(0, 1);
+        game_state
+    }
+
+    #[test]
+    /// Test that the defender can beat a later attack while an earlier one stays open
+    fn test_defend_at_chosen_later_attack() {
+        let mut game_state = create_defense_state(
+            vec![Card::new(Suit::Diamonds, Rank::Queen)],
+            vec![
+                Card::new(Suit::Hearts, Rank::Seven),
+                Card::new(Suit::Diamonds, Rank::Ten),
+            ],
+        );
+        assert!(game_state.defend_at(1, 0).is_ok());
+        assert!(game_state.table_cards[0].1.is_none());
+        assert_eq!(
+            game_state.table_cards[1].1,
+            Some(Card::new(Suit::Diamonds, Rank::Queen))
+        );
+    }
+
+    #[test]
+    /// Test that defend_at rejects defended or missing attacks and leaves the hand intact
+    fn test_defend_at_rejects_invalid_target() {
+        let mut game_state = create_defense_state(
+            vec![Card::new(Suit::Diamonds, Rank::Queen)],
+            vec![Card::new(Suit::Diamonds, Rank::Ten)],
+        );
+        game_state.table_cards[0].1 = Some(Card::new(Suit::Diamonds, Rank::Jack));
+        assert_eq!(game_state.defend_at(0, 0), Err(GameError::AlreadyDefended));
+        assert_eq!(
+            game_state.defend_at(5, 0),
+            Err(GameError::InvalidAttackIndex)
+        );
+        assert_eq!(game_state.players[1].hand_size(), 1);
+    }
+
+    #[test]
+    /// Test that with the house rule a same-rank trump passes the attack without being played
+    fn test_trump_reveal_pass_enabled() {
+        let mut game_state = create_defense_state(
+            vec![Card::new(Suit::Spades, Rank::Seven)],
+            vec![Card::new(Suit::Hearts, Rank::Seven)],
+        );
+        game_state.players[0].hand = vec![Card::new(Suit::Clubs, Rank::Ace)];
+        game_state.allow_trump_reveal_pass = true;
+        assert!(game_state.pass_attack(0, 0).is_ok());
+        assert_eq!(game_state.players[1].hand_size(), 1);
+        assert_eq!(game_state.table_cards.len(), 1);
+        assert_eq!(game_state.current_attacker, 1);
+        assert_eq!(game_state.current_defender, 0);
+        assert_eq!(game_state.game_phase, GamePhase::Defense);
+    }
+
+    #[test]
+    /// Test that without the house rule a same-rank trump is played like any other pass
+    fn test_trump_reveal_pass_disabled() {
+        let mut game_state = create_defense_state(
+            vec![Card::new(Suit::Spades, Rank::Seven)],
+            vec![Card::new(Suit::Hearts, Rank::Seven)],
+        );
+        // Two cards, so the passed round's two attacks fit the new defender's hand
+        game_state.players[0].hand = vec![
+            Card::new(Suit::Clubs, Rank::Ace),
+            Card::new(Suit::Clubs, Rank::King),
+        ];
+        assert!(game_state.pass_attack(0, 0).is_ok());
+        assert!(game_state.players[1].is_empty_hand());
+        assert_eq!(game_state.table_cards.len(), 2);
+        assert_eq!(game_state.current_defender, 0);
+    }
+
+    #[test]
+    /// Test that defending with a same-rank trump beats the attack instead of passing it
+    fn test_defend_with_same_rank_does_not_pass() {
+        let mut game_state = create_defense_state(
+            vec![Card::new(Suit::Spades, Rank::Seven)],
+            vec![Card::new(Suit::Hearts, Rank::Seven)],
+        );
+        assert!(game_state.defend(0).is_ok());
+        assert_eq!(game_state.table_cards.len(), 1);
+        assert_eq!(
+            game_state.table_cards[0].1,
+            Some(Card::new(Suit::Spades, Rank::Seven))
+        );
+        assert_eq!(game_state.current_defender, 1);
+    }
+
+    // Helper for a round where player 1 has just beaten the only attack
+    fn create_reinforce_state(attacker_hand: Vec<Card>, defender_hand: Vec<Card>) -> GameState {
+        let mut game_state =
+            create_defense_state(defender_hand, vec![Card::new(Suit::Hearts, Rank::Seven)]);
+        game_state.players[0].hand = attacker_hand;
+        game_state.discard_cards(vec![(0, Card::new(Suit::Hearts, Rank::Nine))]);
+        game_state
+    }
+
+    #[test]
+    /// Test that the attacker can reinforce and the defender can beat the new card
+    fn test_reinforce_then_defend() {
+        let mut game_state = create_reinforce_state(
+            vec![
+                Card::new(Suit::Clubs, Rank::Nine),
+                Card::new(Suit::Clubs, Rank::King),
+            ],
+            vec![Card::new(Suit::Clubs, Rank::Ace)],
+        );
+        assert_eq!(game_state.game_phase, GamePhase::Reinforce);
+        // The King matches nothing on the table
+        assert_eq!(
+            game_state.reinforce(1),
+            Err(GameError::IllegalReinforcement)
+        );
+        assert!(game_state.reinforce(0).is_ok());
+        assert_eq!(game_state.game_phase, GamePhase::Defense);
+        assert!(game_state.defend(0).is_ok());
+        let defended = vec![(1, Card::new(Suit::Clubs, Rank::Ace))];
+        // The defender's hand is empty now, so the round closes without another window
+        game_state.discard_cards(defended);
+        assert_eq!(game_state.game_phase, GamePhase::Drawing);
+        assert!(game_state.table_cards.is_empty());
+        assert_eq!(game_state.discard_pile.len(), 4);
+        assert_eq!(game_state.current_attacker, 1);
+    }
+
+    #[test]
+    /// Test that unseen trumps leave out the player's own hand, the table and the discards
+    fn test_unseen_trumps() {
+        let mut game_state = create_defense_state(
+            vec![Card::new(Suit::Spades, Rank::Ace)],
+            vec![Card::new(Suit::Spades, Rank::Six)],
+        );
+        game_state.players[0].hand = vec![
+            Card::new(Suit::Spades, Rank::Seven),
+            Card::new(Suit::Hearts, Rank::Seven),
+        ];
+        game_state.discard_pile = vec![
+            Card::new(Suit::Spades, Rank::Eight),
+            Card::new(Suit::Clubs, Rank::Eight),
+        ];
+        game_state.deck.cards.clear();
+        // Seen by player 0: 7♠ in hand, 6♠ on the table and 8♠ discarded
+        assert_eq!(game_state.unseen_trumps(0), Some(6));
+        // The face-up card under the deck counts as seen
+        game_state
+            .deck
+            .cards
+            .push(Card::new(Suit::Spades, Rank::King));
+        assert_eq!(game_state.unseen_trumps(0), Some(5));
+        // Under the other trump rules the bottom card is dealt face down
+        game_state.trump_rule = TrumpRule::Random;
+        assert_eq!(game_state.unseen_trumps(0), Some(6));
+        game_state.trump_rule = TrumpRule::BottomCard;
+        game_state.deck.cards.clear();
+        // Player 1 holds the A♠ but hasn't seen the 7♠
+        assert_eq!(game_state.unseen_trumps(1), Some(6));
+        game_state.trump_suit = None;
+        assert_eq!(game_state.unseen_trumps(0), None);
+        // The other suits are counted the same way, trump or not
+        assert_eq!(game_state.unseen_in_suit(0, Suit::Spades), 6);
+        assert_eq!(game_state.unseen_in_suit(0, Suit::Hearts), 8);
+        assert_eq!(game_state.unseen_in_suit(0, Suit::Clubs), 8);
+        assert_eq!(game_state.unseen_in_suit(0, Suit::Diamonds), 9);
+        // A 52 card pack has 13 of each suit
+        game_state.pack_size = DeckSize::Full;
+        assert_eq!(game_state.unseen_in_suit(0, Suit::Diamonds), 13);
+    }
+
+    #[test]
+    /// Test that a game is dealt from a pack of the chosen size and counts its cards by it
+    fn test_deck_sizes_deal_and_play() {
+        for (pack_size, cards) in [
+            (DeckSize::Short, 20),
+            (DeckSize::Standard, 36),
+            (DeckSize::Full, 52),
+        ] {
+            let mut game_state = GameState::new_with_seed(11);
+            game_state.pack_size = pack_size;
+            game_state.add_player("Player".to_string(), PlayerType::Human);
+            game_state.add_player("Computer".to_string(), PlayerType::Computer);
+            game_state.setup_game();
+            assert_eq!(game_state.deck_size(), cards);
+            assert_eq!(game_state.card_count(), cards);
+            assert_eq!(game_state.deck().remaining(), cards - 12);
+            let ranks = pack_size.ranks();
+            assert!(
+                game_state.players()[0]
+                    .hand()
+                    .iter()
+                    .all(|card| ranks.contains(&card.rank))
+            );
+        }
+    }
+
+    #[test]
+    /// Test that four players aren't dealt from a 20 card pack but from a standard one
+    fn test_short_pack_with_four_players() {
+        let mut game_state = GameState::new_with_seed(11);
+        game_state.pack_size = DeckSize::Short;
+        game_state.add_player("Player".to_string(), PlayerType::Human);
+        for seat in 1..4 {
+            game_state.add_player(format!("Computer {}", seat), PlayerType::Computer);
+        }
+        assert!(!game_state.pack_fits_table(DeckSize::Short));
+        game_state.setup_game();
+        assert_eq!(game_state.pack_size, DeckSize::Standard);
+        assert_eq!(game_state.deck().remaining(), 36 - 24);
+        assert!(game_state.players().iter().all(|p| p.hand_size() == 6));
+        // Two short packs are enough again
+        game_state.deck_count = 2;
+        assert!(game_state.pack_fits_table(DeckSize::Short));
+    }
+
+    #[test]
+    /// Test that taking outside the Defense phase is an error and leaves the table alone
+    fn test_take_cards_in_attack_phase_errors() {
+        let mut game_state = create_defense_state(
+            vec![Card::new(Suit::Clubs, Rank::Seven)],
+            vec![Card::new(Suit::Hearts, Rank::Six)],
+        );
+        game_state.game_phase = GamePhase::Attack;
+        assert_eq!(game_state.take_cards(), Err(GameError::WrongPhase));
+        assert_eq!(game_state.table_cards.len(), 1);
+        assert_eq!(game_state.players[1].hand_size(), 1);
+    }
+
+    #[test]
+    /// Test that a defender who cannot beat the reinforcement takes the whole table
+    fn test_reinforce_then_take() {
+        let mut game_state = create_reinforce_state(
+            vec![Card::new(Suit::Clubs, Rank::Seven)],
+            vec![Card::new(Suit::Diamonds, Rank::Six)],
+        );
+        assert_eq!(game_state.game_phase, GamePhase::Reinforce);
+        assert!(game_state.reinforce(0).is_ok());
+        assert_eq!(game_state.defend(0), Err(GameError::IllegalDefense));
+        assert!(game_state.take_cards().is_ok());
+        assert_eq!(game_state.take_cards(), Err(GameError::WrongPhase));
+        assert_eq!(game_state.game_phase, GamePhase::Drawing);
+        assert_eq!(game_state.players[1].hand_size(), 4);
+        assert_eq!(game_state.last_action(1), Some(PlayerAction::Took(3)));
+        assert_eq!(game_state.last_action(0), Some(PlayerAction::Attacking));
+        assert_eq!(
+            game_state.last_round_summary,
+            Some(RoundSummary::Taken {
+                defender: 1,
+                cards: 3
+            })
+        );
+    }
+
+    #[test]
+    /// Test that the round ends straight away when the attacker has nothing to add
+    fn test_no_reinforcement_window_without_matching_cards() {
+        let game_state = create_reinforce_state(
+            vec![Card::new(Suit::Clubs, Rank::King)],
+            vec![Card::new(Suit::Diamonds, Rank::Six)],
+        );
+        assert_eq!(game_state.game_phase, GamePhase::Drawing);
+        assert_eq!(game_state.discard_pile.len(), 2);
+        assert_eq!(
+            game_state.last_round_summary,
+            Some(RoundSummary::Discarded {
+                defender: 1,
+                cards: 2
+            })
+        );
+    }
+
+    #[test]
+    /// Test that the round summary is gone once the next round's first attack lands
+    fn test_round_summary_cleared_by_next_attack() {
+        let mut game_state = create_reinforce_state(
+            vec![Card::new(Suit::Clubs, Rank::King)],
+            vec![Card::new(Suit::Diamonds, Rank::Six)],
+        );
+        game_state.draw_cards();
+        assert!(game_state.last_round_summary.is_some());
+        assert_eq!(game_state.current_round(), 2);
+        let attacker = game_state.current_attacker;
+        assert!(game_state.attack(0, attacker).is_ok());
+        assert_eq!(game_state.last_round_summary, None);
+    }
+
+    #[test]
+    /// Test that surrendering mid-defense ends the game with the surrendering player as durak
+    fn test_surrender_from_defense() {
+        let mut game_state =
+            create_defense_state(vec![], vec![Card::new(Suit::Hearts, Rank::Seven)]);
+        assert!(game_state.surrender(1).is_ok());
+        assert_eq!(game_state.game_phase, GamePhase::GameOver);
+        assert_eq!(game_state.durak(), Some(1));
+        assert_eq!(game_state.winner(), Some(0));
+        assert_eq!(game_state.surrender(0), Err(GameError::WrongPhase));
+    }
+
+    #[test]
+    /// Test that in a multiplayer game the surrendering attacker loses to everyone still in
+    fn test_surrender_multiplayer() {
+        let mut game_state = GameState::new();
+        for idx in 0..3 {
+            game_state.add_player(format!("Player {}", idx), PlayerType::Computer);
+        }
+        game_state.game_phase = GamePhase::Attack;
+        game_state.current_attacker = 2;
+        assert!(game_state.surrender(2).is_ok());
+        assert_eq!(game_state.durak(), Some(2));
+        assert_eq!(game_state.winner(), Some(0));
+    }
+
+    #[test]
+    /// Test that the defender beating the last attack with their last card, while the attacker
+    /// is also out of cards and the deck is empty, ends the game in a draw
+    fn test_simultaneous_empty_hands_is_draw() {
+        let mut game_state = GameState::from_notation(
+            "Player*: | Computer: 8H | Table: 7H-_ | Trump: S | Attacker: Player | Defender: Computer",
+        )
+        .unwrap();
+        assert!(game_state.defend(0).is_ok());
+        game_state.discard_cards(vec![]);
+        assert_eq!(game_state.game_phase, GamePhase::Drawing);
+        game_state.draw_cards();
+        assert!(game_state.check_game_over());
+        assert!(game_state.is_draw());
+        assert_eq!(game_state.winner(), None);
+        assert_eq!(game_state.durak(), None);
+    }
+
+    #[test]
+    /// Test that an attacker who opens with their last card in the endgame waits for the
+    /// defender's answer and is skipped once out, leaving the taker to the player behind them
+    fn test_attacker_empties_hand_in_endgame() {
+        let mut game_state = GameState::from_notation(
+            "Human*: 7H | AI: 8H 9C | Other: TC JC | Trump: S | Phase: Attack \
+             | Attacker: Human | Defender: AI",
+        )
+        .unwrap();
+        game_state.attack(0, 0).unwrap();
+        assert!(game_state.is_out(0));
+        assert!(
+            !game_state.check_game_over(),
+            "the defender still has to answer"
+        );
+        assert_eq!(game_state.game_phase, GamePhase::Defense);
+        game_state.take_cards().unwrap();
+        game_state.draw_cards();
+        assert_eq!(game_state.game_phase, GamePhase::Attack);
+        assert_eq!(game_state.current_attacker(), 2);
+        assert_eq!(
+            game_state.current_defender(),
+            1,
+            "the empty seat is skipped"
+        );
+        // Heads-up, beating the last attack leaves the defender as the durak
+        let mut game_state = GameState::from_notation(
+            "Human*: 7H | AI: 8H 9C | Trump: S | Phase: Attack | Attacker: Human | Defender: AI",
+        )
+        .unwrap();
+        game_state.attack(0, 0).unwrap();
+        assert!(!game_state.check_game_over());
+        game_state.defend(0).unwrap();
+        game_state.discard_cards(vec![]);
+        game_state.draw_cards();
+        assert!(game_state.check_game_over());
+        assert_eq!(game_state.durak(), Some(1));
+        assert_eq!(game_state.winner(), Some(0));
+    }
+
+    #[test]
+    /// Test that a defender who beats the round with their last card in the endgame is out:
+    /// the attack moves on to the next player still holding cards
+    fn test_defender_empties_hand_in_endgame() {
+        let mut game_state = GameState::from_notation(
+            "Human*: 7H 9D | AI: 8H | Other: TC JC | Trump: S | Phase: Attack \
+             | Attacker: Human | Defender: AI",
+        )
+        .unwrap();
+        game_state.attack(0, 0).unwrap();
+        game_state.defend(0).unwrap();
+        game_state.discard_cards(vec![]);
+        assert_eq!(game_state.game_phase, GamePhase::Drawing);
+        assert!(game_state.is_out(1));
+        game_state.draw_cards();
+        assert!(!game_state.check_game_over());
+        assert_eq!(game_state.current_attacker(), 2);
+        assert_eq!(game_state.current_defender(), 0);
+        game_state.attack(0, 2).unwrap();
+        assert_eq!(game_state.current_defender(), 0);
+    }
+
+    #[test]
+    /// Test that in a four player game players who go out leave the table, the roles skip
+    /// them, and the game ends with the last player holding cards as durak
+    fn test_players_leave_in_order() {
+        let mut game_state = GameState::from_notation(
+            "A*: 7H | B: 8H 9C | C: TC JC 6D | D: QD KD | Trump: S | Phase: Attack \
+             | Attacker: A | Defender: B",
+        )
+        .unwrap();
+        game_state.attack(0, 0).unwrap();
+        game_state.defend(0).unwrap();
+        game_state.discard_cards(vec![]);
+        game_state.draw_cards();
+        assert_eq!(game_state.finished, vec![0]);
+        assert_eq!(game_state.current_attacker(), 1);
+        assert_eq!(game_state.current_defender(), 2);
+
+        game_state.attack(0, 1).unwrap();
+        game_state.defend(0).unwrap();
+        game_state.discard_cards(vec![]);
+        game_state.draw_cards();
+        assert_eq!(game_state.finished, vec![0, 1]);
+        game_state.attack(1, 2).unwrap();
+        game_state.defend(0).unwrap();
+        game_state.discard_cards(vec![]);
+        game_state.draw_cards();
+        assert_eq!(game_state.current_attacker(), 3);
+        assert_eq!(
+            game_state.current_defender(),
+            2,
+            "the players out are skipped"
+        );
+
+        game_state.attack(0, 3).unwrap();
+        game_state.take_cards().unwrap();
+        game_state.draw_cards();
+        assert_eq!(game_state.game_phase, GamePhase::GameOver);
+        assert_eq!(game_state.durak(), Some(2));
+        assert_eq!(game_state.winner(), Some(0));
+        assert_eq!(game_state.rankings(), vec![0, 1, 3, 2]);
+        assert!(!game_state.is_draw());
+    }
+
+    #[test]
+    /// Test that a mulligan redeals the whole deck, even with an attack on the table,
+    /// and can only be used once
+    fn test_redeal_keeps_every_card() {
+        let mut game_state = GameState::new();
+        game_state.add_player("Player".to_string(), PlayerType::Human);
+        game_state.add_player("Computer".to_string(), PlayerType::Computer);
+        game_state.seed = Some(3);
+        game_state.allow_mulligan = true;
+        game_state.setup_game();
+        let attacker = game_state.current_attacker();
+        game_state.attack(0, attacker).unwrap();
+        assert!(game_state.redeal().is_ok());
+        let in_hands: usize = game_state.players().iter().map(|p| p.hand_size()).sum();
+        assert_eq!(in_hands + game_state.deck().remaining(), 36);
+        assert!(game_state.table_cards().is_empty());
+        assert_eq!(game_state.seed, Some(3));
+        assert_eq!(game_state.redeal(), Err(GameError::MulliganUnavailable));
+    }
+
+    #[test]
+    /// Test that only the draw that empties the deck raises the endgame notice
+    fn test_endgame_notice_on_last_draw() {
+        let mut game_state = GameState::new();
+        game_state.add_player("Player".to_string(), PlayerType::Human);
+        game_state.add_player("Computer".to_string(), PlayerType::Computer);
+        game_state.setup_game();
+        game_state.players[0].hand.truncate(3);
+        let leftover = game_state.deck.remaining() - 2;
+        game_state.deck.deal(leftover);
+        game_state.game_phase = GamePhase::Drawing;
+        game_state.draw_cards();
+        assert!(game_state.deck().is_empty());
+        assert!(game_state.endgame_notice);
+        // Once the deck is gone a later draw has nothing to announce
+        game_state.endgame_notice = false;
+        game_state.game_phase = GamePhase::Drawing;
+        game_state.draw_cards();
+        assert!(!game_state.endgame_notice);
+    }
+
+    #[test]
+    /// Test that a defender who starts the round with three cards faces at most three
+    /// attacks, even after beating some of them
+    fn test_attack_limit_follows_start_hand() {
+        let mut game_state = GameState::from_notation(
+            "Human*: 6H 6D 6C 6S 7H | AI: 9H 9D 9C | Trump: S | Phase: Attack",
+        )
+        .unwrap();
+        game_state.attack(0, 0).unwrap();
+        assert_eq!(game_state.attack_limit(), 3);
+        game_state.defend(0).unwrap();
+        // The defender is down to two cards, but the round started with three
+        assert_eq!(game_state.attack_limit(), 3);
+        game_state.attack(0, 0).unwrap();
+        game_state.attack(0, 0).unwrap();
+        assert_eq!(game_state.attack_room(), 0);
+        assert_eq!(game_state.attack(0, 0), Err(GameError::TableFull));
+        assert_eq!(game_state.table_cards().len(), 3);
+        // A defender without cards can't be opened against either
+        let mut game_state =
+            GameState::from_notation("Human*: 6H | AI: | Trump: S | Phase: Attack | Deck: 7C 8C")
+                .unwrap();
+        assert_eq!(game_state.attack(0, 0), Err(GameError::TableFull));
+        assert!(game_state.table_cards().is_empty());
+    }
+
+    #[test]
+    /// Test that the trump card is the bottom card of the deck until it is drawn last
+    fn test_trump_card_until_drawn() {
+        let mut game_state = GameState::from_notation(
+            "Human*: 6H | AI: 7D | Deck: 8C 9S | Trump: S | Phase: Drawing",
+        )
+        .unwrap();
+        assert_eq!(game_state.trump_card(), game_state.deck().bottom_card());
+        let trump_card = *game_state.trump_card().unwrap();
+        game_state.draw_cards();
+        assert!(game_state.deck().is_empty());
+        assert_eq!(game_state.trump_card(), None);
+        assert!(
+            game_state
+                .players()
+                .iter()
+                .any(|player| player.hand().contains(&trump_card))
+        );
+        game_state.trump_suit = None;
+        game_state.deck.cards.push(trump_card);
+        assert_eq!(game_state.trump_card(), None);
+        // A randomly picked trump leaves the bottom card face down, whatever its suit
+        let mut game_state = GameState::new_with_seed(5);
+        game_state.trump_rule = TrumpRule::Random;
+        game_state.add_player("Human".to_string(), PlayerType::Human);
+        game_state.add_player("AI".to_string(), PlayerType::Computer);
+        game_state.setup_game();
+        assert!(game_state.trump_suit().is_some());
+        assert!(game_state.deck().bottom_card().is_some());
+        assert_eq!(game_state.trump_card(), None);
+    }
+
+    #[test]
+    /// Test that the cards of a multi-card attack join one wave: the roles are set by the
+    /// first card and stay put, also when a third player adds to the table
+    fn test_attack_wave_keeps_roles_and_phase() {
+        let mut game_state = create_drawing_state(0, &[4, 6, 2]);
+        game_state.players[0].hand = vec![Card::new(Suit::Hearts, Rank::Seven); 3];
+        game_state.players[0]
+            .hand
+            .push(Card::new(Suit::Clubs, Rank::Nine));
+        game_state.players[2].hand = vec![Card::new(Suit::Spades, Rank::Seven); 2];
+        game_state.current_attacker = 0;
+        game_state.current_defender = 0;
+        game_state.game_phase = GamePhase::Attack;
+        for attacks in 1..=3 {
+            game_state.attack(0, 0).unwrap();
+            assert_eq!(game_state.table_cards().len(), attacks);
+            assert_eq!(game_state.current_attacker(), 0);
+            assert_eq!(game_state.current_defender(), 1);
+            assert_eq!(*game_state.game_phase(), GamePhase::Defense);
+        }
+        assert_eq!(game_state.round_defender_hand, Some(6));
+        // Seat 2 sits behind seat 1, but the wave is still against seat 1
+        game_state.attack(0, 2).unwrap();
+        assert_eq!(game_state.current_attacker(), 0);
+        assert_eq!(game_state.current_defender(), 1);
+        assert_eq!(*game_state.game_phase(), GamePhase::Defense);
+    }
+
+    #[test]
+    /// Test that attack refuses cards legal_attacks doesn't offer and plays outside the
+    /// attack and defense phases
+    fn test_attack_rejects_illegal_cards() {
+        let mut game_state = GameState::from_notation(
+            "Human*: 6H 7D | AI: 9H 9D 9C | Trump: S | Phase: Drawing | Attacker: Human | Defender: AI",
+        )
+        .unwrap();
+        assert_eq!(game_state.attack(0, 0), Err(GameError::WrongPhase));
+        game_state.game_phase = GamePhase::Attack;
+        game_state.attack(0, 0).unwrap();
+        // The Seven matches no rank on the table and the defender can't add to the wave
+        assert_eq!(game_state.attack(0, 0), Err(GameError::IllegalAttack));
+        assert_eq!(game_state.attack(0, 1), Err(GameError::IllegalAttack));
+        assert_eq!(game_state.attack(5, 0), Err(GameError::InvalidCardIndex));
+        assert_eq!(game_state.table_cards().len(), 1);
+        assert_eq!(game_state.players()[0].hand_size(), 1);
+    }
+
+    #[test]
+    /// Test that the flat house rule allows six attacks whatever the defender held
+    fn test_flat_attack_limit() {
+        let mut game_state = GameState::from_notation(
+            "Human*: 6H 6D 6C 6S 7H | AI: 9H 9D 9C | Trump: S | Phase: Attack",
+        )
+        .unwrap();
+        game_state.flat_attack_limit = true;
+        game_state.attack(0, 0).unwrap();
+        assert_eq!(game_state.attack_limit(), 6);
+        assert_eq!(game_state.attack_room(), 5);
+    }
+
+    #[test]
+    /// Test that an attack can't be passed to a player with fewer cards than it would hold
+    fn test_pass_needs_room_in_next_hand() {
+        let mut game_state =
+            GameState::from_notation("Human*: 7D | AI: 6H 7S | Table: 6D-_ | Trump: S").unwrap();
+        assert!(!game_state.pass_fits(1));
+        assert_eq!(game_state.pass_attack(0, 0), Err(GameError::TableFull));
+        assert_eq!(game_state.table_cards().len(), 1);
+    }
+
+    #[test]
+    /// Test that with everyone but the defender out the attack can't be passed, not even
+    /// under the flat limit, and the out players stay out
+    fn test_no_pass_to_players_who_are_out() {
+        let mut game_state = GameState::from_notation(
+            "AI 0: | AI 1: | AI 2: | AI 3*: QC QS | Table: QH-_ | Trump: S | Phase: Defense \
+             | Attacker: AI 2 | Defender: AI 3",
+        )
+        .unwrap();
+        game_state.flat_attack_limit = true;
+        assert_eq!(game_state.next_defender(3), None);
+        assert!(!game_state.pass_fits(1));
+        assert_eq!(game_state.pass_attack(0, 0), Err(GameError::NoPassTarget));
+        assert_eq!(game_state.current_defender(), 3);
+        assert!(game_state.players()[0].is_empty_hand());
+        game_state.take_cards().unwrap();
+        game_state.draw_cards();
+        assert_eq!(*game_state.game_phase(), GamePhase::GameOver);
+        assert_eq!(game_state.durak(), Some(3));
+    }
+
+    #[test]
+    /// Test where the table goes for every way a round can end, with no card lost or
+    /// made up on the way
+    fn test_table_disposition_keeps_every_card() {
+        let start = "AI: 7C 8C | Human*: 9H 7D KD | Table: 7H-_ | Deck: 6S | Trump: S";
+
+        // Defended: the table goes to the discard pile once the attacker adds nothing
+        let mut defended = GameState::from_notation(start).unwrap();
+        let card_count = defended.card_count();
+        defended.defend(0).unwrap();
+        defended.discard_cards(Vec::new());
+        assert_eq!(defended.game_phase, GamePhase::Reinforce);
+        assert_eq!(defended.card_count(), card_count);
+        defended.decline_throw_in();
+        assert_eq!(defended.game_phase, GamePhase::Drawing);
+        assert_eq!(defended.discard_pile.len(), 2);
+        assert_eq!(defended.card_count(), card_count);
+
+        // Taken: the table goes to the defender's hand
+        let mut taken = GameState::from_notation(start).unwrap();
+        taken.take_cards().unwrap();
+        assert!(taken.table_cards.is_empty());
+        assert!(taken.discard_pile.is_empty());
+        assert_eq!(taken.players[1].hand_size(), 4);
+        assert_eq!(taken.card_count(), card_count);
+
+        // Passed: the round goes on and the table stays for the next defender
+        let mut passed = GameState::from_notation(start).unwrap();
+        passed.pass_attack(1, 0).unwrap();
+        assert_eq!(passed.game_phase, GamePhase::Defense);
+        assert_eq!(passed.table_cards.len(), 2);
+        assert!(passed.discard_pile.is_empty());
+        assert_eq!(passed.card_count(), card_count);
+
+        // Drawing never touches the table, it is already empty
+        taken.draw_cards();
+        assert_eq!(taken.card_count(), card_count);
+    }
+
+    #[test]
+    /// Test that an attacker who is out hands the attack on, and that with one player left
+    /// holding cards the game is over instead
+    fn test_retire_empty_attacker() {
+        let mut game_state =
+            GameState::from_notation("A: | B*: 7H | C: 8H | Attacker: A | Defender: B").unwrap();
+        assert!(game_state.retire_empty_attacker());
+        assert_eq!(game_state.current_attacker, 1);
+        assert_eq!(game_state.current_defender, 2);
+        assert_eq!(game_state.game_phase, GamePhase::Attack);
+        assert!(!game_state.retire_empty_attacker());
+
+        let mut game_state = GameState::from_notation("A: | B*: 7H | C:").unwrap();
+        assert!(game_state.retire_empty_attacker());
+        assert_eq!(game_state.game_phase, GamePhase::GameOver);
+        assert_eq!(game_state.durak(), Some(1));
+    }
+
+    #[test]
+    /// Test that passing in the Attack phase discards a beaten table and hands the attack
+    /// to the defender, but is refused while an attack is open or before the round opens
+    fn test_end_attack() {
+        let mut game_state = GameState::from_notation(
+            "AI: 8C | Human*: 7D | Table: 7H-9H | Phase: Attack | Trump: S",
+        )
+        .unwrap();
+        let card_count = game_state.card_count();
+        assert!(game_state.end_attack().is_ok());
+        assert!(game_state.table_cards.is_empty());
+        assert_eq!(game_state.discard_pile.len(), 2);
+        assert_eq!(game_state.current_attacker, 1);
+        assert_eq!(game_state.game_phase, GamePhase::Drawing);
+        assert_eq!(game_state.card_count(), card_count);
+
+        let mut game_state = GameState::from_notation(
+            "AI: 8C | Human*: 7D | Table: 7H-9H 7S-_ | Phase: Attack | Trump: S",
+        )
+        .unwrap();
+        assert_eq!(game_state.end_attack(), Err(GameError::IncompleteDefense));
+        assert_eq!(game_state.table_cards.len(), 2);
+        assert_eq!(game_state.game_phase, GamePhase::Attack);
+
+        let mut game_state = GameState::from_notation("AI: 8C | Human*: 7D").unwrap();
+        assert_eq!(game_state.end_attack(), Err(GameError::NoCardsOnTable));
+    }
+
+    #[test]
+    /// Test that the card tracker sees a taken table go to the defender, and forgets a
+    /// picked-up card once it has been played and discarded
+    fn test_card_tracker_follows_rounds() {
+        let mut game_state = GameState::from_notation(
+            "Human*: 7D 8D | AI: 9S 6C | Table: 7H-_ | Phase: Defense | Attacker: Human \
+             | Defender: AI | Trump: S",
+        )
+        .unwrap();
+        let seven = Card::new(Suit::Hearts, Rank::Seven);
+        assert!(!game_state.card_tracker().seen(&seven));
+        game_state.take_cards().unwrap();
+        assert!(game_state.card_tracker().seen(&seven));
+        assert_eq!(
+            game_state.card_tracker().known_hand(1).collect::<Vec<_>>(),
+            vec![&seven]
+        );
+        assert_eq!(game_state.card_tracker().remaining_of_rank(Rank::Seven), 3);
+
+        // The AI plays the seven back and the Human beats it
+        game_state.table_cards = vec![(seven, Some(Card::new(Suit::Hearts, Rank::Eight)))];
+        game_state.players[1].hand.retain(|card| *card != seven);
+        game_state.game_phase = GamePhase::Attack;
+        game_state.current_attacker = 1;
+        game_state.current_defender = 0;
+        game_state.end_attack().unwrap();
+        assert_eq!(game_state.card_tracker().known_hand(1).count(), 0);
+        assert!(game_state.card_tracker().seen(&seven));
+        assert_eq!(game_state.card_tracker().remaining_of_rank(Rank::Seven), 3);
+        assert_eq!(game_state.card_tracker().remaining_of_rank(Rank::Eight), 3);
+    }
+
+    #[test]
+    /// Test that a defender who has beaten one attack can no longer pass the next
+    fn test_no_pass_after_defending() {
+        let mut game_state = GameState::from_notation(
+            "Human*: 6S 6C 9C 9D TS TD | AI: 8H 7C | Table: 7H-_ 7D-_ | Trump: S | Attacker: Human | Defender: AI",
+        )
+        .unwrap();
+        assert!(game_state.clone().pass_attack(1, 1).is_ok());
+        game_state.defend_at(0, 0).unwrap();
+        assert!(!game_state.pass_open());
+        assert_eq!(
+            game_state.pass_attack(0, 1),
+            Err(GameError::PassAfterDefense)
+        );
+        assert_eq!(game_state.current_defender(), 1);
+        assert_eq!(game_state.table_cards().len(), 2);
+    }
+
+    #[test]
+    /// Test that with three players the third one throws in once the attacker adds nothing,
+    /// without taking over the attack
+    fn test_third_player_throws_in() {
+        let mut game_state = GameState::from_notation(
+            "Human*: 7C 9S | AI: 8D KD | Other: 7S 6C | Table: 7H-8H | Trump: D | Phase: Defense | Attacker: Human | Defender: AI",
+        )
+        .unwrap();
+        game_state.discard_cards(vec![]);
+        assert_eq!(game_state.game_phase, GamePhase::Reinforce);
+        assert_eq!(game_state.current_thrower(), 0);
+        game_state.decline_throw_in();
+        assert_eq!(game_state.game_phase, GamePhase::Reinforce);
+        assert_eq!(game_state.current_thrower(), 2);
+        // The defender is never offered a throw-in
+        assert!(!game_state.can_throw_in(1));
+        let seven = game_state.players[2]
+            .hand()
+            .iter()
+            .position(|card| card.rank == Rank::Seven)
+            .unwrap();
+        assert_eq!(
+            game_state.throw_in(2, 1 - seven),
+            Err(GameError::IllegalReinforcement)
+        );
+        game_state.throw_in(2, seven).unwrap();
+        assert_eq!(game_state.game_phase, GamePhase::Defense);
+        assert_eq!(game_state.table_cards().len(), 2);
+        assert_eq!(game_state.current_attacker(), 0);
+        assert_eq!(game_state.current_defender(), 1);
+        assert_eq!(game_state.players[2].hand_size(), 1);
+    }
+
+    #[test]
+    /// Test that the round ends once every player in turn has added nothing
+    fn test_round_ends_when_nobody_throws_in() {
+        let mut game_state = GameState::from_notation(
+            "Human*: 7C 9S | AI: 8D KD | Other: 7S 6C | Table: 7H-8H | Trump: D | Phase: Defense | Attacker: Human | Defender: AI",
+        )
+        .unwrap();
+        game_state.discard_cards(vec![]);
+        game_state.decline_throw_in();
+        game_state.decline_throw_in();
+        assert_eq!(game_state.game_phase, GamePhase::Drawing);
+        assert!(game_state.table_cards().is_empty());
+        assert_eq!(game_state.thrower, None);
+    }
+
+    #[test]
+    /// Test that any card opens a round, only cards matching the table follow, and the
+    /// defender or a full round allows none
+    fn test_legal_attacks() {
+        let mut game_state = GameState::from_notation(
+            "Human*: 7H 9D 7S | AI: 8H 6D 7C | Trump: S | Phase: Attack \
+             | Attacker: Human | Defender: AI",
+        )
+        .unwrap();
+        assert_eq!(game_state.legal_attacks(0), vec![0, 1, 2]);
+        assert!(game_state.legal_attacks(1).is_empty(), "the defender");
+        assert!(game_state.legal_attacks(5).is_empty(), "no such player");
+        game_state.attack(0, 0).unwrap();
+        assert_eq!(game_state.legal_attacks(0), vec![1], "only 7S matches");
+        game_state.defend_at(0, 0).unwrap();
+        // 9D matches neither the 7 nor the 8 that beat it
+        assert_eq!(game_state.legal_attacks(0), vec![1]);
+        game_state.players[1].hand.clear();
+        assert!(
+            game_state.legal_attacks(0).is_empty(),
+            "the defender is out of cards"
+        );
+        // A defender who began the round with a single card has room for one attack
+        let game_state = GameState::from_notation(
+            "Human*: 7H | AI: 8C | Table: 7S-_ | Trump: S | Attacker: Human | Defender: AI",
+        )
+        .unwrap();
+        assert!(game_state.legal_attacks(0).is_empty());
+    }
+
+    #[test]
+    /// Test that the defenses are the cards that beat the attack: a higher card of its suit,
+    /// any trump against a plain card, a higher trump against a trump and a joker against all
+    fn test_legal_defenses() {
+        let mut game_state = GameState::from_notation(
+            "Human*: 6C | AI: 8H 6H 6S QD JKH | Table: 7H-_ 9S-_ | Trump: S \
+             | Attacker: Human | Defender: AI",
+        )
+        .unwrap();
+        assert_eq!(game_state.legal_defenses(0), vec![0, 2, 4]);
+        assert_eq!(
+            game_state.legal_defenses(1),
+            vec![4],
+            "only the joker beats 9S"
+        );
+        assert!(game_state.legal_defenses(2).is_empty(), "no such attack");
+        game_state.defend_at(0, 0).unwrap();
+        assert!(game_state.legal_defenses(0).is_empty(), "already beaten");
+        assert_eq!(
+            game_state.defend_at(1, 0),
+            Err(GameError::IllegalDefense),
+            "defend_at agrees with legal_defenses"
+        );
+        // Without a trump only a higher card of the same suit beats
+        let game_state = GameState::from_notation(
+            "Human*: 6C | AI: 8H 6S QD | Table: 7H-_ | Attacker: Human | Defender: AI",
+        )
+        .unwrap();
+        assert_eq!(game_state.legal_defenses(0), vec![0]);
+    }
+}
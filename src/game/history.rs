@@ -0,0 +1,225 @@
+/*
+ * history.rs - The moves of a game, for the replay viewer
+ *
+ * This file contains the move log kept on every game:
+ * - The moves as they were made, with the player and the cards
+ * - The board of the deal, put back onto a game state to draw it
+ * - Rebuilding the board after any move by playing the moves again from the deal
+ */
+use super::deck::Deck;
+use super::{Card, GamePhase, GameState};
+use serde::{Deserialize, Serialize};
+
+/// A move of the game, the player index first
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Move {
+    // Opened, added to or threw in on the round
+    Attack {
+        player: usize,
+        cards: Vec<Card>,
+    },
+    Defend {
+        player: usize,
+        attack: Card,
+        card: Card,
+    },
+    // A trump only shown for a reveal pass stays in hand
+    Pass {
+        player: usize,
+        card: Card,
+        reveal: bool,
+    },
+    Take {
+        player: usize,
+        cards: Vec<Card>,
+    },
+    // The defender beat every attack and the table went to the discard pile
+    Discard {
+        player: usize,
+        cards: Vec<Card>,
+    },
+    Draw {
+        player: usize,
+        cards: Vec<Card>,
+    },
+}
+
+impl Move {
+    /// Plays this move again on `state`: the cards go where they went in the game, and an
+    /// opening attack or a pass seats the attacker and defender the way it did then
+    fn replay_on(&self, state: &mut GameState) {
+        match self {
+            Move::Attack { player, cards } => {
+                if state.table_cards.is_empty() {
+                    state.current_attacker = *player;
+                    state.current_defender = state.next_in_play(*player);
+                }
+                for card in cards {
+                    remove_from_hand(state, *player, card);
+                    state.table_cards.push((*card, None));
+                }
+                state.game_phase = GamePhase::Defense;
+            }
+            Move::Defend {
+                player,
+                attack,
+                card,
+            } => {
+                remove_from_hand(state, *player, card);
+                if let Some((_, defense)) = state
+                    .table_cards
+                    .iter_mut()
+                    .find(|(on_table, defense)| on_table == attack && defense.is_none())
+                {
+                    *defense = Some(*card);
+                }
+            }
+            Move::Pass {
+                player,
+                card,
+                reveal,
+            } => {
+                if !reveal {
+                    remove_from_hand(state, *player, card);
+                    state.table_cards.push((*card, None));
+                }
+                state.current_attacker = *player;
+                state.current_defender = state.next_in_play(*player);
+            }
+            Move::Take { player, cards } => {
+                state.table_cards.clear();
+                state.players[*player].add_cards(cards.clone());
+                state.round += 1;
+                state.game_phase = GamePhase::Drawing;
+            }
+            Move::Discard { player, cards } => {
+                state.table_cards.clear();
+                state.discard_pile.extend(cards);
+                state.round += 1;
+                state.current_attacker = *player;
+                state.current_defender = state.next_in_play(*player);
+                state.game_phase = GamePhase::Drawing;
+            }
+            Move::Draw { player, cards } => {
+                state.deck.deal(cards.len());
+                state.players[*player].add_cards(cards.clone());
+                state.game_phase = GamePhase::Attack;
+            }
+        }
+    }
+}
+
+/// Takes `card` out of the hand of `player`, leaving the rest in order
+fn remove_from_hand(state: &mut GameState, player: usize, card: &Card) {
+    let hand = &mut state.players[player].hand;
+    if let Some(idx) = hand.iter().position(|held| held == card) {
+        hand.remove(idx);
+    }
+}
+
+/// Everything on the table and in the hands at one point of the game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Board {
+    hands: Vec<Vec<Card>>,
+    table_cards: Vec<(Card, Option<Card>)>,
+    deck: Deck,
+    discard_pile: Vec<Card>,
+    current_attacker: usize,
+    current_defender: usize,
+    game_phase: GamePhase,
+    round: u32,
+}
+
+impl Board {
+    /// The board of `state` as it is now
+    pub fn of(state: &GameState) -> Self {
+        Self {
+            hands: state.players.iter().map(|p| p.hand.clone()).collect(),
+            table_cards: state.table_cards.clone(),
+            deck: state.deck.clone(),
+            discard_pile: state.discard_pile.clone(),
+            current_attacker: state.current_attacker,
+            current_defender: state.current_defender,
+            game_phase: state.game_phase.clone(),
+            round: state.round,
+        }
+    }
+    /// Lays this board out on `state`, which keeps its players and house rules.
+    /// Only meant for drawing, the move history and the rest of the game are left alone.
+    fn apply_to(&self, state: &mut GameState) {
+        for (player, hand) in state.players.iter_mut().zip(&self.hands) {
+            player.hand = hand.clone();
+        }
+        state.table_cards = self.table_cards.clone();
+        state.deck = self.deck.clone();
+        state.discard_pile = self.discard_pile.clone();
+        state.current_attacker = self.current_attacker;
+        state.current_defender = self.current_defender;
+        state.game_phase = self.game_phase.clone();
+        state.round = self.round;
+        state.thrower = None;
+    }
+}
+
+/// The moves of the game in play and the deal they were made from. Only the deal is kept
+/// as a board, the board after a move is rebuilt from it, see `board_at`.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MoveHistory {
+    start: Option<Board>, // The deal, `None` for a game set up without one
+    moves: Vec<Move>,
+}
+
+impl MoveHistory {
+    /// An empty history starting from the freshly dealt `start`
+    pub fn new(start: Board) -> Self {
+        Self {
+            start: Some(start),
+            moves: Vec::new(),
+        }
+    }
+    /// Logs `played`. Cards added by the same attacker one after another are one attack,
+    /// a take or a discard always ends the round in between.
+    pub fn record(&mut self, played: Move) {
+        if let (
+            Move::Attack { player, cards },
+            Some(Move::Attack {
+                player: last_player,
+                cards: last_cards,
+            }),
+        ) = (&played, self.moves.last_mut())
+            && player == last_player
+        {
+            last_cards.extend(cards);
+            return;
+        }
+        self.moves.push(played);
+    }
+    /// Every move so far, in the order they were made
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+    /// Number of moves so far
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+    /// Whether the deal was recorded, a game set up without one has nothing to replay
+    pub fn has_deal(&self) -> bool {
+        self.start.is_some()
+    }
+    /// `state` with the board laid out on it as it was before any move at step 0, and after
+    /// the `step`th move from there on. `None` past the last move or for a game without a
+    /// recorded deal.
+    pub fn board_at(&self, state: &GameState, step: usize) -> Option<GameState> {
+        let start = self.start.as_ref()?;
+        let moves = self.moves.get(..step)?;
+        let mut board = state.clone();
+        start.apply_to(&mut board);
+        for played in moves {
+            played.replay_on(&mut board);
+        }
+        Some(board)
+    }
+}
@@ -1,14 +1,20 @@
 pub mod ai;
 pub mod card;
 pub mod deck;
+pub mod error;
 pub mod game_state;
+pub mod history;
 pub mod player;
+pub mod simulation;
 
 #[cfg(test)]
 mod ai_logic_test;
+pub mod notation;
 
 pub use ai::AiDifficulty;
 pub use ai::AiPlayer;
+pub use ai::MatchRecord;
 pub use card::Card;
-pub use game_state::{GamePhase, GameState};
-pub use player::PlayerType;
+pub use error::GameError;
+pub use game_state::{GamePhase, GameState, RoundSummary, TrumpRule};
+pub use player::{HandSortMode, PlayerType};
@@ -0,0 +1,378 @@
+/*
+ * notation.rs - Compact text form of a GameState for tests
+ *
+ * A state is written as `|`-separated `Key: value` sections, for example
+ * `AI: 6H 7S | Human*: | Table: 6D-_ | Trump: S`:
+ * - `<name>: <cards>` is a player in seating order, a trailing `*` marks the human
+ * - `Table:` attack-defense pairs, `_` for an undefended attack
+ * - `Deck:` the draw pile from the top, `Trump:` a suit letter or `-` for no trump
+ * - `Phase:`, `Attacker:` and `Defender:` name the phase and the players by name
+ * Cards are rank then suit: 6-10, J, Q, K, A (or T for ten) and C, D, H, S.
+ *
+ * Tests and benchmarks use it to write scenarios in one line.
+ */
+use super::card::{Card, Rank, Suit};
+use super::game_state::{GamePhase, GameState, TrumpRule};
+use super::player::{Player, PlayerType};
+use std::collections::HashSet;
+use std::fmt::Display;
+
+/// Reasons a notation string cannot be turned into a `GameState`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotationError {
+    MissingColon(String),
+    InvalidCard(String),
+    InvalidTablePair(String),
+    InvalidSuit(String),
+    InvalidPhase(String),
+    UnknownPlayer(String),
+    DuplicateCard(Card),
+    NotEnoughPlayers,
+}
+
+impl Display for NotationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NotationError::MissingColon(section) => {
+                write!(f, "Section '{}' is not of the form 'Key: value'", section)
+            }
+            NotationError::InvalidCard(card) => write!(f, "Invalid card '{}'", card),
+            NotationError::InvalidTablePair(pair) => write!(f, "Invalid table pair '{}'", pair),
+            NotationError::InvalidSuit(suit) => write!(f, "Invalid trump suit '{}'", suit),
+            NotationError::InvalidPhase(phase) => write!(f, "Invalid phase '{}'", phase),
+            NotationError::UnknownPlayer(name) => write!(f, "No player named '{}'", name),
+            NotationError::DuplicateCard(card) => write!(f, "Card {} appears twice", card),
+            NotationError::NotEnoughPlayers => write!(f, "At least two players are needed"),
+        }
+    }
+}
+
+impl std::error::Error for NotationError {}
+
+impl GameState {
+    /// Builds a state from its notation. Unless given, the attacker is the first player,
+    /// the defender the next one, and the phase is Defense while an attack is open and
+    /// Attack otherwise.
+    pub fn from_notation(notation: &str) -> Result<GameState, NotationError> {
+        let mut game_state = GameState::new();
+        game_state.deck.cards.clear();
+        let mut phase = None;
+        let mut attacker = None;
+        let mut defender = None;
+        for section in notation.split('|') {
+            let section = section.trim();
+            let (key, value) = section
+                .split_once(':')
+                .ok_or_else(|| NotationError::MissingColon(section.to_string()))?;
+            let value = value.trim();
+            match key.trim() {
+                "Table" => {
+                    game_state.table_cards = value
+                        .split_whitespace()
+                        .map(parse_table_pair)
+                        .collect::<Result<_, _>>()?
+                }
+                "Deck" => game_state.deck.cards = parse_cards(value)?,
+                "Trump" => {
+                    game_state.trump_suit = parse_trump(value)?;
+                    if game_state.trump_suit.is_none() {
+                        game_state.trump_rule = TrumpRule::NoTrump;
+                    }
+                }
+                "Phase" => phase = Some(parse_phase(value)?),
+                "Attacker" => attacker = Some(value.to_string()),
+                "Defender" => defender = Some(value.to_string()),
+                name => {
+                    let (name, player_type) = match name.strip_suffix('*') {
+                        Some(name) => (name, PlayerType::Human),
+                        None => (name, PlayerType::Computer),
+                    };
+                    let mut player = Player::new(name.to_string(), player_type);
+                    player.hand = parse_cards(value)?;
+                    game_state.players.push(player);
+                }
+            }
+        }
+        if game_state.players.len() < 2 {
+            return Err(NotationError::NotEnoughPlayers);
+        }
+        game_state.deck.trump_suit = game_state.trump_suit;
+        game_state.last_actions = vec![None; game_state.players.len()];
+        game_state.current_attacker = match attacker {
+            Some(name) => game_state.player_index(&name)?,
+            None => 0,
+        };
+        game_state.current_defender = match defender {
+            Some(name) => game_state.player_index(&name)?,
+            None => (game_state.current_attacker + 1) % game_state.players.len(),
+        };
+        game_state.game_phase = phase.unwrap_or_else(|| {
+            if game_state
+                .table_cards
+                .iter()
+                .any(|(_, defense)| defense.is_none())
+            {
+                GamePhase::Defense
+            } else {
+                GamePhase::Attack
+            }
+        });
+        game_state.check_unique_cards()?;
+        Ok(game_state)
+    }
+    /// Writes the state in the form `from_notation` reads. Every section is spelled out
+    /// so the result parses back to the same state.
+    pub fn to_notation(&self) -> String {
+        let mut sections: Vec<String> = self
+            .players
+            .iter()
+            .map(|player| {
+                let marker = if player.player_type == PlayerType::Human {
+                    "*"
+                } else {
+                    ""
+                };
+                format!("{}{}: {}", player.name, marker, format_cards(&player.hand))
+            })
+            .collect();
+        let table: Vec<String> = self
+            .table_cards
+            .iter()
+            .map(|(attack, defense)| match defense {
+                Some(defense) => format!("{}-{}", format_card(attack), format_card(defense)),
+                None => format!("{}-_", format_card(attack)),
+            })
+            .collect();
+        sections.push(format!("Table: {}", table.join(" ")));
+        sections.push(format!("Deck: {}", format_cards(&self.deck.cards)));
+        sections.push(format!(
+            "Trump: {}",
+            self.trump_suit.map(suit_letter).unwrap_or('-')
+        ));
+        sections.push(format!("Phase: {}", self.game_phase));
+        sections.push(format!(
+            "Attacker: {}",
+            self.players[self.current_attacker].name
+        ));
+        sections.push(format!(
+            "Defender: {}",
+            self.players[self.current_defender].name
+        ));
+        sections
+            .iter()
+            .map(|section| section.trim_end())
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+    fn player_index(&self, name: &str) -> Result<usize, NotationError> {
+        self.players
+            .iter()
+            .position(|player| player.name == name)
+            .ok_or_else(|| NotationError::UnknownPlayer(name.to_string()))
+    }
+    /// A card can only be in one place at a time
+    fn check_unique_cards(&self) -> Result<(), NotationError> {
+        let mut seen = HashSet::new();
+        let hands = self.players.iter().flat_map(|player| player.hand.iter());
+        let table = self
+            .table_cards
+            .iter()
+            .flat_map(|(attack, defense)| std::iter::once(attack).chain(defense));
+        for card in hands.chain(table).chain(&self.deck.cards) {
+            if !seen.insert(*card) {
+                return Err(NotationError::DuplicateCard(*card));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_card(text: &str) -> Result<Card, NotationError> {
+    let invalid = || NotationError::InvalidCard(text.to_string());
+    let mut chars = text.chars();
+    let suit = chars.next_back().ok_or_else(invalid)?;
+    let rank = match chars.as_str() {
+        "2" => Rank::Two,
+        "3" => Rank::Three,
+        "4" => Rank::Four,
+        "5" => Rank::Five,
+        "6" => Rank::Six,
+        "7" => Rank::Seven,
+        "8" => Rank::Eight,
+        "9" => Rank::Nine,
+        "10" | "T" => Rank::Ten,
+        "J" => Rank::Jack,
+        "Q" => Rank::Queen,
+        "K" => Rank::King,
+        "A" => Rank::Ace,
+        "JK" => Rank::Joker,
+        _ => return Err(invalid()),
+    };
+    let suit = parse_suit(suit.encode_utf8(&mut [0; 4])).ok_or_else(invalid)?;
+    Ok(Card::new(suit, rank))
+}
+
+fn parse_cards(text: &str) -> Result<Vec<Card>, NotationError> {
+    text.split_whitespace().map(parse_card).collect()
+}
+
+fn parse_table_pair(text: &str) -> Result<(Card, Option<Card>), NotationError> {
+    let (attack, defense) = text
+        .split_once('-')
+        .ok_or_else(|| NotationError::InvalidTablePair(text.to_string()))?;
+    let defense = match defense {
+        "_" => None,
+        card => Some(parse_card(card)?),
+    };
+    Ok((parse_card(attack)?, defense))
+}
+
+fn parse_suit(text: &str) -> Option<Suit> {
+    match text {
+        "C" => Some(Suit::Clubs),
+        "D" => Some(Suit::Diamonds),
+        "H" => Some(Suit::Hearts),
+        "S" => Some(Suit::Spades),
+        _ => None,
+    }
+}
+
+fn parse_trump(text: &str) -> Result<Option<Suit>, NotationError> {
+    match text {
+        "-" => Ok(None),
+        suit => parse_suit(suit)
+            .map(Some)
+            .ok_or_else(|| NotationError::InvalidSuit(suit.to_string())),
+    }
+}
+
+fn parse_phase(text: &str) -> Result<GamePhase, NotationError> {
+    match text {
+        "Setup" => Ok(GamePhase::Setup),
+        "Attack" => Ok(GamePhase::Attack),
+        "Defense" => Ok(GamePhase::Defense),
+        "Reinforce" => Ok(GamePhase::Reinforce),
+        "Drawing" => Ok(GamePhase::Drawing),
+        "GameOver" => Ok(GamePhase::GameOver),
+        _ => Err(NotationError::InvalidPhase(text.to_string())),
+    }
+}
+
+fn suit_letter(suit: Suit) -> char {
+    match suit {
+        Suit::Clubs => 'C',
+        Suit::Diamonds => 'D',
+        Suit::Hearts => 'H',
+        Suit::Spades => 'S',
+    }
+}
+
+fn format_card(card: &Card) -> String {
+    format!("{}{}", card.rank.symbol(), suit_letter(card.suit))
+}
+
+fn format_cards(cards: &[Card]) -> String {
+    cards.iter().map(format_card).collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Test that a defense scenario parses into hands, table, trump and roles
+    fn test_from_notation_defense() {
+        let game_state =
+            GameState::from_notation("AI: 6H 7S | Human*: | Table: 6D-_ 9C-KC | Trump: S").unwrap();
+        assert_eq!(game_state.players.len(), 2);
+        assert_eq!(game_state.players[1].player_type, PlayerType::Human);
+        assert_eq!(
+            game_state.players[0].hand,
+            vec![
+                Card::new(Suit::Hearts, Rank::Six),
+                Card::new(Suit::Spades, Rank::Seven)
+            ]
+        );
+        assert_eq!(
+            game_state.table_cards,
+            vec![
+                (Card::new(Suit::Diamonds, Rank::Six), None),
+                (
+                    Card::new(Suit::Clubs, Rank::Nine),
+                    Some(Card::new(Suit::Clubs, Rank::King))
+                ),
+            ]
+        );
+        assert_eq!(game_state.trump_suit, Some(Suit::Spades));
+        assert_eq!(game_state.game_phase, GamePhase::Defense);
+        assert!(game_state.deck.is_empty());
+    }
+
+    #[test]
+    /// Test that writing a state out and reading it back gives the same notation
+    fn test_notation_round_trip() {
+        let notation = "AI: 10H AS | Human*: 6C | Table: 7D-8D | Deck: JC QC | Trump: - \
+                        | Phase: Reinforce | Attacker: Human | Defender: AI";
+        let game_state = GameState::from_notation(notation).unwrap();
+        assert_eq!(game_state.trump_rule, TrumpRule::NoTrump);
+        assert_eq!(game_state.current_attacker, 1);
+        assert_eq!(game_state.current_defender, 0);
+        let written = game_state.to_notation();
+        assert_eq!(
+            written,
+            "AI: 10H AS | Human*: 6C | Table: 7D-8D | Deck: JC QC | Trump: - \
+             | Phase: Reinforce | Attacker: Human | Defender: AI"
+        );
+        assert_eq!(
+            GameState::from_notation(&written).unwrap().to_notation(),
+            written
+        );
+    }
+
+    #[test]
+    /// Test that malformed notation is rejected with the matching error
+    fn test_from_notation_errors() {
+        let cases = [
+            (
+                "AI 6H | Human*:",
+                NotationError::MissingColon("AI 6H".to_string()),
+            ),
+            (
+                "AI: 6X | Human*:",
+                NotationError::InvalidCard("6X".to_string()),
+            ),
+            (
+                "AI: 1H | Human*:",
+                NotationError::InvalidCard("1H".to_string()),
+            ),
+            (
+                "AI: 6♥ | Human*:",
+                NotationError::InvalidCard("6♥".to_string()),
+            ),
+            (
+                "AI: | Human*: | Table: 6H",
+                NotationError::InvalidTablePair("6H".to_string()),
+            ),
+            (
+                "AI: | Human*: | Trump: X",
+                NotationError::InvalidSuit("X".to_string()),
+            ),
+            (
+                "AI: | Human*: | Phase: Nap",
+                NotationError::InvalidPhase("Nap".to_string()),
+            ),
+            (
+                "AI: | Human*: | Attacker: Bob",
+                NotationError::UnknownPlayer("Bob".to_string()),
+            ),
+            (
+                "AI: 6H | Human*: 6H",
+                NotationError::DuplicateCard(Card::new(Suit::Hearts, Rank::Six)),
+            ),
+            ("AI: 6H", NotationError::NotEnoughPlayers),
+        ];
+        for (notation, error) in cases {
+            assert_eq!(GameState::from_notation(notation).unwrap_err(), error);
+        }
+    }
+}
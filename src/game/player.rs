@@ -1,16 +1,36 @@
 use super::card::{Card, Suit};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PlayerType {
     Human,
     Computer,
 }
 
-#[derive(Debug, Clone)]
+/// Order a hand is kept in. Cards are played by their index in the sorted hand.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HandSortMode {
+    #[default]
+    Suit, // By suit, then rank
+    Rank, // By rank, then suit, so same-rank cards for a multi-attack sit together
+}
+
+impl HandSortMode {
+    pub fn next(self) -> Self {
+        match self {
+            HandSortMode::Suit => HandSortMode::Rank,
+            HandSortMode::Rank => HandSortMode::Suit,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Player {
     pub name: String,
     pub player_type: PlayerType,
     pub hand: Vec<Card>,
+    #[serde(default)]
+    pub sort_mode: HandSortMode,
 }
 
 impl Player {
@@ -19,6 +39,7 @@ impl Player {
             name,
             player_type,
             hand: Vec::new(),
+            sort_mode: HandSortMode::default(),
         }
     }
     /*
@@ -51,15 +72,21 @@ impl Player {
     }
 
     pub fn sort_hand(&mut self) {
-        // Sort by suit and then by rank
-        self.hand.sort_by(|a, b| {
-            if a.suit == b.suit {
-                a.rank.cmp(&b.rank)
-            } else {
-                // Sort by suit enum order
-                (a.suit as usize).cmp(&(b.suit as usize))
-            }
-        });
+        match self.sort_mode {
+            // Sort by suit enum order and then by rank
+            HandSortMode::Suit => self
+                .hand
+                .sort_by_key(|card| (card.suit as usize, card.rank)),
+            HandSortMode::Rank => self
+                .hand
+                .sort_by_key(|card| (card.rank, card.suit as usize)),
+        }
+    }
+
+    /// Changes the order the hand is kept in and re-sorts it straight away
+    pub fn set_sort_mode(&mut self, sort_mode: HandSortMode) {
+        self.sort_mode = sort_mode;
+        self.sort_hand();
     }
 
     pub fn remove_card(&mut self, index: usize) -> Option<Card> {
@@ -78,6 +105,39 @@ impl Player {
         self.hand.is_empty()
     }
 
+    /// Number of trump cards currently held
+    pub fn trump_count(&self, trump_suit: Suit) -> usize {
+        self.hand
+            .iter()
+            .filter(|card| card.suit == trump_suit)
+            .count()
+    }
+
+    /// Hand index of each of `cards`, `None` where the hand has no copy left. Every index
+    /// is handed out once, so in a multi-deck game two identical cards map to the two
+    /// copies in the hand rather than both to the first.
+    pub fn find_cards(&self, cards: &[Card]) -> Vec<Option<usize>> {
+        let mut claimed = vec![false; self.hand.len()];
+        cards
+            .iter()
+            .map(|card| {
+                let idx =
+                    (0..self.hand.len()).find(|&idx| !claimed[idx] && self.hand[idx] == *card)?;
+                claimed[idx] = true;
+                Some(idx)
+            })
+            .collect()
+    }
+
+    /// Sum of `Card::strategic_value` over the hand, the same worth the AI weighs its
+    /// cards by. Trumps count for much more than everything else.
+    pub fn hand_value(&self, trump_suit: Option<Suit>) -> u32 {
+        self.hand
+            .iter()
+            .map(|card| card.strategic_value(trump_suit))
+            .sum()
+    }
+
     pub fn get_lowest_trump(&self, trump_suit: Suit) -> Option<(usize, Card)> {
         self.hand
             .iter()
@@ -91,7 +151,7 @@ impl Player {
     pub fn get_valid_defenses(
         &self,
         attacking_card: &Card,
-        trump_suit: Suit,
+        trump_suit: Option<Suit>,
     ) -> Vec<(usize, Card)> {
         self.hand
             .iter()
@@ -114,3 +174,84 @@ impl Player {
             .map(|(idx, &card)| (idx, card))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::card::Rank;
+
+    #[test]
+    /// Test that rank order groups same-rank cards and later draws keep the chosen order
+    fn test_sort_by_rank() {
+        let mut player = Player::new("Player".to_string(), PlayerType::Human);
+        player.add_cards(vec![
+            Card::new(Suit::Spades, Rank::Seven),
+            Card::new(Suit::Clubs, Rank::Nine),
+            Card::new(Suit::Hearts, Rank::Seven),
+        ]);
+        player.set_sort_mode(HandSortMode::Rank);
+        player.add_cards(vec![Card::new(Suit::Diamonds, Rank::Seven)]);
+        assert_eq!(
+            player.hand(),
+            [
+                Card::new(Suit::Diamonds, Rank::Seven),
+                Card::new(Suit::Hearts, Rank::Seven),
+                Card::new(Suit::Spades, Rank::Seven),
+                Card::new(Suit::Clubs, Rank::Nine),
+            ]
+        );
+    }
+
+    #[test]
+    /// Test that duplicate cards from a multi-deck game are found at different indices
+    fn test_find_duplicate_cards() {
+        let mut player = Player::new("Player".to_string(), PlayerType::Human);
+        let seven = Card::new(Suit::Hearts, Rank::Seven);
+        player.add_cards(vec![seven, Card::new(Suit::Clubs, Rank::Nine), seven]);
+        assert_eq!(player.find_cards(&[seven, seven]), [Some(1), Some(2)]);
+        assert_eq!(
+            player.find_cards(&[seven, seven, seven]),
+            [Some(1), Some(2), None]
+        );
+    }
+
+    #[test]
+    /// Test that the hand value adds up the cards' strategic values
+    fn test_hand_value() {
+        let mut player = Player::new("Player".to_string(), PlayerType::Human);
+        assert_eq!(player.hand_value(Some(Suit::Spades)), 0);
+        let cards = [
+            Card::new(Suit::Spades, Rank::Six),
+            Card::new(Suit::Hearts, Rank::Ace),
+        ];
+        player.add_cards(cards.to_vec());
+        let expected: u32 = cards
+            .iter()
+            .map(|card| card.strategic_value(Some(Suit::Spades)))
+            .sum();
+        assert_eq!(player.hand_value(Some(Suit::Spades)), expected);
+        assert!(player.hand_value(Some(Suit::Spades)) > player.hand_value(None));
+    }
+
+    #[test]
+    /// Test that only cards of the trump suit are counted
+    fn test_trump_count_mixed_hand() {
+        let mut player = Player::new("Player".to_string(), PlayerType::Human);
+        player.add_cards(vec![
+            Card::new(Suit::Spades, Rank::Six),
+            Card::new(Suit::Hearts, Rank::Ace),
+            Card::new(Suit::Spades, Rank::King),
+            Card::new(Suit::Clubs, Rank::Nine),
+        ]);
+        assert_eq!(player.trump_count(Suit::Spades), 2);
+        assert_eq!(player.trump_count(Suit::Hearts), 1);
+        assert_eq!(player.trump_count(Suit::Diamonds), 0);
+    }
+
+    #[test]
+    /// Test that an empty hand holds no trumps
+    fn test_trump_count_empty_hand() {
+        let player = Player::new("Computer".to_string(), PlayerType::Computer);
+        assert_eq!(player.trump_count(Suit::Spades), 0);
+    }
+}
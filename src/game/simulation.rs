@@ -0,0 +1,197 @@
+/*
+ * simulation.rs - AI-vs-AI games on the game state alone
+ *
+ * This file contains a headless driver for balancing the difficulties:
+ * - Dealing a seeded game between two AI players
+ * - Driving the attack, defense, reinforcement and draw loop to the end of the game
+ * - Reporting who won, who is the durak and how many rounds it took
+ */
+use super::ai::AiPlayer;
+use super::{AiDifficulty, GamePhase, GameState, PlayerType};
+// Straight to the `log` crate, a headless game has no overlay to show its messages
+use log::{debug, error};
+
+/// Moves after which a game is given up on. A real game needs a few hundred at most.
+const MAX_MOVES: usize = 10_000;
+
+/// The outcome of a simulated game. Seat 0 played `ai_a`, seat 1 `ai_b`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameResult {
+    pub winner: Option<usize>, // `None` for a draw or a game that got stuck
+    pub durak: Option<usize>,
+    pub rounds: u32,
+    pub first_attacker: usize,
+}
+
+/// Deals a game seeded with `seed` between two computer players, seat 0 at `ai_a`.
+/// Each AI rolls its chances from its own generator, seeded from `seed` too.
+fn deal(ai_a: AiDifficulty, ai_b: AiDifficulty, seed: u64) -> (GameState, [AiPlayer; 2]) {
+    let mut state = GameState::new_with_seed(seed);
+    for (seat, difficulty) in [ai_a, ai_b].into_iter().enumerate() {
+        state.add_player(
+            format!("AI {} ({})", seat, difficulty),
+            PlayerType::Computer,
+        );
+    }
+    state.setup_game();
+    let ais = [
+        AiPlayer::new_seeded(ai_a, seed),
+        AiPlayer::new_seeded(ai_b, seed.wrapping_add(1)),
+    ];
+    (state, ais)
+}
+
+/// Lets whoever is up make one move: an attack, a defense, a pass, a take, a throw-in or
+/// the draw. The AI's decisions are applied the way `ai_handler` applies them in a game.
+fn play_move(state: &mut GameState, ais: &[AiPlayer]) {
+    // Out of cards is not a pass, the attack moves on or the game ends
+    if state.retire_empty_attacker() {
+        return;
+    }
+    match *state.game_phase() {
+        GamePhase::Attack => {
+            let attacker = state.current_attacker();
+            let mut plan = ais[attacker]
+                .make_attack_move(state, attacker)
+                .unwrap_or_default();
+            // Highest index first, so playing a card doesn't shift the ones still to come
+            plan.sort_by_key(|&(hand_idx, _)| std::cmp::Reverse(hand_idx));
+            for (hand_idx, card) in plan {
+                if let Err(e) = state.attack(hand_idx, attacker) {
+                    debug!("Simulated attack with {} failed: {}", card, e);
+                }
+            }
+            if *state.game_phase() == GamePhase::Attack
+                && let Err(e) = state.end_attack()
+            {
+                debug!("Simulated attacker cannot pass: {}", e);
+            }
+        }
+        GamePhase::Defense => {
+            let defender = state.current_defender();
+            let ai = &ais[defender];
+            let open_attack = state
+                .table_cards()
+                .iter()
+                .position(|(_, defense)| defense.is_none());
+            let result = if ai.should_take_cards(state, defender) {
+                state.take_cards()
+            } else if let Some((hand_idx, _)) = ai.make_pass_move(state, defender)
+                && let Some(attack_idx) = open_attack
+            {
+                state.pass_attack(hand_idx, attack_idx)
+            } else {
+                match ai.make_full_defense(state, defender) {
+                    Some(plan) => state.defend_with(&plan),
+                    None => state.take_cards(),
+                }
+            };
+            // A defense that didn't land is a take, the round has to end somehow
+            if let Err(e) = result {
+                debug!("Simulated defense failed: {}, taking cards", e);
+                let _ = state.take_cards();
+            }
+        }
+        GamePhase::Reinforce => {
+            let thrower = state.current_thrower();
+            let mut plan = ais[thrower]
+                .make_attack_move(state, thrower)
+                .unwrap_or_default();
+            plan.sort_by_key(|&(hand_idx, _)| std::cmp::Reverse(hand_idx));
+            let added = plan
+                .into_iter()
+                .filter(|&(hand_idx, _)| state.throw_in(thrower, hand_idx).is_ok())
+                .count();
+            if added == 0 {
+                state.decline_throw_in();
+            }
+        }
+        GamePhase::Drawing => state.draw_cards(),
+        GamePhase::Setup | GamePhase::GameOver => {}
+    }
+}
+
+/// Plays a whole game between two AI players without a terminal, seat 0 at `ai_a`. The
+/// deal and the AI's chance rolls all come from `seed`, so the same seed always plays the
+/// same game.
+pub fn simulate_game(ai_a: AiDifficulty, ai_b: AiDifficulty, seed: u64) -> GameResult {
+    let (mut state, ais) = deal(ai_a, ai_b, seed);
+    let first_attacker = state.current_attacker();
+    let mut moves = 0;
+    while !state.check_game_over() && moves < MAX_MOVES {
+        play_move(&mut state, &ais);
+        moves += 1;
+    }
+    if moves == MAX_MOVES {
+        error!("Simulated game with seed {} did not finish", seed);
+    }
+    GameResult {
+        winner: state.winner(),
+        durak: state.durak(),
+        rounds: state.round,
+        first_attacker,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Test that a seeded deal and seeded AIs make the same 20 moves every run
+    fn test_seeded_moves_are_exact() {
+        let (mut state, ais) = deal(AiDifficulty::Medium, AiDifficulty::Hard, 5);
+        for _ in 0..20 {
+            play_move(&mut state, &ais);
+        }
+        assert_eq!(
+            state.to_notation(),
+            "AI 0 (Medium): 9C 10C KC JD KD 10H KS | AI 1 (Hard): 7C 8C JC 8H AH 8S | Table: \
+             | Deck: 10D 7H 10S 8D 9S 6H 9D 7S JS KH 6D 7D QD JH AC AD 6C AS QC | Trump: C \
+             | Phase: Attack | Attacker: AI 1 (Hard) | Defender: AI 0 (Medium)"
+        );
+    }
+
+    #[test]
+    /// Test that playing the move history again from the deal rebuilds the cards of every
+    /// board of a whole game
+    fn test_history_rebuilds_every_board() {
+        let (mut state, ais) = deal(AiDifficulty::Medium, AiDifficulty::Hard, 7);
+        let mut moves = 0;
+        while !state.check_game_over() && moves < MAX_MOVES {
+            play_move(&mut state, &ais);
+            moves += 1;
+            let history = state.history();
+            let board = history.board_at(&state, history.len()).unwrap();
+            assert_eq!(board.players(), state.players());
+            assert_eq!(board.table_cards(), state.table_cards());
+            assert_eq!(board.deck(), state.deck());
+            assert_eq!(board.discard_pile(), state.discard_pile());
+            assert_eq!(board.current_round(), state.current_round());
+        }
+        assert!(
+            state
+                .history()
+                .board_at(&state, state.history().len() + 1)
+                .is_none()
+        );
+    }
+
+    #[test]
+    /// Test that simulated games finish, replay exactly from their seed, and that Hard
+    /// beats Easy more often than not
+    fn test_simulate_game() {
+        let result = simulate_game(AiDifficulty::Hard, AiDifficulty::Medium, 42);
+        assert_eq!(
+            result,
+            simulate_game(AiDifficulty::Hard, AiDifficulty::Medium, 42)
+        );
+        assert!(result.rounds > 0);
+        assert!(result.winner.is_some() || result.durak.is_none());
+        let hard_wins = (0..40)
+            .map(|seed| simulate_game(AiDifficulty::Hard, AiDifficulty::Easy, seed))
+            .filter(|result| result.durak == Some(1))
+            .count();
+        assert!(hard_wins > 20, "Hard won only {} of 40", hard_wins);
+    }
+}
@@ -0,0 +1,10 @@
+/*
+ * lib.rs - The game as a library
+ *
+ * main.rs only sets up the terminal and runs the app. Everything else lives here so the
+ * benchmarks in `benches/` can reach the game state and the AI.
+ */
+pub mod app;
+pub mod cli;
+pub mod game;
+pub mod ui;
@@ -1,30 +1,79 @@
 use anyhow::Result;
 use crossterm::{
-    ExecutableCommand, execute,
+    ExecutableCommand,
+    cursor::Show,
+    execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use ratatui::{Terminal, backend::CrosstermBackend};
 use std::io;
+use std::panic;
+use std::sync::Arc;
 
-mod app;
-mod game;
-mod ui;
-
-use app::App;
+use durak::app::App;
+use durak::app::simulation::{format_results, run_simulation};
+use durak::cli::{CliOptions, USAGE};
 extern crate lazy_static;
 extern crate log;
 extern crate ratatui;
 
 fn main() -> Result<()> {
+    // Parse command line options before touching the terminal
+    let options = match CliOptions::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("Error: {}\n\n{}", err, USAGE);
+            std::process::exit(2);
+        }
+    };
+    if options.help {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+    // Optional log file for looking into crashes, see `DURAK_LOG` in the README
+    if let Err(err) = durak::ui::debug_overlay::init_file_logger() {
+        eprintln!("Warning: could not open the log file: {}", err);
+    }
+    // A simulation only prints its results, the terminal is left alone
+    if let Some(games) = options.sim {
+        let seed = options.seed.unwrap_or_else(rand::random);
+        let results = run_simulation(games, options.sim_difficulties(), seed);
+        print!(
+            "{}",
+            format_results(&results, options.format.unwrap_or_default())
+        );
+        return Ok(());
+    }
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen)?;
+    // The restoration below only runs when app.run returns, so a panic needs its own
+    // hook to leave raw mode and the alternate screen before the message is printed
+    let original_hook: Arc<dyn Fn(&panic::PanicHookInfo<'_>) + Sync + Send> =
+        panic::take_hook().into();
+    let terminal_hook = Arc::clone(&original_hook);
+    panic::set_hook(Box::new(move |panic_info| {
+        log::error!("{}", panic_info);
+        let _ = disable_raw_mode();
+        let _ = execute!(io::stdout(), LeaveAlternateScreen, Show);
+        terminal_hook(panic_info);
+    }));
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
     // Create app and run it
     let mut app = App::new();
+    app.load_settings();
+    app.load_key_bindings();
+    options.apply(&mut app);
+    if options.has_game_options() {
+        // Skip the menus and jump straight into a configured game
+        app.start_game_action();
+    }
     let res = app.run(&mut terminal);
+    // Past this point the terminal is restored below, put the original hook back
+    let _ = panic::take_hook();
+    panic::set_hook(Box::new(move |panic_info| original_hook(panic_info)));
     // At this point, safe_exit should have restored the terminal if
     // an error occurred within the app.run function.
     // Just in case where safe_exit wasn't called we restore the raw input
@@ -1,15 +1,98 @@
 use crate::game::Card;
+use crate::game::card::{Rank, Suit};
+use crate::ui::theme::Theme;
 use ratatui::{
     buffer::Buffer,
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Widget},
 };
 
+/// How large the cards in the hands and on the table are drawn, picked on the main menu
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CardSize {
+    Small, // Narrow cards, so a full hand fits on a small terminal
+    #[default]
+    Medium,
+    Large, // Tall cards with the pips drawn out
+}
+
+impl CardSize {
+    pub fn next(self) -> Self {
+        match self {
+            CardSize::Small => CardSize::Medium,
+            CardSize::Medium => CardSize::Large,
+            CardSize::Large => CardSize::Small,
+        }
+    }
+    /// Width of a card in a hand
+    pub fn card_width(self) -> u16 {
+        match self {
+            CardSize::Small => 5,
+            CardSize::Medium => 8,
+            CardSize::Large => 11,
+        }
+    }
+    /// Height of a card, borders included
+    pub fn card_height(self) -> u16 {
+        match self {
+            CardSize::Small | CardSize::Medium => 3,
+            CardSize::Large => 7,
+        }
+    }
+    /// Width of an attack and its defense on the table
+    pub fn pair_width(self) -> u16 {
+        match self {
+            CardSize::Small => 7,
+            CardSize::Medium | CardSize::Large => 13,
+        }
+    }
+    /// Width of the deck with the trump card peeking out from under it
+    pub fn deck_width(self) -> u16 {
+        self.card_width() + DECK_PEEK
+    }
+}
+
+/// Columns of the trump card left showing beside the deck, enough for its label
+const DECK_PEEK: u16 = 4;
+
+/// Pips in the top, middle and bottom rows of a large number card
+fn pip_rows(rank: Rank) -> Option<[usize; 3]> {
+    match rank {
+        Rank::Two => Some([1, 0, 1]),
+        Rank::Three => Some([1, 1, 1]),
+        Rank::Four => Some([2, 0, 2]),
+        Rank::Five => Some([2, 1, 2]),
+        Rank::Six => Some([2, 2, 2]),
+        Rank::Seven => Some([2, 3, 2]),
+        Rank::Eight => Some([3, 2, 3]),
+        Rank::Nine => Some([3, 3, 3]),
+        Rank::Ten => Some([4, 2, 4]),
+        _ => None,
+    }
+}
+
+/// A suit drawn big, three rows of its own symbol with `centre` in the middle of the
+/// second row: the rank of a face card, otherwise the symbol again
+fn big_pip(suit: Suit, centre: &str) -> [String; 3] {
+    let s = suit.symbol();
+    let middle = format!("{s}{s}{centre}{s}{s}");
+    match suit {
+        Suit::Hearts => [format!("{s}{s} {s}{s}"), middle, s.to_string()],
+        Suit::Diamonds => [s.to_string(), middle, s.to_string()],
+        Suit::Clubs | Suit::Spades => [s.to_string(), middle, "|".to_string()],
+    }
+}
+
 pub struct CardView {
     card: Card,
     selected: bool,
+    trump: bool,
+    open: bool,
+    dimmed: bool,
+    compact: bool,
+    theme: Theme,
 }
 
 impl CardView {
@@ -17,6 +100,11 @@ impl CardView {
         Self {
             card,
             selected: false,
+            trump: false,
+            open: false,
+            dimmed: false,
+            compact: false,
+            theme: Theme::default(),
         }
     }
 
@@ -24,6 +112,61 @@ impl CardView {
         self.selected = selected;
         self
     }
+    /// Marks the card as a trump with a ★ and a cyan border
+    pub fn trump(mut self, trump: bool) -> Self {
+        self.trump = trump;
+        self
+    }
+    /// Marks an attack still waiting for a defense with a blinking red border
+    pub fn open(mut self, open: bool) -> Self {
+        self.open = open;
+        self
+    }
+    /// Dims a card whose pair on the table is already beaten
+    pub fn dimmed(mut self, dimmed: bool) -> Self {
+        self.dimmed = dimmed;
+        self
+    }
+    /// Only the label in the top-left corner, the layout a card falls back to anyway in
+    /// an area less than five rows tall
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+    /// The middle of a card, `rows` by `width` between its labels: the pips of a number
+    /// card when they fit, otherwise one big pip with a face card's rank in it, and
+    /// "JOKER" for a joker. Too small for either, it is just the suit symbol.
+    fn face_lines(&self, rows: u16, width: u16) -> Vec<String> {
+        let suit = self.card.suit.symbol();
+        let pips = pip_rows(self.card.rank).filter(|pips| {
+            rows >= 3
+                && pips
+                    .iter()
+                    .all(|&count| (count * 2).saturating_sub(1) <= width as usize)
+        });
+        let middle = if self.card.is_joker() {
+            vec!["JOKER".to_string()]
+        } else if let Some(pips) = pips {
+            pips.map(|count| vec![suit; count].join(" ")).to_vec()
+        } else if rows >= 3 && width >= 5 {
+            let centre = match self.card.rank {
+                Rank::Jack | Rank::Queen | Rank::King => self.card.rank.symbol(),
+                _ => suit,
+            };
+            big_pip(self.card.suit, centre).to_vec()
+        } else {
+            vec![suit.to_string()]
+        };
+        // Centre the three rows in whatever height the card has between its labels
+        let padding = (rows as usize).saturating_sub(middle.len()) / 2;
+        let mut lines = vec![String::new(); padding];
+        lines.extend(middle);
+        lines
+    }
 }
 
 impl Widget for CardView {
@@ -31,30 +174,81 @@ impl Widget for CardView {
         if area.width < 5 || area.height < 3 {
             return;
         }
-        let color = if self.card.suit.is_red() {
-            Color::Red
+        let color = self.theme.suit_color(self.card.suit.is_red());
+        // Bold as well as coloured, so a selection still shows without colours
+        let border_style = if self.selected {
+            Style::default()
+                .fg(self.theme.selected_border)
+                .add_modifier(Modifier::BOLD)
+        } else if self.open {
+            Style::default()
+                .fg(self.theme.open_attack)
+                .add_modifier(Modifier::SLOW_BLINK)
+        } else if self.trump {
+            Style::default().fg(self.theme.trump)
         } else {
-            Color::White
+            Style::default().fg(self.theme.card_border)
         };
-        let border_style = if self.selected {
-            Style::default().fg(Color::Yellow)
+        let text_modifier = if self.dimmed {
+            Modifier::DIM
         } else {
-            Style::default().fg(Color::DarkGray)
+            Modifier::empty()
         };
         // Create card block
         let block = Block::default()
             .borders(Borders::ALL)
-            .border_style(border_style);
+            .border_style(border_style.add_modifier(text_modifier));
         // Get inner area before rendering
         let inner_area = block.inner(area);
         // Render card block
         block.render(area, buf);
         // Render rank and suit at top-left
-        let rank_suit = Paragraph::new(Line::from(vec![Span::styled(
-            format!("{}{}", self.card.rank.symbol(), self.card.suit.symbol()),
-            Style::default().fg(color),
-        )]));
+        let mut spans = vec![Span::styled(
+            self.card.to_string(),
+            Style::default().fg(color).add_modifier(text_modifier),
+        )];
+        if self.trump {
+            spans.push(Span::styled("★", Style::default().fg(self.theme.trump)));
+        }
+        let rank_suit = Paragraph::new(Line::from(spans));
         rank_suit.render(inner_area, buf);
+        // With room for it the label is mirrored in the bottom corner, the face in between
+        if self.compact || area.height < 5 {
+            return;
+        }
+        let label_style = Style::default().fg(color).add_modifier(text_modifier);
+        let mut mirrored = Vec::new();
+        if self.trump {
+            mirrored.push(Span::styled("★", Style::default().fg(self.theme.trump)));
+        }
+        let label = if self.card.is_joker() {
+            self.card.to_string()
+        } else {
+            format!("{}{}", self.card.suit.symbol(), self.card.rank.symbol())
+        };
+        mirrored.push(Span::styled(label, label_style));
+        let bottom_area = Rect {
+            y: inner_area.y + inner_area.height - 1,
+            height: 1,
+            ..inner_area
+        };
+        Paragraph::new(Line::from(mirrored))
+            .alignment(Alignment::Right)
+            .render(bottom_area, buf);
+        let face_rows = inner_area.height - 2;
+        let face_area = Rect {
+            y: inner_area.y + 1,
+            height: face_rows,
+            ..inner_area
+        };
+        let face_lines: Vec<Line> = self
+            .face_lines(face_rows, inner_area.width)
+            .into_iter()
+            .map(|line| Line::styled(line, label_style))
+            .collect();
+        Paragraph::new(face_lines)
+            .alignment(Alignment::Center)
+            .render(face_area, buf);
     }
 }
 
@@ -62,6 +256,10 @@ pub struct CardRowView {
     cards: Vec<Card>,
     selected_idx: Option<usize>,
     multiple_selected: Option<Vec<usize>>,
+    trump_suit: Option<Suit>,
+    size: CardSize,
+    theme: Theme,
+    show_indices: bool,
 }
 
 impl CardRowView {
@@ -70,37 +268,78 @@ impl CardRowView {
             cards,
             selected_idx: None,
             multiple_selected: None,
+            trump_suit: None,
+            size: CardSize::default(),
+            theme: Theme::default(),
+            show_indices: false,
         }
     }
+    pub fn size(mut self, size: CardSize) -> Self {
+        self.size = size;
+        self
+    }
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+    /// Emphasise the cards of the trump suit, if the game has one
+    pub fn trump_suit(mut self, trump_suit: Option<Suit>) -> Self {
+        self.trump_suit = trump_suit;
+        self
+    }
     pub fn with_multiple_selection(mut self, selected: Vec<usize>) -> Self {
         self.multiple_selected = Some(selected);
         self
     }
+    /// Puts the cursor on a card: a yellow border and a ▲ underneath it
     pub fn select(mut self, idx: Option<usize>) -> Self {
         self.selected_idx = idx;
         self
     }
+    /// Debug aid: the card's index in the hand, as `attack` and `defend` take it, above
+    /// each card when there is a spare row for it
+    pub fn show_indices(mut self, show_indices: bool) -> Self {
+        self.show_indices = show_indices;
+        self
+    }
 }
 
 impl Widget for CardRowView {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        if area.width < 1 || area.height < 3 || self.cards.is_empty() {
+        if area.width < 1 || area.height < self.size.card_height() || self.cards.is_empty() {
             return;
         }
-        let card_width = 8_u16;
+        // The index row goes on top, before the cursor marker gets the bottom row
+        let index_row = self.show_indices && area.height > self.size.card_height();
+        let area = if index_row {
+            Rect {
+                y: area.y + 1,
+                height: area.height - 1,
+                ..area
+            }
+        } else {
+            area
+        };
+        let card_width = self.size.card_width();
         let spacing = 1_u16;
         let visible_cards =
             ((area.width as usize) / (card_width as usize + spacing as usize)).max(1);
         let cards_to_render = self.cards.len().min(visible_cards);
-        let widths = std::iter::repeat(Constraint::Length(card_width))
-            .take(cards_to_render)
+        // Each section holds a card and the gap after it, so the card keeps its full width
+        let widths = std::iter::repeat_n(Constraint::Length(card_width + spacing), cards_to_render)
             .collect::<Vec<_>>();
         let layout = Layout::default()
             .direction(Direction::Horizontal)
             .constraints(widths)
-            .horizontal_margin((area.width - (card_width + spacing) * cards_to_render as u16) / 2);
+            .horizontal_margin(
+                area.width
+                    .saturating_sub((card_width + spacing) * cards_to_render as u16)
+                    / 2,
+            );
         // Split the area into sections for each card
         let sections = layout.split(area);
+        // The bottom row is kept for the cursor marker when the cards have room to spare
+        let marker_row = area.height > self.size.card_height();
         for (i, &card) in self.cards.iter().take(visible_cards).enumerate() {
             // Card can be selected in two ways:
             // 1. It's the currently selected card (cursor)
@@ -112,60 +351,475 @@ impl Widget for CardRowView {
                 .is_some_and(|selected| selected.contains(&i));
             // Either selection method makes the card highlighted
             let is_selected = is_cursor_selected || is_multiple_selected;
-            // Calculate card area with spacing (manually handle spacing)
+            // The gap at the end of the section stays empty
             let mut card_area = sections[i];
-            if i < visible_cards - 1 {
-                card_area.width = card_area.width.saturating_sub(spacing);
+            card_area.width = card_area.width.saturating_sub(spacing);
+            if marker_row {
+                card_area.height = card_area.height.saturating_sub(1);
             }
             CardView::new(card)
+                .theme(self.theme)
                 .selected(is_selected)
+                .trump(self.trump_suit == Some(card.suit))
                 .render(card_area, buf);
+            if index_row {
+                let index_area = Rect {
+                    y: card_area.y - 1,
+                    height: 1,
+                    ..card_area
+                };
+                Paragraph::new(i.to_string())
+                    .style(Style::default().fg(self.theme.accent))
+                    .alignment(Alignment::Center)
+                    .render(index_area, buf);
+            }
+            if marker_row && is_cursor_selected {
+                let marker_area = Rect {
+                    y: card_area.y + card_area.height,
+                    height: 1,
+                    ..card_area
+                };
+                Paragraph::new("▲")
+                    .style(
+                        Style::default()
+                            .fg(self.theme.highlight)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                    .alignment(Alignment::Center)
+                    .render(marker_area, buf);
+            }
         }
     }
 }
 
 pub struct TableView {
     table_cards: Vec<(Card, Option<Card>)>,
+    selected_attack_idx: Option<usize>,
+    scroll: Option<usize>,
+    size: CardSize,
+    theme: Theme,
 }
 
 impl TableView {
     pub fn new(table_cards: Vec<(Card, Option<Card>)>) -> Self {
-        Self { table_cards }
+        Self {
+            table_cards,
+            selected_attack_idx: None,
+            scroll: None,
+            size: CardSize::default(),
+            theme: Theme::default(),
+        }
+    }
+    /// The pair to scroll to when the table is wider than the area. The selected attack
+    /// still wins, without either the first open attack is kept in view.
+    pub fn scroll(mut self, pair_idx: Option<usize>) -> Self {
+        self.scroll = pair_idx;
+        self
+    }
+    /// Index of the leftmost pair drawn when only `visible` pairs fit. The pair in focus
+    /// is centred where it can be: the selected attack, the scroll position, the first
+    /// open attack or else the newest pair.
+    fn first_visible(&self, visible: usize) -> usize {
+        let len = self.table_cards.len();
+        let focus = self
+            .selected_attack_idx
+            .or(self.scroll)
+            .filter(|&idx| idx < len)
+            .or_else(|| {
+                self.table_cards
+                    .iter()
+                    .position(|(_, defense)| defense.is_none())
+            })
+            .unwrap_or(len.saturating_sub(1));
+        focus
+            .saturating_sub(visible / 2)
+            .min(len.saturating_sub(visible))
+    }
+    pub fn size(mut self, size: CardSize) -> Self {
+        self.size = size;
+        self
+    }
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+    /// Highlight the attack the defender is about to answer
+    pub fn select_attack(mut self, idx: Option<usize>) -> Self {
+        self.selected_attack_idx = idx;
+        self
     }
 }
 
 impl Widget for TableView {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        if area.width < 1 || area.height < 7 || self.table_cards.is_empty() {
+        let card_height = self.size.card_height();
+        if area.width < 1 || area.height <= card_height * 2 || self.table_cards.is_empty() {
             return;
         }
-        let pair_width = 13_u16; // Each attack/defense pair needs space
+        let pair_width = self.size.pair_width(); // Each attack/defense pair needs space
         let spacing = 1_u16;
-        let visible_pairs =
-            ((area.width as usize) / (pair_width as usize + spacing as usize)).max(1);
+        let fits =
+            |width: u16| ((width as usize) / (pair_width as usize + spacing as usize)).max(1);
+        // When not every pair fits, a column on each side is kept for the scroll arrows
+        let visible_pairs = if fits(area.width) < self.table_cards.len() {
+            fits(area.width.saturating_sub(2))
+        } else {
+            fits(area.width)
+        };
         let pairs_to_render = self.table_cards.len().min(visible_pairs);
-        let widths = std::iter::repeat(Constraint::Length(pair_width))
-            .take(pairs_to_render)
+        let first = self.first_visible(pairs_to_render);
+        let widths = std::iter::repeat_n(Constraint::Length(pair_width), pairs_to_render)
             .collect::<Vec<_>>();
         let horizontal_layout = Layout::default()
             .direction(Direction::Horizontal)
             .constraints(widths)
-            .horizontal_margin((area.width - (pair_width + spacing) * pairs_to_render as u16) / 2);
+            .horizontal_margin(
+                area.width
+                    .saturating_sub((pair_width + spacing) * pairs_to_render as u16)
+                    / 2,
+            );
         // Split the area into sections for each pair
         let sections = horizontal_layout.split(area);
-        for (i, (attack_card, defend_card)) in
-            self.table_cards.iter().take(pairs_to_render).enumerate()
+        for (i, (attack_card, defend_card)) in self
+            .table_cards
+            .iter()
+            .skip(first)
+            .take(pairs_to_render)
+            .enumerate()
         {
             // For each pair, create a vertical layout for attack/defense cards
             let pair_layout = Layout::default()
                 .direction(Direction::Vertical)
-                .constraints([Constraint::Length(3), Constraint::Length(3)]);
+                .constraints([
+                    Constraint::Length(card_height),
+                    Constraint::Length(card_height),
+                ]);
             // Split vertically for attack/defense
             let card_sections = pair_layout.split(sections[i]);
-            CardView::new(*attack_card).render(card_sections[0], buf);
+            // Open attacks call for a defense, beaten pairs fade into the background
+            let beaten = defend_card.is_some();
+            CardView::new(*attack_card)
+                .theme(self.theme)
+                .selected(self.selected_attack_idx == Some(first + i))
+                .open(!beaten)
+                .dimmed(beaten)
+                .render(card_sections[0], buf);
             if let Some(card) = defend_card {
-                CardView::new(*card).render(card_sections[1], buf);
+                CardView::new(*card)
+                    .theme(self.theme)
+                    .dimmed(true)
+                    .render(card_sections[1], buf);
             }
         }
+        // Arrows on the sides say how many pairs are scrolled out of view
+        let arrow_style = Style::default()
+            .fg(self.theme.highlight)
+            .add_modifier(Modifier::BOLD);
+        let arrow_y = area.y + card_height.min(area.height - 1);
+        if first > 0 {
+            buf.set_string(area.x, arrow_y, "◀", arrow_style);
+        }
+        if first + pairs_to_render < self.table_cards.len() {
+            buf.set_string(area.right() - 1, arrow_y, "▶", arrow_style);
+        }
+    }
+}
+
+/// The stock pile: a face-down card with the count of cards left on it, and the trump
+/// card at the bottom of the deck peeking out from under it
+pub struct DeckView {
+    remaining: usize,
+    trump_card: Option<Card>,
+    trump_suit: Option<Suit>,
+    size: CardSize,
+    theme: Theme,
+}
+
+impl DeckView {
+    /// `trump_card` is the face-up bottom card, `None` once the deck is empty
+    pub fn new(remaining: usize, trump_card: Option<Card>) -> Self {
+        Self {
+            remaining,
+            trump_card,
+            trump_suit: None,
+            size: CardSize::default(),
+            theme: Theme::default(),
+        }
+    }
+    /// Marks the bottom card as a trump when it is one, under some trump rules it isn't
+    pub fn trump_suit(mut self, trump_suit: Option<Suit>) -> Self {
+        self.trump_suit = trump_suit;
+        self
+    }
+    pub fn size(mut self, size: CardSize) -> Self {
+        self.size = size;
+        self
+    }
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+    /// The face-down card on top, filled with a pattern and the count in the middle
+    fn render_back(&self, area: Rect, buf: &mut Buffer) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.card_back));
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+        let pattern = "░".repeat(inner_area.width as usize);
+        let back_lines: Vec<Line> = (0..inner_area.height)
+            .map(|_| Line::styled(pattern.clone(), Style::default().fg(self.theme.card_back)))
+            .collect();
+        Paragraph::new(back_lines).render(inner_area, buf);
+        let count_area = Rect {
+            y: inner_area.y + inner_area.height / 2,
+            height: 1,
+            ..inner_area
+        };
+        Paragraph::new(self.remaining.to_string())
+            .style(
+                Style::default()
+                    .fg(self.theme.text)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .alignment(Alignment::Center)
+            .render(count_area, buf);
+    }
+}
+
+impl Widget for DeckView {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let card_width = self.size.card_width();
+        let card_height = self.size.card_height();
+        if area.width < self.size.deck_width() || area.height < card_height {
+            return;
+        }
+        let left = area.x + (area.width - self.size.deck_width()) / 2;
+        let card_area = Rect::new(left, area.y, card_width, card_height);
+        if let Some(card) = self.trump_card {
+            CardView::new(card)
+                .theme(self.theme)
+                .trump(card.is_trump(self.trump_suit))
+                .render(card_area, buf);
+        }
+        // The bottom card is the last one left, with nothing on top of it
+        let shown = usize::from(self.trump_card.is_some());
+        if self.remaining > shown {
+            let back_area = Rect {
+                x: left + DECK_PEEK,
+                ..card_area
+            };
+            self.render_back(back_area, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts the cells of `buf` showing `symbol`
+    fn count_symbol(buf: &Buffer, symbol: &str) -> usize {
+        buf.content()
+            .iter()
+            .filter(|cell| cell.symbol() == symbol)
+            .count()
+    }
+
+    /// The rows of `buf` as text
+    fn rows(buf: &Buffer) -> Vec<String> {
+        let area = buf.area;
+        (area.y..area.bottom())
+            .map(|y| {
+                (area.x..area.right())
+                    .map(|x| buf.get(x, y).symbol())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    /// Test that a card with the room draws its pips and both labels, and a compact one or
+    /// one in a short area only the label
+    fn test_large_card_draws_pips() {
+        let card = Card::new(Suit::Hearts, Rank::Nine);
+        let area = Rect::new(0, 0, 11, 8);
+        let mut buf = Buffer::empty(area);
+        CardView::new(card).render(area, &mut buf);
+        assert_eq!(count_symbol(&buf, "♥"), 9 + 2);
+        let mut buf = Buffer::empty(area);
+        CardView::new(card).compact(true).render(area, &mut buf);
+        assert_eq!(count_symbol(&buf, "♥"), 1);
+        let area = Rect::new(0, 0, 11, 4);
+        let mut buf = Buffer::empty(area);
+        CardView::new(card).render(area, &mut buf);
+        assert_eq!(count_symbol(&buf, "♥"), 1);
+    }
+
+    #[test]
+    /// Test that a face card gets a big red pip with its rank in it and the mirrored label
+    /// in the bottom corner, and that the pips of a ten too wide for the card give way
+    fn test_card_face_art() {
+        let theme = Theme::default();
+        let area = Rect::new(0, 0, 11, 7);
+        let mut buf = Buffer::empty(area);
+        CardView::new(Card::new(Suit::Hearts, Rank::King))
+            .theme(theme)
+            .render(area, &mut buf);
+        assert_eq!(
+            rows(&buf)[1..6],
+            [
+                "│K♥       │",
+                "│  ♥♥ ♥♥  │",
+                "│  ♥♥K♥♥  │",
+                "│    ♥    │",
+                "│       ♥K│",
+            ]
+        );
+        assert_eq!(buf.get(5, 3).fg, theme.suit_color(true));
+
+        // Seven columns inside the card fit a ten's rows of four, five don't
+        let ten = Card::new(Suit::Spades, Rank::Ten);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 9, 7));
+        CardView::new(ten).render(buf.area, &mut buf);
+        assert_eq!(count_symbol(&buf, "♠"), 10 + 2);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 7, 7));
+        CardView::new(ten).render(buf.area, &mut buf);
+        assert_eq!(rows(&buf)[3], "│♠♠♠♠♠│");
+    }
+
+    #[test]
+    /// Test that a card renders in every area from the smallest up without panicking
+    fn test_card_view_any_area() {
+        let cards = [
+            Card::new(Suit::Hearts, Rank::Ten),
+            Card::new(Suit::Clubs, Rank::Queen),
+            Card::new(Suit::Diamonds, Rank::Ace),
+            Card::joker(Suit::Spades),
+        ];
+        for card in cards {
+            for width in 0..14 {
+                for height in 0..10 {
+                    let area = Rect::new(0, 0, width, height);
+                    let mut buf = Buffer::empty(area);
+                    CardView::new(card).trump(true).render(area, &mut buf);
+                }
+            }
+        }
+    }
+
+    #[test]
+    /// Test that hand indices are drawn above the cards only when asked for
+    fn test_card_row_indices() {
+        let cards = vec![
+            Card::new(Suit::Hearts, Rank::King),
+            Card::new(Suit::Diamonds, Rank::Queen),
+            Card::new(Suit::Spades, Rank::Ace),
+        ];
+        let size = CardSize::Small;
+        let area = Rect::new(0, 0, size.card_width() * 3 + 3, size.card_height() + 2);
+        let mut buf = Buffer::empty(area);
+        CardRowView::new(cards.clone())
+            .size(size)
+            .show_indices(true)
+            .render(area, &mut buf);
+        for idx in ["0", "1", "2"] {
+            assert_eq!(count_symbol(&buf, idx), 1);
+        }
+        // Every card is drawn, not only the last one
+        let text = rows(&buf).concat();
+        for card in &cards {
+            assert!(text.contains(&card.to_string()), "{} missing", card);
+        }
+        let mut buf = Buffer::empty(area);
+        CardRowView::new(cards).size(size).render(area, &mut buf);
+        assert_eq!(count_symbol(&buf, "0"), 0);
+    }
+
+    #[test]
+    /// Test that a hand and the table narrower than a single card render without panicking
+    fn test_narrow_areas_do_not_underflow() {
+        let card = Card::new(Suit::Hearts, Rank::Nine);
+        for size in [CardSize::Small, CardSize::Medium, CardSize::Large] {
+            for width in 1..size.pair_width() + 1 {
+                let area = Rect::new(0, 0, width, size.card_height() * 2 + 1);
+                let mut buf = Buffer::empty(area);
+                CardRowView::new(vec![card; 3])
+                    .size(size)
+                    .render(area, &mut buf);
+                TableView::new(vec![(card, None), (card, Some(card))])
+                    .size(size)
+                    .render(area, &mut buf);
+                DeckView::new(12, Some(card))
+                    .size(size)
+                    .render(area, &mut buf);
+            }
+        }
+    }
+
+    #[test]
+    /// Test that a table too wide for the area scrolls to the open attack and shows arrows
+    /// for the pairs out of view
+    fn test_table_view_scrolls_to_open_attack() {
+        let beaten = |rank| {
+            (
+                Card::new(Suit::Clubs, rank),
+                Some(Card::new(Suit::Clubs, Rank::Ace)),
+            )
+        };
+        let mut table = vec![
+            beaten(Rank::Six),
+            beaten(Rank::Seven),
+            beaten(Rank::Eight),
+            beaten(Rank::Nine),
+            beaten(Rank::Ten),
+        ];
+        table.push((Card::new(Suit::Hearts, Rank::King), None));
+        let size = CardSize::Medium;
+        let area = Rect::new(
+            0,
+            0,
+            (size.pair_width() + 1) * 3 + 2,
+            size.card_height() * 2 + 1,
+        );
+        let render = |view: TableView| {
+            let mut buf = Buffer::empty(area);
+            view.size(size).render(area, &mut buf);
+            buf
+        };
+        // The open K♥ at the end is scrolled into view, the first pairs are not drawn
+        let buf = render(TableView::new(table.clone()));
+        assert_eq!(count_symbol(&buf, "♥"), 1);
+        assert_eq!(count_symbol(&buf, "◀"), 1);
+        assert_eq!(count_symbol(&buf, "▶"), 0);
+        // Scrolling back to the start hides the open attack
+        let buf = render(TableView::new(table.clone()).scroll(Some(0)));
+        assert_eq!(count_symbol(&buf, "♥"), 0);
+        assert_eq!(count_symbol(&buf, "◀"), 0);
+        assert_eq!(count_symbol(&buf, "▶"), 1);
+        // but not while that attack is the one selected
+        let buf = render(TableView::new(table).scroll(Some(0)).select_attack(Some(5)));
+        assert_eq!(count_symbol(&buf, "♥"), 1);
+    }
+
+    #[test]
+    /// Test that the deck shows its count on the card back with the trump card's label
+    /// still visible, and only the trump card once it is the last one left
+    fn test_deck_view_shows_count_and_trump() {
+        let trump = Card::new(Suit::Hearts, Rank::Nine);
+        let area = Rect::new(0, 0, CardSize::Medium.deck_width(), 3);
+        let mut buf = Buffer::empty(area);
+        DeckView::new(24, Some(trump))
+            .trump_suit(Some(Suit::Hearts))
+            .render(area, &mut buf);
+        let middle_row: String = (0..area.width)
+            .map(|x| buf.get(x, 1).symbol().to_string())
+            .collect();
+        assert!(middle_row.contains("9♥"), "{}", middle_row);
+        assert!(middle_row.contains("24"), "{}", middle_row);
+        let mut buf = Buffer::empty(area);
+        DeckView::new(1, Some(trump)).render(area, &mut buf);
+        assert_eq!(count_symbol(&buf, "░"), 0);
+        assert_eq!(count_symbol(&buf, "♥"), 1);
     }
 }
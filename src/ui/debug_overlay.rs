@@ -7,6 +7,9 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Widget},
 };
+use std::fs::{self, OpenOptions};
+use std::io;
+use std::path::PathBuf;
 use std::sync::Mutex;
 // Buffer to hold our UI log messages
 lazy_static! {
@@ -41,6 +44,37 @@ impl From<Level> for LogLevel {
     }
 }
 
+/// Messages the overlay keeps, the oldest is dropped when a new one comes in
+pub const LOG_CAPACITY: usize = 100;
+
+/// Environment variable that turns on the log file, set to a level filter like `debug`
+pub const LOG_ENV_VAR: &str = "DURAK_LOG";
+
+/// Location of the log file, `<config dir>/durak/durak.log`
+pub fn log_file_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("durak").join("durak.log"))
+}
+
+/// Sends the `log` records to the log file when `DURAK_LOG` is set, e.g. `DURAK_LOG=debug`.
+/// Returns the file being written, or `None` when file logging is off.
+pub fn init_file_logger() -> io::Result<Option<PathBuf>> {
+    let Ok(filters) = std::env::var(LOG_ENV_VAR) else {
+        return Ok(None);
+    };
+    let path = log_file_path()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No config directory"))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let file = OpenOptions::new().create(true).append(true).open(&path)?;
+    env_logger::Builder::new()
+        .parse_filters(&filters)
+        .target(env_logger::Target::Pipe(Box::new(file)))
+        .try_init()
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    Ok(Some(path))
+}
+
 // Logging functions that log to both the regular system and the UI overlay
 pub fn debug<S: AsRef<str>>(message: S) {
     let message_ref = message.as_ref();
@@ -67,37 +101,76 @@ pub fn trace<S: AsRef<str>>(message: S) {
     log_message(message_ref, LogLevel::Trace);
 }
 
-// Add a message to our UI log buffer
+// Add a message to our UI log buffer and forward it to the `log` crate
 fn log_message(message: &str, level: LogLevel) {
     // Create timestamp
     let now = chrono::Local::now();
     let timestamp = now.format("%H:%M:%S%.3f").to_string();
 
+    // Convert LogLevel to log::Level for storage
+    let log_level = match level {
+        LogLevel::Error => Level::Error,
+        LogLevel::Warn => Level::Warn,
+        LogLevel::Info => Level::Info,
+        LogLevel::Debug => Level::Debug,
+        LogLevel::Trace => Level::Trace,
+    };
+    // Goes to the log file when one was set up with `init_file_logger`
+    log::log!(log_level, "{}", message);
+
     if let Ok(mut buffer) = UI_LOG_BUFFER.lock() {
-        // Keep only the last 100 messages to avoid memory issues
-        if buffer.len() >= 100 {
+        // Keep only the last `LOG_CAPACITY` messages to avoid memory issues
+        if buffer.len() >= LOG_CAPACITY {
             buffer.remove(0);
         }
 
-        // Convert LogLevel to log::Level for storage
-        let log_level = match level {
-            LogLevel::Error => Level::Error,
-            LogLevel::Warn => Level::Warn,
-            LogLevel::Info => Level::Info,
-            LogLevel::Debug => Level::Debug,
-            LogLevel::Trace => Level::Trace,
-        };
-
         buffer.push((timestamp, message.to_string(), log_level));
     }
 }
 
+/// Messages in the overlay's buffer right now
+pub fn log_len() -> usize {
+    UI_LOG_BUFFER.lock().map_or(0, |buffer| buffer.len())
+}
+
 // Debug overlay widget
-pub struct DebugOverlay {}
+pub struct DebugOverlay {
+    stats: Option<String>,
+    ai_trace: Option<String>,
+    scroll: usize, // Messages hidden below the window, 0 shows the newest
+}
+
+impl Default for DebugOverlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl DebugOverlay {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            stats: None,
+            ai_trace: None,
+            scroll: 0,
+        }
+    }
+
+    /// Scroll the log back by `scroll` messages from the newest
+    pub fn with_scroll(mut self, scroll: usize) -> Self {
+        self.scroll = scroll;
+        self
+    }
+
+    /// Pin a line of game statistics above the log messages
+    pub fn with_stats(mut self, stats: String) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    /// Pin the reasoning behind the AI's latest decision below the statistics
+    pub fn with_ai_trace(mut self, ai_trace: String) -> Self {
+        self.ai_trace = Some(ai_trace);
+        self
     }
 
     fn get_log_color(level: LogLevel) -> Color {
@@ -121,9 +194,30 @@ impl Widget for DebugOverlay {
             width: area.width,
             height: log_area_height,
         };
+        // Get log messages from our buffer
+        let messages = if let Ok(buffer) = UI_LOG_BUFFER.lock() {
+            buffer.clone()
+        } else {
+            Vec::new()
+        };
+        // Inside the borders each pinned line takes a row, as long as one is left for the log
+        let visible = [&self.stats, &self.ai_trace]
+            .iter()
+            .filter(|line| line.is_some())
+            .fold(log_area_height.saturating_sub(2) as usize, |rows, _| {
+                if rows > 1 { rows - 1 } else { rows }
+            });
+        let scroll = self.scroll.min(messages.len().saturating_sub(1));
+        let newest = messages.len() - scroll;
+        let oldest = (newest.saturating_sub(visible) + 1).min(newest);
         // Create a background for our debug area
         let debug_block = Block::default()
-            .title(" Debug Overlay [d to toggle] ")
+            .title(format!(
+                " Debug Overlay [d to toggle, PgUp/PgDn to scroll] [{}-{} / {}] ",
+                oldest,
+                newest,
+                messages.len()
+            ))
             .borders(Borders::ALL)
             .style(
                 Style::default()
@@ -132,19 +226,35 @@ impl Widget for DebugOverlay {
                     .add_modifier(Modifier::BOLD),
             );
         // Get inner area before rendering the block
-        let inner_area = debug_block.inner(log_area);
+        let mut inner_area = debug_block.inner(log_area);
         // Render the block background
         debug_block.render(log_area, buf);
-        // Get log messages from our buffer
-        let messages = if let Ok(buffer) = UI_LOG_BUFFER.lock() {
-            buffer.clone()
-        } else {
-            Vec::new()
-        };
+        // Pinned statistics and the AI's reasoning take the first lines of the overlay
+        let pinned = [(self.stats, Color::Yellow), (self.ai_trace, Color::Cyan)];
+        for (line, color) in pinned {
+            let Some(line) = line else {
+                continue;
+            };
+            if inner_area.height <= 1 {
+                break;
+            }
+            let line_area = Rect {
+                height: 1,
+                ..inner_area
+            };
+            Paragraph::new(line)
+                .style(Style::default().bg(Color::Black).fg(color))
+                .render(line_area, buf);
+            inner_area.y += 1;
+            inner_area.height -= 1;
+        }
         // Create text for log messages
         let mut text = Vec::new();
-        for (timestamp, message, level) in
-            messages.into_iter().rev().take(inner_area.height as usize)
+        for (timestamp, message, level) in messages
+            .into_iter()
+            .rev()
+            .skip(scroll)
+            .take(inner_area.height as usize)
         {
             let log_level = LogLevel::from(level);
             let level_str = format!(
@@ -1,18 +1,61 @@
-use super::card_view::{CardRowView, TableView};
-use crate::game::{GamePhase, GameState};
+use super::card_view::{CardRowView, CardSize, DeckView, TableView};
+use super::i18n::{Text, tr, tr_action, tr_fmt, tr_on_off};
+use super::theme::Theme;
+use crate::game::ai::{AiComment, AttackReason};
+use crate::game::card::Suit;
+use crate::game::{Card, GamePhase, GameState, RoundSummary};
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Direction, Layout, Rect},
     prelude::*,
-    style::{Color, Style},
+    style::Style,
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Widget},
+    widgets::{Block, Borders, Paragraph, Widget, Wrap},
 };
+use std::time::Duration;
+
+/// Height of the status bar and of the help line, the rows that don't grow with the cards
+const STATUS_HEIGHT: u16 = 3;
+const HELP_HEIGHT: u16 = 3;
+/// Smallest terminal width that fits a full hand of bordered cards. Together with
+/// `GameUI::min_height` anything smaller falls back to the compact layout.
+const MIN_WIDTH: u16 = 60;
+/// Smallest terminal the compact single-panel layout still fits in
+pub const COMPACT_MIN_HEIGHT: u16 = 10;
+pub const COMPACT_MIN_WIDTH: u16 = 24;
+/// Height of the player-order strip shown above the status bar in games with more than two players
+const PLAYER_STRIP_HEIGHT: u16 = 3;
+/// Deck size at which the status bar starts counting the trumps the human hasn't seen
+const TRUMP_COUNTDOWN_DECK_SIZE: usize = 4;
+/// Width of the commentary panel between the table and the deck, borders included
+const COMMENTARY_WIDTH: u16 = 30;
+/// Frames of the spinner next to the AI's "thinking" message
+const SPINNER_FRAMES: [&str; 4] = ["◐", "◓", "◑", "◒"];
 
 pub struct GameUI<'a> {
     game_state: &'a GameState,
     selected_idx: Option<usize>,
+    selected_attack_idx: Option<usize>,
     multiple_selected: Option<&'a Vec<usize>>,
+    pending_quit: bool,
+    pending_surrender: bool,
+    turn_time_left: Option<Duration>,
+    step_ai: bool,
+    reveal_hands: bool,
+    show_indices: bool,
+    undo_available: bool,
+    practice_move: Option<u64>,
+    replay: Option<String>,
+    defense_briefing: bool,
+    demo: bool,
+    show_aids: bool,
+    commentary: Option<Vec<AiComment>>,
+    viewer: Option<usize>,
+    dealt_cards: Option<usize>,
+    table_scroll: Option<usize>,
+    ai_thinking: Option<usize>,
+    card_size: CardSize,
+    theme: Theme,
 }
 
 impl<'a> GameUI<'a> {
@@ -20,7 +63,30 @@ impl<'a> GameUI<'a> {
         Self {
             game_state,
             selected_idx: None,
+            selected_attack_idx: None,
             multiple_selected: None,
+            pending_quit: false,
+            pending_surrender: false,
+            turn_time_left: None,
+            step_ai: false,
+            reveal_hands: false,
+            show_indices: false,
+            undo_available: false,
+            practice_move: None,
+            replay: None,
+            defense_briefing: false,
+            demo: false,
+            show_aids: true,
+            commentary: None,
+            viewer: game_state
+                .players()
+                .iter()
+                .position(|player| player.player_type() == &crate::game::PlayerType::Human),
+            dealt_cards: None,
+            table_scroll: None,
+            ai_thinking: None,
+            card_size: CardSize::default(),
+            theme: Theme::default(),
         }
     }
 
@@ -29,54 +95,313 @@ impl<'a> GameUI<'a> {
         self
     }
 
+    pub fn select_attack(mut self, idx: Option<usize>) -> Self {
+        self.selected_attack_idx = idx;
+        self
+    }
+
     pub fn with_multiple_selection(mut self, selected: &'a Vec<usize>) -> Self {
         self.multiple_selected = Some(selected);
         self
     }
 
-    fn render_status_bar(&self, area: Rect, buf: &mut Buffer) {
+    pub fn pending_quit(mut self, pending: bool) -> Self {
+        self.pending_quit = pending;
+        self
+    }
+    /// Whether a surrender is waiting for confirmation
+    pub fn pending_surrender(mut self, pending: bool) -> Self {
+        self.pending_surrender = pending;
+        self
+    }
+    /// Whether the AI is in single-step debug mode
+    pub fn step_ai(mut self, step_ai: bool) -> Self {
+        self.step_ai = step_ai;
+        self
+    }
+    /// Debug view that shows the computer players' hands face up
+    pub fn reveal_hands(mut self, reveal_hands: bool) -> Self {
+        self.reveal_hands = reveal_hands;
+        self
+    }
+    /// Debug view that numbers the cards of the hand on screen by their hand index
+    pub fn show_indices(mut self, show_indices: bool) -> Self {
+        self.show_indices = show_indices;
+        self
+    }
+    /// Whether 'u' can take back the human's last move, the help line only offers it then
+    pub fn undo_available(mut self, undo_available: bool) -> Self {
+        self.undo_available = undo_available;
+        self
+    }
+    /// Lists the attacks the human is about to face until a key is pressed
+    pub fn defense_briefing(mut self, defense_briefing: bool) -> Self {
+        self.defense_briefing = defense_briefing;
+        self
+    }
+    /// An AI-vs-AI demo game, the help line only says how to leave it
+    pub fn demo(mut self, demo: bool) -> Self {
+        self.demo = demo;
+        self
+    }
+    /// The player whose hand is drawn face up at the bottom, the first human by default.
+    /// Every other hand is shown as a card count.
+    pub fn viewer(mut self, viewer: Option<usize>) -> Self {
+        self.viewer = viewer;
+        self
+    }
+    /// The table pair the human scrolled to, see `TableView::scroll`
+    pub fn table_scroll(mut self, table_scroll: Option<usize>) -> Self {
+        self.table_scroll = table_scroll;
+        self
+    }
+    /// While the deal is animated, how many cards of each hand are shown so far
+    pub fn dealt_cards(mut self, dealt_cards: Option<usize>) -> Self {
+        self.dealt_cards = dealt_cards;
+        self
+    }
+    /// The part of a hand dealt so far, all of it once the deal animation is over
+    fn dealt_hand<'h>(&self, hand: &'h [Card]) -> &'h [Card] {
+        &hand[..self.dealt_cards.unwrap_or(usize::MAX).min(hand.len())]
+    }
+    /// Unseen-card counts in the status bar: trumps in the endgame, every suit while the
+    /// human defends
+    pub fn show_aids(mut self, show_aids: bool) -> Self {
+        self.show_aids = show_aids;
+        self
+    }
+    /// The AI's latest moves for the commentary panel beside the table, `None` hides the
+    /// panel. Left out when the terminal is too narrow for it.
+    pub fn commentary(mut self, commentary: Option<Vec<AiComment>>) -> Self {
+        self.commentary = commentary;
+        self
+    }
+    /// Move number of a practice game, shown in the status bar
+    pub fn practice_move(mut self, practice_move: Option<u64>) -> Self {
+        self.practice_move = practice_move;
+        self
+    }
+    /// The replay's current move, shown in the status bar. A replay has no prompts, the help
+    /// line only says how to step through it.
+    pub fn replay(mut self, replay: Option<String>) -> Self {
+        self.replay = replay;
+        self
+    }
+    /// Spinner frame for the "thinking" message while the AI is about to move
+    pub fn ai_thinking(mut self, frame: Option<usize>) -> Self {
+        self.ai_thinking = frame;
+        self
+    }
+    /// How large the cards in the hands and on the table are drawn
+    pub fn card_size(mut self, card_size: CardSize) -> Self {
+        self.card_size = card_size;
+        self
+    }
+    /// Colours of the whole screen, cards included
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+    /// Height of a player's panel: the cards, the cursor marker and a line to spare inside
+    /// the border
+    fn hand_height(&self) -> u16 {
+        self.card_size.card_height() + 4
+    }
+    /// Smallest height the table gets, an attack and its defense stacked with room to spare
+    fn table_height(&self) -> u16 {
+        self.card_size.card_height() * 2 + 4
+    }
+    /// Width of the deck panel beside the table, the deck and the panel's borders
+    fn deck_panel_width(&self) -> u16 {
+        self.card_size.deck_width() + 2
+    }
+    /// Smallest terminal height that fits the game layout with the chosen card size
+    fn min_height(&self) -> u16 {
+        STATUS_HEIGHT + self.hand_height() * 2 + self.table_height() + HELP_HEIGHT
+    }
+    /// Countdown shown in the status bar while the human's turn timer runs
+    pub fn turn_time_left(mut self, time_left: Option<Duration>) -> Self {
+        self.turn_time_left = time_left;
+        self
+    }
+
+    /// Round number, phase, trump, deck count and the turn clock on one line
+    fn status_line(&self) -> Line<'static> {
         let phase_text = match self.game_state.game_phase() {
-            GamePhase::Setup => "Setting up game...".to_string(),
+            GamePhase::Setup => tr(Text::StatusSetup).to_string(),
             GamePhase::Attack => {
                 let attacker = &self.game_state.players()[self.game_state.current_attacker()];
-                format!("{}'s turn to attack", attacker.name())
+                tr_fmt(Text::StatusAttack, &[&attacker.name()])
             }
             GamePhase::Defense => {
                 let defender = &self.game_state.players()[self.game_state.current_defender()];
-                format!("{}'s turn to defend or pass", defender.name())
+                tr_fmt(Text::StatusDefense, &[&defender.name()])
+            }
+            GamePhase::Reinforce => {
+                let thrower = &self.game_state.players()[self.game_state.current_thrower()];
+                tr_fmt(Text::StatusReinforce, &[&thrower.name()])
             }
-            GamePhase::Drawing => "Drawing cards...".to_string(),
+            GamePhase::Drawing => tr(Text::StatusDrawing).to_string(),
             GamePhase::GameOver => {
                 if let Some(winner_idx) = self.game_state.winner() {
                     let winner = &self.game_state.players()[winner_idx];
-                    format!("Game over! {} is the winner!", winner.name())
+                    tr_fmt(Text::StatusGameOverWinner, &[&winner.name()])
+                } else if self.game_state.is_draw() {
+                    tr(Text::DrawNoDurak).to_string()
                 } else {
-                    "Game over!".to_string()
+                    tr(Text::StatusGameOver).to_string()
                 }
             }
         };
 
         let trump_text = if let Some(trump_suit) = self.game_state.trump_suit() {
-            format!("Trump: {}", trump_suit.symbol())
+            tr_fmt(Text::Trump, &[&trump_suit.symbol()])
         } else {
-            "No trump".to_string()
+            tr(Text::NoTrump).to_string()
         };
 
-        let deck_count = format!("Cards left: {}", self.game_state.deck().remaining());
+        let deck_count = tr_fmt(Text::CardsLeft, &[&self.game_state.deck().remaining()]);
+        let deck_color = if self.game_state.deck().is_empty() {
+            self.theme.warning
+        } else {
+            self.theme.trump
+        };
 
-        let status_line = Line::from(vec![
-            Span::styled(phase_text, Style::default().fg(Color::Green)),
+        let round_text = tr_fmt(Text::StatusRound, &[&self.game_state.current_round()]);
+        let mut spans = vec![
+            Span::styled(round_text, Style::default().fg(self.theme.text)),
+            Span::raw(" | "),
+            Span::styled(phase_text, Style::default().fg(self.theme.status)),
             Span::raw(" | "),
-            Span::styled(trump_text, Style::default().fg(Color::Yellow)),
+            Span::styled(trump_text, Style::default().fg(self.theme.highlight)),
             Span::raw(" | "),
-            Span::styled(deck_count, Style::default().fg(Color::Cyan)),
-        ]);
+            Span::styled(deck_count, Style::default().fg(deck_color)),
+        ];
+        let human_idx = self.viewer;
+        // The worth of the human's hand as the AI would weigh it, and how many trumps it holds
+        if self.show_aids
+            && let Some(human_idx) = human_idx
+        {
+            let trump_suit = self.game_state.trump_suit();
+            let hand = &self.game_state.players()[human_idx];
+            let trumps = hand
+                .hand()
+                .iter()
+                .filter(|card| card.is_trump(trump_suit))
+                .count();
+            spans.push(Span::raw(" | "));
+            spans.push(Span::styled(
+                tr_fmt(Text::HandValue, &[&hand.hand_value(trump_suit), &trumps]),
+                Style::default().fg(self.theme.highlight),
+            ));
+        }
+        // In the endgame, count the trumps that could still be in the opponents' hands
+        if self.show_aids
+            && self.game_state.deck().remaining() <= TRUMP_COUNTDOWN_DECK_SIZE
+            && let Some(unseen) = human_idx.and_then(|idx| self.game_state.unseen_trumps(idx))
+        {
+            spans.push(Span::raw(" | "));
+            spans.push(Span::styled(
+                tr_fmt(Text::UnseenTrumps, &[&unseen]),
+                Style::default().fg(self.theme.highlight),
+            ));
+        }
+        // Once some attacks are beaten, the rest still have to be beaten or the whole table taken
+        if let Some(human_idx) = human_idx
+            && *self.game_state.game_phase() == GamePhase::Defense
+            && self.game_state.current_defender() == human_idx
+        {
+            let table = self.game_state.table_cards();
+            let defended = table
+                .iter()
+                .filter(|(_, defense)| defense.is_some())
+                .count();
+            if defended > 0 && defended < table.len() {
+                spans.push(Span::raw(" | "));
+                spans.push(Span::styled(
+                    tr_fmt(Text::DefendedSoFar, &[&defended, &table.len()]),
+                    Style::default().fg(self.theme.open_attack),
+                ));
+            }
+        }
+        // While defending, how many of each suit could still come from the other players
+        if self.show_aids
+            && let Some(human_idx) = human_idx
+            && *self.game_state.game_phase() == GamePhase::Defense
+            && self.game_state.current_defender() == human_idx
+        {
+            let counts = Suit::all()
+                .into_iter()
+                .map(|suit| {
+                    format!(
+                        "{}{}",
+                        suit.symbol(),
+                        self.game_state.unseen_in_suit(human_idx, suit)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            spans.push(Span::raw(" | "));
+            spans.push(Span::styled(
+                tr_fmt(Text::UnseenBySuit, &[&counts]),
+                Style::default().fg(self.theme.highlight),
+            ));
+        }
+        if let Some(time_left) = self.turn_time_left {
+            // Round up so the clock only shows 0s at the moment it expires
+            let seconds = time_left.as_millis().div_ceil(1000);
+            let color = if seconds <= 5 {
+                self.theme.warning
+            } else {
+                self.theme.text
+            };
+            spans.push(Span::raw(" | "));
+            spans.push(Span::styled(
+                tr_fmt(Text::TimeLeft, &[&seconds]),
+                Style::default().fg(color),
+            ));
+        }
+        if let Some(frame) = self.ai_thinking {
+            spans.push(Span::raw(" | "));
+            spans.push(Span::styled(
+                format!(
+                    "{} {}",
+                    SPINNER_FRAMES[frame % SPINNER_FRAMES.len()],
+                    tr(Text::AiThinking)
+                ),
+                Style::default().fg(self.theme.highlight),
+            ));
+        }
+        if let Some(practice_move) = self.practice_move {
+            spans.push(Span::raw(" | "));
+            spans.push(Span::styled(
+                tr_fmt(Text::PracticeMove, &[&practice_move]),
+                Style::default().fg(self.theme.accent),
+            ));
+        }
+        if let Some(replay) = &self.replay {
+            spans.push(Span::raw(" | "));
+            spans.push(Span::styled(
+                replay.clone(),
+                Style::default().fg(self.theme.accent),
+            ));
+        }
+        Line::from(spans)
+    }
 
-        let paragraph = Paragraph::new(status_line)
+    fn render_status_bar(&self, area: Rect, buf: &mut Buffer) {
+        // The border turns red for the endgame, once there is nothing left to draw
+        let border_style = if self.game_state.deck().is_empty() {
+            Style::default().fg(self.theme.warning)
+        } else {
+            Style::default()
+        };
+        let paragraph = Paragraph::new(self.status_line())
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title("Game Status")
+                    .border_style(border_style)
+                    .title(tr(Text::StatusTitle))
                     .title_alignment(Alignment::Center),
             )
             .alignment(ratatui::layout::Alignment::Center);
@@ -87,12 +412,15 @@ impl<'a> GameUI<'a> {
     fn render_player_hand(&self, area: Rect, buf: &mut Buffer, player_idx: usize) {
         let player = &self.game_state.players()[player_idx];
         let player_name = player.name();
+        // Anyone throwing in during the reinforcement window is in the round too
         let is_current_player = player_idx == self.game_state.current_attacker()
-            || player_idx == self.game_state.current_defender();
+            || player_idx == self.game_state.current_defender()
+            || (*self.game_state.game_phase() == GamePhase::Reinforce
+                && player_idx == self.game_state.current_thrower());
         let title_style = if is_current_player {
-            Style::default().fg(Color::Yellow)
+            Style::default().fg(self.theme.highlight)
         } else {
-            Style::default().fg(Color::White)
+            Style::default().fg(self.theme.text)
         };
         let block = Block::default()
             .borders(Borders::ALL)
@@ -101,92 +429,576 @@ impl<'a> GameUI<'a> {
             .title_alignment(Alignment::Center);
         let inner_area = block.inner(area);
         block.render(area, buf);
-        if player.player_type() == &crate::game::PlayerType::Human {
-            let selected = if player_idx == self.game_state.current_attacker()
-                || player_idx == self.game_state.current_defender()
-            {
+        if self.viewer == Some(player_idx) {
+            let selected = if is_current_player {
                 self.selected_idx
             } else {
                 None
             };
-            let mut row_view = CardRowView::new(player.hand().to_vec()).select(selected);
+            let mut row_view = CardRowView::new(self.dealt_hand(player.hand()).to_vec())
+                .theme(self.theme)
+                .select(selected)
+                .trump_suit(self.game_state.trump_suit())
+                .size(self.card_size)
+                .show_indices(self.show_indices);
             if let Some(selected_cards) = self.multiple_selected {
                 row_view = row_view.with_multiple_selection(selected_cards.clone());
             }
             row_view.render(inner_area, buf);
+        } else if self.reveal_hands {
+            // Debug only: label the row so it is never mistaken for normal play
+            let sections = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(1), Constraint::Min(0)])
+                .split(inner_area);
+            let label = if self.replay.is_some() {
+                Text::ReplayRevealedHand
+            } else {
+                Text::DebugRevealedHand
+            };
+            Paragraph::new(tr(label))
+                .style(Style::default().fg(self.theme.accent))
+                .alignment(Alignment::Center)
+                .render(sections[0], buf);
+            CardRowView::new(self.dealt_hand(player.hand()).to_vec())
+                .theme(self.theme)
+                .trump_suit(self.game_state.trump_suit())
+                .size(self.card_size)
+                .render(sections[1], buf);
         } else {
-            let card_count = format!("{} cards", player.hand_size());
-            let para = Paragraph::new(card_count)
-                .style(Style::default().fg(Color::Red))
-                .alignment(Alignment::Center);
+            let card_count = tr_fmt(Text::CardCount, &[&self.dealt_hand(player.hand()).len()]);
+            let mut lines = vec![Line::styled(
+                card_count,
+                Style::default().fg(self.theme.warning),
+            )];
+            // What a player at a physical table would have seen them do last
+            if let Some(action) = self.game_state.last_action(player_idx) {
+                lines.push(Line::styled(
+                    tr_action(action),
+                    Style::default().fg(self.theme.muted),
+                ));
+            }
+            let para = Paragraph::new(lines).alignment(Alignment::Center);
             para.render(inner_area, buf);
         }
     }
     fn render_table(&self, area: Rect, buf: &mut Buffer) {
         let block = Block::default()
             .borders(Borders::ALL)
-            .title("Table")
+            .title(tr(Text::TableTitle))
             .title_alignment(Alignment::Center);
         // Get inner area before rendering block
         let inner_area = block.inner(area);
         // Render the block
         block.render(area, buf);
-        if !self.game_state.table_cards().is_empty() {
-            TableView::new(self.game_state.table_cards().to_vec()).render(inner_area, buf);
+        if self.dealt_cards.is_some() {
+            Paragraph::new(tr(Text::Dealing))
+                .style(Style::default().fg(self.theme.muted))
+                .alignment(Alignment::Center)
+                .render(inner_area, buf);
+        } else if self.game_state.endgame_notice {
+            self.render_endgame_banner(inner_area, buf);
+        } else if self.defense_briefing {
+            Paragraph::new(self.briefing_lines())
+                .alignment(Alignment::Center)
+                .render(inner_area, buf);
+        } else if *self.game_state.game_phase() == GamePhase::Drawing {
+            self.render_draw_summary(inner_area, buf);
+        } else if !self.game_state.table_cards().is_empty() {
+            TableView::new(self.game_state.table_cards().to_vec())
+                .theme(self.theme)
+                .select_attack(self.selected_attack_idx)
+                .scroll(self.table_scroll)
+                .size(self.card_size)
+                .render(inner_area, buf);
         } else {
-            let para = Paragraph::new("No cards on table")
-                .style(Style::default().fg(Color::DarkGray))
+            let para = Paragraph::new(tr(Text::NoCardsOnTable))
+                .style(Style::default().fg(self.theme.muted))
                 .alignment(Alignment::Center);
             para.render(inner_area, buf);
         }
     }
-    fn render_help(&self, area: Rect, buf: &mut Buffer) {
+    /// The deck beside the table, see `DeckView`, with the trump suit written below it.
+    /// Once the deck is empty the trump suit is still shown so it is never out of sight.
+    fn render_deck(&self, area: Rect, buf: &mut Buffer) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(tr(Text::DeckTitle))
+            .title_alignment(Alignment::Center);
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(self.card_size.card_height()), // Deck and trump card
+                Constraint::Length(1),                            // Trump suit
+            ])
+            .split(inner_area);
+        let trump_suit = self.game_state.trump_suit();
+        // Unless it named the trump suit the bottom card means nothing and stays face down
+        let trump_card = self.game_state.trump_card().copied();
+        DeckView::new(self.game_state.deck().remaining(), trump_card)
+            .theme(self.theme)
+            .trump_suit(trump_suit)
+            .size(self.card_size)
+            .render(layout[0], buf);
+        let trump_text = match trump_suit {
+            Some(trump_suit) => tr_fmt(Text::Trump, &[&trump_suit.symbol()]),
+            None => tr(Text::NoTrump).to_string(),
+        };
+        Paragraph::new(trump_text)
+            .style(
+                Style::default()
+                    .fg(self.theme.trump)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .alignment(Alignment::Center)
+            .render(layout[1], buf);
+    }
+    /// Shown once, right after the last card leaves the deck
+    fn render_endgame_banner(&self, area: Rect, buf: &mut Buffer) {
+        let banner_area = Rect {
+            y: area.y + area.height.saturating_sub(3) / 2,
+            height: area.height.min(3),
+            ..area
+        };
+        Paragraph::new(tr(Text::EndgameBanner))
+            .style(
+                Style::default()
+                    .fg(self.theme.warning)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(self.theme.warning)),
+            )
+            .render(banner_area, buf);
+    }
+    /// The attacks still to beat, shown when the turn passes to the human defender
+    fn briefing_lines(&self) -> Vec<Line<'static>> {
+        let attacker = &self.game_state.players()[self.game_state.current_attacker()];
+        let open_attacks: Vec<String> = self
+            .game_state
+            .table_cards()
+            .iter()
+            .filter(|(_, defense)| defense.is_none())
+            .map(|(attack, _)| attack.to_string())
+            .collect();
+        vec![
+            Line::styled(
+                tr_fmt(Text::BriefingTitle, &[&attacker.name()]),
+                Style::default().fg(self.theme.highlight),
+            ),
+            Line::from(tr_fmt(Text::BriefingAttacks, &[&open_attacks.join(" ")])),
+        ]
+    }
+    /// Between rounds: who draws how many cards and what is left in the deck afterwards
+    fn render_draw_summary(&self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(self.draw_summary_lines())
+            .alignment(Alignment::Center)
+            .render(area, buf);
+    }
+    /// The outcome of the round that just ended, e.g. "Player took 3 cards"
+    fn round_summary_text(&self, summary: RoundSummary) -> String {
+        let name = |idx: usize| {
+            self.game_state
+                .players()
+                .get(idx)
+                .map_or("", |player| player.name())
+        };
+        match summary {
+            RoundSummary::Discarded { defender, cards } => {
+                tr_fmt(Text::RoundBeaten, &[&name(defender), &cards])
+            }
+            RoundSummary::Taken { defender, cards: 1 } => {
+                tr_fmt(Text::RoundTookOne, &[&name(defender)])
+            }
+            RoundSummary::Taken { defender, cards } => {
+                tr_fmt(Text::RoundTookMany, &[&name(defender), &cards])
+            }
+        }
+    }
+    fn draw_summary_lines(&self) -> Vec<Line<'static>> {
+        let draws = self.game_state.draw_preview();
+        let deck_before = self.game_state.deck().remaining();
+        let deck_after = deck_before - draws.iter().sum::<usize>();
+        let mut lines = vec![Line::styled(
+            tr(Text::DrawSummaryTitle),
+            Style::default().fg(self.theme.title),
+        )];
+        if let Some(summary) = self.game_state.last_round_summary {
+            lines.push(Line::styled(
+                self.round_summary_text(summary),
+                Style::default().fg(self.theme.highlight),
+            ));
+        }
+        for (player, count) in self.game_state.players().iter().zip(draws) {
+            let key = if count == 1 {
+                Text::DrawsOne
+            } else {
+                Text::DrawsMany
+            };
+            lines.push(Line::from(tr_fmt(key, &[&player.name(), &count])));
+        }
+        lines.push(Line::styled(
+            tr_fmt(Text::DeckAfterDraw, &[&deck_before, &deck_after]),
+            Style::default().fg(self.theme.trump),
+        ));
+        lines
+    }
+    /// Every player in seating order with their card count, marking the attacker (⚔) and
+    /// the defender (🛡). The human is highlighted.
+    fn render_player_strip(&self, area: Rect, buf: &mut Buffer) {
+        let mut spans = Vec::new();
+        for (idx, player) in self.game_state.players().iter().enumerate() {
+            if idx > 0 {
+                spans.push(Span::styled(" → ", Style::default().fg(self.theme.muted)));
+            }
+            let marker = if idx == self.game_state.current_attacker() {
+                "⚔ "
+            } else if idx == self.game_state.current_defender() {
+                "🛡 "
+            } else {
+                ""
+            };
+            let style = if player.player_type() == &crate::game::PlayerType::Human {
+                Style::default()
+                    .fg(self.theme.highlight)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(self.theme.text)
+            };
+            spans.push(Span::styled(
+                format!("{}{} ({})", marker, player.name(), player.hand_size()),
+                style,
+            ));
+        }
+        Paragraph::new(Line::from(spans))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(tr(Text::PlayersTitle))
+                    .title_alignment(Alignment::Center),
+            )
+            .alignment(Alignment::Center)
+            .render(area, buf);
+    }
+    /// The whole game in one bordered panel for small terminals: hands and the table are
+    /// written as "RankSuit" tokens instead of bordered cards.
+    fn render_compact(&self, area: Rect, buf: &mut Buffer) {
+        let trump_suit = self.game_state.trump_suit();
+        let card_style = |card: &Card| {
+            if trump_suit == Some(card.suit) {
+                Style::default().fg(self.theme.trump)
+            } else if card.suit.is_red() {
+                Style::default().fg(self.theme.red_suit)
+            } else {
+                Style::default().fg(self.theme.black_suit)
+            }
+        };
+        let mut lines = vec![self.status_line()];
+        if self.game_state.endgame_notice {
+            lines.push(Line::styled(
+                tr(Text::EndgameBanner),
+                Style::default()
+                    .fg(self.theme.warning)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        for (idx, player) in self.game_state.players().iter().enumerate() {
+            if self.viewer == Some(idx) {
+                continue;
+            }
+            let mut spans = vec![Span::raw(format!(
+                "{}: {}",
+                player.name(),
+                tr_fmt(Text::CardCount, &[&self.dealt_hand(player.hand()).len()])
+            ))];
+            if let Some(action) = self.game_state.last_action(idx) {
+                spans.push(Span::styled(
+                    format!(" ({})", tr_action(action)),
+                    Style::default().fg(self.theme.muted),
+                ));
+            }
+            lines.push(Line::from(spans));
+        }
+        if self.dealt_cards.is_some() {
+            lines.push(Line::from(tr(Text::Dealing)));
+        } else if self.defense_briefing {
+            lines.extend(self.briefing_lines());
+        } else if *self.game_state.game_phase() == GamePhase::Drawing {
+            lines.extend(self.draw_summary_lines());
+        } else {
+            let mut spans = vec![Span::raw(format!("{}: ", tr(Text::TableTitle)))];
+            for (idx, (attack, defense)) in self.game_state.table_cards().iter().enumerate() {
+                let mut attack_style = card_style(attack);
+                if self.selected_attack_idx == Some(idx) {
+                    attack_style = attack_style.add_modifier(Modifier::REVERSED);
+                }
+                spans.push(Span::styled(attack.to_string(), attack_style));
+                spans.push(Span::raw("/"));
+                match defense {
+                    Some(defense) => {
+                        spans.push(Span::styled(defense.to_string(), card_style(defense)))
+                    }
+                    None => spans.push(Span::raw("_")),
+                }
+                spans.push(Span::raw(" "));
+            }
+            lines.push(Line::from(spans));
+        }
+        if let Some(human) = self.viewer.map(|idx| &self.game_state.players()[idx]) {
+            let mut spans = vec![Span::styled(
+                format!("{}: ", human.name()),
+                Style::default().fg(self.theme.highlight),
+            )];
+            for (idx, card) in self.dealt_hand(human.hand()).iter().enumerate() {
+                let mut style = card_style(card);
+                let multi_selected = self
+                    .multiple_selected
+                    .is_some_and(|selected| selected.contains(&idx));
+                if multi_selected {
+                    style = style.add_modifier(Modifier::UNDERLINED);
+                }
+                if self.selected_idx == Some(idx) {
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+                spans.push(Span::styled(card.to_string(), style));
+                spans.push(Span::raw(" "));
+            }
+            lines.push(Line::from(spans));
+        }
+        lines.push(Line::styled(
+            self.help_text(),
+            Style::default().fg(self.theme.muted),
+        ));
+        Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL))
+            .wrap(Wrap { trim: true })
+            .render(area, buf);
+    }
+    /// Key bindings for the current phase, or the pending confirmation question
+    fn help_text(&self) -> String {
         let current_phase = self.game_state.game_phase();
         let multiple_selection = self.multiple_selected.is_some();
+        let selection_mode = tr_on_off(multiple_selection);
         let help_text = match current_phase {
-            GamePhase::Attack => format!(
-                "←/→: Select card | M: Multi-select mode {} | Space: Toggle selection | Enter: Play card(s) | P: Pass | q: Quit",
-                if multiple_selection { "ON" } else { "OFF" }
-            ),
-            GamePhase::Defense => format!(
-                "←/→: Select card | M: Multi-select mode {} | Space: Toggle selection | Enter: Play card (same rank = pass) | T: Take cards | q: Quit",
-                if multiple_selection { "ON" } else { "OFF" }
-            ),
-            GamePhase::GameOver => "Q: Quit | N: New game".to_string(),
-            GamePhase::Drawing => "Press any key to continue".to_string(),
+            _ if self.demo => tr(Text::HelpDemo).to_string(),
+            _ if self.replay.is_some() => tr(Text::HelpReplay).to_string(),
+            _ if self.pending_quit => tr(Text::HelpQuitConfirm).to_string(),
+            _ if self.pending_surrender => tr(Text::HelpSurrenderConfirm).to_string(),
+            _ if self.defense_briefing => tr(Text::HelpDrawing).to_string(),
+            GamePhase::Attack => tr_fmt(Text::HelpAttack, &[&selection_mode]),
+            GamePhase::Defense => {
+                let reveal = if self.game_state.allow_trump_reveal_pass
+                    && !self.game_state.forbid_trump_pass
+                {
+                    tr(Text::HelpRevealPass)
+                } else {
+                    ""
+                };
+                tr_fmt(Text::HelpDefense, &[&selection_mode, &reveal])
+            }
+            GamePhase::Reinforce => tr_fmt(Text::HelpReinforce, &[&selection_mode]),
+            GamePhase::GameOver => tr(Text::HelpGameOver).to_string(),
+            GamePhase::Drawing => tr(Text::HelpDrawing).to_string(),
             _ => "".to_string(),
         };
-        let para = Paragraph::new(help_text)
+        let confirming = self.pending_quit || self.pending_surrender;
+        let help_text = if self.undo_available && !confirming {
+            tr_fmt(Text::HelpUndo, &[&help_text])
+        } else {
+            help_text
+        };
+        if self.step_ai && !confirming {
+            tr_fmt(Text::HelpStepAi, &[&help_text])
+        } else {
+            help_text
+        }
+    }
+    /// One AI move in words, see `AiComment`
+    fn comment_text(&self, comment: &AiComment) -> String {
+        let name = self.game_state.players()[comment.player_idx()].name();
+        let cards = |cards: &[Card]| {
+            let cards: Vec<String> = cards.iter().map(Card::to_string).collect();
+            cards.join(" ")
+        };
+        match comment {
+            AiComment::Attack {
+                cards: played,
+                adding,
+                reason,
+                ..
+            } => {
+                let key = match reason {
+                    Some(AttackReason::WeakRank) => Text::CommentWeakRank,
+                    Some(AttackReason::DrainTrumps) => Text::CommentDrainTrumps,
+                    Some(AttackReason::Forcing) => Text::CommentForcing,
+                    None if *adding => Text::CommentAdd,
+                    None => Text::CommentAttack,
+                };
+                tr_fmt(key, &[&name, &cards(played)])
+            }
+            AiComment::StopAdding { .. } => tr_fmt(Text::CommentStopAdding, &[&name]),
+            AiComment::Defend {
+                pairs,
+                used_trump,
+                saved_trumps,
+                ..
+            } => {
+                let beaten: Vec<String> = pairs
+                    .iter()
+                    .map(|(attack, defense)| tr_fmt(Text::CommentBeatWith, &[attack, defense]))
+                    .collect();
+                let key = if *used_trump {
+                    Text::CommentUsedTrump
+                } else if *saved_trumps {
+                    Text::CommentSavedTrumps
+                } else {
+                    Text::CommentDefend
+                };
+                tr_fmt(key, &[&name, &beaten.join(", ")])
+            }
+            AiComment::Take { could_not_beat, .. } => {
+                let key = if *could_not_beat {
+                    Text::CommentCantBeat
+                } else {
+                    Text::CommentTake
+                };
+                tr_fmt(key, &[&name])
+            }
+            AiComment::Pass { card, .. } => tr_fmt(Text::CommentPass, &[&name, card]),
+        }
+    }
+    /// The commentary panel, newest comment on top so the oldest are the ones cut off
+    fn render_commentary(&self, area: Rect, buf: &mut Buffer, comments: &[AiComment]) {
+        let lines: Vec<Line> = comments
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(idx, comment)| {
+                let color = if idx == 0 {
+                    self.theme.text
+                } else {
+                    self.theme.muted
+                };
+                Line::styled(self.comment_text(comment), Style::default().fg(color))
+            })
+            .collect();
+        Paragraph::new(lines)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title("Help")
+                    .title(tr(Text::CommentaryTitle))
                     .title_alignment(Alignment::Center),
             )
-            .style(Style::default().fg(Color::White))
+            .wrap(Wrap { trim: true })
+            .render(area, buf);
+    }
+    fn render_help(&self, area: Rect, buf: &mut Buffer) {
+        let para = Paragraph::new(self.help_text())
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(tr(Text::HelpTitle))
+                    .title_alignment(Alignment::Center),
+            )
+            .style(Style::default().fg(self.theme.text))
             .alignment(Alignment::Center);
         para.render(area, buf);
     }
 }
 
+/// The message shown instead of a screen when the terminal is below the smallest usable size
+pub fn terminal_too_small(area: Rect, theme: &Theme) -> Paragraph<'static> {
+    let message = tr_fmt(
+        Text::TerminalTooSmall,
+        &[
+            &area.width,
+            &area.height,
+            &COMPACT_MIN_WIDTH,
+            &COMPACT_MIN_HEIGHT,
+        ],
+    );
+    Paragraph::new(message)
+        .style(Style::default().fg(theme.warning))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+}
+
 impl Widget for GameUI<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
+        let show_player_strip = self.game_state.players().len() > 2;
+        let min_height = if show_player_strip {
+            self.min_height() + PLAYER_STRIP_HEIGHT
+        } else {
+            self.min_height()
+        };
+        if area.height < min_height || area.width < MIN_WIDTH {
+            if area.height >= COMPACT_MIN_HEIGHT && area.width >= COMPACT_MIN_WIDTH {
+                self.render_compact(area, buf);
+                return;
+            }
+            terminal_too_small(area, &self.theme).render(area, buf);
+            return;
+        }
+        let area = if show_player_strip {
+            let strip_area = Rect {
+                height: PLAYER_STRIP_HEIGHT,
+                ..area
+            };
+            self.render_player_strip(strip_area, buf);
+            Rect {
+                y: area.y + PLAYER_STRIP_HEIGHT,
+                height: area.height - PLAYER_STRIP_HEIGHT,
+                ..area
+            }
+        } else {
+            area
+        };
         let vertical_layout = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(3), // Status bar
-                Constraint::Length(7), // Player 1 (top, computer)
-                Constraint::Min(10),   // Table (middle)
-                Constraint::Length(7), // Player 0 (bottom, human)
-                Constraint::Length(3), // Help
+                Constraint::Length(STATUS_HEIGHT),      // Status bar
+                Constraint::Length(self.hand_height()), // Opponent (top)
+                Constraint::Min(self.table_height()),   // Table (middle)
+                Constraint::Length(self.hand_height()), // Viewer (bottom, human)
+                Constraint::Length(HELP_HEIGHT),        // Help
             ])
             .split(area);
         self.render_status_bar(vertical_layout[0], buf);
-        // For a 2-player game
+        // For a 2-player game. The viewer sits at the bottom, in a hotseat game that's
+        // whichever human has the device.
         if self.game_state.players().len() >= 2 {
-            self.render_player_hand(vertical_layout[1], buf, 1); // Computer player
-            self.render_table(vertical_layout[2], buf);
-            self.render_player_hand(vertical_layout[3], buf, 0); // Human player
+            let bottom = self.viewer.unwrap_or(0);
+            let top = if bottom == 0 { 1 } else { 0 };
+            self.render_player_hand(vertical_layout[1], buf, top);
+            let commentary = self
+                .commentary
+                .as_ref()
+                .filter(|_| area.width >= MIN_WIDTH + COMMENTARY_WIDTH);
+            let commentary_width = if commentary.is_some() {
+                COMMENTARY_WIDTH
+            } else {
+                0
+            };
+            let middle = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([
+                    Constraint::Min(0),
+                    Constraint::Length(commentary_width),
+                    Constraint::Length(self.deck_panel_width()),
+                ])
+                .split(vertical_layout[2]);
+            self.render_table(middle[0], buf);
+            if let Some(comments) = commentary {
+                self.render_commentary(middle[1], buf, comments);
+            }
+            self.render_deck(middle[2], buf);
+            self.render_player_hand(vertical_layout[3], buf, bottom);
         }
         self.render_help(vertical_layout[4], buf);
     }
@@ -0,0 +1,851 @@
+use crate::game::AiDifficulty;
+use crate::game::game_state::PlayerAction;
+use lazy_static::lazy_static;
+use std::fmt::Display;
+use std::sync::Mutex;
+
+// Language every `tr` lookup currently resolves to
+lazy_static! {
+    static ref LOCALE: Mutex<Locale> = Mutex::new(Locale::English);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    English,
+    Russian,
+}
+
+impl Locale {
+    /// The language after this one, used by the main menu to cycle through them
+    pub fn next(self) -> Self {
+        match self {
+            Locale::English => Locale::Russian,
+            Locale::Russian => Locale::English,
+        }
+    }
+}
+
+impl Display for Locale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Locale::English => write!(f, "English"),
+            Locale::Russian => write!(f, "Русский"),
+        }
+    }
+}
+
+pub fn set_locale(locale: Locale) {
+    if let Ok(mut current) = LOCALE.lock() {
+        *current = locale;
+    }
+}
+
+pub fn current_locale() -> Locale {
+    LOCALE
+        .lock()
+        .map(|locale| *locale)
+        .unwrap_or(Locale::English)
+}
+
+/// Keys for every piece of user-facing prose. Placeholders are written as `{}` and filled
+/// in order by `tr_fmt`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Text {
+    // Main menu
+    GameTitle,
+    MenuContinue,
+    MenuStart,
+    MenuDifficulty,
+    MenuTrumpRule,
+    MenuRevealPass,
+    MenuTrumpPass,
+    MenuJokers,
+    MenuDecks,
+    DifficultyDeckSizeKey,
+    DeckSizeSetting,
+    MenuPractice,
+    MenuDefendFirst,
+    MenuMulligan,
+    MenuAttackLimit,
+    MenuSelectionStyle,
+    MenuCardSize,
+    MenuTheme,
+    MenuAids,
+    MenuCommentary,
+    MenuHotseat,
+    MenuDemo,
+    MenuLanguage,
+    MenuRules,
+    MenuQuit,
+    MenuDebug,
+    CurrentDifficulty,
+    TrumpMode,
+    TrumpModeStandard,
+    TrumpModeNoTrump,
+    TrumpModeRandom,
+    TrumpModeDealer,
+    RevealPassSetting,
+    TrumpPassSetting,
+    JokersSetting,
+    DecksSetting,
+    VariantDeck,
+    RulesActive,
+    SummaryTitle,
+    SummaryStart,
+    PracticeSetting,
+    DefendFirstSetting,
+    MulliganSetting,
+    AttackLimitSetting,
+    AttackLimitHand,
+    AttackLimitSix,
+    SelectionStyleSetting,
+    SelectionWrap,
+    SelectionClamp,
+    CardSizeSetting,
+    ThemeSetting,
+    ThemeDefault,
+    ThemeHighContrast,
+    ThemeMonochrome,
+    AidsSetting,
+    CommentarySetting,
+    AnalysisTitle,
+    AnalysisSummary,
+    AnalysisNoDivergence,
+    AnalysisDivergence,
+    AnalysisScrollHint,
+    MoveAttack,
+    MoveStop,
+    MoveDefend,
+    MovePass,
+    MoveTake,
+    CommentaryTitle,
+    CommentAttack,
+    CommentAdd,
+    CommentWeakRank,
+    CommentDrainTrumps,
+    CommentForcing,
+    CommentStopAdding,
+    CommentDefend,
+    CommentSavedTrumps,
+    CommentUsedTrump,
+    CommentBeatWith,
+    CommentTake,
+    CommentCantBeat,
+    CommentPass,
+    HotseatSetting,
+    HandoverTo,
+    HandoverContinue,
+    CardSizeSmall,
+    CardSizeMedium,
+    CardSizeLarge,
+    On,
+    Off,
+    LanguageSetting,
+    // Difficulty select
+    DemoTitle,
+    DemoFirstAi,
+    DemoSecondAi,
+    DemoStart,
+    DifficultyTitle,
+    DifficultyEasyKey,
+    DifficultyMediumKey,
+    DifficultyHardKey,
+    DifficultyDynamicKey,
+    DifficultyCurrent,
+    BackToMenu,
+    Easy,
+    Medium,
+    Hard,
+    Dynamic,
+    // Rules page
+    RulesTitle,
+    RulesBlockTitle,
+    // Game over screen
+    GameOverTitle,
+    WinnerIs,
+    DurakIs,
+    DrawNoDurak,
+    GameOverFallback,
+    GameOverInstructions,
+    // Status bar
+    StatusTitle,
+    StatusSetup,
+    StatusAttack,
+    StatusDefense,
+    StatusReinforce,
+    StatusDrawing,
+    StatusGameOverWinner,
+    StatusGameOver,
+    Trump,
+    NoTrump,
+    CardsLeft,
+    UnseenTrumps,
+    UnseenBySuit,
+    HandValue,
+    DefendedSoFar,
+    TimeLeft,
+    PracticeMove,
+    EndgameBanner,
+    // Table and hands
+    TableTitle,
+    DeckTitle,
+    NoCardsOnTable,
+    Dealing,
+    PlayersTitle,
+    CardCount,
+    DebugRevealedHand,
+    DrawSummaryTitle,
+    BriefingTitle,
+    BriefingAttacks,
+    StatusRound,
+    RoundBeaten,
+    RoundTookOne,
+    RoundTookMany,
+    DrawsOne,
+    DrawsMany,
+    DeckAfterDraw,
+    TerminalTooSmall,
+    // Opponent actions
+    ActionAttacking,
+    ActionDefended,
+    ActionPassedAttack,
+    ActionTookOne,
+    ActionTookMany,
+    ActionDrewTo,
+    // Help bar
+    HelpTitle,
+    HelpQuitConfirm,
+    HelpSurrenderConfirm,
+    HelpAttack,
+    HelpDefense,
+    HelpRevealPass,
+    HelpReinforce,
+    HelpGameOver,
+    HelpDrawing,
+    HelpDemo,
+    HelpReplay,
+    ReplayDeal,
+    ReplayMove,
+    ReplayDefend,
+    ReplayTake,
+    ReplayDiscard,
+    ReplayDraw,
+    ReplayRevealedHand,
+    AiThinking,
+    HelpStepAi,
+    HelpUndo,
+}
+
+/// Looks up `key` in the current language
+pub fn tr(key: Text) -> &'static str {
+    match current_locale() {
+        Locale::English => english(key),
+        Locale::Russian => russian(key),
+    }
+}
+
+/// Looks up `key` and fills its `{}` placeholders with `args` in order
+pub fn tr_fmt(key: Text, args: &[&dyn Display]) -> String {
+    let mut parts = tr(key).split("{}");
+    let mut text = parts.next().unwrap_or_default().to_string();
+    for (idx, part) in parts.enumerate() {
+        if let Some(arg) = args.get(idx) {
+            text.push_str(&arg.to_string());
+        }
+        text.push_str(part);
+    }
+    text
+}
+
+/// "On"/"Off" in the current language
+pub fn tr_on_off(on: bool) -> &'static str {
+    tr(if on { Text::On } else { Text::Off })
+}
+
+/// Difficulty name in the current language
+pub fn tr_difficulty(difficulty: AiDifficulty) -> &'static str {
+    tr(match difficulty {
+        AiDifficulty::Easy => Text::Easy,
+        AiDifficulty::Medium => Text::Medium,
+        AiDifficulty::Hard => Text::Hard,
+        AiDifficulty::Dynamic => Text::Dynamic,
+    })
+}
+
+/// An opponent's last action in the current language
+pub fn tr_action(action: PlayerAction) -> String {
+    match action {
+        PlayerAction::Attacking => tr(Text::ActionAttacking).to_string(),
+        PlayerAction::Defended => tr(Text::ActionDefended).to_string(),
+        PlayerAction::PassedAttack => tr(Text::ActionPassedAttack).to_string(),
+        PlayerAction::Took(1) => tr(Text::ActionTookOne).to_string(),
+        PlayerAction::Took(count) => tr_fmt(Text::ActionTookMany, &[&count]),
+        PlayerAction::DrewTo(count) => tr_fmt(Text::ActionDrewTo, &[&count]),
+    }
+}
+
+/// The rules page, one entry per line
+pub fn rules_lines() -> &'static [&'static str] {
+    match current_locale() {
+        Locale::English => &ENGLISH_RULES,
+        Locale::Russian => &RUSSIAN_RULES,
+    }
+}
+
+fn english(key: Text) -> &'static str {
+    match key {
+        Text::GameTitle => "Durak Card Game",
+        Text::MenuContinue => "Press 'c' to continue your saved game",
+        Text::MenuStart => "Press 's' to start a new game",
+        Text::MenuDifficulty => "Press 'a' to change AI difficulty",
+        Text::MenuTrumpRule => "Press 't' to change the trump rule",
+        Text::MenuRevealPass => "Press 'v' to toggle the trump reveal pass rule",
+        Text::MenuTrumpPass => "Press 'u' to toggle passing with a trump",
+        Text::MenuJokers => "Press 'j' to toggle jokers",
+        Text::MenuDecks => "Press 'n' to change the number of decks",
+        Text::DifficultyDeckSizeKey => "Press 's' to change the deck size",
+        Text::DeckSizeSetting => "Cards per deck: {}",
+        Text::MenuPractice => "Press 'p' to toggle practice mode",
+        Text::MenuDefendFirst => "Press 'g' to always defend the first round",
+        Text::MenuMulligan => "Press 'm' to toggle the mulligan (press 'r' in game to redeal)",
+        Text::MenuAttackLimit => "Press 'f' to switch the attack limit",
+        Text::MenuSelectionStyle => "Press 'w' to switch how the card cursor moves past the ends",
+        Text::MenuCardSize => "Press 'z' to change the card size",
+        Text::MenuTheme => "Press 'o' to change the colour theme",
+        Text::MenuAids => "Press 'i' to toggle the unseen-card counts and hand value",
+        Text::MenuCommentary => "Press 'k' to toggle the AI commentary",
+        Text::MenuHotseat => "Press 'h' to play against a second human at this terminal",
+        Text::MenuDemo => "Press 'e' to watch an AI demo game",
+        Text::MenuLanguage => "Press 'l' to change the language",
+        Text::MenuRules => "Press 'r' to view game rules",
+        Text::MenuQuit => "Press 'q' to quit",
+        Text::MenuDebug => "Press 'd' to toggle debug overlay",
+        Text::CurrentDifficulty => "Current AI Difficulty: {}",
+        Text::TrumpMode => "Trump Mode: {}",
+        Text::TrumpModeStandard => "Standard",
+        Text::TrumpModeNoTrump => "No trump",
+        Text::TrumpModeRandom => "Random suit",
+        Text::TrumpModeDealer => "Dealer chooses",
+        Text::RevealPassSetting => "Trump Reveal Pass: {}",
+        Text::TrumpPassSetting => "No Passing With Trumps: {}",
+        Text::JokersSetting => "Jokers: {}",
+        Text::DecksSetting => "Decks: {}",
+        Text::VariantDeck => "{}-card deck, {} players",
+        Text::RulesActive => "Active rules:",
+        Text::SummaryTitle => "New Game",
+        Text::SummaryStart => "Press Enter to start, or 'b' to change the settings",
+        Text::PracticeSetting => "Practice mode: {}",
+        Text::DefendFirstSetting => "Defend first: {}",
+        Text::MulliganSetting => "Mulligan: {}",
+        Text::AttackLimitSetting => "Attack limit: {}",
+        Text::AttackLimitHand => "defender's hand, up to 6",
+        Text::AttackLimitSix => "always 6",
+        Text::SelectionStyleSetting => "Card cursor: {}",
+        Text::SelectionWrap => "wraps around",
+        Text::SelectionClamp => "stops at the ends",
+        Text::CardSizeSetting => "Card size: {}",
+        Text::ThemeSetting => "Theme: {}",
+        Text::ThemeDefault => "default",
+        Text::ThemeHighContrast => "high contrast",
+        Text::ThemeMonochrome => "monochrome",
+        Text::AidsSetting => "Card counts and hand value: {}",
+        Text::CommentarySetting => "AI commentary: {}",
+        Text::AnalysisTitle => " How the {} AI would have played ",
+        Text::AnalysisSummary => "It agreed with {} of your {} moves",
+        Text::AnalysisNoDivergence => "It would have played exactly as you did",
+        Text::AnalysisDivergence => "Round {}: you chose to {}, the AI would have chosen to {}",
+        Text::AnalysisScrollHint => " ↑/↓ to scroll ",
+        Text::MoveAttack => "attack with {}",
+        Text::MoveStop => "stop adding cards",
+        Text::MoveDefend => "beat it with {}",
+        Text::MovePass => "pass it on with {}",
+        Text::MoveTake => "take the cards",
+        Text::CommentaryTitle => "Commentary",
+        Text::CommentAttack => "{} attacks with {}",
+        Text::CommentAdd => "{} adds {}",
+        Text::CommentWeakRank => "{} adds {}, the defender is probably out of answers to that rank",
+        Text::CommentDrainTrumps => "{} adds the trump {} to drain the defender's trumps",
+        Text::CommentForcing => "{} plays {} so the defender can't get rid of their last cards",
+        Text::CommentStopAdding => "{} has nothing more to add",
+        Text::CommentDefend => "{} beat {}",
+        Text::CommentSavedTrumps => "{} saved its trumps and beat {}",
+        Text::CommentUsedTrump => "{} had to use a trump and beat {}",
+        Text::CommentBeatWith => "{} with {}",
+        Text::CommentTake => "{} takes the cards",
+        Text::CommentCantBeat => "{} can't beat everything and takes the cards",
+        Text::CommentPass => "{} passes the attack on with {}",
+        Text::HotseatSetting => "Hotseat: {}",
+        Text::HandoverTo => "Pass the device to {}",
+        Text::HandoverContinue => "Press any key when ready",
+        Text::CardSizeSmall => "small",
+        Text::CardSizeMedium => "medium",
+        Text::CardSizeLarge => "large",
+        Text::On => "On",
+        Text::Off => "Off",
+        Text::LanguageSetting => "Language: {}",
+        Text::DemoTitle => "AI Demo",
+        Text::DemoFirstAi => "Press '1' to change the first AI: {}",
+        Text::DemoSecondAi => "Press '2' to change the second AI: {}",
+        Text::DemoStart => "Press Enter to start the demo, then any key to stop it",
+        Text::DifficultyTitle => "Select AI Difficulty",
+        Text::DifficultyEasyKey => "Press '1' for Easy AI",
+        Text::DifficultyMediumKey => "Press '2' for Medium AI",
+        Text::DifficultyHardKey => "Press '3' for Hard AI",
+        Text::DifficultyDynamicKey => "Press '4' for Dynamic AI (adapts to your results)",
+        Text::DifficultyCurrent => "Current: {}",
+        Text::BackToMenu => "Press 'b' to go back to main menu",
+        Text::Easy => "Easy",
+        Text::Medium => "Medium",
+        Text::Hard => "Hard",
+        Text::Dynamic => "Dynamic",
+        Text::RulesTitle => "Durak Game Rules",
+        Text::RulesBlockTitle => "Game Rules",
+        Text::GameOverTitle => "Game Over",
+        Text::WinnerIs => "{} is the winner!",
+        Text::DurakIs => "{} is the durak!",
+        Text::DrawNoDurak => "Draw — no durak!",
+        Text::GameOverFallback => "Game Over!",
+        Text::GameOverInstructions => {
+            "Press 'N' for a rematch | Press 'R' to replay the game | Press 'M' for the menu | Press 'Q' to quit"
+        }
+        Text::StatusTitle => "Game Status",
+        Text::StatusSetup => "Setting up game...",
+        Text::StatusAttack => "{}'s turn to attack",
+        Text::StatusDefense => "{}'s turn to defend or pass",
+        Text::StatusReinforce => "{} may throw in matching cards or pass",
+        Text::StatusDrawing => "Drawing cards...",
+        Text::StatusGameOverWinner => "Game over! {} is the winner!",
+        Text::StatusGameOver => "Game over!",
+        Text::Trump => "Trump: {}",
+        Text::NoTrump => "No trump",
+        Text::CardsLeft => "Cards left: {}",
+        Text::UnseenTrumps => "Unseen trumps: {}",
+        Text::UnseenBySuit => "Unseen: {}",
+        Text::HandValue => "Hand value: {}, trumps: {}",
+        Text::DefendedSoFar => "Defended {} of {} - beat the rest or press T to take",
+        Text::TimeLeft => "Time: {}s",
+        Text::PracticeMove => "Practice: move {} (PgUp/PgDn)",
+        Text::EndgameBanner => "Deck empty — endgame! No more drawing. Press any key",
+        Text::TableTitle => "Table",
+        Text::DeckTitle => "Deck",
+        Text::NoCardsOnTable => "No cards on table",
+        Text::Dealing => "Dealing…",
+        Text::PlayersTitle => "Players",
+        Text::CardCount => "{} cards",
+        Text::DebugRevealedHand => "DEBUG: revealed hand",
+        Text::DrawSummaryTitle => "Round over - drawing cards",
+        Text::BriefingTitle => "Your turn to defend against {}",
+        Text::BriefingAttacks => "Attacks to beat: {}",
+        Text::StatusRound => "Round {}",
+        Text::RoundBeaten => "{} beat every attack - {} cards discarded",
+        Text::RoundTookOne => "{} took 1 card",
+        Text::RoundTookMany => "{} took {} cards",
+        Text::DrawsOne => "{} draws {} card",
+        Text::DrawsMany => "{} draws {} cards",
+        Text::DeckAfterDraw => "Deck: {} -> {} cards",
+        Text::TerminalTooSmall => "Terminal too small ({}x{}), please resize to at least {}x{}",
+        Text::ActionAttacking => "attacking",
+        Text::ActionDefended => "defended",
+        Text::ActionPassedAttack => "passed the attack",
+        Text::ActionTookOne => "took 1 card",
+        Text::ActionTookMany => "took {} cards",
+        Text::ActionDrewTo => "drew to {}",
+        Text::HelpTitle => "Help",
+        Text::HelpQuitConfirm => "Quit game? (y/n)",
+        Text::HelpSurrenderConfirm => "Surrender and lose this game? (y/n)",
+        Text::HelpAttack => {
+            "←/→: Select card | 2-9/0/J/Q/K/A: Jump to rank | M: Multi-select mode {} | Space: Toggle selection | S: Sort | a: Lowest card | Enter: Play card(s) | P: Pass | [/]: Scroll table | F5: Save | X: Surrender | q: Quit"
+        }
+        Text::HelpDefense => {
+            "←/→: Select card | 2-9/0/J/Q/K/A: Jump to rank | M: Multi-select mode {} | Space: Toggle selection | S: Sort | a: Lowest card | Tab: Choose attack | Enter: Defend | P: Pass (same rank{}) | T: Take cards | [/]: Scroll table | F5: Save | X: Surrender | q: Quit"
+        }
+        Text::HelpRevealPass => ", same-rank trump = reveal",
+        Text::HelpReinforce => {
+            "←/→: Select card | 2-9/0/J/Q/K/A: Jump to rank | M: Multi-select mode {} | Space: Toggle selection | S: Sort | a: Lowest card | Enter: Add card(s) | P: Add nothing | [/]: Scroll table | F5: Save | X: Surrender | q: Quit"
+        }
+        Text::HelpGameOver => "Q: Quit | N: Rematch | M: Menu",
+        Text::HelpDrawing => "Press any key to continue",
+        Text::HelpDemo => "AI demo - press any key to return to the menu",
+        Text::HelpReplay => "←/→: Step through the moves | Esc: Back",
+        Text::ReplayDeal => "Replay: the deal, {} moves to follow",
+        Text::ReplayMove => "Replay: move {} of {} - {}",
+        Text::ReplayDefend => "{} beats {} with {}",
+        Text::ReplayTake => "{} takes {}",
+        Text::ReplayDiscard => "{} beats off the attack, {} to the discard pile",
+        Text::ReplayDraw => "{} draws {}",
+        Text::ReplayRevealedHand => "REPLAY: hand face up",
+        Text::AiThinking => "Computer is thinking…",
+        Text::HelpStepAi => ".: Step AI | F: Fast forward | {}",
+        Text::HelpUndo => "U: Undo | {}",
+    }
+}
+
+fn russian(key: Text) -> &'static str {
+    match key {
+        Text::GameTitle => "Карточная игра Дурак",
+        Text::MenuContinue => "Нажмите 'c', чтобы продолжить сохранённую игру",
+        Text::MenuStart => "Нажмите 's', чтобы начать новую игру",
+        Text::MenuDifficulty => "Нажмите 'a', чтобы изменить сложность ИИ",
+        Text::MenuTrumpRule => "Нажмите 't', чтобы сменить правило выбора козыря",
+        Text::MenuRevealPass => "Нажмите 'v', чтобы включить/выключить перевод показом козыря",
+        Text::MenuTrumpPass => "Нажмите 'u', чтобы запретить/разрешить перевод козырем",
+        Text::MenuJokers => "Нажмите 'j', чтобы добавить/убрать джокеров",
+        Text::MenuDecks => "Нажмите 'n', чтобы изменить число колод",
+        Text::DifficultyDeckSizeKey => "Нажмите 's', чтобы изменить размер колоды",
+        Text::DeckSizeSetting => "Карт в колоде: {}",
+        Text::MenuPractice => "Нажмите 'p', чтобы включить/выключить тренировку",
+        Text::MenuDefendFirst => "Нажмите 'g', чтобы всегда отбиваться в первом раунде",
+        Text::MenuMulligan => "Нажмите 'm', чтобы разрешить пересдачу ('r' в игре)",
+        Text::MenuAttackLimit => "Нажмите 'f', чтобы сменить предел атаки",
+        Text::MenuSelectionStyle => "Нажмите 'w', чтобы сменить поведение курсора у краёв руки",
+        Text::MenuCardSize => "Нажмите 'z', чтобы изменить размер карт",
+        Text::MenuTheme => "Нажмите 'o', чтобы сменить цветовую тему",
+        Text::MenuAids => "Нажмите 'i', чтобы включить или выключить подсчёт карт и силу руки",
+        Text::MenuCommentary => "Нажмите 'k', чтобы включить или выключить комментарии ИИ",
+        Text::MenuHotseat => "Нажмите 'h', чтобы играть со вторым человеком за этим терминалом",
+        Text::MenuDemo => "Нажмите 'e', чтобы посмотреть демо-игру ИИ",
+        Text::MenuLanguage => "Нажмите 'l', чтобы сменить язык",
+        Text::MenuRules => "Нажмите 'r', чтобы посмотреть правила",
+        Text::MenuQuit => "Нажмите 'q', чтобы выйти",
+        Text::MenuDebug => "Нажмите 'd', чтобы показать/скрыть отладку",
+        Text::CurrentDifficulty => "Сложность ИИ: {}",
+        Text::TrumpMode => "Козырь: {}",
+        Text::TrumpModeStandard => "Обычный",
+        Text::TrumpModeNoTrump => "Без козыря",
+        Text::TrumpModeRandom => "Случайная масть",
+        Text::TrumpModeDealer => "Выбирает сдающий",
+        Text::RevealPassSetting => "Перевод показом козыря: {}",
+        Text::TrumpPassSetting => "Запрет перевода козырем: {}",
+        Text::JokersSetting => "Джокеры: {}",
+        Text::DecksSetting => "Колод: {}",
+        Text::VariantDeck => "Колода из {} карт, игроков: {}",
+        Text::RulesActive => "Действующие правила:",
+        Text::SummaryTitle => "Новая игра",
+        Text::SummaryStart => "Нажмите Enter, чтобы начать, или 'b', чтобы изменить настройки",
+        Text::PracticeSetting => "Тренировка: {}",
+        Text::DefendFirstSetting => "Отбиваться первым: {}",
+        Text::MulliganSetting => "Пересдача: {}",
+        Text::AttackLimitSetting => "Предел атаки: {}",
+        Text::AttackLimitHand => "по картам защитника, до 6",
+        Text::AttackLimitSix => "всегда 6",
+        Text::SelectionStyleSetting => "Курсор карт: {}",
+        Text::SelectionWrap => "переходит на другой край",
+        Text::SelectionClamp => "останавливается у края",
+        Text::CardSizeSetting => "Размер карт: {}",
+        Text::ThemeSetting => "Тема: {}",
+        Text::ThemeDefault => "обычная",
+        Text::ThemeHighContrast => "контрастная",
+        Text::ThemeMonochrome => "монохромная",
+        Text::AidsSetting => "Подсчёт карт и сила руки: {}",
+        Text::CommentarySetting => "Комментарии ИИ: {}",
+        Text::AnalysisTitle => " Как сыграл бы ИИ ({}) ",
+        Text::AnalysisSummary => "Совпало ходов: {} из {}",
+        Text::AnalysisNoDivergence => "ИИ сыграл бы точно так же",
+        Text::AnalysisDivergence => "Раунд {}: вы решили {}, ИИ решил бы {}",
+        Text::AnalysisScrollHint => " ↑/↓ — прокрутка ",
+        Text::MoveAttack => "пойти с {}",
+        Text::MoveStop => "больше не подкидывать",
+        Text::MoveDefend => "отбиться картами {}",
+        Text::MovePass => "перевести картой {}",
+        Text::MoveTake => "взять карты",
+        Text::CommentaryTitle => "Комментарии",
+        Text::CommentAttack => "{} ходит с {}",
+        Text::CommentAdd => "{} подкидывает {}",
+        Text::CommentWeakRank => {
+            "{} подкидывает {}, этот ранг защищающемуся, скорее всего, нечем бить"
+        }
+        Text::CommentDrainTrumps => "{} подкидывает козырь {}, чтобы выманить козыри защищающегося",
+        Text::CommentForcing => "{} ходит с {}, чтобы защищающийся не избавился от последних карт",
+        Text::CommentStopAdding => "{} больше ничего не подкидывает",
+        Text::CommentDefend => "{} отбивает {}",
+        Text::CommentSavedTrumps => "{} бережёт козыри и отбивает {}",
+        Text::CommentUsedTrump => "{} отбивается козырем: {}",
+        Text::CommentBeatWith => "{} картой {}",
+        Text::CommentTake => "{} берёт карты",
+        Text::CommentCantBeat => "{} не может отбиться и берёт карты",
+        Text::CommentPass => "{} переводит атаку картой {}",
+        Text::HotseatSetting => "Игра вдвоём: {}",
+        Text::HandoverTo => "Передайте устройство: {}",
+        Text::HandoverContinue => "Нажмите любую клавишу, когда будете готовы",
+        Text::CardSizeSmall => "маленький",
+        Text::CardSizeMedium => "средний",
+        Text::CardSizeLarge => "крупный",
+        Text::On => "Вкл",
+        Text::Off => "Выкл",
+        Text::LanguageSetting => "Язык: {}",
+        Text::DemoTitle => "Демо ИИ",
+        Text::DemoFirstAi => "Нажмите '1', чтобы сменить первый ИИ: {}",
+        Text::DemoSecondAi => "Нажмите '2', чтобы сменить второй ИИ: {}",
+        Text::DemoStart => "Нажмите Enter, чтобы начать демо, и любую клавишу, чтобы остановить",
+        Text::DifficultyTitle => "Выберите сложность ИИ",
+        Text::DifficultyEasyKey => "Нажмите '1' для лёгкого ИИ",
+        Text::DifficultyMediumKey => "Нажмите '2' для среднего ИИ",
+        Text::DifficultyHardKey => "Нажмите '3' для сложного ИИ",
+        Text::DifficultyDynamicKey => {
+            "Нажмите '4' для адаптивного ИИ (подстраивается под ваши результаты)"
+        }
+        Text::DifficultyCurrent => "Текущая: {}",
+        Text::BackToMenu => "Нажмите 'b', чтобы вернуться в главное меню",
+        Text::Easy => "Лёгкий",
+        Text::Medium => "Средний",
+        Text::Hard => "Сложный",
+        Text::Dynamic => "Адаптивный",
+        Text::RulesTitle => "Правила игры Дурак",
+        Text::RulesBlockTitle => "Правила",
+        Text::GameOverTitle => "Игра окончена",
+        Text::WinnerIs => "{} побеждает!",
+        Text::DurakIs => "{} остаётся в дураках!",
+        Text::DrawNoDurak => "Ничья — дурака нет!",
+        Text::GameOverFallback => "Игра окончена!",
+        Text::GameOverInstructions => {
+            "Нажмите 'N' для реванша | Нажмите 'R' для повтора партии | Нажмите 'M' для меню | Нажмите 'Q' для выхода"
+        }
+        Text::StatusTitle => "Состояние игры",
+        Text::StatusSetup => "Подготовка игры...",
+        Text::StatusAttack => "{} ходит",
+        Text::StatusDefense => "{} отбивается или переводит",
+        Text::StatusReinforce => "{} может подкинуть карты или сказать «бито»",
+        Text::StatusDrawing => "Добор карт...",
+        Text::StatusGameOverWinner => "Игра окончена! {} побеждает!",
+        Text::StatusGameOver => "Игра окончена!",
+        Text::Trump => "Козырь: {}",
+        Text::NoTrump => "Без козыря",
+        Text::CardsLeft => "В колоде: {}",
+        Text::UnseenTrumps => "Козырей не видно: {}",
+        Text::UnseenBySuit => "Не видно: {}",
+        Text::HandValue => "Сила руки: {}, козырей: {}",
+        Text::DefendedSoFar => "Отбито {} из {} - отбейте остальные или нажмите T, чтобы взять",
+        Text::TimeLeft => "Время: {} с",
+        Text::PracticeMove => "Тренировка: ход {} (PgUp/PgDn)",
+        Text::EndgameBanner => "Колода пуста — эндшпиль! Добора больше нет. Нажмите любую клавишу",
+        Text::TableTitle => "Стол",
+        Text::DeckTitle => "Колода",
+        Text::NoCardsOnTable => "На столе нет карт",
+        Text::Dealing => "Раздача…",
+        Text::PlayersTitle => "Игроки",
+        Text::CardCount => "Карт: {}",
+        Text::DebugRevealedHand => "ОТЛАДКА: открытая рука",
+        Text::DrawSummaryTitle => "Раунд окончен - добор карт",
+        Text::BriefingTitle => "Ваш ход: отбейтесь от игрока {}",
+        Text::BriefingAttacks => "Нужно побить: {}",
+        Text::StatusRound => "Раунд {}",
+        Text::RoundBeaten => "{} отбился - в сброс ушло карт: {}",
+        Text::RoundTookOne => "{} взял 1 карту",
+        Text::RoundTookMany => "{} взял карт: {}",
+        Text::DrawsOne => "{} берёт {} карту",
+        Text::DrawsMany => "{} берёт карт: {}",
+        Text::DeckAfterDraw => "Колода: {} -> {} карт",
+        Text::TerminalTooSmall => "Окно слишком маленькое ({}x{}), увеличьте его хотя бы до {}x{}",
+        Text::ActionAttacking => "ходит",
+        Text::ActionDefended => "отбился",
+        Text::ActionPassedAttack => "перевёл",
+        Text::ActionTookOne => "взял 1 карту",
+        Text::ActionTookMany => "взял карт: {}",
+        Text::ActionDrewTo => "добрал до {}",
+        Text::HelpTitle => "Помощь",
+        Text::HelpQuitConfirm => "Выйти из игры? (y/n)",
+        Text::HelpSurrenderConfirm => "Сдаться и проиграть эту игру? (y/n)",
+        Text::HelpAttack => {
+            "←/→: Выбор карты | 2-9/0/J/Q/K/A: К рангу | M: Несколько карт {} | Пробел: Отметить | S: Сортировка | a: Младшая карта | Enter: Сыграть | P: Пас | [/]: Прокрутка стола | F5: Сохранить | X: Сдаться | q: Выход"
+        }
+        Text::HelpDefense => {
+            "←/→: Выбор карты | 2-9/0/J/Q/K/A: К рангу | M: Несколько карт {} | Пробел: Отметить | S: Сортировка | a: Младшая карта | Tab: Выбор атаки | Enter: Отбиться | P: Перевести (тот же ранг{}) | T: Взять | [/]: Прокрутка стола | F5: Сохранить | X: Сдаться | q: Выход"
+        }
+        Text::HelpRevealPass => ", козырь того же ранга = показать",
+        Text::HelpReinforce => {
+            "←/→: Выбор карты | 2-9/0/J/Q/K/A: К рангу | M: Несколько карт {} | Пробел: Отметить | S: Сортировка | a: Младшая карта | Enter: Подкинуть | P: Бито | [/]: Прокрутка стола | F5: Сохранить | X: Сдаться | q: Выход"
+        }
+        Text::HelpGameOver => "Q: Выход | N: Реванш | M: Меню",
+        Text::HelpDrawing => "Нажмите любую клавишу",
+        Text::HelpDemo => "Демо ИИ - нажмите любую клавишу, чтобы вернуться в меню",
+        Text::HelpReplay => "←/→: Листать ходы | Esc: Назад",
+        Text::ReplayDeal => "Повтор: раздача, ходов впереди: {}",
+        Text::ReplayMove => "Повтор: ход {} из {} - {}",
+        Text::ReplayDefend => "{} бьёт {} картой {}",
+        Text::ReplayTake => "{} берёт {}",
+        Text::ReplayDiscard => "{} отбивается, {} уходят в отбой",
+        Text::ReplayDraw => "{} добирает {}",
+        Text::ReplayRevealedHand => "ПОВТОР: рука открыта",
+        Text::AiThinking => "Компьютер думает…",
+        Text::HelpStepAi => ".: Шаг ИИ | F: Без остановок | {}",
+        Text::HelpUndo => "U: Отменить ход | {}",
+    }
+}
+
+const ENGLISH_RULES: [&str; 32] = [
+    "",
+    " ",
+    "Objective:",
+    "Get rid of all your cards. The last player with cards is the 'durak' (fool).",
+    " ",
+    "Setup:",
+    "- Each player gets 6 cards from a 36-card deck (6 to Ace)",
+    "- Bottom card determines trump suit (higher priority), unless another trump rule is set",
+    "- Player with lowest trump card goes first",
+    " ",
+    "Gameplay:",
+    "- Attacker plays a card; defender must beat it with higher card of same suit or trump",
+    "- Passing: Defender can PASS with a card of the same rank (7♠ → 7♥) to the next player by pressing P",
+    "- When a pass occurs, the original attacker must now defend against both cards",
+    "- After successful defense, attacker can add cards of the same rank as those on table",
+    "- With 3+ players everyone but the defender may then throw in matching cards in turn",
+    "- Defender can defend against multiple cards if they have matching cards",
+    "- If defender can't or won't defend, they pick up all cards on the table",
+    "- After successful defense, defender becomes next attacker",
+    "- Players draw after each round to maintain 6 cards (attacker first, defender last)",
+    " ",
+    "Multiple Card Attacks:",
+    "- Press 'm' to toggle multiple selection mode",
+    "- Use spacebar to select/deselect multiple cards with the same rank",
+    "- Press Enter to play all selected cards at once",
+    "- You can only attack with cards of ranks already on the table",
+    "- A round holds no more attacks than the defender's cards at its start, 6 at most",
+    " ",
+    "End Game:",
+    "- Once deck is empty and a player has no cards left, that player is out",
+    "- The last player with cards is the 'durak'",
+    " ",
+];
+
+const RUSSIAN_RULES: [&str; 32] = [
+    "",
+    " ",
+    "Цель:",
+    "Избавиться от всех карт. Последний игрок с картами на руках - «дурак».",
+    " ",
+    "Подготовка:",
+    "- Каждый игрок получает 6 карт из колоды в 36 карт (от шестёрки до туза)",
+    "- Нижняя карта колоды определяет козырь (старшая масть), если не выбрано другое правило",
+    "- Первым ходит игрок с младшим козырем",
+    " ",
+    "Ход игры:",
+    "- Атакующий кладёт карту; защищающийся бьёт её старшей картой той же масти или козырем",
+    "- Перевод: защищающийся может ПЕРЕВЕСТИ атаку картой того же ранга (7♠ → 7♥) следующему игроку клавишей P",
+    "- После перевода первый атакующий должен отбиваться от обеих карт",
+    "- Когда всё отбито, атакующий может подкинуть карты тех рангов, что уже лежат на столе",
+    "- Если игроков трое и больше, затем по очереди подкидывают все, кроме защищающегося",
+    "- Защищающийся может отбиваться от нескольких карт сразу, если у него есть подходящие",
+    "- Если защищающийся не может или не хочет отбиваться, он забирает все карты со стола",
+    "- После успешной защиты защищающийся ходит следующим",
+    "- После каждого раунда игроки добирают до 6 карт (атакующий первым, отбивающийся последним)",
+    " ",
+    "Атака несколькими картами:",
+    "- Нажмите 'm', чтобы включить выбор нескольких карт",
+    "- Пробелом отмечайте карты одного ранга",
+    "- Нажмите Enter, чтобы сыграть все отмеченные карты",
+    "- Подкидывать можно только карты рангов, которые уже есть на столе",
+    "- В одном отбое не больше атак, чем карт у защитника в начале, и не больше 6",
+    " ",
+    "Конец игры:",
+    "- Когда колода пуста, игрок без карт выходит из игры",
+    "- Последний игрок с картами - «дурак»",
+    " ",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Translations must take the same arguments as the English text they replace
+    #[test]
+    fn test_placeholders_match_between_languages() {
+        let keys = [
+            Text::CurrentDifficulty,
+            Text::TrumpMode,
+            Text::WinnerIs,
+            Text::StatusAttack,
+            Text::StatusDefense,
+            Text::StatusReinforce,
+            Text::CardsLeft,
+            Text::UnseenTrumps,
+            Text::UnseenBySuit,
+            Text::HandValue,
+            Text::DefendedSoFar,
+            Text::TimeLeft,
+            Text::PracticeMove,
+            Text::ReplayDeal,
+            Text::ReplayMove,
+            Text::ReplayDefend,
+            Text::ReplayTake,
+            Text::ReplayDiscard,
+            Text::ReplayDraw,
+            Text::PracticeSetting,
+            Text::DefendFirstSetting,
+            Text::DecksSetting,
+            Text::DeckSizeSetting,
+            Text::VariantDeck,
+            Text::MulliganSetting,
+            Text::TrumpPassSetting,
+            Text::AttackLimitSetting,
+            Text::SelectionStyleSetting,
+            Text::CardSizeSetting,
+            Text::ThemeSetting,
+            Text::AidsSetting,
+            Text::CommentarySetting,
+            Text::AnalysisTitle,
+            Text::AnalysisSummary,
+            Text::AnalysisDivergence,
+            Text::MoveAttack,
+            Text::MoveDefend,
+            Text::MovePass,
+            Text::CommentAttack,
+            Text::CommentAdd,
+            Text::CommentWeakRank,
+            Text::CommentDrainTrumps,
+            Text::CommentForcing,
+            Text::CommentStopAdding,
+            Text::CommentDefend,
+            Text::CommentSavedTrumps,
+            Text::CommentUsedTrump,
+            Text::CommentBeatWith,
+            Text::CommentTake,
+            Text::CommentCantBeat,
+            Text::CommentPass,
+            Text::HotseatSetting,
+            Text::HandoverTo,
+            Text::DemoFirstAi,
+            Text::DemoSecondAi,
+            Text::BriefingTitle,
+            Text::BriefingAttacks,
+            Text::CardCount,
+            Text::StatusRound,
+            Text::RoundBeaten,
+            Text::RoundTookOne,
+            Text::RoundTookMany,
+            Text::DrawsOne,
+            Text::DrawsMany,
+            Text::DeckAfterDraw,
+            Text::TerminalTooSmall,
+            Text::ActionTookMany,
+            Text::ActionDrewTo,
+            Text::HelpAttack,
+            Text::HelpDefense,
+            Text::HelpReinforce,
+            Text::HelpStepAi,
+            Text::HelpUndo,
+        ];
+        for key in keys {
+            assert_eq!(
+                english(key).matches("{}").count(),
+                russian(key).matches("{}").count(),
+                "{:?}",
+                key
+            );
+        }
+    }
+
+    /// Arguments fill placeholders in order; missing ones leave the slot empty
+    #[test]
+    fn test_tr_fmt_substitutes_in_order() {
+        assert_eq!(
+            tr_fmt(Text::DeckAfterDraw, &[&10, &4]),
+            english(Text::DeckAfterDraw)
+                .replacen("{}", "10", 1)
+                .replacen("{}", "4", 1)
+        );
+        assert_eq!(
+            tr_fmt(Text::TimeLeft, &[]),
+            tr(Text::TimeLeft).replace("{}", "")
+        );
+    }
+}
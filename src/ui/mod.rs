@@ -2,3 +2,5 @@
 pub mod card_view;
 pub mod debug_overlay;
 pub mod game_ui;
+pub mod i18n;
+pub mod theme;
@@ -0,0 +1,133 @@
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+/// The colour schemes picked on the main menu
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThemeKind {
+    #[default]
+    Default,
+    HighContrast, // Bright colours only, nothing dark grey on black
+    Monochrome,   // The terminal's own colours, selections still stand out by their markers
+}
+
+impl ThemeKind {
+    pub fn next(self) -> Self {
+        match self {
+            ThemeKind::Default => ThemeKind::HighContrast,
+            ThemeKind::HighContrast => ThemeKind::Monochrome,
+            ThemeKind::Monochrome => ThemeKind::Default,
+        }
+    }
+}
+
+/// Every colour the menus, the game screen and the cards are drawn with, by what it is for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub kind: ThemeKind,
+    pub title: Color,           // Screen titles and headings
+    pub text: Color,            // Ordinary text
+    pub status: Color,          // The phase in the status bar
+    pub highlight: Color,       // Whose turn it is, the cursor and other things to look at
+    pub warning: Color,         // Card counts, the empty deck and other bad news
+    pub muted: Color,           // Hints and anything in the background
+    pub accent: Color,          // Debug and practice information
+    pub trump: Color,           // Trump cards and the trump suit
+    pub red_suit: Color,        // Hearts and diamonds
+    pub black_suit: Color,      // Clubs and spades
+    pub card_border: Color,     // The border of an ordinary card
+    pub selected_border: Color, // The border of a selected card
+    pub open_attack: Color,     // The border of an attack waiting for a defense
+    pub card_back: Color,       // The face-down deck
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::new(ThemeKind::Default)
+    }
+}
+
+impl Theme {
+    pub fn new(kind: ThemeKind) -> Self {
+        match kind {
+            ThemeKind::Default => Self {
+                kind,
+                title: Color::Green,
+                text: Color::White,
+                status: Color::Green,
+                highlight: Color::Yellow,
+                warning: Color::Red,
+                muted: Color::DarkGray,
+                accent: Color::Magenta,
+                trump: Color::Cyan,
+                red_suit: Color::Red,
+                black_suit: Color::White,
+                card_border: Color::DarkGray,
+                selected_border: Color::Yellow,
+                open_attack: Color::LightRed,
+                card_back: Color::Blue,
+            },
+            ThemeKind::HighContrast => Self {
+                kind,
+                title: Color::LightGreen,
+                text: Color::White,
+                status: Color::LightGreen,
+                highlight: Color::LightYellow,
+                warning: Color::LightRed,
+                muted: Color::Gray,
+                accent: Color::LightMagenta,
+                trump: Color::LightCyan,
+                red_suit: Color::LightRed,
+                black_suit: Color::White,
+                card_border: Color::White,
+                selected_border: Color::LightYellow,
+                open_attack: Color::LightMagenta,
+                card_back: Color::LightBlue,
+            },
+            ThemeKind::Monochrome => Self {
+                kind,
+                title: Color::Reset,
+                text: Color::Reset,
+                status: Color::Reset,
+                highlight: Color::Reset,
+                warning: Color::Reset,
+                muted: Color::Reset,
+                accent: Color::Reset,
+                trump: Color::Reset,
+                red_suit: Color::Reset,
+                black_suit: Color::Reset,
+                card_border: Color::Reset,
+                selected_border: Color::Reset,
+                open_attack: Color::Reset,
+                card_back: Color::Reset,
+            },
+        }
+    }
+    /// The next theme on the menu
+    pub fn next(self) -> Self {
+        Self::new(self.kind.next())
+    }
+    /// The colour a card's rank and suit are written in
+    pub fn suit_color(&self, red: bool) -> Color {
+        if red { self.red_suit } else { self.black_suit }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cycling through the themes visits each one and comes back to the start
+    #[test]
+    fn test_theme_cycle() {
+        let mut theme = Theme::default();
+        let mut seen = vec![theme.kind];
+        for _ in 0..2 {
+            theme = theme.next();
+            assert!(!seen.contains(&theme.kind));
+            seen.push(theme.kind);
+        }
+        assert_eq!(theme.next(), Theme::default());
+        // Monochrome leaves every colour to the terminal
+        assert_eq!(Theme::new(ThemeKind::Monochrome).trump, Color::Reset);
+    }
+}